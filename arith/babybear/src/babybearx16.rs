@@ -1,25 +1,119 @@
 // A BabyBearx16 stores 512 bits of data.
 // With AVX it stores a single __m512i element.
 // With NEON it stores four uint32x4_t elements.
+// On other targets, or with the `portable` feature, it stores an array of 16 BabyBear elements.
 
 use arith::FFTField;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 mod babybear_neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 pub type BabyBearx16 = babybear_neon::NeonBabyBear;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+// Also compiled in next to the AVX2 implementation, for the hot paths to be dispatched to at
+// runtime, see `arith::SimdBackend`
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(feature = "portable")
+))]
 mod babybear_avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    target_feature = "avx512f",
+    not(feature = "portable")
+))]
 pub type BabyBearx16 = babybear_avx512::AVXBabyBear;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 mod babybear_avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 pub type BabyBearx16 = babybear_avx256::AVXBabyBear;
 
+// Pure Rust fallback, also compiled in tests to be checked against the SIMD implementations
+#[cfg(any(
+    test,
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod babybear_portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type BabyBearx16 = babybear_portable::PortableBabyBear;
+
+// The AVX-512 implementation of the same memory layout as the AVX2 one, which the prover hot paths
+// run on when the CPU supports AVX-512, or BabyBearx16 itself where there is no other to dispatch
+// to
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+pub type AVX512BabyBearx16 = babybear_avx512::AVXBabyBear;
+#[cfg(not(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+)))]
+pub type AVX512BabyBearx16 = BabyBearx16;
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+impl Ord for AVX512BabyBearx16 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for BabyBearx16 is not supported")
+    }
+}
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for AVX512BabyBearx16 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for BabyBearx16 is not supported")
+    }
+}
+
+// The portable implementation has its own, being also compiled in tests
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
 impl Ord for BabyBearx16 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
@@ -27,6 +121,13 @@ impl Ord for BabyBearx16 {
     }
 }
 
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
 #[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for BabyBearx16 {
     #[inline(always)]
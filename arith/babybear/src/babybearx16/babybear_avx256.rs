@@ -36,7 +36,10 @@ const PACKED_MOD: __m256i = unsafe { transmute([BABY_BEAR_MOD; 8]) };
 
 const PACKED_MU: __m256i = unsafe { transmute::<[u32; 8], __m256i>([0x88000001; 8]) };
 
+// 64-byte aligned as the AVX-512 implementation, for the prover hot paths to run on the latter
+// in place, see `arith::SimdBackend`
 #[derive(Clone, Copy)]
+#[repr(align(64))]
 pub struct AVXBabyBear {
     pub v: [__m256i; 2],
 }
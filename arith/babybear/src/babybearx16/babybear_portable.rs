@@ -0,0 +1,267 @@
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, SimdField};
+use ethnum::U256;
+use rand::RngCore;
+use serdes::{ExpSerde, SerdeResult};
+
+use crate::{babybear::BABY_BEAR_MOD, BabyBear};
+
+const BABY_BEAR_PACK_SIZE: usize = 16;
+
+/// PortableBabyBear packs 16 BabyBear elements in an array, operated on lane by lane.
+/// Used on targets without AVX2 or NEON, or with the `portable` feature.
+#[derive(Clone, Copy)]
+pub struct PortableBabyBear {
+    pub v: [BabyBear; BABY_BEAR_PACK_SIZE],
+}
+
+field_common!(PortableBabyBear);
+
+impl Ord for PortableBabyBear {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for BabyBearx16 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for PortableBabyBear {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for BabyBearx16 is not supported")
+    }
+}
+
+impl ExpSerde for PortableBabyBear {
+    #[inline(always)]
+    /// serialize self into bytes, as the Montgomery form of the lanes like the SIMD layouts
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        let mut data = [0u8; 64];
+        data.chunks_exact_mut(4)
+            .zip(self.v.iter())
+            .for_each(|(d, x)| d.copy_from_slice(&x.value.to_le_bytes()));
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut data = [0; 64];
+        reader.read_exact(&mut data)?;
+        let values: [u32; BABY_BEAR_PACK_SIZE] =
+            std::array::from_fn(|i| u32::from_le_bytes(data[i * 4..i * 4 + 4].try_into().unwrap()));
+        Ok(Self {
+            // Safety: BabyBear is a transparent wrapper of its Montgomery form
+            v: unsafe {
+                transmute::<[u32; BABY_BEAR_PACK_SIZE], [BabyBear; BABY_BEAR_PACK_SIZE]>(values)
+            },
+        })
+    }
+}
+
+impl Field for PortableBabyBear {
+    const NAME: &'static str = "Portable Packed BabyBear";
+
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self {
+        v: [BabyBear::ZERO; BABY_BEAR_PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [BabyBear::ONE; BABY_BEAR_PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [BabyBear::INV_2; BABY_BEAR_PACK_SIZE],
+    };
+
+    const MODULUS: U256 = U256([BABY_BEAR_MOD as u128, 0]);
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        Self {
+            v: std::array::from_fn(|_| BabyBear::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        Self {
+            v: std::array::from_fn(|_| BabyBear::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(Self {
+            v: self.v.map(|x| x.inv().unwrap()),
+        })
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::pack_full(&BabyBear::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for PortableBabyBear {
+    type Scalar = BabyBear;
+
+    const PACK_SIZE: usize = BABY_BEAR_PACK_SIZE;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_full(x: &BabyBear) -> PortableBabyBear {
+        PortableBabyBear {
+            v: [*x; BABY_BEAR_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == BABY_BEAR_PACK_SIZE);
+        Self {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<BabyBear> for PortableBabyBear {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        PortableBabyBear::pack_full(&x)
+    }
+}
+
+impl Debug for PortableBabyBear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.v.iter().all(|x| *x == self.v[0]) {
+            write!(f, "[BabyBear; 16]<16 x {:?}>", self.v[0])
+        } else {
+            write!(f, "[BabyBear; 16]<{:?}>", self.v)
+        }
+    }
+}
+
+impl Default for PortableBabyBear {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialEq for PortableBabyBear {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl Eq for PortableBabyBear {}
+
+impl Mul<&BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+
+    #[inline(always)]
+    fn mul(self, rhs: &BabyBear) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x * rhs),
+        }
+    }
+}
+
+impl Mul<BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+    #[inline(always)]
+    fn mul(self, rhs: BabyBear) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+    #[inline(always)]
+    fn add(self, rhs: BabyBear) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x + rhs),
+        }
+    }
+}
+
+impl From<u32> for PortableBabyBear {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        PortableBabyBear::pack_full(&BabyBear::from(x))
+    }
+}
+
+impl Neg for PortableBabyBear {
+    type Output = PortableBabyBear;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    PortableBabyBear {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    PortableBabyBear {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    PortableBabyBear {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
+
+impl Hash for PortableBabyBear {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.v.iter().for_each(|x| state.write_u32(x.value));
+    }
+}
@@ -14,12 +14,17 @@ pub use babybear::{BabyBear, BabyBearParameters};
 
 mod babybearx16;
 pub use babybearx16::BabyBearx16;
+pub use babybearx16::AVX512BabyBearx16;
 
 mod babybear_ext;
 pub use babybear_ext::BabyBearExt3;
 
 mod babybear_ext3x16;
 pub use babybear_ext3x16::BabyBearExt3x16;
+pub use babybear_ext3x16::AVX512BabyBearExt3x16;
+
+mod poseidon2;
+pub use poseidon2::{Poseidon2BabyBear16, Poseidon2BabyBear24};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,58 @@
+use gkr_hashers::Poseidon2Params;
+
+use crate::BabyBear;
+
+/// Diagonal of the internal matrix minus the identity for the width 16 instance of the
+/// Poseidon2 reference implementation
+const MAT_DIAG16_M_1: [u32; 16] = [
+    0x0a632d94, 0x6db657b7, 0x56fbdc9e, 0x052b3d8a, 0x33745201, 0x5c03108c, 0x0beba37b, 0x258c2e8b,
+    0x12029f39, 0x694909ce, 0x6d231724, 0x21c3b222, 0x3c0904a5, 0x01d6acda, 0x27705c83, 0x5231c802,
+];
+
+/// Diagonal of the internal matrix minus the identity for the width 24 instance of the
+/// Poseidon2 reference implementation
+const MAT_DIAG24_M_1: [u32; 24] = [
+    0x409133f0, 0x1667a8a1, 0x06a6c7b6, 0x6f53160e, 0x273b11d1, 0x03176c5d, 0x72f9bbf9, 0x73ceba91,
+    0x5cdef81d, 0x01393285, 0x46daee06, 0x065d7ba6, 0x52d72d6f, 0x05dd05e0, 0x3bab4b63, 0x6ada3842,
+    0x2fc5fbec, 0x770d61b0, 0x5715aae9, 0x03ef0e90, 0x75b6c770, 0x242adf5f, 0x00d0ca4c, 0x36c0e388,
+];
+
+/// Poseidon2 over BabyBear with a state of 16 elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Poseidon2BabyBear16;
+
+impl Poseidon2Params for Poseidon2BabyBear16 {
+    type ElemT = BabyBear;
+
+    const SBOX_POW: usize = 7;
+
+    const FULL_ROUNDS: usize = 8;
+
+    const PARTIAL_ROUNDS: usize = 13;
+
+    const STATE_WIDTH: usize = 16;
+
+    fn internal_diag_m_1() -> Vec<Self::ElemT> {
+        BabyBear::new_array(MAT_DIAG16_M_1).to_vec()
+    }
+}
+
+/// Poseidon2 over BabyBear with a state of 24 elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Poseidon2BabyBear24;
+
+impl Poseidon2Params for Poseidon2BabyBear24 {
+    type ElemT = BabyBear;
+
+    const SBOX_POW: usize = 7;
+
+    const FULL_ROUNDS: usize = 8;
+
+    const PARTIAL_ROUNDS: usize = 21;
+
+    const STATE_WIDTH: usize = 24;
+
+    fn internal_diag_m_1() -> Vec<Self::ElemT> {
+        BabyBear::new_array(MAT_DIAG24_M_1).to_vec()
+    }
+}
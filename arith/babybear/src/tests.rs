@@ -1,11 +1,16 @@
 use arith::{
     random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_inversion_tests, random_simd_field_tests, Field, FieldParameters,
+    random_inversion_tests, random_simd_field_equivalence_tests, random_simd_field_tests, Field,
+    FieldParameters,
 };
 use ark_std::test_rng;
+use gkr_hashers::{FiatShamirHasher, Poseidon2FiatShamirHasher, Poseidon2Params};
 use serdes::ExpSerde;
 
-use crate::{babybear::BabyBearParameters, BabyBear, BabyBearExt3, BabyBearExt3x16, BabyBearx16};
+use crate::{
+    babybear::BabyBearParameters, babybearx16::babybear_portable::PortableBabyBear, BabyBear,
+    BabyBearExt3, BabyBearExt3x16, BabyBearx16, Poseidon2BabyBear16, Poseidon2BabyBear24,
+};
 
 // CMD: RUSTFLAGS="-C target-feature=+avx512f" cargo test --package arith --lib --
 // tests::baby_bear::test_field --exact --show-output
@@ -39,6 +44,20 @@ fn test_simd_field() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn test_portable_simd_field() {
+    random_field_tests::<PortableBabyBear>("Portable BabyBearx16".to_string());
+    random_simd_field_tests::<PortableBabyBear>("Portable BabyBearx16".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<PortableBabyBear, _>(&mut rng, "Portable BabyBearx16".to_string());
+
+    // bit-identical to the SIMD implementation of the target
+    random_simd_field_equivalence_tests::<BabyBearx16, PortableBabyBear>(
+        "Portable BabyBearx16".to_string(),
+    );
+}
+
 // CMD: RUSTFLAGS="-C target-feature=+avx512f" cargo test --package arith --lib --
 // tests::baby_bear_ext::test_field --exact --show-output
 #[test]
@@ -95,3 +114,37 @@ fn test_exponentiation() {
         assert_eq!(base.exp(exponent as u128), expected_result);
     }
 }
+
+fn test_poseidon2_permutation_helper<P: Poseidon2Params<ElemT = BabyBear>>(expected: &[u32]) {
+    let perm = Poseidon2FiatShamirHasher::<P>::new();
+    // the round constants are generated once, and shared by the later instances
+    assert_eq!(Poseidon2FiatShamirHasher::<P>::new(), perm);
+
+    let mut state = (0..P::STATE_WIDTH as u32)
+        .map(BabyBear::new)
+        .collect::<Vec<_>>();
+    perm.permute(&mut state);
+    assert_eq!(
+        state,
+        expected
+            .iter()
+            .cloned()
+            .map(BabyBear::new)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_poseidon2_permutation() {
+    test_poseidon2_permutation_helper::<Poseidon2BabyBear16>(&[
+        0x64dd325e, 0x0a4c0c9b, 0x4305ed32, 0x5edb9ce5, 0x1d2607fb, 0x44961b21, 0x32a8a34f,
+        0x060ffc6b, 0x3e259223, 0x576c03e3, 0x0a75967b, 0x67d49636, 0x4b2295ca, 0x614c59cf,
+        0x74327df4, 0x6ae5e07d,
+    ]);
+    test_poseidon2_permutation_helper::<Poseidon2BabyBear24>(&[
+        0x567f5e9e, 0x5fd8dd28, 0x401377b3, 0x054d37be, 0x3919b8d2, 0x649f3fa3, 0x45d81d73,
+        0x69e5e1d9, 0x453a804b, 0x73bd739b, 0x2aefb586, 0x365de757, 0x4bf7edbb, 0x3cd46b65,
+        0x0a4233af, 0x299fda77, 0x2aa85b02, 0x6315ba8d, 0x2706b19d, 0x359b81de, 0x63dd0ef2,
+        0x6644d7fb, 0x1819fa89, 0x1d42636e,
+    ]);
+}
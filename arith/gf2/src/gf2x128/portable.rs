@@ -0,0 +1,183 @@
+use std::{
+    hash::Hasher,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field};
+use ethnum::U256;
+use serdes::{ExpSerde, SerdeResult};
+
+use crate::GF2;
+
+/// PortableGF2x128 packs 128 GF2 elements in two u64 words, in the bit order of the SIMD
+/// layouts. Used on targets without AVX2 or NEON, or with the `portable` feature.
+/// The SimdField implementation is shared with them in gf2x128.rs.
+#[derive(Debug, Clone, Copy)]
+pub struct PortableGF2x128 {
+    pub v: [u64; 2],
+}
+
+field_common!(PortableGF2x128);
+
+impl ExpSerde for PortableGF2x128 {
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        writer.write_all(&self.v[0].to_le_bytes())?;
+        writer.write_all(&self.v[1].to_le_bytes())?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut u = [0u8; 16];
+        reader.read_exact(&mut u)?;
+        Ok(Self::from_le_bytes(&u))
+    }
+}
+
+impl PortableGF2x128 {
+    #[inline(always)]
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        PortableGF2x128 {
+            v: [
+                u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+                u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            ],
+        }
+    }
+}
+
+impl Field for PortableGF2x128 {
+    const NAME: &'static str = "Portable Galois Field 2 SIMD 128";
+
+    const SIZE: usize = 128 / 8;
+
+    const FIELD_SIZE: usize = 1; // in bits
+
+    const ZERO: Self = PortableGF2x128 { v: [0; 2] };
+
+    const ONE: Self = PortableGF2x128 { v: [!0u64; 2] };
+
+    const INV_2: Self = PortableGF2x128 { v: [0; 2] };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == [0; 2]
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        let mut u = [0u8; 16];
+        rng.fill_bytes(&mut u);
+        Self::from_le_bytes(&u)
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        let mut u = [0u8; 16];
+        rng.fill_bytes(&mut u);
+        Self::from_le_bytes(&u)
+    }
+
+    #[inline(always)]
+    fn exp(&self, exponent: u128) -> Self {
+        if exponent == 0 {
+            return Self::one();
+        }
+        *self
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF2x128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+}
+
+impl Default for PortableGF2x128 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialEq for PortableGF2x128 {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl Neg for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    fn neg(self) -> PortableGF2x128 {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2x128 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        assert!(v < 2);
+        if v == 0 {
+            PortableGF2x128::ZERO
+        } else {
+            PortableGF2x128::ONE
+        }
+    }
+}
+
+impl From<GF2> for PortableGF2x128 {
+    #[inline(always)]
+    fn from(v: GF2) -> Self {
+        assert!(v.v < 2);
+        if v.v == 0 {
+            PortableGF2x128::ZERO
+        } else {
+            PortableGF2x128::ONE
+        }
+    }
+}
+
+impl std::hash::Hash for PortableGF2x128 {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.v[0]);
+        state.write_u64(self.v[1]);
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2x128, b: &PortableGF2x128) -> PortableGF2x128 {
+    PortableGF2x128 {
+        v: [a.v[0] ^ b.v[0], a.v[1] ^ b.v[1]],
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2x128, b: &PortableGF2x128) -> PortableGF2x128 {
+    PortableGF2x128 {
+        v: [a.v[0] ^ b.v[0], a.v[1] ^ b.v[1]],
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2x128, b: &PortableGF2x128) -> PortableGF2x128 {
+    PortableGF2x128 {
+        v: [a.v[0] & b.v[0], a.v[1] & b.v[1]],
+    }
+}
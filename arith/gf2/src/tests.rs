@@ -1,9 +1,12 @@
 use ark_std::test_rng;
 use std::io::Cursor;
 
-use arith::{random_field_tests, random_inversion_tests, random_simd_field_tests, SimdField};
+use arith::{
+    random_field_tests, random_inversion_tests, random_simd_field_equivalence_tests,
+    random_simd_field_tests, SimdField,
+};
 
-use crate::{GF2x128, GF2x64, GF2x8, GF2};
+use crate::{gf2x128::portable::PortableGF2x128, GF2x128, GF2x64, GF2x8, GF2};
 
 #[test]
 fn test_field() {
@@ -25,6 +28,17 @@ fn test_simd_field() {
     random_simd_field_tests::<GF2x128>("Vectorized GF2 len 128".to_string());
 }
 
+#[test]
+fn test_portable_simd_field() {
+    random_field_tests::<PortableGF2x128>("Portable GF2 len 128".to_string());
+    random_simd_field_tests::<PortableGF2x128>("Portable GF2 len 128".to_string());
+
+    // bit-identical to the SIMD implementation of the target
+    random_simd_field_equivalence_tests::<GF2x128, PortableGF2x128>(
+        "Portable GF2 len 128".to_string(),
+    );
+}
+
 fn custom_serde_vectorize_gf2<F: SimdField<Scalar = GF2>>() {
     let mut rng = test_rng();
 
@@ -42,5 +56,6 @@ fn custom_serde_vectorize_gf2<F: SimdField<Scalar = GF2>>() {
 fn test_custom_serde_vectorize_gf2() {
     custom_serde_vectorize_gf2::<GF2x8>();
     custom_serde_vectorize_gf2::<GF2x64>();
-    custom_serde_vectorize_gf2::<GF2x128>()
+    custom_serde_vectorize_gf2::<GF2x128>();
+    custom_serde_vectorize_gf2::<PortableGF2x128>()
 }
@@ -5,16 +5,51 @@ use gf2::GF2x8;
 
 use crate::GF2_128x8;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 pub type GF2_128 = neon::NeonGF2_128;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(feature = "portable")
+))]
 mod avx;
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(feature = "portable")
+))]
 pub type GF2_128 = avx::AVXGF2_128;
 
+// Pure Rust fallback, also compiled in tests to be checked against the SIMD implementations
+#[cfg(any(
+    test,
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type GF2_128 = portable::PortableGF2_128;
+
+// The portable implementation has its own, being also compiled in tests
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
 impl Ord for GF2_128 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
@@ -22,6 +57,13 @@ impl Ord for GF2_128 {
     }
 }
 
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
 #[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for GF2_128 {
     #[inline(always)]
@@ -0,0 +1,264 @@
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field};
+use ethnum::U256;
+use gf2::GF2;
+use serdes::{ExpSerde, SerdeResult};
+
+/// PortableGF2_128 stores the 128 coefficients of a GF2_128 element in a u128, the i-th bit
+/// being the coefficient of x^i, as the little endian layout of the AVX and NEON registers.
+/// Used on targets without AVX2 or NEON, or with the `portable` feature.
+#[derive(Debug, Clone, Copy)]
+pub struct PortableGF2_128 {
+    pub v: u128,
+}
+
+field_common!(PortableGF2_128);
+
+impl Ord for PortableGF2_128 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for GF2_128 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for PortableGF2_128 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for GF2_128 is not supported")
+    }
+}
+
+impl ExpSerde for PortableGF2_128 {
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        writer.write_all(&self.v.to_le_bytes())?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut u = [0u8; 16];
+        reader.read_exact(&mut u)?;
+        Ok(PortableGF2_128 {
+            v: u128::from_le_bytes(u),
+        })
+    }
+}
+
+impl Field for PortableGF2_128 {
+    const NAME: &'static str = "Portable Galois Field 2^128";
+
+    const SIZE: usize = 128 / 8;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = PortableGF2_128 { v: 0 };
+
+    const ONE: Self = PortableGF2_128 { v: 1 };
+
+    const INV_2: Self = PortableGF2_128 { v: 0 };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        let mut u = [0u8; 16];
+        rng.fill_bytes(&mut u);
+        PortableGF2_128 {
+            v: u128::from_le_bytes(u),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128 {
+            v: (rng.next_u32() % 2) as u128,
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let p_m2 = !(0u128) - 1;
+        Some(Self::exp(self, p_m2))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        PortableGF2_128 {
+            v: u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        }
+    }
+}
+
+impl ExtensionField for PortableGF2_128 {
+    const DEGREE: usize = 128;
+
+    const W: u32 = 0x87;
+
+    const X: Self = PortableGF2_128 { v: 2 };
+
+    type BaseField = GF2;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        if base.v == 0 {
+            Self::zero()
+        } else {
+            *self
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128 {
+            v: self.v ^ base.v as u128,
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        PortableGF2_128 {
+            v: mul_by_x_internal(self.v),
+        }
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        let v = limbs
+            .iter()
+            .take(Self::DEGREE)
+            .enumerate()
+            .fold(0u128, |acc, (ith_limb, limb)| {
+                acc | ((limb.v as u128) << ith_limb)
+            });
+        PortableGF2_128 { v }
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        (0..Self::DEGREE)
+            .map(|ith_bit| GF2 {
+                v: ((self.v >> ith_bit) & 1) as u8,
+            })
+            .collect()
+    }
+}
+
+impl Mul<GF2> for PortableGF2_128 {
+    type Output = PortableGF2_128;
+
+    #[inline(always)]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl From<GF2> for PortableGF2_128 {
+    #[inline(always)]
+    fn from(v: GF2) -> Self {
+        PortableGF2_128 { v: v.v as u128 }
+    }
+}
+
+/// Multiply by x modulo p(x) = x^128 + x^7 + x^2 + x + 1
+#[inline(always)]
+fn mul_by_x_internal(a: u128) -> u128 {
+    let reduction = (a >> 127) * 0x87;
+    (a << 1) ^ reduction
+}
+
+/// Carry-less multiplication of a and b into 256 bits, reduced modulo p(x)
+#[inline]
+fn gfmul(a: u128, b: u128) -> u128 {
+    // schoolbook, a shifted by each bit of b, masked to stay branch free
+    let mut lo = 0u128;
+    let mut hi = 0u128;
+    for i in 0..128 {
+        let mask = 0u128.wrapping_sub((b >> i) & 1);
+        lo ^= (a << i) & mask;
+        if i > 0 {
+            hi ^= (a >> (128 - i)) & mask;
+        }
+    }
+
+    // x^128 = x^7 + x^2 + x + 1, the bits of hi overflowing by this shift are folded again
+    let overflow = (hi >> 127) ^ (hi >> 126) ^ (hi >> 121);
+    let hi = hi ^ overflow;
+    lo ^ hi ^ (hi << 1) ^ (hi << 2) ^ (hi << 7)
+}
+
+impl Default for PortableGF2_128 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl PartialEq for PortableGF2_128 {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl Eq for PortableGF2_128 {}
+
+impl Neg for PortableGF2_128 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2_128 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        PortableGF2_128 { v: v as u128 }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    PortableGF2_128 { v: a.v ^ b.v }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    PortableGF2_128 { v: a.v ^ b.v }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    PortableGF2_128 { v: gfmul(a.v, b.v) }
+}
+
+impl Hash for PortableGF2_128 {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.v.to_le_bytes());
+    }
+}
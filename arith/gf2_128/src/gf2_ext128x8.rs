@@ -5,51 +5,164 @@ use gf2::GF2x8;
 
 use crate::GF2_128;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 pub type GF2_128x8 = neon::NeonGF2_128x8;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+// Also compiled in next to the AVX2 implementation, for the hot paths to be dispatched to at
+// runtime, see `arith::SimdBackend`
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(feature = "portable")
+))]
 mod avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    target_feature = "avx512f",
+    not(feature = "portable")
+))]
 pub type GF2_128x8 = avx512::AVX512GF2_128x8;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 mod avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 pub type GF2_128x8 = avx256::AVX256GF2_128x8;
 
-impl Ord for GF2_128x8 {
+// Pure Rust fallback, also compiled in tests to be checked against the SIMD implementations
+#[cfg(any(
+    test,
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type GF2_128x8 = portable::PortableGF2_128x8;
+
+// The AVX-512 implementation of the same memory layout as the AVX2 one, which the prover hot paths
+// run on when the CPU supports AVX-512, or GF2_128x8 itself where there is no other to dispatch to
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+pub use avx512::AVX512GF2_128x8;
+#[cfg(not(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+)))]
+pub type AVX512GF2_128x8 = GF2_128x8;
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+impl Ord for AVX512GF2_128x8 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
         unimplemented!("Ord for GF2_128x8 is not supported")
     }
 }
 
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 #[allow(clippy::non_canonical_partial_ord_impl)]
-impl PartialOrd for GF2_128x8 {
+impl PartialOrd for AVX512GF2_128x8 {
     #[inline(always)]
     fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
         unimplemented!("PartialOrd for GF2_128x8 is not supported")
     }
 }
 
-impl Mul<GF2_128> for GF2_128x8 {
-    type Output = GF2_128x8;
-
+// The portable implementation has its own, being also compiled in tests
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
+impl Ord for GF2_128x8 {
     #[inline(always)]
-    fn mul(self, rhs: GF2_128) -> Self::Output {
-        self * Self::from(rhs)
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for GF2_128x8 is not supported")
     }
 }
 
-impl Add<GF2x8> for GF2_128x8 {
-    type Output = GF2_128x8;
-
+#[cfg(all(
+    not(feature = "portable"),
+    any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    )
+))]
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for GF2_128x8 {
     #[inline(always)]
-    fn add(self, rhs: GF2x8) -> Self::Output {
-        self.add_by_base_field(&rhs)
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for GF2_128x8 is not supported")
     }
 }
+
+macro_rules! gf2_128x8_common {
+    ($ty:ty) => {
+        impl Mul<GF2_128> for $ty {
+            type Output = $ty;
+
+            #[inline(always)]
+            fn mul(self, rhs: GF2_128) -> Self::Output {
+                self * Self::from(rhs)
+            }
+        }
+
+        impl Add<GF2x8> for $ty {
+            type Output = $ty;
+
+            #[inline(always)]
+            fn add(self, rhs: GF2x8) -> Self::Output {
+                self.add_by_base_field(&rhs)
+            }
+        }
+    };
+}
+
+gf2_128x8_common!(GF2_128x8);
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+gf2_128x8_common!(AVX512GF2_128x8);
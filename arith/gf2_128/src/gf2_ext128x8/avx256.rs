@@ -13,7 +13,10 @@ use serdes::{ExpSerde, SerdeError, SerdeResult};
 
 use crate::GF2_128;
 
+// 64-byte aligned as the AVX-512 implementation, for the prover hot paths to run on the latter
+// in place, see `arith::SimdBackend`
 #[derive(Clone, Copy)]
+#[repr(align(64))]
 pub struct AVX256GF2_128x8 {
     data: [__m256i; 4],
 }
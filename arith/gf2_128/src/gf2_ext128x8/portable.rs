@@ -0,0 +1,338 @@
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field, SimdField};
+use ethnum::U256;
+use gf2::{GF2x8, GF2};
+use serdes::{ExpSerde, SerdeResult};
+
+use crate::GF2_128;
+
+const GF2_128_PACK_SIZE: usize = 8;
+
+/// PortableGF2_128x8 packs 8 GF2_128 elements in an array, operated on lane by lane.
+/// The i-th lane goes with the i-th bit of a GF2x8, as in the AVX and NEON layouts.
+/// Used on targets without AVX2 or NEON, or with the `portable` feature.
+#[derive(Clone, Copy, Debug)]
+pub struct PortableGF2_128x8 {
+    pub v: [GF2_128; GF2_128_PACK_SIZE],
+}
+
+field_common!(PortableGF2_128x8);
+
+impl Ord for PortableGF2_128x8 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for GF2_128x8 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for PortableGF2_128x8 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for GF2_128x8 is not supported")
+    }
+}
+
+impl Default for PortableGF2_128x8 {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl PartialEq for PortableGF2_128x8 {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl Eq for PortableGF2_128x8 {}
+
+impl ExpSerde for PortableGF2_128x8 {
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|x| x.serialize_into(&mut writer))
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut v = [GF2_128::ZERO; GF2_128_PACK_SIZE];
+        for x in v.iter_mut() {
+            *x = GF2_128::deserialize_from(&mut reader)?;
+        }
+        Ok(Self { v })
+    }
+}
+
+impl Field for PortableGF2_128x8 {
+    const NAME: &'static str = "Portable Galois Field 2^128 SIMD 8";
+
+    const SIZE: usize = 16 * GF2_128_PACK_SIZE;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = PortableGF2_128x8 {
+        v: [GF2_128::ZERO; GF2_128_PACK_SIZE],
+    };
+
+    const ONE: Self = PortableGF2_128x8 {
+        v: [GF2_128::ONE; GF2_128_PACK_SIZE],
+    };
+
+    const INV_2: Self = PortableGF2_128x8 {
+        v: [GF2_128::INV_2; GF2_128_PACK_SIZE],
+    };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        Self {
+            v: std::array::from_fn(|_| GF2_128::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        Self {
+            v: std::array::from_fn(|_| GF2_128::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let p_m2 = !(0u128) - 1;
+        Some(Self::exp(self, p_m2))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::pack_full(&GF2_128::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for PortableGF2_128x8 {
+    type Scalar = GF2_128;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        Self {
+            v: self.v.map(|x| x * challenge),
+        }
+    }
+
+    const PACK_SIZE: usize = GF2_128_PACK_SIZE;
+
+    #[inline]
+    fn pack_full(base: &Self::Scalar) -> Self {
+        Self {
+            v: [*base; GF2_128_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GF2_128_PACK_SIZE);
+        Self {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<GF2_128> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2_128) -> Self {
+        Self::pack_full(&v)
+    }
+}
+
+impl Neg for PortableGF2_128x8 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        Self::pack_full(&GF2_128::from(v))
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    PortableGF2_128x8 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    add_internal(a, b)
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    PortableGF2_128x8 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
+
+/// The i-th bit of a GF2x8, being the base field element of the i-th lane
+#[inline(always)]
+fn ith_bit(base: &GF2x8, i: usize) -> GF2 {
+    GF2 {
+        v: (base.v >> i) & 1u8,
+    }
+}
+
+impl ExtensionField for PortableGF2_128x8 {
+    const DEGREE: usize = GF2_128::DEGREE;
+
+    const W: u32 = GF2_128::W;
+
+    const X: Self = Self {
+        v: [GF2_128::X; GF2_128_PACK_SIZE],
+    };
+
+    type BaseField = GF2x8;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        Self {
+            v: std::array::from_fn(|i| self.v[i].mul_by_base_field(&ith_bit(base, i))),
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        Self {
+            v: std::array::from_fn(|i| self.v[i].add_by_base_field(&ith_bit(base, i))),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self {
+            v: self.v.map(|x| x.mul_by_x()),
+        }
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        let mut local_limbs = limbs.to_vec();
+        local_limbs.resize(Self::DEGREE, Self::BaseField::ZERO);
+
+        let mut buffer = vec![GF2::ZERO; Self::DEGREE * Self::PACK_SIZE];
+
+        local_limbs.iter().enumerate().for_each(|(ith_limb, limb)| {
+            let unpacked = limb.unpack();
+            unpacked.iter().enumerate().for_each(|(ith_gf2, gf2_val)| {
+                buffer[ith_gf2 * Self::DEGREE + ith_limb] = *gf2_val;
+            });
+        });
+
+        let gf2_128s: Vec<_> = buffer
+            .chunks(Self::DEGREE)
+            .map(GF2_128::from_limbs)
+            .collect();
+
+        Self::pack(&gf2_128s)
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        let mut buffer = vec![GF2::ZERO; Self::DEGREE * Self::PACK_SIZE];
+        self.v
+            .iter()
+            .enumerate()
+            .for_each(|(ith_gf2_128, gf2_128_val)| {
+                let limbs = gf2_128_val.to_limbs();
+                limbs.iter().enumerate().for_each(|(ith_limb, limb)| {
+                    buffer[ith_limb * Self::PACK_SIZE + ith_gf2_128] = *limb;
+                })
+            });
+
+        buffer.chunks(Self::PACK_SIZE).map(GF2x8::pack).collect()
+    }
+}
+
+impl From<GF2x8> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2x8) -> Self {
+        Self {
+            v: std::array::from_fn(|i| GF2_128::from(ith_bit(&v, i))),
+        }
+    }
+}
+
+impl Mul<GF2x8> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline]
+    fn mul(self, rhs: GF2x8) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl Mul<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self
+        }
+    }
+}
+
+impl Add<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn add(self, rhs: GF2) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x.add_by_base_field(&rhs)),
+        }
+    }
+}
+
+impl std::hash::Hash for PortableGF2_128x8 {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.v.iter().for_each(|x| x.hash(state));
+    }
+}
@@ -1,13 +1,16 @@
 use arith::{
-    random_extension_field_tests, random_field_tests, random_from_limbs_to_limbs_tests,
-    random_inversion_tests, random_simd_field_tests,
+    random_extension_field_equivalence_tests, random_extension_field_tests, random_field_tests,
+    random_from_limbs_to_limbs_tests, random_inversion_tests, random_simd_field_equivalence_tests,
+    random_simd_field_tests, ExtensionField, Field,
 };
 use ark_std::test_rng;
 use gf2::{GF2x8, GF2};
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 use crate::gf2_ext128::neon::{gfadd, gfmul};
 
+use crate::gf2_ext128::portable::PortableGF2_128;
+use crate::gf2_ext128x8::portable::PortableGF2_128x8;
 use crate::{GF2_128x8, GF2_128};
 
 #[test]
@@ -30,7 +33,82 @@ fn test_ext_field() {
     random_from_limbs_to_limbs_tests::<GF2x8, GF2_128x8>("Simd GF2 Ext128".to_string());
 }
 
-#[cfg(target_arch = "aarch64")]
+#[test]
+fn test_portable_ext_field() {
+    random_field_tests::<PortableGF2_128>("Portable GF2 Ext128".to_string());
+    random_field_tests::<PortableGF2_128x8>("Portable Simd GF2 Ext128".to_string());
+    random_simd_field_tests::<PortableGF2_128x8>("Portable Simd GF2 Ext128".to_string());
+
+    random_extension_field_tests::<PortableGF2_128>("Portable GF2 Ext128".to_string());
+    random_extension_field_tests::<PortableGF2_128x8>("Portable Simd GF2 Ext128".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<PortableGF2_128, _>(&mut rng, "Portable GF2_128".to_string());
+
+    random_from_limbs_to_limbs_tests::<GF2, PortableGF2_128>("Portable GF2 Ext128".to_string());
+    random_from_limbs_to_limbs_tests::<GF2x8, PortableGF2_128x8>(
+        "Portable Simd GF2 Ext128".to_string(),
+    );
+
+    // bit-identical to the SIMD implementations of the target
+    random_extension_field_equivalence_tests::<GF2_128, PortableGF2_128>(
+        "Portable GF2 Ext128".to_string(),
+    );
+    random_extension_field_equivalence_tests::<GF2_128x8, PortableGF2_128x8>(
+        "Portable Simd GF2 Ext128".to_string(),
+    );
+    random_simd_field_equivalence_tests::<GF2_128x8, PortableGF2_128x8>(
+        "Portable Simd GF2 Ext128".to_string(),
+    );
+}
+
+#[test]
+// known answer test, the same as for the NEON gfmul
+fn test_portable_gf_mul_kat() {
+    let mul = |a: u128, b: u128| (PortableGF2_128 { v: a } * PortableGF2_128 { v: b }).v;
+    let from_u32_lanes = |lanes: [u32; 4]| {
+        lanes
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &lane| (acc << 32) | lane as u128)
+    };
+
+    assert_eq!(mul(0, 1234567890), 0);
+    assert_eq!(mul((3u128 << 64) + 5, 0), 0);
+    assert_eq!(mul(1, (3u128 << 64) + 5), (3u128 << 64) + 5);
+    assert_eq!(
+        mul((3u128 << 64) + 5, (1u128 << 64) + 7),
+        (12u128 << 64) + 402
+    );
+    assert_eq!(
+        mul((1u128 << 64) + 7, (1u128 << 96) + (1 << 64) + (1 << 32) + 1),
+        from_u32_lanes([128, 128, 6, 6])
+    );
+    assert_eq!(
+        mul(
+            u128::from_le_bytes([7u8; 16]),
+            u128::from_le_bytes([5u8; 16])
+        ),
+        from_u32_lanes([232394202; 4])
+    );
+
+    let mut a = [6u8; 16];
+    a[8] = 0;
+    let mut b = [5u8; 16];
+    b[4] = 1;
+    assert_eq!(
+        mul(u128::from_le_bytes(a), u128::from_le_bytes(b)),
+        from_u32_lanes([508894806, 1107902981, 155322701, 155322714])
+    );
+
+    // x^127 * x = x^7 + x^2 + x + 1
+    let x_127 = PortableGF2_128 { v: 1 << 127 };
+    assert_eq!(x_127.mul_by_x().v, 0x87);
+    assert_eq!((x_127 * PortableGF2_128::X).v, 0x87);
+    assert_eq!(x_127.inv().unwrap() * x_127, PortableGF2_128::ONE);
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 #[test]
 // known answer test, results cross-checked with avx_gf2_128
 fn test_gf_mul_kat() {
@@ -117,7 +195,7 @@ fn test_gf_mul_kat() {
     }
 }
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
 #[test]
 fn test_gf_mul_rnd() {
     use core::mem::transmute;
@@ -12,367 +12,405 @@ use serdes::ExpSerde;
 
 use crate::{Goldilocks, GoldilocksExt2, Goldilocksx8};
 
-/// Degree-2 extension of Goldilocks field with 8-element SIMD operations
-/// Represents elements as a + bX where X^2 = 7
-#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, ExpSerde)]
-pub struct GoldilocksExt2x8 {
-    pub c0: Goldilocksx8, // constant term
-    pub c1: Goldilocksx8, // coefficient of X
-}
-
-field_common!(GoldilocksExt2x8);
-
-impl SimdField for GoldilocksExt2x8 {
-    type Scalar = GoldilocksExt2;
-
-    const PACK_SIZE: usize = Goldilocksx8::PACK_SIZE;
-
-    #[inline]
-    fn scale(&self, challenge: &Self::Scalar) -> Self {
-        *self * *challenge
-    }
-
-    #[inline]
-    fn pack_full(base: &Self::Scalar) -> Self {
-        Self {
-            c0: Goldilocksx8::pack_full(&base.v[0]),
-            c1: Goldilocksx8::pack_full(&base.v[1]),
-        }
-    }
-
-    #[inline]
-    fn pack(base_vec: &[Self::Scalar]) -> Self {
-        assert!(base_vec.len() == Self::PACK_SIZE);
-        let mut v0s = vec![];
-        let mut v1s = vec![];
-        for scalar in base_vec {
-            v0s.push(scalar.v[0]);
-            v1s.push(scalar.v[1]);
+/// Implements the degree 2 extension of Goldilocks over a SIMDx8 implementation of Goldilocks
+macro_rules! goldilocks_ext2x8 {
+    ($ext:ident, $packed:ident) => {
+        /// Degree-2 extension of Goldilocks field with 8-element SIMD operations
+        /// Represents elements as a + bX where X^2 = 7
+        #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, ExpSerde)]
+        pub struct $ext {
+            pub c0: $packed, // constant term
+            pub c1: $packed, // coefficient of X
         }
-        Self {
-            c0: Goldilocksx8::pack(&v0s),
-            c1: Goldilocksx8::pack(&v1s),
-        }
-    }
-
-    #[inline]
-    fn unpack(&self) -> Vec<Self::Scalar> {
-        let v0s = self.c0.unpack();
-        let v1s = self.c1.unpack();
-        v0s.into_iter()
-            .zip(v1s)
-            .map(|(v0, v1)| GoldilocksExt2 { v: [v0, v1] })
-            .collect()
-    }
-}
 
-impl From<Goldilocksx8> for GoldilocksExt2x8 {
-    #[inline]
-    fn from(x: Goldilocksx8) -> Self {
-        Self {
-            c0: x,
-            c1: Goldilocksx8::ZERO,
+        field_common!($ext);
+
+        impl SimdField for $ext {
+            type Scalar = GoldilocksExt2;
+
+            const PACK_SIZE: usize = $packed::PACK_SIZE;
+
+            #[inline]
+            fn scale(&self, challenge: &Self::Scalar) -> Self {
+                *self * *challenge
+            }
+
+            #[inline]
+            fn pack_full(base: &Self::Scalar) -> Self {
+                Self {
+                    c0: $packed::pack_full(&base.v[0]),
+                    c1: $packed::pack_full(&base.v[1]),
+                }
+            }
+
+            #[inline]
+            fn pack(base_vec: &[Self::Scalar]) -> Self {
+                assert!(base_vec.len() == Self::PACK_SIZE);
+                let mut v0s = vec![];
+                let mut v1s = vec![];
+                for scalar in base_vec {
+                    v0s.push(scalar.v[0]);
+                    v1s.push(scalar.v[1]);
+                }
+                Self {
+                    c0: $packed::pack(&v0s),
+                    c1: $packed::pack(&v1s),
+                }
+            }
+
+            #[inline]
+            fn unpack(&self) -> Vec<Self::Scalar> {
+                let v0s = self.c0.unpack();
+                let v1s = self.c1.unpack();
+                v0s.into_iter()
+                    .zip(v1s)
+                    .map(|(v0, v1)| GoldilocksExt2 { v: [v0, v1] })
+                    .collect()
+            }
         }
-    }
-}
-
-impl ExtensionField for GoldilocksExt2x8 {
-    type BaseField = Goldilocksx8;
 
-    const DEGREE: usize = 2;
-
-    const W: u32 = 7;
-
-    const X: Self = Self {
-        c0: Goldilocksx8::ZERO,
-        c1: Goldilocksx8::ONE,
-    };
-
-    #[inline]
-    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
-        Self {
-            c0: self.c0 * base,
-            c1: self.c1 * base,
+        impl From<$packed> for $ext {
+            #[inline]
+            fn from(x: $packed) -> Self {
+                Self {
+                    c0: x,
+                    c1: $packed::ZERO,
+                }
+            }
         }
-    }
 
-    #[inline]
-    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
-        Self {
-            c0: self.c0 + base,
-            c1: self.c1,
-        }
-    }
-
-    #[inline]
-    fn mul_by_x(&self) -> Self {
-        // (a + bX) * X = aX + bX^2
-        // where X^2 = 7
-        // = 7b + aX
-        Self {
-            c0: self.c1 * Goldilocksx8::pack_full(&Goldilocks { v: 7u64 }),
-            c1: self.c0,
+        impl ExtensionField for $ext {
+            type BaseField = $packed;
+
+            const DEGREE: usize = 2;
+
+            const W: u32 = 7;
+
+            const X: Self = Self {
+                c0: $packed::ZERO,
+                c1: $packed::ONE,
+            };
+
+            #[inline]
+            fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+                Self {
+                    c0: self.c0 * base,
+                    c1: self.c1 * base,
+                }
+            }
+
+            #[inline]
+            fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+                Self {
+                    c0: self.c0 + base,
+                    c1: self.c1,
+                }
+            }
+
+            #[inline]
+            fn mul_by_x(&self) -> Self {
+                // (a + bX) * X = aX + bX^2
+                // where X^2 = 7
+                // = 7b + aX
+                Self {
+                    c0: self.c1 * $packed::pack_full(&Goldilocks { v: 7u64 }),
+                    c1: self.c0,
+                }
+            }
+
+            #[inline]
+            fn to_limbs(&self) -> Vec<Self::BaseField> {
+                vec![self.c0, self.c1]
+            }
+
+            #[inline]
+            fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+                assert!(limbs.len() >= 2);
+                Self {
+                    c0: limbs[0],
+                    c1: limbs[1],
+                }
+            }
         }
-    }
-
-    #[inline]
-    fn to_limbs(&self) -> Vec<Self::BaseField> {
-        vec![self.c0, self.c1]
-    }
-
-    #[inline]
-    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
-        assert!(limbs.len() >= 2);
-        Self {
-            c0: limbs[0],
-            c1: limbs[1],
-        }
-    }
-}
 
-impl Mul<Goldilocksx8> for GoldilocksExt2x8 {
-    type Output = Self;
+        impl Mul<$packed> for $ext {
+            type Output = Self;
 
-    #[inline]
-    fn mul(self, rhs: Goldilocksx8) -> Self {
-        self.mul_by_base_field(&rhs)
-    }
-}
-
-impl From<GoldilocksExt2> for GoldilocksExt2x8 {
-    #[inline]
-    fn from(x: GoldilocksExt2) -> Self {
-        Self {
-            c0: Goldilocksx8::pack_full(&x.v[0]),
-            c1: Goldilocksx8::pack_full(&x.v[1]),
+            #[inline]
+            fn mul(self, rhs: $packed) -> Self {
+                self.mul_by_base_field(&rhs)
+            }
         }
-    }
-}
-
-impl Field for GoldilocksExt2x8 {
-    const NAME: &'static str = "Goldilocks Extension Field 2x8";
-
-    const SIZE: usize = 512 / 8 * 2;
-
-    const FIELD_SIZE: usize = 64 * 2;
-
-    const MODULUS: U256 = Goldilocks::MODULUS;
-
-    const ZERO: Self = Self {
-        c0: Goldilocksx8::ZERO,
-        c1: Goldilocksx8::ZERO,
-    };
 
-    const ONE: Self = Self {
-        c0: Goldilocksx8::ONE,
-        c1: Goldilocksx8::ZERO,
-    };
-
-    const INV_2: Self = Self {
-        c0: Goldilocksx8::INV_2,
-        c1: Goldilocksx8::ZERO,
-    };
-
-    #[inline]
-    fn zero() -> Self {
-        Self::ZERO
-    }
-
-    #[inline]
-    fn one() -> Self {
-        Self::ONE
-    }
-
-    #[inline]
-    fn is_zero(&self) -> bool {
-        self.c0.is_zero() && self.c1.is_zero()
-    }
-
-    #[inline]
-    fn random_unsafe(mut rng: impl RngCore) -> Self {
-        Self {
-            c0: Goldilocksx8::random_unsafe(&mut rng),
-            c1: Goldilocksx8::random_unsafe(&mut rng),
+        impl From<GoldilocksExt2> for $ext {
+            #[inline]
+            fn from(x: GoldilocksExt2) -> Self {
+                Self {
+                    c0: $packed::pack_full(&x.v[0]),
+                    c1: $packed::pack_full(&x.v[1]),
+                }
+            }
         }
-    }
 
-    #[inline]
-    fn random_bool(mut rng: impl RngCore) -> Self {
-        Self {
-            c0: Goldilocksx8::random_bool(&mut rng),
-            c1: Goldilocksx8::ZERO,
-        }
-    }
-
-    #[inline]
-    fn as_u32_unchecked(&self) -> u32 {
-        unimplemented!("self is a vector, cannot convert to u32")
-    }
-
-    #[inline]
-    fn from_uniform_bytes(_bytes: &[u8]) -> Self {
-        unimplemented!("vec Goldilocks: cannot convert from 32 bytes")
-    }
-
-    #[inline]
-    fn square(&self) -> Self {
-        square_internal(self)
-    }
-
-    fn inv(&self) -> Option<Self> {
-        if self.is_zero() {
-            return None;
+        impl Field for $ext {
+            const NAME: &'static str = "Goldilocks Extension Field 2x8";
+
+            const SIZE: usize = 512 / 8 * 2;
+
+            const FIELD_SIZE: usize = 64 * 2;
+
+            const MODULUS: U256 = Goldilocks::MODULUS;
+
+            const ZERO: Self = Self {
+                c0: $packed::ZERO,
+                c1: $packed::ZERO,
+            };
+
+            const ONE: Self = Self {
+                c0: $packed::ONE,
+                c1: $packed::ZERO,
+            };
+
+            const INV_2: Self = Self {
+                c0: $packed::INV_2,
+                c1: $packed::ZERO,
+            };
+
+            #[inline]
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            #[inline]
+            fn one() -> Self {
+                Self::ONE
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                self.c0.is_zero() && self.c1.is_zero()
+            }
+
+            #[inline]
+            fn random_unsafe(mut rng: impl RngCore) -> Self {
+                Self {
+                    c0: $packed::random_unsafe(&mut rng),
+                    c1: $packed::random_unsafe(&mut rng),
+                }
+            }
+
+            #[inline]
+            fn random_bool(mut rng: impl RngCore) -> Self {
+                Self {
+                    c0: $packed::random_bool(&mut rng),
+                    c1: $packed::ZERO,
+                }
+            }
+
+            #[inline]
+            fn as_u32_unchecked(&self) -> u32 {
+                unimplemented!("self is a vector, cannot convert to u32")
+            }
+
+            #[inline]
+            fn from_uniform_bytes(_bytes: &[u8]) -> Self {
+                unimplemented!("vec Goldilocks: cannot convert from 32 bytes")
+            }
+
+            #[inline]
+            fn square(&self) -> Self {
+                square_internal(self)
+            }
+
+            fn inv(&self) -> Option<Self> {
+                if self.is_zero() {
+                    return None;
+                }
+
+                let compliment = Self {
+                    c0: -self.c0,
+                    c1: self.c1,
+                };
+
+                let w_base = $packed::pack_full(&Goldilocks { v: Self::W as u64 });
+                let normalize = (-self.c0.square() + self.c1.square() * w_base).inv()?;
+
+                Some(compliment * normalize)
+            }
         }
 
-        let compliment = Self {
-            c0: -self.c0,
-            c1: self.c1,
-        };
+        impl Mul<GoldilocksExt2> for $ext {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: GoldilocksExt2) -> Self::Output {
+                // (a0 + a1*x) * (b0 + b1*x) mod (x^2 - 7)
+                // = a0*b0 + (a0*b1 + a1*b0)*x + a1*b1*x^2 mod (x^2 - 7)
+                // = (a0*b0 + 7*a1*b1) + (a0*b1 + a1*b0)*x
+                let seven = Goldilocks::from(7u32);
+                Self {
+                    c0: self.c0 * rhs.v[0] + self.c1 * rhs.v[1] * seven,
+                    c1: self.c0 * rhs.v[1] + self.c1 * rhs.v[0],
+                }
+            }
+        }
 
-        let w_base = Goldilocksx8::pack_full(&Goldilocks { v: Self::W as u64 });
-        let normalize = (-self.c0.square() + self.c1.square() * w_base).inv()?;
+        impl Mul<Goldilocks> for $ext {
+            type Output = Self;
 
-        Some(compliment * normalize)
-    }
-}
+            #[inline]
+            fn mul(self, rhs: Goldilocks) -> Self {
+                Self {
+                    c0: self.c0 * rhs,
+                    c1: self.c1 * rhs,
+                }
+            }
+        }
 
-impl Mul<GoldilocksExt2> for GoldilocksExt2x8 {
-    type Output = Self;
-
-    #[inline]
-    fn mul(self, rhs: GoldilocksExt2) -> Self::Output {
-        // (a0 + a1*x) * (b0 + b1*x) mod (x^2 - 7)
-        // = a0*b0 + (a0*b1 + a1*b0)*x + a1*b1*x^2 mod (x^2 - 7)
-        // = (a0*b0 + 7*a1*b1) + (a0*b1 + a1*b0)*x
-        let seven = Goldilocks::from(7u32);
-        Self {
-            c0: self.c0 * rhs.v[0] + self.c1 * rhs.v[1] * seven,
-            c1: self.c0 * rhs.v[1] + self.c1 * rhs.v[0],
+        impl Add<Goldilocks> for $ext {
+            type Output = $ext;
+            #[inline(always)]
+            fn add(self, rhs: Goldilocks) -> Self::Output {
+                $ext {
+                    // Goldilocksx8 + Goldilocks
+                    c0: self.c0 + rhs,
+                    c1: self.c1,
+                }
+            }
         }
-    }
-}
 
-impl Mul<Goldilocks> for GoldilocksExt2x8 {
-    type Output = Self;
+        impl Neg for $ext {
+            type Output = Self;
 
-    #[inline]
-    fn mul(self, rhs: Goldilocks) -> Self {
-        Self {
-            c0: self.c0 * rhs,
-            c1: self.c1 * rhs,
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self {
+                    c0: -self.c0,
+                    c1: -self.c1,
+                }
+            }
         }
-    }
-}
 
-impl Add<Goldilocks> for GoldilocksExt2x8 {
-    type Output = GoldilocksExt2x8;
-    #[inline(always)]
-    fn add(self, rhs: Goldilocks) -> Self::Output {
-        GoldilocksExt2x8 {
-            // Goldilocksx8 + Goldilocks
-            c0: self.c0 + rhs,
-            c1: self.c1,
+        impl From<u32> for $ext {
+            #[inline]
+            fn from(value: u32) -> Self {
+                Self {
+                    c0: $packed::from(value),
+                    c1: $packed::ZERO,
+                }
+            }
         }
-    }
-}
 
-impl Neg for GoldilocksExt2x8 {
-    type Output = Self;
+        #[inline(always)]
+        fn add_internal(a: &$ext, b: &$ext) -> $ext {
+            $ext {
+                c0: a.c0 + b.c0,
+                c1: a.c1 + b.c1,
+            }
+        }
 
-    #[inline]
-    fn neg(self) -> Self::Output {
-        Self {
-            c0: -self.c0,
-            c1: -self.c1,
+        #[inline(always)]
+        fn sub_internal(a: &$ext, b: &$ext) -> $ext {
+            $ext {
+                c0: a.c0 - b.c0,
+                c1: a.c1 - b.c1,
+            }
         }
-    }
-}
 
-impl From<u32> for GoldilocksExt2x8 {
-    #[inline]
-    fn from(value: u32) -> Self {
-        Self {
-            c0: Goldilocksx8::from(value),
-            c1: Goldilocksx8::ZERO,
+        #[inline(always)]
+        fn mul_internal(a: &$ext, b: &$ext) -> $ext {
+            // (a + bX)(c + dX) = ac + (ad + bc)X + bdX^2
+            // where X^2 = 7
+            // = (ac + 7bd) + (ad + bc)X
+            let ac = a.c0 * b.c0;
+            let bd = a.c1 * b.c1;
+            let ad = a.c0 * b.c1;
+            let bc = a.c1 * b.c0;
+            $ext {
+                c0: ac + bd * $packed::from(7u64),
+                c1: ad + bc,
+            }
         }
-    }
-}
 
-#[inline(always)]
-fn add_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
-    GoldilocksExt2x8 {
-        c0: a.c0 + b.c0,
-        c1: a.c1 + b.c1,
-    }
-}
+        #[inline(always)]
+        fn square_internal(a: &$ext) -> $ext {
+            let r0 = a.c0.square() + a.c1.square() * $packed::pack_full(&7u64.into());
+            let r1 = a.c0 * a.c1.double();
+            $ext { c0: r0, c1: r1 }
+        }
 
-#[inline(always)]
-fn sub_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
-    GoldilocksExt2x8 {
-        c0: a.c0 - b.c0,
-        c1: a.c1 - b.c1,
-    }
-}
+        impl Ord for $ext {
+            #[inline(always)]
+            fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+                unimplemented!("Ord for $ext is not supported")
+            }
+        }
 
-#[inline(always)]
-fn mul_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
-    // (a + bX)(c + dX) = ac + (ad + bc)X + bdX^2
-    // where X^2 = 7
-    // = (ac + 7bd) + (ad + bc)X
-    let ac = a.c0 * b.c0;
-    let bd = a.c1 * b.c1;
-    let ad = a.c0 * b.c1;
-    let bc = a.c1 * b.c0;
-    GoldilocksExt2x8 {
-        c0: ac + bd * Goldilocksx8::from(7u64),
-        c1: ad + bc,
-    }
-}
+        #[allow(clippy::non_canonical_partial_ord_impl)]
+        impl PartialOrd for $ext {
+            #[inline(always)]
+            fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+                unimplemented!("PartialOrd for $ext is not supported")
+            }
+        }
 
-#[inline(always)]
-fn square_internal(a: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
-    let r0 = a.c0.square() + a.c1.square() * Goldilocksx8::pack_full(&7u64.into());
-    let r1 = a.c0 * a.c1.double();
-    GoldilocksExt2x8 { c0: r0, c1: r1 }
-}
+        impl FFTField for $ext {
+            const TWO_ADICITY: usize = 33;
+
+            #[inline(always)]
+            fn root_of_unity() -> Self {
+                let var = GoldilocksExt2 {
+                    v: [
+                        Goldilocks::ZERO,
+                        Goldilocks {
+                            v: 0xd95051a31cf4a6ef,
+                        },
+                    ],
+                };
+                Self::pack_full(&var)
+            }
+        }
 
-impl Ord for GoldilocksExt2x8 {
-    #[inline(always)]
-    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
-        unimplemented!("Ord for GoldilocksExt2x8 is not supported")
-    }
-}
+        impl Add<$packed> for $ext {
+            type Output = $ext;
 
-#[allow(clippy::non_canonical_partial_ord_impl)]
-impl PartialOrd for GoldilocksExt2x8 {
-    #[inline(always)]
-    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
-        unimplemented!("PartialOrd for GoldilocksExt2x8 is not supported")
-    }
+            #[inline(always)]
+            fn add(self, rhs: $packed) -> Self::Output {
+                self.add_by_base_field(&rhs)
+            }
+        }
+    };
 }
 
-impl FFTField for GoldilocksExt2x8 {
-    const TWO_ADICITY: usize = 33;
-
-    #[inline(always)]
-    fn root_of_unity() -> Self {
-        let var = GoldilocksExt2 {
-            v: [
-                Goldilocks::ZERO,
-                Goldilocks {
-                    v: 0xd95051a31cf4a6ef,
-                },
-            ],
-        };
-        Self::pack_full(&var)
-    }
+goldilocks_ext2x8!(GoldilocksExt2x8, Goldilocksx8);
+
+// The same extension over the AVX-512 implementation of Goldilocksx8, which the prover hot paths
+// run on when the CPU supports AVX-512, or GoldilocksExt2x8 itself where there is no other to
+// dispatch to
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+mod avx512 {
+    use super::*;
+    use crate::AVX512Goldilocksx8;
+
+    goldilocks_ext2x8!(AVX512GoldilocksExt2x8, AVX512Goldilocksx8);
 }
 
-impl Add<Goldilocksx8> for GoldilocksExt2x8 {
-    type Output = GoldilocksExt2x8;
-
-    #[inline(always)]
-    fn add(self, rhs: Goldilocksx8) -> Self::Output {
-        self.add_by_base_field(&rhs)
-    }
-}
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+pub use avx512::AVX512GoldilocksExt2x8;
+#[cfg(not(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+)))]
+pub type AVX512GoldilocksExt2x8 = GoldilocksExt2x8;
@@ -1,24 +1,104 @@
 // A Goldilocksx8 stores 512 bits of data.
 // With AVX it stores a single __m512i element.
-// With NEON it stores 8 u64 elements.
-// NEON doesn't use and arm instructions and can be used as a fallback for other architectures.
+// With NEON, on other targets, or with the `portable` feature, it stores an array of 8
+// Goldilocks elements, without using any arm instructions.
 
-#[cfg(target_arch = "aarch64")]
-mod goldilocks_neon;
-#[cfg(target_arch = "aarch64")]
-pub type Goldilocksx8 = goldilocks_neon::NeonGoldilocks;
+// Also compiled in tests to be checked against the AVX implementations
+#[cfg(any(
+    test,
+    feature = "portable",
+    not(all(target_arch = "x86_64", target_feature = "avx2"))
+))]
+pub(crate) mod goldilocks_portable;
+#[cfg(any(
+    feature = "portable",
+    not(all(target_arch = "x86_64", target_feature = "avx2"))
+))]
+pub type Goldilocksx8 = goldilocks_portable::PortableGoldilocks;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+// Also compiled in next to the AVX2 implementation, for the hot paths to be dispatched to at
+// runtime, see `arith::SimdBackend`
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(feature = "portable")
+))]
 mod goldilocks_avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    target_feature = "avx512f",
+    not(feature = "portable")
+))]
 pub type Goldilocksx8 = goldilocks_avx512::AVXGoldilocks;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 mod goldilocks_avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
 pub type Goldilocksx8 = goldilocks_avx256::AVXGoldilocks;
 
+// The AVX-512 implementation of the same memory layout as the AVX2 one, which the prover hot paths
+// run on when the CPU supports AVX-512, or Goldilocksx8 itself where there is no other to dispatch
+// to
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+pub type AVX512Goldilocksx8 = goldilocks_avx512::AVXGoldilocks;
+#[cfg(not(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+)))]
+pub type AVX512Goldilocksx8 = Goldilocksx8;
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+impl Ord for AVX512Goldilocksx8 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for Goldilocksx8 is not supported")
+    }
+}
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "portable")
+))]
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for AVX512Goldilocksx8 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for Goldilocksx8 is not supported")
+    }
+}
+
+// The portable implementation has its own, being also compiled in tests
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx2"
+))]
 impl Ord for Goldilocksx8 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
@@ -26,6 +106,11 @@ impl Ord for Goldilocksx8 {
     }
 }
 
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx2"
+))]
 #[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Goldilocksx8 {
     #[inline(always)]
@@ -33,7 +33,10 @@ const PACKED_0: __m256i = unsafe { transmute([0u64; 4]) };
 /// Packed inverse of 2
 const PACKED_INV_2: __m256i = unsafe { transmute([0x7FFFFFFF80000001u64; 4]) };
 
+// 64-byte aligned as the AVX-512 implementation, for the prover hot paths to run on the latter
+// in place, see `arith::SimdBackend`
 #[derive(Debug, Clone, Copy)]
+#[repr(align(64))]
 pub struct AVXGoldilocks {
     // using two __m256i to simulate a __m512i
     pub v: [__m256i; 2],
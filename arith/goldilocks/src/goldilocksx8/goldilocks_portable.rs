@@ -14,18 +14,33 @@ use crate::{goldilocks::p2_instructions, Goldilocks};
 /// Number of Goldilocks elements packed
 const GOLDILOCKS_PACK_SIZE: usize = 8;
 
-/// NeonGoldilocks packs 8 Goldilocks elements
-/// Unlike NeonM31 we end up not using neon's 128-bit vectorized operations
-/// Working on vectors seems to be slower since we only pack 2 elements per slot
+/// PortableGoldilocks packs 8 Goldilocks elements in an array, operated on lane by lane.
+/// It is used on aarch64, where working on neon's 128-bit vectors seems to be slower since we
+/// only pack 2 elements per slot, and on targets without AVX2, or with the `portable` feature.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ExpSerde)]
-pub struct NeonGoldilocks {
+pub struct PortableGoldilocks {
     pub v: [Goldilocks; 8],
 }
 
-field_common!(NeonGoldilocks);
+field_common!(PortableGoldilocks);
 
-impl Field for NeonGoldilocks {
-    const NAME: &'static str = "Neon Packed Goldilocks";
+impl Ord for PortableGoldilocks {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for Goldilocksx8 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for PortableGoldilocks {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for Goldilocksx8 is not supported")
+    }
+}
+
+impl Field for PortableGoldilocks {
+    const NAME: &'static str = "Portable Packed Goldilocks";
 
     const SIZE: usize = 64; // 8 * 8 bytes
 
@@ -106,7 +121,7 @@ impl Field for NeonGoldilocks {
     }
 }
 
-impl SimdField for NeonGoldilocks {
+impl SimdField for PortableGoldilocks {
     type Scalar = Goldilocks;
 
     const PACK_SIZE: usize = GOLDILOCKS_PACK_SIZE;
@@ -144,14 +159,14 @@ impl SimdField for NeonGoldilocks {
     }
 }
 
-impl Default for NeonGoldilocks {
+impl Default for PortableGoldilocks {
     #[inline(always)]
     fn default() -> Self {
         Self::zero()
     }
 }
 
-impl From<u32> for NeonGoldilocks {
+impl From<u32> for PortableGoldilocks {
     #[inline(always)]
     fn from(x: u32) -> Self {
         Self {
@@ -160,7 +175,7 @@ impl From<u32> for NeonGoldilocks {
     }
 }
 
-impl From<u64> for NeonGoldilocks {
+impl From<u64> for PortableGoldilocks {
     #[inline(always)]
     fn from(x: u64) -> Self {
         Self {
@@ -169,14 +184,14 @@ impl From<u64> for NeonGoldilocks {
     }
 }
 
-impl From<Goldilocks> for NeonGoldilocks {
+impl From<Goldilocks> for PortableGoldilocks {
     #[inline(always)]
     fn from(x: Goldilocks) -> Self {
         Self { v: [x; 8] }
     }
 }
 
-impl std::hash::Hash for NeonGoldilocks {
+impl std::hash::Hash for PortableGoldilocks {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         for elem in &self.v {
@@ -185,7 +200,7 @@ impl std::hash::Hash for NeonGoldilocks {
     }
 }
 
-impl Mul<&Goldilocks> for NeonGoldilocks {
+impl Mul<&Goldilocks> for PortableGoldilocks {
     type Output = Self;
 
     #[inline(always)]
@@ -195,7 +210,7 @@ impl Mul<&Goldilocks> for NeonGoldilocks {
     }
 }
 
-impl Mul<Goldilocks> for NeonGoldilocks {
+impl Mul<Goldilocks> for PortableGoldilocks {
     type Output = Self;
 
     #[inline(always)]
@@ -205,16 +220,16 @@ impl Mul<Goldilocks> for NeonGoldilocks {
     }
 }
 
-impl Add<Goldilocks> for NeonGoldilocks {
-    type Output = NeonGoldilocks;
+impl Add<Goldilocks> for PortableGoldilocks {
+    type Output = PortableGoldilocks;
     #[inline(always)]
     fn add(self, rhs: Goldilocks) -> Self::Output {
-        let res = self.v.map(|x| x * rhs);
+        let res = self.v.map(|x| x + rhs);
         Self { v: res }
     }
 }
 
-impl Neg for NeonGoldilocks {
+impl Neg for PortableGoldilocks {
     type Output = Self;
     #[inline(always)]
     fn neg(self) -> Self {
@@ -227,8 +242,8 @@ impl Neg for NeonGoldilocks {
 }
 
 #[inline(always)]
-fn add_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
-    let mut res = NeonGoldilocks::zero();
+fn add_internal(a: &PortableGoldilocks, b: &PortableGoldilocks) -> PortableGoldilocks {
+    let mut res = PortableGoldilocks::zero();
     for i in 0..8 {
         res.v[i] = a.v[i] + b.v[i];
     }
@@ -236,8 +251,8 @@ fn add_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
 }
 
 #[inline(always)]
-fn sub_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
-    let mut res = NeonGoldilocks::zero();
+fn sub_internal(a: &PortableGoldilocks, b: &PortableGoldilocks) -> PortableGoldilocks {
+    let mut res = PortableGoldilocks::zero();
     for i in 0..8 {
         res.v[i] = a.v[i] - b.v[i];
     }
@@ -245,15 +260,15 @@ fn sub_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
 }
 
 #[inline(always)]
-fn mul_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
-    let mut res = NeonGoldilocks::zero();
+fn mul_internal(a: &PortableGoldilocks, b: &PortableGoldilocks) -> PortableGoldilocks {
+    let mut res = PortableGoldilocks::zero();
     for i in 0..8 {
         res.v[i] = a.v[i] * b.v[i];
     }
     res
 }
 
-impl FFTField for NeonGoldilocks {
+impl FFTField for PortableGoldilocks {
     const TWO_ADICITY: usize = 32;
 
     /// The `2^s` root of unity.
@@ -11,10 +11,16 @@ pub use goldilocks_ext::GoldilocksExt2;
 /// Goldilocks x8
 mod goldilocksx8;
 pub use goldilocksx8::Goldilocksx8;
+pub use goldilocksx8::AVX512Goldilocksx8;
 
 /// Goldilocks extension field x8
 mod goldilocks_ext2x8;
 pub use goldilocks_ext2x8::GoldilocksExt2x8;
+pub use goldilocks_ext2x8::AVX512GoldilocksExt2x8;
+
+/// Poseidon2 over Goldilocks
+mod poseidon2;
+pub use poseidon2::{Poseidon2Goldilocks12, Poseidon2Goldilocks8};
 
 #[cfg(test)]
 mod tests;
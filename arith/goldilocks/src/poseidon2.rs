@@ -0,0 +1,73 @@
+use gkr_hashers::Poseidon2Params;
+
+use crate::Goldilocks;
+
+/// Diagonal of the internal matrix minus the identity for the width 8 instance of the
+/// Poseidon2 reference implementation
+const MAT_DIAG8_M_1: [u64; 8] = [
+    0xa98811a1fed4e3a5,
+    0x1cc48b54f377e2a0,
+    0xe40cd4f6c5609a26,
+    0x11de79ebca97a4a3,
+    0x9177c73d8b7e929c,
+    0x2a6fe8085797e791,
+    0x3de6e93329f8d5ad,
+    0x3f7af9125da962fe,
+];
+
+/// Diagonal of the internal matrix minus the identity for the width 12 instance of the
+/// Poseidon2 reference implementation
+const MAT_DIAG12_M_1: [u64; 12] = [
+    0xc3b6c08e23ba9300,
+    0xd84b5de94a324fb6,
+    0x0d0c371c5b35b84f,
+    0x7964f570e7188037,
+    0x5daf18bbd996604b,
+    0x6743bc47b9595257,
+    0x5528b9362c59bb70,
+    0xac45e25b7127b68b,
+    0xa2077d7dfbb606b5,
+    0xf3faac6faee378ae,
+    0x0c6388b51545e883,
+    0xd27dbb6944917b60,
+];
+
+/// Poseidon2 over Goldilocks with a state of 8 elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Poseidon2Goldilocks8;
+
+impl Poseidon2Params for Poseidon2Goldilocks8 {
+    type ElemT = Goldilocks;
+
+    const SBOX_POW: usize = 7;
+
+    const FULL_ROUNDS: usize = 8;
+
+    const PARTIAL_ROUNDS: usize = 22;
+
+    const STATE_WIDTH: usize = 8;
+
+    fn internal_diag_m_1() -> Vec<Self::ElemT> {
+        MAT_DIAG8_M_1.map(Goldilocks::from).to_vec()
+    }
+}
+
+/// Poseidon2 over Goldilocks with a state of 12 elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Poseidon2Goldilocks12;
+
+impl Poseidon2Params for Poseidon2Goldilocks12 {
+    type ElemT = Goldilocks;
+
+    const SBOX_POW: usize = 7;
+
+    const FULL_ROUNDS: usize = 8;
+
+    const PARTIAL_ROUNDS: usize = 22;
+
+    const STATE_WIDTH: usize = 12;
+
+    fn internal_diag_m_1() -> Vec<Self::ElemT> {
+        MAT_DIAG12_M_1.map(Goldilocks::from).to_vec()
+    }
+}
@@ -1,20 +1,24 @@
 use arith::{
     random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_from_limbs_to_limbs_tests, random_inversion_tests, random_simd_field_tests,
-    ExtensionField, Field,
+    random_from_limbs_to_limbs_tests, random_inversion_tests, random_simd_field_equivalence_tests,
+    random_simd_field_tests, ExtensionField, Field,
 };
 use ark_std::test_rng;
 use ethnum::U256;
+use gkr_hashers::{FiatShamirHasher, Poseidon2FiatShamirHasher, Poseidon2Params};
 use rand::thread_rng;
 use serdes::ExpSerde;
 
 use crate::{
-    goldilocks::mod_reduce_u64, Goldilocks, GoldilocksExt2, GoldilocksExt2x8, Goldilocksx8,
+    goldilocks::mod_reduce_u64, goldilocksx8::goldilocks_portable::PortableGoldilocks, Goldilocks,
+    GoldilocksExt2, GoldilocksExt2x8, Goldilocksx8, Poseidon2Goldilocks12, Poseidon2Goldilocks8,
     EPSILON, GOLDILOCKS_MOD,
 };
 
 fn get_avx_version() -> &'static str {
-    if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+    if cfg!(feature = "portable") {
+        return "Portable";
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
         return "AVX512";
     } else if cfg!(all(
         target_arch = "x86_64",
@@ -25,7 +29,7 @@ fn get_avx_version() -> &'static str {
     } else if cfg!(target_arch = "aarch64") {
         return "arm64";
     }
-    "Unknown"
+    "Portable"
 }
 
 #[test]
@@ -34,6 +38,7 @@ fn test_avx_version() {
     println!("Current AVX version: {}", avx_version);
     assert!([
         "arm64",
+        "Portable",
         "AVX512",
         "AVX2 (256-bit)",
         "AVX (256-bit)",
@@ -72,6 +77,20 @@ fn test_simd_field() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn test_portable_simd_field() {
+    random_field_tests::<PortableGoldilocks>("Portable Goldilocksx8".to_string());
+    random_simd_field_tests::<PortableGoldilocks>("Portable Goldilocksx8".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<PortableGoldilocks, _>(&mut rng, "Portable Goldilocksx8".to_string());
+
+    // bit-identical to the SIMD implementation of the target
+    random_simd_field_equivalence_tests::<Goldilocksx8, PortableGoldilocks>(
+        "Portable Goldilocksx8".to_string(),
+    );
+}
+
 #[test]
 fn test_ext_field() {
     random_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
@@ -239,3 +258,48 @@ fn test_edge_cases() {
     let x = GoldilocksExt2::X;
     assert_eq!(x * x, GoldilocksExt2::from(Goldilocks::from(7u32)));
 }
+
+fn test_poseidon2_permutation_helper<P: Poseidon2Params<ElemT = Goldilocks>>(expected: &[u64]) {
+    let perm = Poseidon2FiatShamirHasher::<P>::new();
+
+    let mut state = (0..P::STATE_WIDTH as u64)
+        .map(Goldilocks::from)
+        .collect::<Vec<_>>();
+    perm.permute(&mut state);
+    assert_eq!(
+        state,
+        expected
+            .iter()
+            .cloned()
+            .map(Goldilocks::from)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_poseidon2_permutation() {
+    test_poseidon2_permutation_helper::<Poseidon2Goldilocks8>(&[
+        0xf019d83b44a1b6bc,
+        0x08cd8462266e2756,
+        0x76b7c3f722d1c69c,
+        0x2eef5c767f054422,
+        0x5fa7e1e57333be8a,
+        0x1ef3495c03193c77,
+        0x53a7d68af3ec2aa2,
+        0xa425b47d55febd5d,
+    ]);
+    test_poseidon2_permutation_helper::<Poseidon2Goldilocks12>(&[
+        0x7dad28df76f00a0c,
+        0x7eeee15a719b2c83,
+        0x9cbeeaab2a21779f,
+        0x55d01e3390da190f,
+        0xc1b07fdd2b1f52d5,
+        0x0eddb45a904bb791,
+        0x5d4a5a83ec7044eb,
+        0xea94a554cd4cb8da,
+        0x05b264ec4aed4aa6,
+        0x3f142c01fb5938d9,
+        0x5b7ce4cb9689b006,
+        0x5a899329ab43d2bb,
+    ]);
+}
@@ -7,6 +7,7 @@ pub use m31::M31;
 /// SIMDx16 for Mersenne-31
 mod m31x16;
 pub use m31x16::M31x16;
+pub use m31x16::AVX512M31x16;
 
 /// Poseidon over Mersenne-31
 mod poseidon;
@@ -23,6 +24,7 @@ pub use m31_ext6::M31Ext6;
 /// SIMDx16 for Degree 3 extension field for Mersenne-31
 mod m31_ext3x16;
 pub use m31_ext3x16::M31Ext3x16;
+pub use m31_ext3x16::AVX512M31Ext3x16;
 
 #[cfg(test)]
 mod tests;
@@ -29,7 +29,10 @@ unsafe fn mod_reduce_epi32_2(x: [__m256i; 2]) -> [__m256i; 2] {
     [mod_reduce_epi32(x[0]), mod_reduce_epi32(x[1])]
 }
 
+// 64-byte aligned as the AVX-512 implementation, for the prover hot paths to run on the latter
+// in place, see `arith::SimdBackend`
 #[derive(Clone, Copy)]
+#[repr(align(64))]
 pub struct AVXM31 {
     pub v: [__m256i; 2],
 }
@@ -0,0 +1,304 @@
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, SimdField};
+use ethnum::U256;
+use rand::RngCore;
+use serdes::{ExpSerde, SerdeResult};
+
+use crate::m31::{mod_reduce_u32_safe, M31, M31_MOD};
+
+const M31_PACK_SIZE: usize = 16;
+
+/// PortableM31 packs 16 M31 elements in an array, operated on lane by lane.
+/// Used on targets without AVX2 or NEON, or with the `portable` feature.
+#[derive(Clone, Copy)]
+pub struct PortableM31 {
+    pub v: [M31; M31_PACK_SIZE],
+}
+
+field_common!(PortableM31);
+
+impl Ord for PortableM31 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for M31x16 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for PortableM31 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for M31x16 is not supported")
+    }
+}
+
+impl ExpSerde for PortableM31 {
+    #[inline(always)]
+    /// serialize self into bytes, the lanes being fully reduced as in the AVX layout
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        let mut data = [0u8; 64];
+        data.chunks_exact_mut(4)
+            .zip(self.v.iter())
+            .for_each(|(d, x)| d.copy_from_slice(&mod_reduce_u32_safe(x.v).to_le_bytes()));
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut v = [M31::ZERO; M31_PACK_SIZE];
+        for x in v.iter_mut() {
+            *x = M31::deserialize_from(&mut reader)?;
+        }
+        Ok(Self { v })
+    }
+}
+
+impl Field for PortableM31 {
+    const NAME: &'static str = "Portable Packed Mersenne 31";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self {
+        v: [M31::ZERO; M31_PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [M31::ONE; M31_PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [M31::INV_2; M31_PACK_SIZE],
+    };
+
+    const MODULUS: U256 = M31::MODULUS;
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        // Caution: this may not produce uniformly random elements
+        Self {
+            v: std::array::from_fn(|_| M31::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        Self {
+            v: std::array::from_fn(|_| M31::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn double(&self) -> Self {
+        self.mul_by_2()
+    }
+
+    #[inline(always)]
+    fn mul_by_2(&self) -> Self {
+        Self {
+            v: self.v.map(|x| x.double()),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_5(&self) -> Self {
+        Self {
+            v: self.v.map(|x| x.mul_by_5()),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+
+        Some(Self {
+            v: self.v.map(|x| x.inv().unwrap()), // safe unwrap
+        })
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let m = M31::from_uniform_bytes(bytes);
+        Self::pack_full(&m)
+    }
+}
+
+impl SimdField for PortableM31 {
+    type Scalar = M31;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    const PACK_SIZE: usize = M31_PACK_SIZE;
+
+    #[inline(always)]
+    fn pack_full(x: &M31) -> PortableM31 {
+        PortableM31 {
+            v: [*x; M31_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == M31_PACK_SIZE);
+        Self {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(&self) -> Self::Scalar {
+        let mut buffer: u64 = self.v.iter().map(|x| x.v as u64).sum();
+
+        buffer = (buffer & M31_MOD as u64) + (buffer >> 31);
+        if buffer == M31_MOD as u64 {
+            Self::Scalar::ZERO
+        } else {
+            Self::Scalar { v: buffer as u32 }
+        }
+    }
+}
+
+impl From<M31> for PortableM31 {
+    #[inline(always)]
+    fn from(x: M31) -> Self {
+        PortableM31::pack_full(&x)
+    }
+}
+
+impl Debug for PortableM31 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.v.map(|x| x.v);
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(
+                f,
+                "[M31; 16]<16 x {}>",
+                if M31_MOD - data[0] > 1024 {
+                    format!("{}", data[0])
+                } else {
+                    format!("-{}", M31_MOD - data[0])
+                }
+            )
+        } else {
+            write!(f, "[M31; 16]<{data:?}>")
+        }
+    }
+}
+
+impl Default for PortableM31 {
+    fn default() -> Self {
+        PortableM31::zero()
+    }
+}
+
+impl PartialEq for PortableM31 {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl Eq for PortableM31 {}
+
+impl Mul<&M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn mul(self, rhs: &M31) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x * rhs),
+        }
+    }
+}
+
+impl Mul<M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn mul(self, rhs: M31) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x + rhs),
+        }
+    }
+}
+
+impl From<u32> for PortableM31 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        PortableM31::pack_full(&M31::from(x))
+    }
+}
+
+impl Neg for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
+
+impl std::hash::Hash for PortableM31 {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.v.iter().for_each(|x| state.write_u32(x.v));
+    }
+}
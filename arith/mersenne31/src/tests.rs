@@ -1,6 +1,7 @@
 use arith::{
     random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_inversion_tests, random_simd_field_tests, SimdField,
+    random_inversion_tests, random_simd_field_equivalence_tests, random_simd_field_tests,
+    SimdField,
 };
 use arith::{random_from_limbs_to_limbs_tests, Field};
 use ark_std::test_rng;
@@ -10,11 +11,14 @@ use serdes::ExpSerde;
 
 use crate::{
     m31::{mod_reduce_u32_safe, M31_MOD},
+    m31x16::m31_portable::PortableM31,
     M31Ext3, M31Ext3x16, M31Ext6, M31x16, M31,
 };
 
 fn get_avx_version() -> &'static str {
-    if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+    if cfg!(feature = "portable") {
+        return "Portable";
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
         return "AVX512";
     } else if cfg!(all(
         target_arch = "x86_64",
@@ -25,7 +29,7 @@ fn get_avx_version() -> &'static str {
     } else if cfg!(target_arch = "aarch64") {
         return "arm64";
     }
-    "Unknown"
+    "Portable"
 }
 
 #[test]
@@ -34,6 +38,7 @@ fn test_avx_version() {
     println!("Current AVX version: {}", avx_version);
     assert!([
         "arm64",
+        "Portable",
         "AVX512",
         "AVX2 (256-bit)",
         "AVX (256-bit)",
@@ -70,6 +75,18 @@ fn test_simd_field() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn test_portable_simd_field() {
+    random_field_tests::<PortableM31>("Portable M31".to_string());
+    random_simd_field_tests::<PortableM31>("Portable M31".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<PortableM31, _>(&mut rng, "Portable M31".to_string());
+
+    // bit-identical to the SIMD implementation of the target
+    random_simd_field_equivalence_tests::<M31x16, PortableM31>("Portable M31".to_string());
+}
+
 #[test]
 fn test_ext_field() {
     random_field_tests::<M31Ext3>("M31 Ext3".to_string());
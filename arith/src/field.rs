@@ -42,6 +42,7 @@ pub trait Field:
     + Ord
     + Send
     + Sync
+    + 'static
 {
     /// name
     const NAME: &'static str;
@@ -16,6 +16,9 @@ pub use bn254::*;
 mod simd_field;
 pub use simd_field::*;
 
+mod simd_backend;
+pub use simd_backend::*;
+
 mod macros;
 
 mod utils;
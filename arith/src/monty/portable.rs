@@ -0,0 +1,5 @@
+use super::MontyParameters;
+
+/// PackedMontyParameters contains constants needed for MONTY operations for packings of Monty31
+/// fields.
+pub trait PackedMontyParameters: MontyParameters {}
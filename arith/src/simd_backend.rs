@@ -0,0 +1,141 @@
+//! The SIMD backend of the packed fields, e.g. M31x16.
+//!
+//! The backend of the packed field types is fixed at compile time. On x86_64 builds with AVX2 but
+//! without `avx512f` enabled at compile time, the AVX-512 implementations of the packed fields are
+//! compiled in as well, and the prover hot paths switch to them at runtime on CPUs supporting
+//! AVX-512. The two implementations share the memory layout and the arithmetic, so that the proofs
+//! are identical on both.
+
+use std::sync::OnceLock;
+
+/// Environment variable to run the prover on a lower backend than the detected one, e.g.
+/// `avx256` on an AVX-512 machine, to compare the proofs of both.
+pub const SIMD_BACKEND_ENV: &str = "EXPANDER_SIMD_BACKEND";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdBackend {
+    /// The pure Rust array-based implementations
+    Portable,
+    /// The aarch64 NEON implementations
+    Neon,
+    /// The x86_64 AVX2 implementations
+    Avx256,
+    /// The x86_64 AVX-512 implementations
+    Avx512,
+}
+
+impl SimdBackend {
+    /// The backend of the packed field types, chosen at compile time
+    #[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+    pub const COMPILED: Self = Self::Neon;
+
+    /// The backend of the packed field types, chosen at compile time
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        target_feature = "avx512f",
+        not(feature = "portable")
+    ))]
+    pub const COMPILED: Self = Self::Avx512;
+
+    /// The backend of the packed field types, chosen at compile time
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f"),
+        not(feature = "portable")
+    ))]
+    pub const COMPILED: Self = Self::Avx256;
+
+    /// The backend of the packed field types, chosen at compile time
+    #[cfg(any(
+        feature = "portable",
+        not(any(
+            target_arch = "aarch64",
+            all(target_arch = "x86_64", target_feature = "avx2")
+        ))
+    ))]
+    pub const COMPILED: Self = Self::Portable;
+
+    /// Whether the AVX-512 implementations are compiled in next to the AVX2 packed field types,
+    /// for the hot paths to be dispatched to them at runtime.
+    pub const AVX512_DISPATCH: bool = cfg!(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f"),
+        not(feature = "portable")
+    ));
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Portable => "portable",
+            Self::Neon => "neon",
+            Self::Avx256 => "avx256",
+            Self::Avx512 => "avx512",
+        }
+    }
+
+    #[inline]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "portable" => Some(Self::Portable),
+            "neon" => Some(Self::Neon),
+            "avx256" | "avx2" => Some(Self::Avx256),
+            "avx512" => Some(Self::Avx512),
+            _ => None,
+        }
+    }
+
+    /// The backend the prover hot paths run on, detected once per process. It is
+    /// [`Self::COMPILED`], or AVX-512 if the AVX-512 implementations are compiled in and the CPU
+    /// and OS support them, unless lowered by [`SIMD_BACKEND_ENV`].
+    ///
+    /// If [`SIMD_BACKEND_ENV`] names a backend that is not available, a warning is logged and the
+    /// detected backend is used.
+    #[inline]
+    pub fn runtime() -> Self {
+        static BACKEND: OnceLock<SimdBackend> = OnceLock::new();
+        *BACKEND.get_or_init(|| {
+            let detected = Self::detect();
+            match std::env::var(SIMD_BACKEND_ENV) {
+                Ok(name) => match Self::from_name(&name) {
+                    Some(backend) if backend == detected || backend == Self::COMPILED => backend,
+                    _ => {
+                        log::warn!(
+                            "{SIMD_BACKEND_ENV}={name} is not available, expected {} or {}, \
+                             running on {}",
+                            Self::COMPILED.name(),
+                            detected.name(),
+                            detected.name()
+                        );
+                        detected
+                    }
+                },
+                Err(_) => detected,
+            }
+        })
+    }
+
+    /// The backend of the CPU, among the compiled in ones
+    #[inline]
+    pub fn detect() -> Self {
+        #[cfg(all(
+            target_arch = "x86_64",
+            target_feature = "avx2",
+            not(target_feature = "avx512f"),
+            not(feature = "portable")
+        ))]
+        if std::arch::is_x86_feature_detected!("avx512f") {
+            return Self::Avx512;
+        }
+
+        Self::COMPILED
+    }
+}
+
+impl std::fmt::Display for SimdBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -5,6 +5,7 @@ use field::{
     random_negation_tests, random_serde_tests, random_squaring_tests, random_subtraction_tests,
 };
 use rand::RngCore;
+use serdes::ExpSerde;
 
 use crate::{ExtensionField, FFTField, Field, SimdField};
 
@@ -13,7 +14,7 @@ mod bn254;
 
 mod field;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
 #[test]
 fn test_mm256_const_init() {
     use std::arch::x86_64::*;
@@ -193,6 +194,122 @@ pub fn random_simd_field_tests<F: SimdField>(_name: String) {
     }
 }
 
+#[inline]
+fn serialized<T: ExpSerde>(x: &T) -> Vec<u8> {
+    let mut buffer = vec![];
+    x.serialize_into(&mut buffer).unwrap();
+    buffer
+}
+
+/// Convert between two implementations of the same field through their serialization
+#[inline]
+fn convert<F: ExpSerde, G: ExpSerde>(x: &F) -> G {
+    G::deserialize_from(serialized(x).as_slice()).unwrap()
+}
+
+/// Check that two implementations of the same field, e.g., a SIMD one and the portable one,
+/// produce bit-identical serializations on random inputs
+pub fn random_field_equivalence_tests<F: Field, G: Field>(type_name: String) {
+    let mut rng = test_rng();
+    let _message = format!("equivalence {type_name}");
+    let start = start_timer!(|| _message);
+
+    assert_eq!(F::SIZE, G::SIZE);
+    assert_eq!(F::FIELD_SIZE, G::FIELD_SIZE);
+    assert_eq!(serialized(&F::ZERO), serialized(&G::ZERO));
+    assert_eq!(serialized(&F::ONE), serialized(&G::ONE));
+    // INV_2 is left out: 2 = 0 in the binary fields, whose SIMD layouts disagree on it
+
+    for _ in 0..1000 {
+        let a = F::random_unsafe(&mut rng);
+        let b = F::random_unsafe(&mut rng);
+        let ga: G = convert(&a);
+        let gb: G = convert(&b);
+        assert_eq!(serialized(&a), serialized(&ga));
+        assert_eq!(a.is_zero(), ga.is_zero());
+
+        assert_eq!(serialized(&(a + b)), serialized(&(ga + gb)));
+        assert_eq!(serialized(&(a - b)), serialized(&(ga - gb)));
+        assert_eq!(serialized(&(a * b)), serialized(&(ga * gb)));
+        assert_eq!(serialized(&(-a)), serialized(&(-ga)));
+        assert_eq!(serialized(&a.double()), serialized(&ga.double()));
+        assert_eq!(serialized(&a.square()), serialized(&ga.square()));
+        assert_eq!(serialized(&a.mul_by_3()), serialized(&ga.mul_by_3()));
+        assert_eq!(serialized(&a.mul_by_5()), serialized(&ga.mul_by_5()));
+        assert_eq!(serialized(&a.mul_by_6()), serialized(&ga.mul_by_6()));
+
+        let e = rng.next_u64() as u128;
+        assert_eq!(serialized(&a.exp(e)), serialized(&ga.exp(e)));
+    }
+    end_timer!(start);
+}
+
+/// Check that two implementations of the same SIMD field agree bit for bit on the SIMD
+/// operations, on top of [random_field_equivalence_tests]
+pub fn random_simd_field_equivalence_tests<F: SimdField, G: SimdField<Scalar = F::Scalar>>(
+    type_name: String,
+) {
+    random_field_equivalence_tests::<F, G>(type_name);
+
+    let mut rng = test_rng();
+    assert_eq!(F::PACK_SIZE, G::PACK_SIZE);
+
+    for _ in 0..1000 {
+        let a = F::random_unsafe(&mut rng);
+        let ga: G = convert(&a);
+        let s = F::Scalar::random_unsafe(&mut rng);
+
+        assert_eq!(serialized(&a.scale(&s)), serialized(&ga.scale(&s)));
+        assert_eq!(serialized(&F::from(s)), serialized(&G::from(s)));
+        assert_eq!(serialized(&F::pack_full(&s)), serialized(&G::pack_full(&s)));
+        assert_eq!(
+            serialized(&a.horizontal_sum()),
+            serialized(&ga.horizontal_sum())
+        );
+
+        let scalars = a.unpack();
+        assert_eq!(serialized(&scalars), serialized(&ga.unpack()));
+        assert_eq!(serialized(&G::pack(&scalars)), serialized(&a));
+    }
+}
+
+/// Check that two implementations of the same extension field agree bit for bit on the
+/// extension field operations, on top of [random_field_equivalence_tests]
+pub fn random_extension_field_equivalence_tests<
+    F: ExtensionField,
+    G: ExtensionField<BaseField = F::BaseField>,
+>(
+    type_name: String,
+) {
+    random_field_equivalence_tests::<F, G>(type_name);
+
+    let mut rng = test_rng();
+    assert_eq!(F::DEGREE, G::DEGREE);
+    assert_eq!(F::W, G::W);
+    assert_eq!(serialized(&F::X), serialized(&G::X));
+
+    for _ in 0..1000 {
+        let a = F::random_unsafe(&mut rng);
+        let ga: G = convert(&a);
+        let s = F::BaseField::random_unsafe(&mut rng);
+
+        assert_eq!(
+            serialized(&a.mul_by_base_field(&s)),
+            serialized(&ga.mul_by_base_field(&s))
+        );
+        assert_eq!(
+            serialized(&a.add_by_base_field(&s)),
+            serialized(&ga.add_by_base_field(&s))
+        );
+        assert_eq!(serialized(&a.mul_by_x()), serialized(&ga.mul_by_x()));
+        assert_eq!(serialized(&F::from(s)), serialized(&G::from(s)));
+
+        let limbs = a.to_limbs();
+        assert_eq!(serialized(&limbs), serialized(&ga.to_limbs()));
+        assert_eq!(serialized(&G::from_limbs(&limbs)), serialized(&a));
+    }
+}
+
 pub fn random_fft_field_tests<F: Field + FFTField>(_name: String) {
     let mut rng = test_rng();
 
@@ -1,23 +1,83 @@
-use std::str::FromStr;
+use std::{fs, future::Future, pin::Pin, process::exit, str::FromStr};
 
 use bin::executor::*;
 use clap::Parser;
-use gkr::gkr_configs::*;
+use gkr::{GKRConfigVisitor, dispatch_gkr_config};
+#[cfg(feature = "socket")]
+use gkr_engine::SocketCommunicator;
 use gkr_engine::{
-    FiatShamirHashType, FieldType, MPIConfig, MPIEngine, PolynomialCommitmentType, root_println,
+    CommBackend, FiatShamirHashType, FieldEngine, GKREngine, MPIConfig, MPIEngine,
+    PolynomialCommitmentType, root_println,
 };
 
 #[tokio::main]
 async fn main() {
     let expander_exec_args = ExpanderExecArgs::parse();
 
-    let fs_hash_type = FiatShamirHashType::from_str(&expander_exec_args.fiat_shamir_hash).unwrap();
-    let pcs_type =
-        PolynomialCommitmentType::from_str(&expander_exec_args.poly_commitment_scheme).unwrap();
+    let fs_hash_type = expander_exec_args
+        .fiat_shamir_hash
+        .as_ref()
+        .map(|s| FiatShamirHashType::from_str(s).unwrap());
+    let pcs_type = expander_exec_args
+        .poly_commitment_scheme
+        .as_ref()
+        .map(|s| PolynomialCommitmentType::from_str(s).unwrap());
 
-    let universe = MPIConfig::init().unwrap();
-    let world = universe.world();
-    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    // A proof records the config it was generated with, so the verifier takes it from there
+    let (fs_hash_type, pcs_type) = match &expander_exec_args.subcommands {
+        ExpanderExecSubCommand::Verify {
+            input_proof_file, ..
+        } => {
+            let bytes = fs::read(input_proof_file).expect("Unable to read proof from file.");
+            let header = load_proof_header(&bytes).unwrap_or_else(|_| {
+                println!("Unable to read proof header from {input_proof_file}.");
+                exit(1);
+            });
+            if fs_hash_type
+                .as_ref()
+                .is_some_and(|t| *t != header.fiat_shamir_hash_type)
+                || pcs_type.as_ref().is_some_and(|t| *t != header.pcs_type)
+            {
+                println!(
+                    "Proof is generated with FS: {:?}, PCS: {:?}, which does not match the command line.",
+                    header.fiat_shamir_hash_type, header.pcs_type
+                );
+                exit(1);
+            }
+            (header.fiat_shamir_hash_type, header.pcs_type)
+        }
+        _ => (
+            fs_hash_type.unwrap_or_default(),
+            pcs_type.unwrap_or_default(),
+        ),
+    };
+
+    // Processes launched with EXPANDER_RENDEZVOUS communicate through sockets instead of MPI
+    #[cfg(feature = "socket")]
+    let socket = SocketCommunicator::from_env().map(|socket| -> Box<dyn CommBackend> {
+        Box::new(socket.unwrap_or_else(|e| {
+            println!("Unable to connect to the other processes: {e}");
+            exit(1);
+        }))
+    });
+    #[cfg(not(feature = "socket"))]
+    let socket = Option::<Box<dyn CommBackend>>::None;
+
+    // Without MPI, the processes are either connected by sockets or the prover runs alone
+    #[cfg(feature = "mpi")]
+    let universe = match socket {
+        Some(_) => None,
+        None => Some(MPIConfig::init().unwrap()),
+    };
+    #[cfg(feature = "mpi")]
+    let world = universe.as_ref().map(|universe| universe.world());
+    let mpi_config = match &socket {
+        Some(socket) => MPIConfig::backend_new(socket.as_ref()),
+        #[cfg(feature = "mpi")]
+        None => MPIConfig::prover_new(universe.as_ref(), world.as_ref()),
+        #[cfg(not(feature = "mpi"))]
+        None => MPIConfig::default(),
+    };
     root_println!(mpi_config, "Fiat-Shamir Hash Type: {:?}", &fs_hash_type);
     root_println!(
         mpi_config,
@@ -25,49 +85,53 @@ async fn main() {
         &pcs_type
     );
 
-    // Get circuit_file based on subcommand
-    let circuit_file = match &expander_exec_args.subcommands {
-        ExpanderExecSubCommand::Prove { circuit_file, .. } => circuit_file,
-        ExpanderExecSubCommand::Verify { circuit_file, .. } => circuit_file,
-        ExpanderExecSubCommand::Serve { circuit_file, .. } => circuit_file,
+    // Get the field type from the circuit file, or from the text dump for the import subcommand
+    let field_type = match &expander_exec_args.subcommands {
+        ExpanderExecSubCommand::Setup { circuit_file, .. }
+        | ExpanderExecSubCommand::Prove { circuit_file, .. }
+        | ExpanderExecSubCommand::Verify { circuit_file, .. }
+        | ExpanderExecSubCommand::Serve { circuit_file, .. }
+        | ExpanderExecSubCommand::Check { circuit_file, .. }
+        | ExpanderExecSubCommand::Inspect { circuit_file, .. } => {
+            detect_field_type_from_circuit_file(circuit_file)
+        }
+        ExpanderExecSubCommand::Import {
+            input_text_file, ..
+        } => detect_field_type_from_circuit_text(input_text_file),
     };
-
-    let field_type = detect_field_type_from_circuit_file(circuit_file);
     root_println!(&mpi_config, "field type: {:?}", field_type);
 
-    match (fs_hash_type.clone(), pcs_type.clone(), field_type.clone()) {
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::M31x16) => {
-            run_command::<M31x16ConfigSha2OrionVanilla>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::Poseidon, PolynomialCommitmentType::Raw, FieldType::M31x16) => {
-            run_command::<M31x16ConfigPoseidonRawVanilla>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::MIMC5, PolynomialCommitmentType::Raw, FieldType::BN254) => {
-            run_command::<BN254ConfigMIMC5Raw>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::BN254) => {
-            run_command::<BN254ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Hyrax, FieldType::BN254) => {
-            run_command::<BN254ConfigSha2Hyrax>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::MIMC5, PolynomialCommitmentType::KZG, FieldType::BN254) => {
-            run_command::<BN254ConfigMIMC5KZG>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::GF2Ext128) => {
-            run_command::<GF2ExtConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::GF2Ext128) => {
-            run_command::<GF2ExtConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::Goldilocksx8) => {
-            run_command::<Goldilocksx8ConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
-        }
-        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::BabyBearx16) => {
-            run_command::<BabyBearx16ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
-        }
-        _ => panic!(
+    dispatch_gkr_config(
+        fs_hash_type.clone(),
+        pcs_type.clone(),
+        field_type.clone(),
+        RunCommand {
+            args: &expander_exec_args,
+            mpi_config: &mpi_config,
+        },
+    )
+    .unwrap_or_else(|| {
+        panic!(
             "FS: {fs_hash_type:?}, PCS: {pcs_type:?}, Field: {field_type:?} setting is not yet integrated in expander-exec"
-        ),
+        )
+    })
+    .await;
+}
+
+/// Runs the command on the config picked at runtime
+struct RunCommand<'a, 'b> {
+    args: &'b ExpanderExecArgs,
+    mpi_config: &'b MPIConfig<'a>,
+}
+
+impl<'b> GKRConfigVisitor for RunCommand<'_, 'b> {
+    type Output = Pin<Box<dyn Future<Output = ()> + 'b>>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        Box::pin(run_command::<Cfg>(self.args, self.mpi_config))
     }
 }
@@ -1,41 +1,54 @@
-use std::{
-    fs,
-    io::Cursor,
-    process::exit,
-    sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, fs, io::Cursor, num::NonZeroUsize, process::exit, sync::Arc};
 
 use arith::Field;
-use circuit::Circuit;
+use circuit::{Circuit, RecursiveCircuit, circuit_text_modulus};
 use clap::{Parser, Subcommand};
 use gkr::{Prover, Verifier};
 use gkr_engine::{
-    BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config, M31x16Config,
-    MPIConfig, MPIEngine, Proof, SharedMemory,
+    ExpanderPCS, FieldEngine, FieldType, GKREngine, MPIConfig, MPIEngine, Proof, ProofEnvelope,
+    ProofHeader, SharedMemory, StatementBinding, root_println,
 };
-use log::info;
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
 use poly_commit::expander_pcs_init_testing_only;
 use serdes::{ExpSerde, SerdeError};
-use warp::{Filter, http::StatusCode, reply};
+
+use crate::{
+    pcs_setup::{
+        PCSParamsOf, PCSProvingKeyOf, PCSScratchPadOf, PCSSetupSource, PCSVerifyingKeyOf,
+        load_pcs_params, load_pcs_proving_key, load_pcs_verifying_key, pcs_setup,
+    },
+    service::{DEFAULT_CIRCUIT, ProvingService, RegisteredCircuit, routes},
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct ExpanderExecArgs {
-    /// Fiat-Shamir Hash: SHA256, or Poseidon, or MiMC5
-    #[arg(short, long, default_value = "SHA256")]
-    pub fiat_shamir_hash: String,
+    /// Fiat-Shamir Hash: SHA256, or Poseidon, or MiMC5. Defaults to SHA256, or to the hash
+    /// recorded in the proof for the verify subcommand
+    #[arg(short, long)]
+    pub fiat_shamir_hash: Option<String>,
 
-    /// Polynomial Commitment Scheme: Raw, or Orion
-    #[arg(short, long, default_value = "Raw")]
-    pub poly_commitment_scheme: String,
+    /// Polynomial Commitment Scheme: Raw, or Orion. Defaults to Raw, or to the PCS recorded in
+    /// the proof for the verify subcommand
+    #[arg(short, long)]
+    pub poly_commitment_scheme: Option<String>,
 
-    /// Prove, Verify, or Serve subcommands
+    /// Setup, Prove, Verify, Serve, Check, Inspect, or Import subcommands
     #[clap(subcommand)]
     pub subcommands: ExpanderExecSubCommand,
 }
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum ExpanderExecSubCommand {
+    Setup {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Output PCS Setup Directory
+        #[arg(short = 's', long)]
+        pcs_setup_dir: String,
+    },
     Prove {
         /// Circuit File Path
         #[arg(short, long)]
@@ -48,6 +61,15 @@ pub enum ExpanderExecSubCommand {
         /// Output Proof Path
         #[arg(short, long)]
         output_proof_file: String,
+
+        /// PCS Setup Directory written by the setup subcommand
+        #[arg(short = 's', long, required_unless_present = "insecure_testing_setup")]
+        pcs_setup_dir: Option<String>,
+
+        /// Use the PCS setup derived from a fixed seed instead of a setup directory. It is
+        /// insecure and only meant for testing
+        #[arg(long, conflicts_with = "pcs_setup_dir")]
+        insecure_testing_setup: bool,
     },
     Verify {
         /// Circuit File Path
@@ -62,9 +84,18 @@ pub enum ExpanderExecSubCommand {
         #[arg(short, long)]
         input_proof_file: String,
 
-        /// MPI size
-        #[arg(short, long, default_value_t = 1)]
-        mpi_size: u32,
+        /// MPI size of the prover, defaults to the one recorded in the proof
+        #[arg(short, long)]
+        mpi_size: Option<u32>,
+
+        /// PCS Setup Directory written by the setup subcommand
+        #[arg(short = 's', long, required_unless_present = "insecure_testing_setup")]
+        pcs_setup_dir: Option<String>,
+
+        /// Use the PCS setup derived from a fixed seed instead of a setup directory. It is
+        /// insecure and only meant for testing
+        #[arg(long, conflicts_with = "pcs_setup_dir")]
+        insecure_testing_setup: bool,
     },
     Serve {
         /// Circuit File Path
@@ -78,42 +109,190 @@ pub enum ExpanderExecSubCommand {
         /// IP Port
         #[arg(short, long)]
         port: u16,
+
+        /// PCS Setup Directory written by the setup subcommand
+        #[arg(short = 's', long, required_unless_present = "insecure_testing_setup")]
+        pcs_setup_dir: Option<String>,
+
+        /// Use the PCS setup derived from a fixed seed instead of a setup directory. It is
+        /// insecure and only meant for testing
+        #[arg(long, conflicts_with = "pcs_setup_dir")]
+        insecure_testing_setup: bool,
+
+        /// Additional circuit served under a name, with the same field, hash and PCS as the
+        /// circuit file: <NAME>=<CIRCUIT_FILE>[,<PCS_SETUP_DIR>], the setup directory being
+        /// required unless --insecure-testing-setup is given
+        #[arg(short, long)]
+        register: Vec<String>,
+
+        /// Number of proofs generated concurrently, at least 1
+        #[arg(long, default_value_t = NonZeroUsize::new(2).unwrap())]
+        workers: NonZeroUsize,
+    },
+    Check {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Witness File Path
+        #[arg(short, long)]
+        witness_file: String,
+
+        /// Maximum number of gates traced back from each non-zero output
+        #[arg(short = 't', long, default_value_t = 16)]
+        max_trace_gates: usize,
+    },
+    Inspect {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Output Text Dump Path
+        #[arg(short, long)]
+        dump_file: Option<String>,
+
+        /// Dump the flattened layered circuit rather than its segments, it cannot be imported
+        #[arg(long)]
+        flatten: bool,
+    },
+    Import {
+        /// Input Text Dump Path
+        #[arg(short, long)]
+        input_text_file: String,
+
+        /// Output Circuit Path
+        #[arg(short, long)]
+        output_circuit_file: String,
     },
 }
 
 pub fn dump_proof_and_claimed_v<F: Field>(
+    header: &ProofHeader,
     proof: &Proof,
     claimed_v: &F,
 ) -> Result<Vec<u8>, SerdeError> {
     let mut bytes = Vec::new();
 
+    header.serialize_into(&mut bytes)?;
     proof.serialize_into(&mut bytes)?;
     claimed_v.serialize_into(&mut bytes)?;
 
     Ok(bytes)
 }
 
-pub fn load_proof_and_claimed_v<F: Field>(bytes: &[u8]) -> Result<(Proof, F), SerdeError> {
+pub fn load_proof_and_claimed_v<F: Field>(bytes: &[u8]) -> Result<ProofEnvelope<F>, SerdeError> {
+    let mut cursor = Cursor::new(bytes);
+
+    ProofEnvelope::deserialize_from(&mut cursor)
+}
+
+/// Read only the header of a proof, e.g., to pick the config to verify it with.
+pub fn load_proof_header(bytes: &[u8]) -> Result<ProofHeader, SerdeError> {
     let mut cursor = Cursor::new(bytes);
 
-    let proof = Proof::deserialize_from(&mut cursor)?;
-    let claimed_v = F::deserialize_from(&mut cursor)?;
+    ProofHeader::deserialize_from(&mut cursor)
+}
 
-    Ok((proof, claimed_v))
+/// Hash of the circuit file, recorded in proofs to bind them to the circuit.
+pub fn circuit_hash(circuit_file: &str) -> [u8; 32] {
+    let bytes = fs::read(circuit_file).expect("Unable to read circuit file.");
+    let mut hash = [0u8; 32];
+    SHA256hasher::new().hash(&mut hash, &bytes);
+    hash
 }
 
 pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
     // read last 32 byte of sentinel field element to determine field type
     let bytes = fs::read(circuit_file).expect("Unable to read circuit file.");
-    let field_bytes = &bytes[8..8 + 32];
-    match field_bytes.try_into().unwrap() {
-        M31x16Config::SENTINEL => FieldType::M31x16,
-        BN254Config::SENTINEL => FieldType::BN254,
-        GF2ExtConfig::SENTINEL => FieldType::GF2Ext128,
-        Goldilocksx8Config::SENTINEL => FieldType::Goldilocksx8,
-        _ => {
-            println!("Unknown field type. Field byte value: {field_bytes:?}");
-            exit(1);
+    field_type_from_sentinel(bytes[8..8 + 32].try_into().unwrap())
+}
+
+pub fn detect_field_type_from_circuit_text(text_file: &str) -> FieldType {
+    let text = fs::read_to_string(text_file).expect("Unable to read circuit text file.");
+    let sentinel = circuit_text_modulus(&text).unwrap_or_else(|e| {
+        println!("Unable to read the modulus of {text_file}: {e}");
+        exit(1);
+    });
+    field_type_from_sentinel(sentinel)
+}
+
+fn field_type_from_sentinel(field_bytes: [u8; 32]) -> FieldType {
+    gkr::field_type_from_sentinel(&field_bytes).unwrap_or_else(|| {
+        println!("Unknown field type. Field byte value: {field_bytes:?}");
+        exit(1);
+    })
+}
+
+fn pcs_setup_source(
+    pcs_setup_dir: Option<&str>,
+    insecure_testing_setup: bool,
+) -> PCSSetupSource<'_> {
+    PCSSetupSource::new(pcs_setup_dir, insecure_testing_setup).unwrap_or_else(|e| {
+        println!("{e}");
+        exit(1);
+    })
+}
+
+/// Load the prover side of the PCS setup.
+pub fn prover_pcs_setup<Cfg: GKREngine>(
+    n_input_vars: usize,
+    mpi_config: &MPIConfig,
+    pcs_setup: PCSSetupSource,
+) -> (PCSParamsOf<Cfg>, PCSProvingKeyOf<Cfg>, PCSScratchPadOf<Cfg>) {
+    match pcs_setup {
+        PCSSetupSource::Dir(pcs_setup_dir) => {
+            let pcs_params =
+                load_pcs_params::<Cfg>(n_input_vars, mpi_config.world_size(), pcs_setup_dir)
+                    .unwrap_or_else(|e| panic!("Unable to load PCS params: {e}"));
+            let pcs_proving_key =
+                load_pcs_proving_key::<Cfg>(n_input_vars, mpi_config, pcs_setup_dir)
+                    .unwrap_or_else(|e| panic!("Unable to load PCS proving key: {e}"));
+            let pcs_scratch =
+                <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::init_scratch_pad(
+                    &pcs_params,
+                    mpi_config,
+                );
+            (pcs_params, pcs_proving_key, pcs_scratch)
+        }
+        PCSSetupSource::InsecureTestingOnly => {
+            root_println!(
+                mpi_config,
+                "WARNING: using the insecure testing only PCS setup"
+            );
+            let (pcs_params, pcs_proving_key, _, pcs_scratch) =
+                expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+                    n_input_vars,
+                    mpi_config,
+                );
+            (pcs_params, pcs_proving_key, pcs_scratch)
+        }
+    }
+}
+
+/// Load the verifier side of the PCS setup.
+pub fn verifier_pcs_setup<Cfg: GKREngine>(
+    n_input_vars: usize,
+    mpi_config: &MPIConfig,
+    pcs_setup: PCSSetupSource,
+) -> (PCSParamsOf<Cfg>, PCSVerifyingKeyOf<Cfg>) {
+    match pcs_setup {
+        PCSSetupSource::Dir(pcs_setup_dir) => {
+            let pcs_params =
+                load_pcs_params::<Cfg>(n_input_vars, mpi_config.world_size(), pcs_setup_dir)
+                    .unwrap_or_else(|e| panic!("Unable to load PCS params: {e}"));
+            let pcs_verification_key =
+                load_pcs_verifying_key::<Cfg>(n_input_vars, mpi_config.world_size(), pcs_setup_dir)
+                    .unwrap_or_else(|e| panic!("Unable to load PCS verifying key: {e}"));
+            (pcs_params, pcs_verification_key)
+        }
+        PCSSetupSource::InsecureTestingOnly => {
+            println!("WARNING: using the insecure testing only PCS setup");
+            let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
+                Cfg::FieldConfig,
+                Cfg::PCSField,
+                Cfg::PCSConfig,
+            >(n_input_vars, mpi_config);
+            (pcs_params, pcs_verification_key)
         }
     }
 }
@@ -121,6 +300,7 @@ pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
 pub fn prove<Cfg: GKREngine>(
     circuit: &mut Circuit<Cfg::FieldConfig>,
     mpi_config: MPIConfig,
+    pcs_setup: PCSSetupSource,
 ) -> (
     <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField,
     Proof,
@@ -131,12 +311,8 @@ where
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(circuit);
 
-    // TODO: Read PCS setup from files
-    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
-        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
-            circuit.log_input_size(),
-            &mpi_config,
-        );
+    let (pcs_params, pcs_proving_key, mut pcs_scratch) =
+        prover_pcs_setup::<Cfg>(circuit.log_input_size(), &mpi_config, pcs_setup);
 
     println!("proving");
     prover.prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch)
@@ -147,14 +323,13 @@ pub fn verify<Cfg: GKREngine>(
     mpi_config: MPIConfig,
     proof: &Proof,
     claimed_v: &<<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField,
+    pcs_setup: PCSSetupSource,
+    statement_binding: StatementBinding,
 ) -> bool {
-    // TODO: Read PCS setup from files
-    let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
-        Cfg::FieldConfig,
-        Cfg::PCSField,
-        Cfg::PCSConfig,
-    >(circuit.log_input_size(), &mpi_config);
-    let verifier = Verifier::<Cfg>::new(mpi_config);
+    let (pcs_params, pcs_verification_key) =
+        verifier_pcs_setup::<Cfg>(circuit.log_input_size(), &mpi_config, pcs_setup);
+    let mut verifier = Verifier::<Cfg>::new(mpi_config);
+    verifier.statement_binding = statement_binding;
     let public_input = circuit.public_input.clone();
     verifier.verify(
         circuit,
@@ -166,9 +341,11 @@ pub fn verify<Cfg: GKREngine>(
     )
 }
 
+// The 'Setup' command must be run with the same mpi size as the 'Prove' command
 // The 'Prove' command can be run with mpi more than one process
 // The 'Verify' command must be run with mpi size = 1
 // The 'Serve' command must be run with mpi size = 1
+// The 'Check' command must be run with the same mpi size as the 'Prove' command
 pub async fn run_command<'a, Cfg: GKREngine + 'static>(
     command: &ExpanderExecArgs,
     mpi_config: &MPIConfig<'a>,
@@ -178,20 +355,36 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
     let subcommands = command.subcommands.clone();
 
     match subcommands {
+        ExpanderExecSubCommand::Setup {
+            circuit_file,
+            pcs_setup_dir,
+        } => {
+            let circuit = Circuit::<Cfg::FieldConfig>::verifier_load_circuit::<Cfg>(&circuit_file);
+
+            pcs_setup::<Cfg>(circuit.log_input_size(), mpi_config, &pcs_setup_dir)
+                .unwrap_or_else(|e| panic!("Unable to write PCS setup: {e}"));
+
+            root_println!(mpi_config, "PCS setup written to {}", pcs_setup_dir);
+        }
         ExpanderExecSubCommand::Prove {
             circuit_file,
             witness_file,
             output_proof_file,
+            pcs_setup_dir,
+            insecure_testing_setup,
         } => {
+            let pcs_setup = pcs_setup_source(pcs_setup_dir.as_deref(), insecure_testing_setup);
             let (mut circuit, mut window) =
                 Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, mpi_config);
             let prover = Prover::<Cfg>::new(mpi_config.clone());
 
             circuit.prover_load_witness_file(&witness_file, mpi_config);
-            let (claimed_v, proof) = prove::<Cfg>(&mut circuit, mpi_config.clone());
+            let (claimed_v, proof) = prove::<Cfg>(&mut circuit, mpi_config.clone(), pcs_setup);
 
             if prover.mpi_config.is_root() {
-                let bytes = dump_proof_and_claimed_v(&proof, &claimed_v)
+                let header =
+                    ProofHeader::new::<Cfg>(mpi_config.world_size(), circuit_hash(&circuit_file));
+                let bytes = dump_proof_and_claimed_v(&header, &proof, &claimed_v)
                     .expect("Unable to serialize proof.");
                 fs::write(output_proof_file, bytes).expect("Unable to write proof to file.");
             }
@@ -203,7 +396,28 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             witness_file,
             input_proof_file,
             mpi_size,
+            pcs_setup_dir,
+            insecure_testing_setup,
         } => {
+            let pcs_setup = pcs_setup_source(pcs_setup_dir.as_deref(), insecure_testing_setup);
+            println!("loading proof file");
+
+            let bytes = fs::read(&input_proof_file).expect("Unable to read proof from file.");
+            let ProofEnvelope {
+                header,
+                proof,
+                claimed_v,
+            } = load_proof_and_claimed_v::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>(
+                &bytes,
+            )
+            .expect("Unable to deserialize proof.");
+
+            let mpi_size = mpi_size.map_or(header.proving_time_mpi_size, |s| s as usize);
+            if let Err(e) = header.check::<Cfg>(mpi_size, &circuit_hash(&circuit_file)) {
+                println!("Unable to verify {input_proof_file}: {e}");
+                exit(1);
+            }
+
             let mpi_config = MPIConfig::verifier_new(mpi_size as i32);
             let verifier = Verifier::<Cfg>::new(mpi_config);
 
@@ -224,35 +438,108 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
 
             circuit.verifier_load_witness_file(&witness_file, &verifier.mpi_config);
 
-            println!("loading proof file");
-
-            let bytes = fs::read(&input_proof_file).expect("Unable to read proof from file.");
-            let (proof, claimed_v) = load_proof_and_claimed_v::<
-                <Cfg::FieldConfig as FieldEngine>::ChallengeField,
-            >(&bytes)
-            .expect("Unable to deserialize proof.");
-
             println!("verifying proof");
 
             assert!(verify::<Cfg>(
                 &mut circuit,
                 verifier.mpi_config,
                 &proof,
-                &claimed_v
+                &claimed_v,
+                pcs_setup,
+                header.statement_binding(),
             ));
 
             println!("success");
         }
+        ExpanderExecSubCommand::Check {
+            circuit_file,
+            witness_file,
+            max_trace_gates,
+        } => {
+            let (mut circuit, mut window) =
+                Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, mpi_config);
+            circuit.prover_load_witness_file(&witness_file, mpi_config);
+
+            let failures = circuit.check_satisfiability(mpi_config, max_trace_gates);
+            if mpi_config.is_root() {
+                failures.iter().for_each(|failure| print!("{failure}"));
+                if failures.is_empty() {
+                    println!("satisfied");
+                } else {
+                    println!("unsatisfied: {} non-zero outputs", failures.len());
+                }
+            }
+            circuit.discard_control_of_shared_mem();
+            mpi_config.free_shared_mem(&mut window);
+
+            // the failures are gathered on the root, which exits with an error as verify does
+            if !failures.is_empty() {
+                exit(1);
+            }
+        }
+        ExpanderExecSubCommand::Inspect {
+            circuit_file,
+            dump_file,
+            flatten,
+        } => {
+            let rc = RecursiveCircuit::<Cfg::FieldConfig>::load(&circuit_file)
+                .expect("Unable to load circuit.");
+            let circuit = Circuit::<Cfg::FieldConfig>::verifier_load_circuit::<Cfg>(&circuit_file);
+
+            println!(
+                "{} segments, {} public inputs, {} outputs, {} expected to be zero",
+                rc.segments.len(),
+                rc.num_public_inputs,
+                rc.num_outputs,
+                rc.expected_num_output_zeros
+            );
+            println!(
+                "{} layers after pre-processing, {} input variables",
+                circuit.layers.len(),
+                circuit.log_input_size()
+            );
+            for (i, stats) in circuit.layer_stats().iter().enumerate() {
+                println!("layer {i}: {stats}");
+            }
+
+            if let Some(dump_file) = dump_file {
+                let text = if flatten {
+                    rc.flatten::<Cfg>().to_text()
+                } else {
+                    rc.to_text()
+                };
+                fs::write(&dump_file, text).expect("Unable to write text dump to file.");
+                println!("text dump written to {dump_file}");
+            }
+        }
+        ExpanderExecSubCommand::Import {
+            input_text_file,
+            output_circuit_file,
+        } => {
+            let text =
+                fs::read_to_string(&input_text_file).expect("Unable to read circuit text file.");
+            let rc = RecursiveCircuit::<Cfg::FieldConfig>::from_text(&text).unwrap_or_else(|e| {
+                println!("Unable to import {input_text_file}: {e}");
+                exit(1);
+            });
+
+            let mut bytes = vec![];
+            rc.serialize_into(&mut bytes)
+                .expect("Unable to serialize circuit.");
+            fs::write(&output_circuit_file, bytes).expect("Unable to write circuit to file.");
+            println!("circuit written to {output_circuit_file}");
+        }
         ExpanderExecSubCommand::Serve {
             circuit_file,
             host_ip,
             port,
+            pcs_setup_dir,
+            insecure_testing_setup,
+            register,
+            workers,
         } => {
-            let mpi_config = MPIConfig::prover_new(None, None);
-            let prover = Prover::<Cfg>::new(mpi_config.clone());
-
             assert!(
-                prover.mpi_config.world_size() == 1,
+                mpi_config.world_size() == 1,
                 "Serve mode is not compatible with mpi for now."
             );
             let host: [u8; 4] = host_ip
@@ -262,103 +549,42 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
                 .try_into()
                 .unwrap();
 
-            let (circuit, _) =
-                Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, &mpi_config);
-
-            // TODO: Read PCS setup from files
-            let (pcs_params, pcs_proving_key, pcs_verification_key, pcs_scratch) =
-                expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
-                    circuit.log_input_size(),
-                    &prover.mpi_config,
+            let mut circuits = HashMap::new();
+            circuits.insert(
+                DEFAULT_CIRCUIT.to_string(),
+                RegisteredCircuit::<Cfg>::load(
+                    &circuit_file,
+                    pcs_setup_source(pcs_setup_dir.as_deref(), insecure_testing_setup),
+                ),
+            );
+            for registration in register {
+                let (name, files) = registration.split_once('=').unwrap_or_else(|| {
+                    println!(
+                        "Expected <NAME>=<CIRCUIT_FILE>[,<PCS_SETUP_DIR>], got {registration}"
+                    );
+                    exit(1);
+                });
+                let (file, setup_dir) = match files.split_once(',') {
+                    Some((file, setup_dir)) => (file, Some(setup_dir)),
+                    None => (files, None),
+                };
+                if detect_field_type_from_circuit_file(file)
+                    != <Cfg::FieldConfig as FieldEngine>::FIELD_TYPE
+                {
+                    println!("Circuit {file} is not over the field of {circuit_file}");
+                    exit(1);
+                }
+                circuits.insert(
+                    name.to_string(),
+                    RegisteredCircuit::<Cfg>::load(
+                        file,
+                        pcs_setup_source(setup_dir, insecure_testing_setup),
+                    ),
                 );
+            }
 
-            let circuit = Arc::new(Mutex::new(circuit));
-            let circuit_clone_for_verifier = circuit.clone();
-            let pcs_params = Arc::new(Mutex::new(pcs_params));
-            let pcs_params_clone_for_verifier = pcs_params.clone();
-            let pcs_proving_key = Arc::new(Mutex::new(pcs_proving_key));
-            let pcs_verification_key = Arc::new(Mutex::new(pcs_verification_key));
-            let pcs_scratch = Arc::new(Mutex::new(pcs_scratch));
-
-            let ready_time = chrono::offset::Utc::now();
-            let ready = warp::path("ready").map(move || {
-                info!("Received ready request.");
-                reply::with_status(format!("Ready since {ready_time:?}"), StatusCode::OK)
-            });
-            let prove =
-                warp::path("prove")
-                    .and(warp::body::bytes())
-                    .map(move |bytes: bytes::Bytes| {
-                        info!("Received prove request.");
-                        let witness_bytes: Vec<u8> = bytes.to_vec();
-
-                        let mut circuit = circuit.lock().unwrap();
-                        let mut prover = Prover::<Cfg>::new(MPIConfig::prover_new(None, None));
-                        prover.prepare_mem(&circuit);
-                        let pcs_params = pcs_params.lock().unwrap();
-                        let pcs_proving_key = pcs_proving_key.lock().unwrap();
-                        let mut pcs_scratch = pcs_scratch.lock().unwrap();
-
-                        circuit.load_witness_bytes(&witness_bytes, &prover.mpi_config, true, true);
-                        let (claimed_v, proof) = prover.prove(
-                            &mut circuit,
-                            &pcs_params,
-                            &pcs_proving_key,
-                            &mut pcs_scratch,
-                        );
-                        reply::with_status(
-                            dump_proof_and_claimed_v(&proof, &claimed_v).unwrap(),
-                            StatusCode::OK,
-                        )
-                    });
-            let verify =
-                warp::path("verify")
-                    .and(warp::body::bytes())
-                    .map(move |bytes: bytes::Bytes| {
-                        info!("Received verify request.");
-                        let witness_and_proof_bytes: Vec<u8> = bytes.to_vec();
-                        let length_of_witness_bytes =
-                            u64::from_le_bytes(witness_and_proof_bytes[0..8].try_into().unwrap())
-                                as usize;
-                        let length_of_proof_bytes =
-                            u64::from_le_bytes(witness_and_proof_bytes[8..16].try_into().unwrap())
-                                as usize;
-                        let witness_bytes =
-                            &witness_and_proof_bytes[16..16 + length_of_witness_bytes];
-                        let proof_bytes = &witness_and_proof_bytes[16 + length_of_witness_bytes
-                            ..16 + length_of_witness_bytes + length_of_proof_bytes];
-
-                        let mut circuit = circuit_clone_for_verifier.lock().unwrap();
-                        let verifier = Verifier::<Cfg>::new(MPIConfig::verifier_new(1));
-                        let pcs_verification_key = pcs_verification_key.lock().unwrap();
-                        circuit.load_witness_bytes(
-                            witness_bytes,
-                            &verifier.mpi_config,
-                            false,
-                            true,
-                        );
-                        let public_input = circuit.public_input.clone();
-                        let (proof, claimed_v) = load_proof_and_claimed_v(proof_bytes).unwrap();
-                        if verifier.verify(
-                            &mut circuit,
-                            &public_input,
-                            &claimed_v,
-                            &pcs_params_clone_for_verifier.lock().unwrap(),
-                            &pcs_verification_key,
-                            &proof,
-                        ) {
-                            "success".to_string()
-                        } else {
-                            "failure".to_string()
-                        }
-                    });
-            warp::serve(
-                warp::post()
-                    .and(prove.or(verify))
-                    .or(warp::get().and(ready)),
-            )
-            .run((host, port))
-            .await;
+            let service = Arc::new(ProvingService::<Cfg>::new(circuits, workers.get()));
+            warp::serve(routes(service)).run((host, port)).await;
         }
     }
 }
@@ -1 +1,3 @@
 pub mod executor;
+pub mod pcs_setup;
+pub mod service;
@@ -55,7 +55,7 @@ fn main() {
     print_info(&args);
 
     // This is a designated single-process benchmark
-    let mpi_config = MPIConfig::prover_new(None, None);
+    let mpi_config = MPIConfig::default();
     let pcs_type = PolynomialCommitmentType::from_str(&args.pcs).unwrap();
 
     match args.field.as_str() {
@@ -264,7 +264,7 @@ where
             let mut pcs_scratch = pcs_scratch.clone();
             thread::spawn(move || {
                 // bench func
-                let local_mpi_config = MPIConfig::prover_new(None, None);
+                let local_mpi_config = MPIConfig::default();
                 let mut prover = Prover::<Cfg>::new(local_mpi_config);
                 prover.prepare_mem(&c);
                 loop {
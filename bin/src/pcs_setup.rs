@@ -0,0 +1,298 @@
+//! Persistent PCS setup for expander-exec.
+//!
+//! `expander-exec setup` writes the PCS params, proving key and verifying key of a circuit into a
+//! setup directory. Each file starts with a [`PCSSetupHeader`] that records the PCS, the field and
+//! the size the setup was generated for, so that `prove`, `verify` and `serve` can reject a setup
+//! that does not match the circuit and MPI size they are running with.
+//!
+//! The params themselves are fully determined by the number of input variables and the MPI world
+//! size, so the params file only carries the header and the params are re-derived on load.
+//! The proving key is generated per MPI rank, hence one proving key file is written per rank.
+
+use std::{fs, path::PathBuf};
+
+use gkr_engine::{
+    ExpErrors, ExpanderPCS, GKREngine, MPIEngine, StructuredReferenceString, load_pcs_setup_bytes,
+};
+pub use gkr_engine::{
+    PCS_SETUP_MAGIC, PCS_SETUP_VERSION, PCSOf, PCSParamsOf, PCSProvingKeyOf, PCSScratchPadOf,
+    PCSSetupFileKind, PCSSetupHeader, PCSVerifyingKeyOf,
+};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+use serdes::ExpSerde;
+
+const PCS_PARAMS_FILE: &str = "pcs.params";
+const PCS_PROVING_KEY_FILE: &str = "pcs.pk";
+const PCS_VERIFYING_KEY_FILE: &str = "pcs.vk";
+
+/// Where `prove`, `verify` and `serve` take the PCS setup from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PCSSetupSource<'a> {
+    /// A setup directory written by `setup`
+    Dir(&'a str),
+    /// The setup derived from a fixed seed, which is insecure and only meant for testing. It has
+    /// to be asked for explicitly with `--insecure-testing-setup`.
+    InsecureTestingOnly,
+}
+
+impl<'a> PCSSetupSource<'a> {
+    /// The setup directory if one is given, otherwise the insecure testing setup if it is
+    /// explicitly allowed.
+    pub fn new(
+        pcs_setup_dir: Option<&'a str>,
+        insecure_testing_setup: bool,
+    ) -> Result<Self, ExpErrors> {
+        match (pcs_setup_dir, insecure_testing_setup) {
+            (Some(dir), _) => Ok(Self::Dir(dir)),
+            (None, true) => Ok(Self::InsecureTestingOnly),
+            (None, false) => Err(ExpErrors::PCSSetupError(
+                "no PCS setup directory given, generate one with `setup`, or pass \
+                `--insecure-testing-setup` to use the insecure testing setup"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+fn proving_key_path(setup_dir: &str, world_size: usize, world_rank: usize) -> PathBuf {
+    let dir = PathBuf::from(setup_dir);
+    if world_size == 1 {
+        dir.join(PCS_PROVING_KEY_FILE)
+    } else {
+        dir.join(format!("{PCS_PROVING_KEY_FILE}.{world_rank}"))
+    }
+}
+
+fn write_setup_file(
+    path: PathBuf,
+    header: &PCSSetupHeader,
+    payload: &impl ExpSerde,
+) -> Result<(), ExpErrors> {
+    let mut bytes = Vec::new();
+    header.serialize_into(&mut bytes)?;
+    payload.serialize_into(&mut bytes)?;
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+fn read_setup_file<T: ExpSerde>(path: PathBuf, expected: &PCSSetupHeader) -> Result<T, ExpErrors> {
+    let bytes = fs::read(&path)?;
+    load_pcs_setup_bytes(&bytes, expected).map_err(|e| match e {
+        ExpErrors::PCSSetupError(msg) => {
+            ExpErrors::PCSSetupError(format!("{}: {msg}", path.display()))
+        }
+        e => e,
+    })
+}
+
+/// Generate a fresh PCS setup for a circuit with `n_input_vars` input variables and write it to
+/// `setup_dir`. Must be run with the same MPI size as the prover.
+pub fn pcs_setup<Cfg: GKREngine>(
+    n_input_vars: usize,
+    mpi_config: &impl MPIEngine,
+    setup_dir: &str,
+) -> Result<(), ExpErrors> {
+    let world_size = mpi_config.world_size();
+    let world_rank = mpi_config.world_rank();
+
+    // all ranks need to derive their SRS shares from the same randomness
+    let mut seed = vec![0u8; 32];
+    if mpi_config.is_root() {
+        rand::thread_rng().fill_bytes(&mut seed);
+    }
+    mpi_config.root_broadcast_bytes(&mut seed);
+    let rng = StdRng::from_seed(seed.try_into().unwrap());
+
+    let pcs_params = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::gen_params(
+        n_input_vars,
+        world_size,
+    );
+    let srs =
+        <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::gen_or_load_srs_for_testing(
+            &pcs_params,
+            mpi_config,
+            rng,
+            None,
+        );
+    let (pcs_proving_key, pcs_verification_key) = srs.into_keys();
+
+    if mpi_config.is_root() {
+        fs::create_dir_all(setup_dir)?;
+    }
+    mpi_config.barrier();
+
+    write_setup_file(
+        proving_key_path(setup_dir, world_size, world_rank),
+        &PCSSetupHeader::new::<Cfg>(
+            PCSSetupFileKind::ProvingKey,
+            n_input_vars,
+            world_size,
+            world_rank,
+        ),
+        &pcs_proving_key,
+    )?;
+
+    if mpi_config.is_root() {
+        write_setup_file(
+            PathBuf::from(setup_dir).join(PCS_PARAMS_FILE),
+            &PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::Params, n_input_vars, world_size, 0),
+            &(),
+        )?;
+        write_setup_file(
+            PathBuf::from(setup_dir).join(PCS_VERIFYING_KEY_FILE),
+            &PCSSetupHeader::new::<Cfg>(
+                PCSSetupFileKind::VerifyingKey,
+                n_input_vars,
+                world_size,
+                0,
+            ),
+            &pcs_verification_key,
+        )?;
+    }
+    mpi_config.barrier();
+
+    Ok(())
+}
+
+/// Load the PCS params from `setup_dir`, checking that they were generated for this circuit size
+/// and MPI world size.
+pub fn load_pcs_params<Cfg: GKREngine>(
+    n_input_vars: usize,
+    world_size: usize,
+    setup_dir: &str,
+) -> Result<PCSParamsOf<Cfg>, ExpErrors> {
+    read_setup_file::<()>(
+        PathBuf::from(setup_dir).join(PCS_PARAMS_FILE),
+        &PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::Params, n_input_vars, world_size, 0),
+    )?;
+
+    Ok(<Cfg::PCSConfig as ExpanderPCS<
+        Cfg::FieldConfig,
+        Cfg::PCSField,
+    >>::gen_params(n_input_vars, world_size))
+}
+
+/// Load the proving key share of the current MPI rank from `setup_dir`.
+pub fn load_pcs_proving_key<Cfg: GKREngine>(
+    n_input_vars: usize,
+    mpi_config: &impl MPIEngine,
+    setup_dir: &str,
+) -> Result<PCSProvingKeyOf<Cfg>, ExpErrors> {
+    let world_size = mpi_config.world_size();
+    let world_rank = mpi_config.world_rank();
+
+    read_setup_file(
+        proving_key_path(setup_dir, world_size, world_rank),
+        &PCSSetupHeader::new::<Cfg>(
+            PCSSetupFileKind::ProvingKey,
+            n_input_vars,
+            world_size,
+            world_rank,
+        ),
+    )
+}
+
+/// Load the verifying key from `setup_dir`. `world_size` is the MPI size used by the prover.
+pub fn load_pcs_verifying_key<Cfg: GKREngine>(
+    n_input_vars: usize,
+    world_size: usize,
+    setup_dir: &str,
+) -> Result<PCSVerifyingKeyOf<Cfg>, ExpErrors> {
+    read_setup_file(
+        PathBuf::from(setup_dir).join(PCS_VERIFYING_KEY_FILE),
+        &PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::VerifyingKey, n_input_vars, world_size, 0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use gkr::{BN254ConfigMIMC5KZG, BN254ConfigSha2Hyrax};
+    use gkr_engine::{ExpErrors, MPIConfig};
+
+    use super::*;
+
+    type Cfg = BN254ConfigMIMC5KZG<'static>;
+
+    const N_INPUT_VARS: usize = 4;
+
+    fn setup_dir(name: &str) -> String {
+        let dir = env::temp_dir().join(format!("expander-pcs-setup-{name}-{}", std::process::id()));
+        dir.to_str().unwrap().to_string()
+    }
+
+    fn assert_setup_error<T>(result: Result<T, ExpErrors>, expected: &str) {
+        match result {
+            Err(ExpErrors::PCSSetupError(msg)) => {
+                assert!(msg.contains(expected), "{msg} does not contain {expected}")
+            }
+            Err(e) => panic!("unexpected error {e}"),
+            Ok(_) => panic!("the setup was not rejected"),
+        }
+    }
+
+    #[test]
+    fn test_pcs_setup_round_trip() {
+        let dir = setup_dir("round-trip");
+        let mpi_config = MPIConfig::default();
+        pcs_setup::<Cfg>(N_INPUT_VARS, &mpi_config, &dir).unwrap();
+
+        load_pcs_params::<Cfg>(N_INPUT_VARS, 1, &dir).unwrap();
+        load_pcs_proving_key::<Cfg>(N_INPUT_VARS, &mpi_config, &dir).unwrap();
+        let vk = load_pcs_verifying_key::<Cfg>(N_INPUT_VARS, 1, &dir).unwrap();
+
+        let mut vk_bytes = vec![];
+        vk.serialize_into(&mut vk_bytes).unwrap();
+        let vk_file = fs::read(PathBuf::from(&dir).join(PCS_VERIFYING_KEY_FILE)).unwrap();
+        assert!(vk_file.ends_with(&vk_bytes));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pcs_setup_mismatch() {
+        let dir = setup_dir("mismatch");
+        let mpi_config = MPIConfig::default();
+        pcs_setup::<Cfg>(N_INPUT_VARS, &mpi_config, &dir).unwrap();
+
+        assert_setup_error(
+            load_pcs_params::<Cfg>(N_INPUT_VARS + 1, 1, &dir),
+            "number of input variables",
+        );
+        assert_setup_error(
+            load_pcs_verifying_key::<Cfg>(N_INPUT_VARS, 2, &dir),
+            "MPI world size",
+        );
+        assert_setup_error(
+            load_pcs_verifying_key::<BN254ConfigSha2Hyrax>(N_INPUT_VARS, 1, &dir),
+            "PCS",
+        );
+
+        // a proving key is not a verifying key
+        fs::copy(
+            PathBuf::from(&dir).join(PCS_PROVING_KEY_FILE),
+            PathBuf::from(&dir).join(PCS_VERIFYING_KEY_FILE),
+        )
+        .unwrap();
+        assert_setup_error(load_pcs_verifying_key::<Cfg>(N_INPUT_VARS, 1, &dir), "kind");
+
+        fs::write(PathBuf::from(&dir).join(PCS_PARAMS_FILE), b"short").unwrap();
+        assert_setup_error(load_pcs_params::<Cfg>(N_INPUT_VARS, 1, &dir), "header");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pcs_setup_source() {
+        assert_eq!(
+            PCSSetupSource::new(Some("setup"), false).unwrap(),
+            PCSSetupSource::Dir("setup")
+        );
+        assert_eq!(
+            PCSSetupSource::new(None, true).unwrap(),
+            PCSSetupSource::InsecureTestingOnly
+        );
+        assert_setup_error(PCSSetupSource::new(None, false), "--insecure-testing-setup");
+    }
+}
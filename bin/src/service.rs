@@ -0,0 +1,796 @@
+//! The proving service of the `serve` subcommand.
+//!
+//! Several circuits can be registered, each with its own PCS setup. Proving jobs are queued and
+//! run by a fixed number of concurrent provers, each with its own copy of the circuit and its own
+//! scratch pads. A finished job is kept until its proof is retrieved, or for
+//! `FINISHED_JOB_TTL` at most.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use circuit::Circuit;
+use gkr::{Prover, Verifier};
+use gkr_engine::{ExpanderPCS, FieldEngine, GKREngine, MPIConfig, ProofEnvelope, ProofHeader};
+use log::info;
+use serde_json::json;
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use warp::{
+    Filter, Rejection, Reply,
+    http::StatusCode,
+    reply::{self, Response},
+};
+
+use crate::{
+    executor::{
+        circuit_hash, dump_proof_and_claimed_v, load_proof_and_claimed_v, prover_pcs_setup,
+        verifier_pcs_setup,
+    },
+    pcs_setup::{PCSParamsOf, PCSProvingKeyOf, PCSSetupSource, PCSVerifyingKeyOf},
+};
+
+/// Name of the circuit given with `-c` to the `serve` subcommand, proven by `/prove`
+pub const DEFAULT_CIRCUIT: &str = "default";
+
+/// How long a finished job whose proof is not retrieved is kept
+pub const FINISHED_JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Error)]
+pub enum ServiceError {
+    #[error("unknown circuit `{0}`")]
+    UnknownCircuit(String),
+
+    #[error("unknown job {0}")]
+    UnknownJob(u64),
+
+    #[error("job {0} is not done yet")]
+    JobNotDone(u64),
+
+    #[error("job {0} failed: {1}")]
+    JobFailed(u64, String),
+
+    #[error("invalid witness: {0}")]
+    InvalidWitness(String),
+
+    #[error("malformed request: {0}")]
+    MalformedRequest(String),
+
+    #[error("malformed proof: {0}")]
+    MalformedProof(String),
+
+    #[error("prover failed: {0}")]
+    ProverFailure(String),
+
+    #[error("verifier failed: {0}")]
+    VerifierFailure(String),
+}
+
+impl ServiceError {
+    /// The HTTP status code to reply with
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ServiceError::UnknownCircuit(_) | ServiceError::UnknownJob(_) => StatusCode::NOT_FOUND,
+            ServiceError::JobNotDone(_) => StatusCode::CONFLICT,
+            ServiceError::JobFailed(_, _) => StatusCode::UNPROCESSABLE_ENTITY,
+            ServiceError::InvalidWitness(_)
+            | ServiceError::MalformedRequest(_)
+            | ServiceError::MalformedProof(_) => StatusCode::BAD_REQUEST,
+            ServiceError::ProverFailure(_) | ServiceError::VerifierFailure(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+/// A circuit the service proves witnesses of, along with its PCS setup.
+pub struct RegisteredCircuit<Cfg: GKREngine> {
+    /// Cloned by each job, as proving writes the witness into the circuit
+    circuit: Mutex<Circuit<Cfg::FieldConfig>>,
+    circuit_hash: [u8; 32],
+    pcs_params: PCSParamsOf<Cfg>,
+    pcs_proving_key: PCSProvingKeyOf<Cfg>,
+    pcs_verification_key: PCSVerifyingKeyOf<Cfg>,
+}
+
+impl<Cfg: GKREngine> RegisteredCircuit<Cfg>
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    /// Load a circuit and its PCS setup.
+    pub fn load(circuit_file: &str, pcs_setup: PCSSetupSource) -> Self {
+        let circuit =
+            Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(circuit_file);
+        Self::new(circuit, circuit_hash(circuit_file), pcs_setup)
+    }
+
+    /// Register a circuit, the hash being recorded in its proofs.
+    pub fn new(
+        circuit: Circuit<Cfg::FieldConfig>,
+        circuit_hash: [u8; 32],
+        pcs_setup: PCSSetupSource,
+    ) -> Self {
+        let mpi_config = MPIConfig::default();
+        let (pcs_params, pcs_proving_key, _) =
+            prover_pcs_setup::<Cfg>(circuit.log_input_size(), &mpi_config, pcs_setup);
+        let (_, pcs_verification_key) =
+            verifier_pcs_setup::<Cfg>(circuit.log_input_size(), &mpi_config, pcs_setup);
+
+        Self {
+            circuit: Mutex::new(circuit),
+            circuit_hash,
+            pcs_params,
+            pcs_proving_key,
+            pcs_verification_key,
+        }
+    }
+
+    /// Prove the witness, returning the proof file, i.e., the proof with its header and claim.
+    ///
+    /// Run on a blocking task by the service, which reports a panic of the prover as a
+    /// `ServiceError::ProverFailure`.
+    pub fn prove(&self, witness: &[u8]) -> Result<Vec<u8>, ServiceError> {
+        let mpi_config = MPIConfig::default();
+        let mut circuit = self
+            .circuit
+            .lock()
+            .unwrap()
+            .with_witness_bytes(witness, &mpi_config, true, true)
+            .map_err(|e| ServiceError::InvalidWitness(e.to_string()))?;
+
+        let mut pcs_scratch =
+            <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::init_scratch_pad(
+                &self.pcs_params,
+                &mpi_config,
+            );
+        let mut prover = Prover::<Cfg>::new(mpi_config);
+        prover.prepare_mem(&circuit);
+
+        let (claimed_v, proof) = prover.prove(
+            &mut circuit,
+            &self.pcs_params,
+            &self.pcs_proving_key,
+            &mut pcs_scratch,
+        );
+
+        let header = ProofHeader::new::<Cfg>(1, self.circuit_hash);
+        dump_proof_and_claimed_v(&header, &proof, &claimed_v)
+            .map_err(|e| ServiceError::ProverFailure(e.to_string()))
+    }
+
+    /// Verify a proof file against the witness.
+    ///
+    /// A proof of another circuit or config fails to verify, while a proof file that does not
+    /// deserialize is a `ServiceError::MalformedProof`.
+    ///
+    /// Run on a blocking task by the service, which reports a panic of the verifier as a
+    /// `ServiceError::VerifierFailure`.
+    pub fn verify(&self, witness: &[u8], proof_file: &[u8]) -> Result<bool, ServiceError> {
+        let mut circuit = self
+            .circuit
+            .lock()
+            .unwrap()
+            .with_witness_bytes(witness, &MPIConfig::default(), false, true)
+            .map_err(|e| ServiceError::InvalidWitness(e.to_string()))?;
+
+        let ProofEnvelope {
+            header,
+            proof,
+            claimed_v,
+        } = load_proof_and_claimed_v(proof_file)
+            .map_err(|e| ServiceError::MalformedProof(e.to_string()))?;
+        if let Err(e) = header.check::<Cfg>(1, &self.circuit_hash) {
+            info!("Rejected proof: {e}");
+            return Ok(false);
+        }
+
+        let mut verifier = Verifier::<Cfg>::new(MPIConfig::verifier_new(1));
+        verifier.statement_binding = header.statement_binding();
+        let public_input = circuit.public_input.clone();
+        match verifier.try_verify(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &self.pcs_params,
+            &self.pcs_verification_key,
+            &proof,
+        ) {
+            Err(e) if e.is_rejection() => {
+                info!("Rejected proof: {e}");
+                Ok(false)
+            }
+            verified => verified.map_err(|e| ServiceError::MalformedProof(e.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done(Arc<Vec<u8>>),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub circuit: String,
+    pub status: JobStatus,
+    pub submitted_at: Instant,
+    pub started_at: Option<Instant>,
+    pub finished_at: Option<Instant>,
+}
+
+impl Job {
+    /// The status of the job in JSON
+    pub fn to_json(&self, id: u64) -> String {
+        let status = match &self.status {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done(_) => "done",
+            JobStatus::Failed(_) => "failed",
+        };
+        let queue_ms = self
+            .started_at
+            .map(|t| (t - self.submitted_at).as_millis() as u64);
+        let proving_ms = self
+            .started_at
+            .zip(self.finished_at)
+            .map(|(start, end)| (end - start).as_millis() as u64);
+        let error = match &self.status {
+            JobStatus::Failed(e) => Some(e),
+            _ => None,
+        };
+
+        json!({
+            "id": id,
+            "circuit": self.circuit,
+            "status": status,
+            "queue_ms": queue_ms,
+            "proving_ms": proving_ms,
+            "error": error,
+        })
+        .to_string()
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.finished_at
+            .is_some_and(|finished_at| finished_at.elapsed() >= ttl)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    completed_jobs: u64,
+    failed_jobs: u64,
+    total_queue_time: Duration,
+    total_proving_time: Duration,
+}
+
+/// Proves witnesses of the registered circuits, with at most `n_workers` proofs at once.
+pub struct ProvingService<Cfg: GKREngine> {
+    circuits: HashMap<String, Arc<RegisteredCircuit<Cfg>>>,
+    n_workers: usize,
+    workers: Semaphore,
+    jobs: Mutex<HashMap<u64, Job>>,
+    finished_job_ttl: Duration,
+    next_job_id: AtomicU64,
+    metrics: Mutex<Metrics>,
+}
+
+impl<Cfg: GKREngine + 'static> ProvingService<Cfg>
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    pub fn new(circuits: HashMap<String, RegisteredCircuit<Cfg>>, n_workers: usize) -> Self {
+        assert!(n_workers > 0, "the service needs at least one prover");
+        Self {
+            circuits: circuits
+                .into_iter()
+                .map(|(name, circuit)| (name, Arc::new(circuit)))
+                .collect(),
+            n_workers,
+            workers: Semaphore::new(n_workers),
+            jobs: Mutex::new(HashMap::new()),
+            finished_job_ttl: FINISHED_JOB_TTL,
+            next_job_id: AtomicU64::new(0),
+            metrics: Mutex::new(Metrics::default()),
+        }
+    }
+
+    /// Keep the finished jobs whose proof is not retrieved for `ttl` instead of
+    /// `FINISHED_JOB_TTL`
+    pub fn with_finished_job_ttl(mut self, ttl: Duration) -> Self {
+        self.finished_job_ttl = ttl;
+        self
+    }
+
+    pub fn circuit(&self, name: &str) -> Result<Arc<RegisteredCircuit<Cfg>>, ServiceError> {
+        self.circuits
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ServiceError::UnknownCircuit(name.to_string()))
+    }
+
+    /// Queue a proving job of the witness, returning its id
+    pub fn submit(self: &Arc<Self>, name: &str, witness: Vec<u8>) -> Result<u64, ServiceError> {
+        let circuit = self.circuit(name)?;
+        let id = self.new_job(name);
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let _ = service.run_job(id, circuit, witness).await;
+        });
+        Ok(id)
+    }
+
+    /// Prove the witness once a prover is available, and wait for the proof
+    pub async fn prove(
+        self: &Arc<Self>,
+        name: &str,
+        witness: Vec<u8>,
+    ) -> Result<Arc<Vec<u8>>, ServiceError> {
+        let circuit = self.circuit(name)?;
+        let id = self.new_job(name);
+        let result = self.run_job(id, circuit, witness).await;
+        // the proof is returned right away, no need to keep the job around
+        self.jobs.lock().unwrap().remove(&id);
+        result
+    }
+
+    fn new_job(&self, name: &str) -> u64 {
+        let id = self.next_job_id.fetch_add(1, Ordering::SeqCst);
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|_, job| !job.is_expired(self.finished_job_ttl));
+        jobs.insert(
+            id,
+            Job {
+                circuit: name.to_string(),
+                status: JobStatus::Queued,
+                submitted_at: Instant::now(),
+                started_at: None,
+                finished_at: None,
+            },
+        );
+        id
+    }
+
+    async fn run_job(
+        &self,
+        id: u64,
+        circuit: Arc<RegisteredCircuit<Cfg>>,
+        witness: Vec<u8>,
+    ) -> Result<Arc<Vec<u8>>, ServiceError> {
+        let _permit = self.workers.acquire().await.unwrap();
+        self.update_job(id, |job| {
+            job.status = JobStatus::Running;
+            job.started_at = Some(Instant::now());
+        });
+
+        let result = tokio::task::spawn_blocking(move || circuit.prove(&witness))
+            .await
+            .unwrap_or_else(|e| Err(ServiceError::ProverFailure(e.to_string())))
+            .map(Arc::new);
+
+        let job = self.update_job(id, |job| {
+            job.finished_at = Some(Instant::now());
+            job.status = match &result {
+                Ok(proof) => JobStatus::Done(proof.clone()),
+                Err(e) => JobStatus::Failed(e.to_string()),
+            };
+        });
+
+        let mut metrics = self.metrics.lock().unwrap();
+        match &result {
+            Ok(_) => metrics.completed_jobs += 1,
+            Err(_) => metrics.failed_jobs += 1,
+        }
+        if let (Some(started_at), Some(finished_at)) = (job.started_at, job.finished_at) {
+            metrics.total_queue_time += started_at - job.submitted_at;
+            metrics.total_proving_time += finished_at - started_at;
+        }
+
+        result
+    }
+
+    fn update_job(&self, id: u64, f: impl FnOnce(&mut Job)) -> Job {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.get_mut(&id).unwrap();
+        f(job);
+        job.clone()
+    }
+
+    pub fn job(&self, id: u64) -> Result<Job, ServiceError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ServiceError::UnknownJob(id))
+    }
+
+    /// The proof file of a finished job, which is then evicted, as is a failed job once its
+    /// error is reported
+    pub fn job_proof(&self, id: u64) -> Result<Arc<Vec<u8>>, ServiceError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let status = &jobs.get(&id).ok_or(ServiceError::UnknownJob(id))?.status;
+        if matches!(status, JobStatus::Queued | JobStatus::Running) {
+            return Err(ServiceError::JobNotDone(id));
+        }
+
+        match jobs.remove(&id).unwrap().status {
+            JobStatus::Done(proof) => Ok(proof),
+            JobStatus::Failed(e) => Err(ServiceError::JobFailed(id, e)),
+            JobStatus::Queued | JobStatus::Running => unreachable!(),
+        }
+    }
+
+    /// Verify a request made of the u64 lengths of the witness and of the proof file, followed
+    /// by the witness and the proof file
+    pub async fn verify(
+        self: &Arc<Self>,
+        name: &str,
+        request: Vec<u8>,
+    ) -> Result<bool, ServiceError> {
+        let circuit = self.circuit(name)?;
+
+        let read_len = |offset: usize| {
+            request
+                .get(offset..offset + 8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        };
+        let (witness_len, proof_len) = read_len(0)
+            .zip(read_len(8))
+            .ok_or_else(|| ServiceError::MalformedRequest("missing lengths".to_string()))?;
+        if 16usize
+            .checked_add(witness_len)
+            .and_then(|len| len.checked_add(proof_len))
+            != Some(request.len())
+        {
+            return Err(ServiceError::MalformedRequest(format!(
+                "expected {witness_len} bytes of witness and {proof_len} bytes of proof, got {} bytes",
+                request.len() - 16
+            )));
+        }
+
+        tokio::task::spawn_blocking(move || {
+            let (witness, proof_file) = request[16..].split_at(witness_len);
+            circuit.verify(witness, proof_file)
+        })
+        .await
+        .unwrap_or_else(|e| Err(ServiceError::VerifierFailure(e.to_string())))
+    }
+
+    /// Queue depth, job counts and timings, in the Prometheus text format
+    pub fn metrics(&self) -> String {
+        let (queued, running) =
+            self.jobs
+                .lock()
+                .unwrap()
+                .values()
+                .fold((0, 0), |(queued, running), job| match job.status {
+                    JobStatus::Queued => (queued + 1, running),
+                    JobStatus::Running => (queued, running + 1),
+                    _ => (queued, running),
+                });
+        let metrics = self.metrics.lock().unwrap();
+        let n_finished = (metrics.completed_jobs + metrics.failed_jobs).max(1) as u32;
+
+        [
+            format!("expander_workers {}", self.n_workers),
+            format!("expander_circuits {}", self.circuits.len()),
+            format!("expander_queued_jobs {queued}"),
+            format!("expander_running_jobs {running}"),
+            format!("expander_completed_jobs {}", metrics.completed_jobs),
+            format!("expander_failed_jobs {}", metrics.failed_jobs),
+            format!(
+                "expander_average_queue_time_ms {}",
+                (metrics.total_queue_time / n_finished).as_millis()
+            ),
+            format!(
+                "expander_average_proving_time_ms {}",
+                (metrics.total_proving_time / n_finished).as_millis()
+            ),
+        ]
+        .join("\n")
+            + "\n"
+    }
+}
+
+/// The HTTP API of the service:
+/// - `GET /ready`
+/// - `GET /metrics`
+/// - `POST /prove` and `POST /verify` on the default circuit, waiting for the result
+/// - `POST /circuits/<name>/prove` and `POST /circuits/<name>/verify`, the same on any circuit
+/// - `POST /circuits/<name>/jobs` to queue a proving job, replying with its id
+/// - `GET /jobs/<id>` for the status of a job, and `GET /jobs/<id>/proof` for its proof
+pub fn routes<Cfg: GKREngine + 'static>(
+    service: Arc<ProvingService<Cfg>>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    let with_service = warp::any().map(move || service.clone());
+    let ready_time = chrono::offset::Utc::now();
+
+    let ready = warp::path!("ready").map(move || {
+        info!("Received ready request.");
+        reply::with_status(format!("Ready since {ready_time:?}"), StatusCode::OK).into_response()
+    });
+    let metrics = warp::path!("metrics")
+        .and(with_service.clone())
+        .map(|service: Arc<ProvingService<Cfg>>| service.metrics().into_response());
+
+    let prove_default = warp::path!("prove").map(|| DEFAULT_CIRCUIT.to_string());
+    let prove_named = warp::path!("circuits" / String / "prove");
+    let prove = prove_default
+        .or(prove_named)
+        .unify()
+        .and(warp::body::bytes())
+        .and(with_service.clone())
+        .then(
+            |name: String, witness: Bytes, service: Arc<ProvingService<Cfg>>| async move {
+                info!("Received prove request for circuit {name}.");
+                match service.prove(&name, witness.to_vec()).await {
+                    Ok(proof) => proof.to_vec().into_response(),
+                    Err(e) => error_reply(e),
+                }
+            },
+        );
+
+    let verify_default = warp::path!("verify").map(|| DEFAULT_CIRCUIT.to_string());
+    let verify_named = warp::path!("circuits" / String / "verify");
+    let verify = verify_default
+        .or(verify_named)
+        .unify()
+        .and(warp::body::bytes())
+        .and(with_service.clone())
+        .then(
+            |name: String, request: Bytes, service: Arc<ProvingService<Cfg>>| async move {
+                info!("Received verify request for circuit {name}.");
+                match service.verify(&name, request.to_vec()).await {
+                    Ok(true) => "success".into_response(),
+                    Ok(false) => "failure".into_response(),
+                    Err(e) => error_reply(e),
+                }
+            },
+        );
+
+    let submit = warp::path!("circuits" / String / "jobs")
+        .and(warp::body::bytes())
+        .and(with_service.clone())
+        .map(
+            |name: String, witness: Bytes, service: Arc<ProvingService<Cfg>>| {
+                info!("Received job for circuit {name}.");
+                match service.submit(&name, witness.to_vec()) {
+                    Ok(id) => {
+                        reply::with_status(id.to_string(), StatusCode::ACCEPTED).into_response()
+                    }
+                    Err(e) => error_reply(e),
+                }
+            },
+        );
+
+    let job_status = warp::path!("jobs" / u64).and(with_service.clone()).map(
+        |id: u64, service: Arc<ProvingService<Cfg>>| match service.job(id) {
+            Ok(job) => reply::with_header(job.to_json(id), "content-type", "application/json")
+                .into_response(),
+            Err(e) => error_reply(e),
+        },
+    );
+    let job_proof = warp::path!("jobs" / u64 / "proof").and(with_service).map(
+        |id: u64, service: Arc<ProvingService<Cfg>>| match service.job_proof(id) {
+            Ok(proof) => proof.to_vec().into_response(),
+            Err(e) => error_reply(e),
+        },
+    );
+
+    warp::post()
+        .and(prove.or(verify).unify().or(submit).unify())
+        .or(warp::get().and(
+            ready
+                .or(metrics)
+                .unify()
+                .or(job_status)
+                .unify()
+                .or(job_proof)
+                .unify(),
+        ))
+        .unify()
+}
+
+fn error_reply(e: ServiceError) -> Response {
+    info!("Rejected request: {e}");
+    reply::with_status(e.to_string(), e.status_code()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use gkr::{M31x1ConfigSha2RawVanilla, utils::statement_binding_test_circuit};
+    use serdes::ExpSerde;
+
+    use super::*;
+
+    type Cfg = M31x1ConfigSha2RawVanilla<'static>;
+    type F = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::CircuitField;
+
+    /// A service with one prover, of the single layer circuit of the statement binding tests
+    fn test_service() -> Arc<ProvingService<Cfg>> {
+        let mut circuit = statement_binding_test_circuit::<<Cfg as GKREngine>::FieldConfig>();
+        circuit.pre_process_gkr::<Cfg>();
+
+        let circuits = HashMap::from([(
+            DEFAULT_CIRCUIT.to_string(),
+            RegisteredCircuit::new(circuit, [1; 32], PCSSetupSource::InsecureTestingOnly),
+        )]);
+        Arc::new(ProvingService::new(circuits, 1))
+    }
+
+    fn witness(service: &ProvingService<Cfg>, public_input: u32) -> Vec<u8> {
+        let circuit = service.circuit(DEFAULT_CIRCUIT).unwrap();
+        let witness = circuit
+            .circuit
+            .lock()
+            .unwrap()
+            .solve_witness(
+                &[(2..6).map(F::from).collect()],
+                &[vec![F::from(public_input)]],
+            )
+            .unwrap();
+        let mut bytes = vec![];
+        witness.serialize_into(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn verify_request(witness: &[u8], proof_file: &[u8]) -> Vec<u8> {
+        [
+            &(witness.len() as u64).to_le_bytes()[..],
+            &(proof_file.len() as u64).to_le_bytes(),
+            witness,
+            proof_file,
+        ]
+        .concat()
+    }
+
+    async fn wait_until_finished(service: &ProvingService<Cfg>, id: u64) -> Job {
+        for _ in 0..1000 {
+            let job = service.job(id).unwrap();
+            if job.finished_at.is_some() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_service_job_lifecycle() {
+        let service = test_service();
+        let witness = witness(&service, 13);
+
+        let id = service.submit(DEFAULT_CIRCUIT, witness.clone()).unwrap();
+        let job = wait_until_finished(&service, id).await;
+        assert!(matches!(job.status, JobStatus::Done(_)));
+
+        let status: serde_json::Value = serde_json::from_str(&job.to_json(id)).unwrap();
+        assert_eq!(status["id"], id);
+        assert_eq!(status["circuit"], DEFAULT_CIRCUIT);
+        assert_eq!(status["status"], "done");
+        assert!(status["queue_ms"].is_u64());
+        assert!(status["proving_ms"].is_u64());
+        assert!(status["error"].is_null());
+
+        // the job is evicted once its proof is retrieved
+        let proof_file = service.job_proof(id).unwrap();
+        assert!(matches!(service.job(id), Err(ServiceError::UnknownJob(_))));
+        assert!(matches!(
+            service.job_proof(id),
+            Err(ServiceError::UnknownJob(_))
+        ));
+
+        let request = verify_request(&witness, &proof_file);
+        assert!(service.verify(DEFAULT_CIRCUIT, request).await.unwrap());
+        let other_witness = self::witness(&service, 14);
+        let request = verify_request(&other_witness, &proof_file);
+        assert!(!service.verify(DEFAULT_CIRCUIT, request).await.unwrap());
+
+        let metrics = service.metrics();
+        assert!(metrics.contains("expander_completed_jobs 1\n"));
+        assert!(metrics.contains("expander_queued_jobs 0\n"));
+    }
+
+    #[tokio::test]
+    async fn test_service_job_errors() {
+        let service = test_service();
+
+        assert!(matches!(
+            service.submit("unknown", vec![]),
+            Err(ServiceError::UnknownCircuit(_))
+        ));
+        assert!(matches!(
+            service.job(1234),
+            Err(ServiceError::UnknownJob(_))
+        ));
+
+        // a job waiting for a prover is not done
+        let id = service.new_job(DEFAULT_CIRCUIT);
+        assert!(matches!(service.job(id).unwrap().status, JobStatus::Queued));
+        assert!(matches!(
+            service.job_proof(id),
+            Err(ServiceError::JobNotDone(_))
+        ));
+
+        // a witness that does not fit the circuit fails its job, and the error is escaped
+        let id = service.submit(DEFAULT_CIRCUIT, vec![0; 3]).unwrap();
+        let job = wait_until_finished(&service, id).await;
+        let JobStatus::Failed(e) = &job.status else {
+            panic!("the job did not fail");
+        };
+        let status: serde_json::Value = serde_json::from_str(&job.to_json(id)).unwrap();
+        assert_eq!(status["status"], "failed");
+        assert_eq!(status["error"], e.as_str());
+        assert!(matches!(
+            service.job_proof(id),
+            Err(ServiceError::JobFailed(_, _))
+        ));
+        assert!(matches!(service.job(id), Err(ServiceError::UnknownJob(_))));
+
+        assert!(matches!(
+            service.prove(DEFAULT_CIRCUIT, vec![0; 3]).await,
+            Err(ServiceError::InvalidWitness(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_service_verify_errors() {
+        let service = test_service();
+        let witness = witness(&service, 13);
+        let proof_file = service
+            .prove(DEFAULT_CIRCUIT, witness.clone())
+            .await
+            .unwrap();
+
+        let mut request = verify_request(&witness, &proof_file);
+        request.pop();
+        assert!(matches!(
+            service.verify(DEFAULT_CIRCUIT, request).await,
+            Err(ServiceError::MalformedRequest(_))
+        ));
+
+        // a truncated proof is malformed, while a proof of another circuit fails to verify
+        let request = verify_request(&witness, &proof_file[..proof_file.len() - 1]);
+        assert!(matches!(
+            service.verify(DEFAULT_CIRCUIT, request).await,
+            Err(ServiceError::MalformedProof(_))
+        ));
+        let mut other_proof_file = proof_file.to_vec();
+        let circuit_hash = other_proof_file
+            .windows(32)
+            .position(|w| w == [1; 32])
+            .unwrap();
+        other_proof_file[circuit_hash] = 2;
+        let request = verify_request(&witness, &other_proof_file);
+        assert!(!service.verify(DEFAULT_CIRCUIT, request).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_service_evicts_expired_jobs() {
+        let service = Arc::new(
+            Arc::into_inner(test_service())
+                .unwrap()
+                .with_finished_job_ttl(Duration::ZERO),
+        );
+        let id = service.submit(DEFAULT_CIRCUIT, vec![0; 3]).unwrap();
+        wait_until_finished(&service, id).await;
+
+        let queued_id = service.new_job(DEFAULT_CIRCUIT);
+        assert!(matches!(service.job(id), Err(ServiceError::UnknownJob(_))));
+        assert!(service.job(queued_id).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use gkr_engine::{FieldEngine, GKREngine};
 use serdes::{ExpSerde, SerdeResult};
 use std::{cmp::max, collections::HashMap, fs, io::Cursor};
+use thiserror::Error;
 
 use crate::*;
 
@@ -67,6 +68,59 @@ impl<C: FieldEngine> Segment<C> {
     }
 }
 
+/// A recursive circuit that does not flatten into a layered circuit, see
+/// `RecursiveCircuit::try_flatten`.
+#[derive(Debug, Error)]
+pub enum FlattenError {
+    #[error("the circuit has no layers")]
+    NoLayers,
+
+    #[error("segment {0} does not exist")]
+    UnknownSegment(SegmentId),
+
+    #[error("segment {0} contains itself")]
+    RecursiveSegment(SegmentId),
+
+    #[error("segment {segment}: {message}")]
+    InvalidSegment { segment: SegmentId, message: String },
+
+    #[error("layer {layer} takes {input_var_num} input variables, the previous layer outputs {output_var_num}")]
+    LayerSizeMismatch {
+        layer: usize,
+        input_var_num: usize,
+        output_var_num: usize,
+    },
+
+    #[error("{expected} outputs are expected to be zero, the circuit has {num_outputs}")]
+    TooManyOutputZeros { expected: usize, num_outputs: usize },
+}
+
+/// Number of values of `var_num` variables, if it fits in a `usize`
+fn var_size(var_num: usize) -> Option<usize> {
+    u32::try_from(var_num)
+        .ok()
+        .and_then(|var_num| 1usize.checked_shl(var_num))
+}
+
+fn check_gates<C: FieldEngine, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+    i_size: usize,
+    o_size: usize,
+) -> Result<(), String> {
+    for gate in gates {
+        if let Some(i_id) = gate.i_ids.iter().find(|&&i_id| i_id >= i_size) {
+            return Err(format!("gate input {i_id} is out of the {i_size} inputs"));
+        }
+        if gate.o_id >= o_size {
+            return Err(format!(
+                "gate output {} is out of the {o_size} outputs",
+                gate.o_id
+            ));
+        }
+    }
+    Ok(())
+}
+
 #[derive(Default)]
 pub struct RecursiveCircuit<C: FieldEngine> {
     pub num_public_inputs: usize,
@@ -85,6 +139,118 @@ impl<C: FieldEngine> RecursiveCircuit<C> {
         <Self as ExpSerde>::deserialize_from(cursor)
     }
 
+    /// Check that the segments of the circuit fit together: the segments exist and do not contain
+    /// themselves, the gates and the child segments fit in the variables of their segment, and
+    /// each layer takes as many variables as the previous one outputs.
+    pub fn validate(&self) -> Result<(), FlattenError> {
+        if self.layers.is_empty() {
+            return Err(FlattenError::NoLayers);
+        }
+        let segment = |id: SegmentId| {
+            self.segments
+                .get(id)
+                .ok_or(FlattenError::UnknownSegment(id))
+        };
+
+        for (id, seg) in self.segments.iter().enumerate() {
+            let invalid = |message: String| FlattenError::InvalidSegment {
+                segment: id,
+                message,
+            };
+            let (Some(i_size), Some(o_size)) = (var_size(seg.i_var_num), var_size(seg.o_var_num))
+            else {
+                return Err(invalid(format!(
+                    "{} input and {} output variables are too many",
+                    seg.i_var_num, seg.o_var_num
+                )));
+            };
+
+            check_gates(&seg.gate_muls, i_size, o_size).map_err(invalid)?;
+            check_gates(&seg.gate_adds, i_size, o_size).map_err(invalid)?;
+            check_gates(&seg.gate_consts, i_size, o_size).map_err(invalid)?;
+            check_gates(&seg.gate_uni, i_size, o_size).map_err(invalid)?;
+
+            for (child_id, allocs) in &seg.child_segs {
+                let child = segment(*child_id)?;
+                let child_i_size = var_size(child.i_var_num).unwrap_or(usize::MAX);
+                let child_o_size = var_size(child.o_var_num).unwrap_or(usize::MAX);
+                for alloc in allocs {
+                    let fits = |offset: usize, child_size: usize, size: usize| {
+                        offset
+                            .checked_add(child_size)
+                            .is_some_and(|end| end <= size)
+                    };
+                    if !fits(alloc.i_offset, child_i_size, i_size)
+                        || !fits(alloc.o_offset, child_o_size, o_size)
+                    {
+                        return Err(invalid(format!(
+                            "segment {child_id} at offsets {} -> {} does not fit",
+                            alloc.i_offset, alloc.o_offset
+                        )));
+                    }
+                }
+            }
+        }
+
+        // the segments reachable from the layers must not contain themselves, flattening recurses
+        // into the child segments
+        let mut visited = vec![false; self.segments.len()];
+        let mut on_path = vec![false; self.segments.len()];
+        for &layer_id in &self.layers {
+            segment(layer_id)?;
+            self.check_acyclic(layer_id, &mut visited, &mut on_path)?;
+        }
+
+        for (layer, ids) in self.layers.windows(2).enumerate() {
+            let output_var_num = max(self.segments[ids[0]].o_var_num, 1);
+            let input_var_num = max(self.segments[ids[1]].i_var_num, 1);
+            if input_var_num != output_var_num {
+                return Err(FlattenError::LayerSizeMismatch {
+                    layer: layer + 1,
+                    input_var_num,
+                    output_var_num,
+                });
+            }
+        }
+
+        let last = &self.segments[*self.layers.last().unwrap()];
+        let num_outputs = 1 << max(last.o_var_num, 1);
+        if self.expected_num_output_zeros > num_outputs {
+            return Err(FlattenError::TooManyOutputZeros {
+                expected: self.expected_num_output_zeros,
+                num_outputs,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_acyclic(
+        &self,
+        id: SegmentId,
+        visited: &mut [bool],
+        on_path: &mut [bool],
+    ) -> Result<(), FlattenError> {
+        if on_path[id] {
+            return Err(FlattenError::RecursiveSegment(id));
+        }
+        if visited[id] {
+            return Ok(());
+        }
+        visited[id] = true;
+        on_path[id] = true;
+        for (child_id, _) in &self.segments[id].child_segs {
+            self.check_acyclic(*child_id, visited, on_path)?;
+        }
+        on_path[id] = false;
+        Ok(())
+    }
+
+    /// Same as `flatten`, rejecting a circuit that does not pass `validate` instead of panicking.
+    pub fn try_flatten<Cfg: GKREngine<FieldConfig = C>>(&self) -> Result<Circuit<C>, FlattenError> {
+        self.validate()?;
+        Ok(self.flatten::<Cfg>())
+    }
+
     pub fn flatten<Cfg: GKREngine<FieldConfig = C>>(&self) -> Circuit<C> {
         let mut ret = Circuit::<C> {
             expected_num_output_zeros: self.expected_num_output_zeros,
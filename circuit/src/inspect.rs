@@ -0,0 +1,85 @@
+use std::{collections::BTreeMap, fmt};
+
+use gkr_engine::FieldEngine;
+
+use crate::{Circuit, CircuitLayer, CoefType, Gate};
+
+/// Statistics of a layer, as printed by `expander-exec inspect`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerStats {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    pub num_mul: usize,
+    pub num_add: usize,
+    pub num_const: usize,
+    /// Number of uni gates of each gate type
+    pub num_uni: BTreeMap<usize, usize>,
+    /// Number of gates with a random coefficient
+    pub num_random_coefs: usize,
+    /// Number of const gates reading a public input
+    pub num_public_input_gates: usize,
+    pub skip_sumcheck_phase_two: bool,
+}
+
+fn count_random_coefs<C: FieldEngine, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+) -> usize {
+    gates
+        .iter()
+        .filter(|gate| gate.coef_type == CoefType::Random)
+        .count()
+}
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    /// `skip_sumcheck_phase_two` is only meaningful once `identify_structure_info` is called.
+    pub fn stats(&self) -> LayerStats {
+        let mut num_uni = BTreeMap::new();
+        self.uni
+            .iter()
+            .for_each(|gate| *num_uni.entry(gate.gate_type).or_insert(0) += 1);
+
+        LayerStats {
+            input_var_num: self.input_var_num,
+            output_var_num: self.output_var_num,
+            num_mul: self.mul.len(),
+            num_add: self.add.len(),
+            num_const: self.const_.len(),
+            num_uni,
+            num_random_coefs: count_random_coefs(&self.mul)
+                + count_random_coefs(&self.add)
+                + count_random_coefs(&self.const_)
+                + count_random_coefs(&self.uni),
+            num_public_input_gates: self
+                .const_
+                .iter()
+                .filter(|gate| matches!(gate.coef_type, CoefType::PublicInput(_)))
+                .count(),
+            skip_sumcheck_phase_two: self.structure_info.skip_sumcheck_phase_two,
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        self.layers.iter().map(|layer| layer.stats()).collect()
+    }
+}
+
+impl fmt::Display for LayerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "input_var_num {}, output_var_num {}, mul {}, add {}, const {}, uni {:?}, \
+            random coefs {}, public input gates {}, skip_sumcheck_phase_two {}",
+            self.input_var_num,
+            self.output_var_num,
+            self.num_mul,
+            self.num_add,
+            self.num_const,
+            self.num_uni,
+            self.num_random_coefs,
+            self.num_public_input_gates,
+            self.skip_sumcheck_phase_two
+        )
+    }
+}
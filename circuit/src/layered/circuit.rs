@@ -4,10 +4,9 @@ use std::io::Cursor;
 use arith::{Field, SimdField};
 use ark_std::test_rng;
 use gkr_engine::{
-    root_println, ExpanderPCS, FieldEngine, GKREngine, MPIConfig, MPIEngine,
-    PolynomialCommitmentType, Transcript,
+    ompi_win_t, root_println, run_simd_kernel, twin_slice, twin_slice_mut, twin_value, ExpanderPCS,
+    FieldEngine, GKREngine, MPIConfig, MPIEngine, PolynomialCommitmentType, SimdKernel, Transcript,
 };
-use mpi::ffi::ompi_win_t;
 use serdes::ExpSerde;
 
 use crate::*;
@@ -41,51 +40,49 @@ impl<C: FieldEngine> CircuitLayer<C> {
         &self,
         res: &mut Vec<C::SimdCircuitField>,
         public_input: &[C::SimdCircuitField],
+    ) {
+        self.evaluate_with_input(&self.input_vals, res, public_input)
+    }
+
+    /// Same as [`Self::evaluate`], on the given input values instead of `self.input_vals`.
+    #[inline]
+    pub fn evaluate_with_input(
+        &self,
+        input_vals: &[C::SimdCircuitField],
+        res: &mut Vec<C::SimdCircuitField>,
+        public_input: &[C::SimdCircuitField],
     ) {
         res.clear();
         res.resize(1 << self.output_var_num, C::SimdCircuitField::zero());
-        for gate in &self.mul {
-            let i0 = &self.input_vals[gate.i_ids[0]];
-            let i1 = &self.input_vals[gate.i_ids[1]];
-            let o = &mut res[gate.o_id];
-            let mul = *i0 * i1;
-            *o += mul * gate.coef;
-        }
-
-        for gate in &self.add {
-            let i0 = self.input_vals[gate.i_ids[0]];
-            let o = &mut res[gate.o_id];
-            *o += i0 * gate.coef;
-        }
-
-        for gate in &self.const_ {
-            let o = &mut res[gate.o_id];
+        run_simd_kernel::<C::SimdCircuitField, _>(LayerEvaluation {
+            layer: self,
+            input_vals,
+            res,
+            public_input,
+        });
+    }
 
-            let coef = match gate.coef_type {
-                CoefType::PublicInput(input_idx) => public_input[input_idx],
-                _ => gate.coef.into(),
-            };
-            *o += coef;
-        }
+    /// The distinct degrees of the uni gates of the layer, in increasing order
+    #[inline]
+    pub fn uni_degrees(&self) -> Vec<usize> {
+        let mut degrees = self
+            .uni
+            .iter()
+            .map(|gate| gate.uni_type().degree())
+            .collect::<Vec<_>>();
+        degrees.sort_unstable();
+        degrees.dedup();
+        degrees
+    }
 
-        for gate in &self.uni {
-            let i0 = &self.input_vals[gate.i_ids[0]];
-            let o = &mut res[gate.o_id];
-            match gate.gate_type {
-                12345 => {
-                    // pow5
-                    let i0_2 = i0.square();
-                    let i0_4 = i0_2.square();
-                    let i0_5 = i0_4 * i0;
-                    *o += i0_5 * gate.coef;
-                }
-                12346 => {
-                    // pow1
-                    *o += *i0 * gate.coef;
-                }
-                _ => panic!("Unknown gate type: {}", gate.gate_type),
-            }
-        }
+    /// The highest degree of the uni gates of the layer, 0 if it has none
+    #[inline]
+    pub fn uni_degree(&self) -> usize {
+        self.uni
+            .iter()
+            .map(|gate| gate.uni_type().degree())
+            .max()
+            .unwrap_or(0)
     }
 
     #[inline]
@@ -118,6 +115,56 @@ impl<C: FieldEngine> CircuitLayer<C> {
     }
 }
 
+/// The evaluation of a layer, run on the SIMD circuit field or its AVX-512 twin
+struct LayerEvaluation<'a, C: FieldEngine> {
+    layer: &'a CircuitLayer<C>,
+    input_vals: &'a [C::SimdCircuitField],
+    res: &'a mut [C::SimdCircuitField],
+    public_input: &'a [C::SimdCircuitField],
+}
+
+impl<C: FieldEngine> SimdKernel for LayerEvaluation<'_, C> {
+    type Output = ();
+
+    #[inline(always)]
+    fn run<G: Field>(self) {
+        let input_vals = twin_slice::<_, G>(self.input_vals);
+        let res = twin_slice_mut::<_, G>(self.res);
+        let public_input = twin_slice::<_, G>(self.public_input);
+        let coef = |coef: C::CircuitField| twin_value::<_, G>(C::SimdCircuitField::from(coef));
+
+        for gate in &self.layer.mul {
+            let i0 = &input_vals[gate.i_ids[0]];
+            let i1 = &input_vals[gate.i_ids[1]];
+            let o = &mut res[gate.o_id];
+            let mul = *i0 * i1;
+            *o += mul * coef(gate.coef);
+        }
+
+        for gate in &self.layer.add {
+            let i0 = input_vals[gate.i_ids[0]];
+            let o = &mut res[gate.o_id];
+            *o += i0 * coef(gate.coef);
+        }
+
+        for gate in &self.layer.const_ {
+            let o = &mut res[gate.o_id];
+
+            let c = match gate.coef_type {
+                CoefType::PublicInput(input_idx) => public_input[input_idx],
+                _ => coef(gate.coef),
+            };
+            *o += c;
+        }
+
+        for gate in &self.layer.uni {
+            let i0 = input_vals[gate.i_ids[0]];
+            let o = &mut res[gate.o_id];
+            *o += gate.uni_type().evaluate(i0) * coef(gate.coef);
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Circuit<C: FieldEngine> {
     pub layers: Vec<CircuitLayer<C>>,
@@ -218,14 +265,44 @@ impl<C: FieldEngine> Circuit<C> {
         is_prover: bool,
         allow_padding_for_testing: bool, // TODO: Consider remove this
     ) {
+        if let Err(e) = self.try_load_witness_bytes(
+            file_bytes,
+            mpi_config,
+            is_prover,
+            allow_padding_for_testing,
+        ) {
+            panic!("{e}");
+        }
+    }
+
+    /// Same as `load_witness_bytes`, rejecting a witness that does not fit the circuit instead
+    /// of panicking. The circuit is left untouched if the witness is rejected.
+    pub fn try_load_witness_bytes(
+        &mut self,
+        file_bytes: &[u8],
+        mpi_config: &MPIConfig,
+        is_prover: bool,
+        allow_padding_for_testing: bool,
+    ) -> Result<(), WitnessLoadError> {
         let cursor = Cursor::new(file_bytes);
-        let mut witness = Witness::<C>::deserialize_from(cursor).unwrap();
+        let mut witness = Witness::<C>::deserialize_from(cursor)?;
 
         // sizes for a single piece of witness
         let private_input_size = 1 << self.log_input_size();
         let public_input_size = witness.num_public_inputs_per_witness;
+        if witness.num_private_inputs_per_witness != private_input_size {
+            return Err(WitnessLoadError::PrivateInputSizeMismatch {
+                expected: private_input_size,
+                got: witness.num_private_inputs_per_witness,
+            });
+        }
+        if public_input_size < self.num_public_inputs() {
+            return Err(WitnessLoadError::NotEnoughPublicInputs {
+                expected: self.num_public_inputs(),
+                got: public_input_size,
+            });
+        }
         let total_size = private_input_size + public_input_size;
-        assert_eq!(witness.num_private_inputs_per_witness, private_input_size);
         root_println!(
             mpi_config,
             "Witness loaded: {} private inputs, {} public inputs, x{} witnesses",
@@ -239,11 +316,11 @@ impl<C: FieldEngine> Circuit<C> {
 
         #[allow(clippy::comparison_chain)]
         if witness.num_witnesses < desired_number_of_witnesses {
-            if !allow_padding_for_testing {
-                panic!(
-                    "Not enough witness, expected {}, got {}",
-                    desired_number_of_witnesses, witness.num_witnesses
-                );
+            if !allow_padding_for_testing || witness.num_witnesses == 0 {
+                return Err(WitnessLoadError::NotEnoughWitnesses {
+                    expected: desired_number_of_witnesses,
+                    got: witness.num_witnesses,
+                });
             } else {
                 println!(
                     "Warning: padding witnesses, expected {}, got {}",
@@ -271,6 +348,26 @@ impl<C: FieldEngine> Circuit<C> {
         } else {
             self.verifier_process_witness(witness, mpi_config);
         }
+        Ok(())
+    }
+
+    /// A copy of the circuit with the witness loaded, see `try_load_witness_bytes`, for a
+    /// circuit shared by several proofs or verifications.
+    pub fn with_witness_bytes(
+        &self,
+        file_bytes: &[u8],
+        mpi_config: &MPIConfig,
+        is_prover: bool,
+        allow_padding_for_testing: bool,
+    ) -> Result<Self, WitnessLoadError> {
+        let mut circuit = self.clone();
+        circuit.try_load_witness_bytes(
+            file_bytes,
+            mpi_config,
+            is_prover,
+            allow_padding_for_testing,
+        )?;
+        Ok(circuit)
     }
 
     pub fn prover_process_witness(&mut self, witness: Witness<C>, mpi_config: &MPIConfig) {
@@ -1,4 +1,5 @@
-use gkr_engine::FieldEngine;
+use arith::Field;
+use gkr_engine::{FieldEngine, FieldType};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum CoefType {
@@ -25,3 +26,99 @@ pub type GateConst<C> = Gate<C, 0>;
 // If I Simply add derive(Copy) to the Gate struct, the compiler does not seem to recognize it
 // for the type aliases. Explicitly state it here.
 impl<C: FieldEngine, const INPUT_NUM: usize> Copy for Gate<C, INPUT_NUM> {}
+
+/// The gate types of the uni gates of the circuit compiler (ExpanderCompilerCollection), which
+/// are the only ones it produces, e.g. in the Poseidon circuit the tests load
+pub const GATE_TYPE_POW5: usize = 12345;
+pub const GATE_TYPE_IDENTITY: usize = 12346;
+
+/// The gate type of `UniGateType::Pow(d)` is `GATE_TYPE_POW_BASE + d`, for any degree up to
+/// `MAX_UNI_GATE_DEGREE` not covered by the compiler's gate types.
+///
+/// This encoding is specific to this crate and not produced by the compiler: such gates are
+/// written with the text format or built with the API of this crate. The base is far above the
+/// compiler's gate types so as not to collide with them.
+pub const GATE_TYPE_POW_BASE: usize = 1 << 20;
+
+/// The maps a uni gate can apply to its input, the gate adding `coef * f(input)` to its output.
+///
+/// Any univariate polynomial is a sum of `Pow` gates on the same wires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniGateType {
+    /// `f(x) = x^d`
+    Pow(usize),
+}
+
+/// The gate types the circuit compiler assigns to uni gates, kept along with the
+/// `GATE_TYPE_POW_BASE + d` encoding
+pub const UNI_GATE_TYPES: [(usize, UniGateType); 2] = [
+    (GATE_TYPE_POW5, UniGateType::Pow(5)),
+    (GATE_TYPE_IDENTITY, UniGateType::Pow(1)),
+];
+
+/// The highest degree of a uni gate, which sets the degree of the GKR square sumcheck
+pub const MAX_UNI_GATE_DEGREE: usize = 7;
+
+impl UniGateType {
+    /// The map of a gate type, either one of `UNI_GATE_TYPES` or `GATE_TYPE_POW_BASE + d` for
+    /// a degree `d` between 1 and `MAX_UNI_GATE_DEGREE`, `None` for any other gate type.
+    #[inline]
+    pub fn from_gate_type(gate_type: usize) -> Option<Self> {
+        UNI_GATE_TYPES
+            .iter()
+            .find(|(t, _)| *t == gate_type)
+            .map(|(_, uni_type)| *uni_type)
+            .or_else(|| {
+                let degree = gate_type.checked_sub(GATE_TYPE_POW_BASE)?;
+                (1..=MAX_UNI_GATE_DEGREE)
+                    .contains(&degree)
+                    .then_some(UniGateType::Pow(degree))
+            })
+    }
+
+    /// The gate type of the map, the one of `UNI_GATE_TYPES` if any
+    #[inline]
+    pub fn gate_type(&self) -> usize {
+        UNI_GATE_TYPES
+            .iter()
+            .find(|(_, uni_type)| uni_type == self)
+            .map_or_else(|| GATE_TYPE_POW_BASE + self.degree(), |(t, _)| *t)
+    }
+
+    #[inline]
+    pub fn degree(&self) -> usize {
+        match self {
+            UniGateType::Pow(d) => *d,
+        }
+    }
+
+    /// Whether GKR square supports the map over `field_type`. Over GF2Ext128, where every power
+    /// is the identity on the bits of the circuit, only the identity and the square, which is
+    /// linear over GF(2), are supported.
+    #[inline]
+    pub fn is_supported_over(&self, field_type: FieldType) -> bool {
+        match field_type {
+            FieldType::GF2Ext128 => matches!(self, UniGateType::Pow(1) | UniGateType::Pow(2)),
+            _ => true,
+        }
+    }
+
+    #[inline]
+    pub fn evaluate<F: Field>(&self, x: F) -> F {
+        match self {
+            UniGateType::Pow(d) => x.exp(*d as u128),
+        }
+    }
+}
+
+impl<C: FieldEngine> GateUni<C> {
+    /// The map applied by this uni gate.
+    ///
+    /// Unknown gate types are rejected when a circuit is deserialized or parsed, so this only
+    /// panics on a gate built by hand with an unknown gate type.
+    #[inline]
+    pub fn uni_type(&self) -> UniGateType {
+        UniGateType::from_gate_type(self.gate_type)
+            .unwrap_or_else(|| panic!("Unknown gate type: {}", self.gate_type))
+    }
+}
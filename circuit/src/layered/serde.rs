@@ -2,10 +2,11 @@ use std::io::{Read, Write};
 
 use arith::Field;
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 
 use super::circuit::{Circuit, CircuitLayer, StructureInfo};
-use super::gates::{CoefType, Gate, GateAdd, GateConst, GateMul, GateUni};
+use super::gates::{CoefType, Gate, GateAdd, GateConst, GateMul};
+use crate::serde::{deserialize_uni_gates, serialize_uni_gates};
 
 impl ExpSerde for CoefType {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
@@ -29,7 +30,7 @@ impl ExpSerde for CoefType {
                 let idx = usize::deserialize_from(&mut reader)?;
                 Ok(CoefType::PublicInput(idx))
             }
-            _ => panic!("Invalid tag for CoefType"),
+            _ => Err(SerdeError::InvalidVariantIndex(tag as usize)),
         }
     }
 }
@@ -74,17 +75,12 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for Gate<C, INPUT_NUM> {
                 C::CircuitField::deserialize_from(&mut reader)?,
             ),
             2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(<usize as ExpSerde>::deserialize_from(&mut reader)?),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
+            // public inputs can only be used with constant gates
+            3 if INPUT_NUM == 0 => (
+                CoefType::PublicInput(<usize as ExpSerde>::deserialize_from(&mut reader)?),
+                C::CircuitField::ZERO,
+            ),
+            _ => return Err(SerdeError::DeserializeError),
         };
 
         Ok(Self {
@@ -104,8 +100,8 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
         self.mul.serialize_into(&mut writer)?;
         self.add.serialize_into(&mut writer)?;
         self.const_.serialize_into(&mut writer)?;
-        self.uni.serialize_into(&mut writer)?;
-        Ok(())
+        // uni gates keep their gate type, which selects the map they apply
+        serialize_uni_gates(&self.uni, &mut writer)
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
@@ -114,7 +110,7 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
         let mul = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
         let add = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
         let const_ = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
-        let uni = Vec::<GateUni<C>>::deserialize_from(&mut reader)?;
+        let uni = deserialize_uni_gates(&mut reader)?;
         Ok(CircuitLayer {
             input_var_num,
             output_var_num,
@@ -7,5 +7,14 @@ pub use layered::*;
 mod witness;
 pub use witness::*;
 
+mod satisfiability;
+pub use satisfiability::*;
+
 mod serde;
 pub use serde::*;
+
+mod text;
+pub use text::*;
+
+mod inspect;
+pub use inspect::*;
@@ -0,0 +1,232 @@
+use std::{collections::BTreeSet, fmt};
+
+use arith::{Field, SimdField};
+use gkr_engine::{FieldEngine, MPIConfig, MPIEngine};
+use serdes::ExpSerde;
+
+use crate::{Circuit, CoefType, Gate, UniGateType};
+
+/// Which gate list of a layer a [`TracedGate`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ExpSerde)]
+pub enum GateKind {
+    Mul,
+    Add,
+    Const,
+    Uni,
+}
+
+/// A gate on the path to a non-zero output, evaluated in the lane of the failure.
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct TracedGate<F: Field> {
+    /// Layer of the gate, 0 being the input layer
+    pub layer: usize,
+    pub kind: GateKind,
+    pub gate_type: usize,
+    pub o_id: usize,
+    pub i_ids: Vec<usize>,
+    pub i_vals: Vec<F>,
+    /// Coefficient of the gate, with public inputs and random coefficients resolved
+    pub coef: F,
+    /// Contribution of the gate to its output
+    pub value: F,
+}
+
+/// A non-zero output among the first `expected_num_output_zeros` outputs of a circuit.
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct UnsatisfiedOutput<F: Field> {
+    /// MPI rank of the piece of witness
+    pub shard: usize,
+    /// SIMD lane of the piece of witness
+    pub lane: usize,
+    pub output: usize,
+    pub value: F,
+    /// Gates the output depends on, from the output layer down to the input layer, in the order
+    /// of a breadth first search
+    pub trace: Vec<TracedGate<F>>,
+    /// Whether the trace stopped at `max_trace_gates` before reaching the inputs
+    pub trace_truncated: bool,
+}
+
+impl fmt::Display for GateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateKind::Mul => write!(f, "mul"),
+            GateKind::Add => write!(f, "add"),
+            GateKind::Const => write!(f, "const"),
+            GateKind::Uni => write!(f, "uni"),
+        }
+    }
+}
+
+impl<F: Field> fmt::Display for UnsatisfiedOutput<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "output {} is {:?} at shard {}, lane {}",
+            self.output, self.value, self.shard, self.lane
+        )?;
+        for gate in &self.trace {
+            writeln!(
+                f,
+                "  layer {} {} gate (type {}) -> {}: inputs {:?} = {:?}, coef {:?}, contributes {:?}",
+                gate.layer,
+                gate.kind,
+                gate.gate_type,
+                gate.o_id,
+                gate.i_ids,
+                gate.i_vals,
+                gate.coef,
+                gate.value
+            )?;
+        }
+        if self.trace_truncated {
+            writeln!(f, "  ...")?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Evaluate the circuit on the loaded witness, and report the non-zero outputs among the first
+    /// `expected_num_output_zeros` ones, over all SIMD lanes and MPI shards.
+    ///
+    /// Each failure comes with a backward trace through the gates it depends on, of at most
+    /// `max_trace_gates` gates. Random coefficients are sampled afresh.
+    ///
+    /// All processes must call this, the failures are gathered at the root, and other processes get
+    /// an empty result.
+    pub fn check_satisfiability(
+        &self,
+        mpi_config: &MPIConfig,
+        max_trace_gates: usize,
+    ) -> Vec<UnsatisfiedOutput<C::CircuitField>> {
+        let circuit = self.with_fresh_rnd_coefs();
+
+        // vals[i] are the inputs of layer i, and the last one holds the outputs
+        let mut vals = vec![circuit.layers[0].input_vals.clone()];
+        for layer in &circuit.layers {
+            let mut output_vals = vec![];
+            layer.evaluate_with_input(
+                vals.last().unwrap(),
+                &mut output_vals,
+                &circuit.public_input,
+            );
+            vals.push(output_vals);
+        }
+
+        let mut failures = vec![];
+        for (output, v) in vals.last().unwrap()[..circuit.expected_num_output_zeros]
+            .iter()
+            .enumerate()
+        {
+            for (lane, value) in v.unpack().into_iter().enumerate() {
+                if !value.is_zero() {
+                    let (trace, trace_truncated) =
+                        circuit.trace_output(&vals, output, lane, max_trace_gates);
+                    failures.push(UnsatisfiedOutput {
+                        shard: mpi_config.world_rank(),
+                        lane,
+                        output,
+                        value,
+                        trace,
+                        trace_truncated,
+                    });
+                }
+            }
+        }
+
+        if mpi_config.is_single_process() {
+            return failures;
+        }
+        let mut all_failures = vec![];
+        mpi_config.gather_varlen_vec(&failures, &mut all_failures);
+        all_failures.into_iter().flatten().collect()
+    }
+
+    /// Walk back from an output through the gates it depends on, layer by layer.
+    fn trace_output(
+        &self,
+        vals: &[Vec<C::SimdCircuitField>],
+        output: usize,
+        lane: usize,
+        max_trace_gates: usize,
+    ) -> (Vec<TracedGate<C::CircuitField>>, bool) {
+        let mut trace = vec![];
+        let mut wires = BTreeSet::from([output]);
+        for (i, layer) in self.layers.iter().enumerate().rev() {
+            let layer_trace = LayerTrace::<C> {
+                layer: i,
+                input_vals: &vals[i],
+                public_input: &self.public_input,
+                lane,
+                wires: &wires,
+            };
+            let mut traced = vec![];
+            layer_trace.trace_gates(&layer.mul, GateKind::Mul, &mut traced);
+            layer_trace.trace_gates(&layer.add, GateKind::Add, &mut traced);
+            layer_trace.trace_gates(&layer.const_, GateKind::Const, &mut traced);
+            layer_trace.trace_gates(&layer.uni, GateKind::Uni, &mut traced);
+
+            if trace.len() + traced.len() > max_trace_gates {
+                traced.truncate(max_trace_gates - trace.len());
+                trace.extend(traced);
+                return (trace, true);
+            }
+            wires = traced
+                .iter()
+                .flat_map(|gate| gate.i_ids.iter().copied())
+                .collect();
+            trace.extend(traced);
+        }
+        (trace, false)
+    }
+}
+
+/// The gates of a layer writing to `wires`, in a single lane.
+struct LayerTrace<'a, C: FieldEngine> {
+    layer: usize,
+    input_vals: &'a [C::SimdCircuitField],
+    public_input: &'a [C::SimdCircuitField],
+    lane: usize,
+    wires: &'a BTreeSet<usize>,
+}
+
+impl<C: FieldEngine> LayerTrace<'_, C> {
+    fn trace_gates<const INPUT_NUM: usize>(
+        &self,
+        gates: &[Gate<C, INPUT_NUM>],
+        kind: GateKind,
+        traced: &mut Vec<TracedGate<C::CircuitField>>,
+    ) {
+        for gate in gates.iter().filter(|gate| self.wires.contains(&gate.o_id)) {
+            let i_vals = gate
+                .i_ids
+                .iter()
+                .map(|&id| self.input_vals[id].unpack()[self.lane])
+                .collect::<Vec<_>>();
+            let coef = match gate.coef_type {
+                CoefType::PublicInput(idx) => self.public_input[idx].unpack()[self.lane],
+                _ => gate.coef,
+            };
+            let value = match kind {
+                GateKind::Mul => coef * i_vals[0] * i_vals[1],
+                GateKind::Add => coef * i_vals[0],
+                GateKind::Const => coef,
+                GateKind::Uni => match UniGateType::from_gate_type(gate.gate_type) {
+                    Some(uni_type) => coef * uni_type.evaluate(i_vals[0]),
+                    None => panic!("Unknown gate type: {}", gate.gate_type),
+                },
+            };
+            traced.push(TracedGate {
+                layer: self.layer,
+                kind,
+                gate_type: gate.gate_type,
+                o_id: gate.o_id,
+                i_ids: gate.i_ids.to_vec(),
+                i_vals,
+                coef,
+                value,
+            });
+        }
+    }
+}
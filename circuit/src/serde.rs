@@ -1,27 +1,46 @@
 use arith::Field;
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 use std::{
     io::{Read, Write},
     vec,
 };
 
 use super::{Allocation, CoefType, Gate, RecursiveCircuit, Segment, Witness};
-use crate::{GateAdd, GateConst, GateMul, SegmentId};
+use crate::{GateAdd, GateConst, GateMul, GateUni, SegmentId, UniGateType};
 pub struct CustomGateWrapper<C: FieldEngine, const INPUT_NUM: usize> {
     pub custom_gate: Gate<C, INPUT_NUM>,
 }
 
 impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, INPUT_NUM> {
-    fn serialize_into<W: std::io::Write>(&self, mut _writer: W) -> SerdeResult<()> {
-        todo!()
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        let gate = &self.custom_gate;
+        gate.gate_type.serialize_into(&mut writer)?;
+        gate.i_ids.to_vec().serialize_into(&mut writer)?;
+        gate.o_id.serialize_into(&mut writer)?;
+
+        match gate.coef_type {
+            CoefType::Constant => {
+                1u8.serialize_into(&mut writer)?;
+                gate.coef.serialize_into(&mut writer)?;
+            }
+            CoefType::Random => {
+                2u8.serialize_into(&mut writer)?;
+            }
+            CoefType::PublicInput(idx) => {
+                3u8.serialize_into(&mut writer)?;
+                idx.serialize_into(&mut writer)?;
+            }
+        }
+
+        Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
-        let gate_type = <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
+        let gate_type = <usize as ExpSerde>::deserialize_from(&mut reader)?;
         let i_ids: [usize; INPUT_NUM] = <Vec<usize> as ExpSerde>::deserialize_from(&mut reader)?
             .try_into()
-            .unwrap();
+            .map_err(|_| SerdeError::DeserializeError)?;
 
         let o_id = <usize as ExpSerde>::deserialize_from(&mut reader)?;
 
@@ -32,17 +51,12 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, I
                 C::CircuitField::deserialize_from(&mut reader)?,
             ),
             2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(<usize as ExpSerde>::deserialize_from(&mut reader)?),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
+            // public inputs can only be used with constant gates
+            3 if INPUT_NUM == 0 => (
+                CoefType::PublicInput(<usize as ExpSerde>::deserialize_from(&mut reader)?),
+                C::CircuitField::ZERO,
+            ),
+            _ => return Err(SerdeError::DeserializeError),
         };
 
         Ok(Self {
@@ -57,22 +71,46 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, I
     }
 }
 
+/// Serialize uni gates along with their gate types
+pub(crate) fn serialize_uni_gates<C: FieldEngine, W: Write>(
+    gates: &[GateUni<C>],
+    mut writer: W,
+) -> SerdeResult<()> {
+    <usize as ExpSerde>::serialize_into(&gates.len(), &mut writer)?;
+    for gate in gates {
+        CustomGateWrapper::<C, 1> { custom_gate: *gate }.serialize_into(&mut writer)?;
+    }
+    Ok(())
+}
+
+/// Deserialize uni gates, rejecting the gate types `UniGateType` does not know
+pub(crate) fn deserialize_uni_gates<C: FieldEngine, R: Read>(
+    mut reader: R,
+) -> SerdeResult<Vec<GateUni<C>>> {
+    let len = <usize as ExpSerde>::deserialize_from(&mut reader)?;
+    (0..len)
+        .map(|_| {
+            let gate = CustomGateWrapper::<C, 1>::deserialize_from(&mut reader)?.custom_gate;
+            match UniGateType::from_gate_type(gate.gate_type) {
+                Some(_) => Ok(gate),
+                None => Err(SerdeError::DeserializeError),
+            }
+        })
+        .collect()
+}
+
 impl<C: FieldEngine> ExpSerde for Segment<C> {
     fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
-        <usize as ExpSerde>::serialize_into(&self.i_var_num, &mut writer)?;
-        <usize as ExpSerde>::serialize_into(&self.o_var_num, &mut writer)?;
+        // the sizes are stored, rather than the numbers of variables
+        <usize as ExpSerde>::serialize_into(&(1 << self.i_var_num), &mut writer)?;
+        <usize as ExpSerde>::serialize_into(&(1 << self.o_var_num), &mut writer)?;
 
         self.child_segs.serialize_into(&mut writer)?;
         self.gate_muls.serialize_into(&mut writer)?;
         self.gate_adds.serialize_into(&mut writer)?;
         self.gate_consts.serialize_into(&mut writer)?;
 
-        <usize as ExpSerde>::serialize_into(&self.gate_uni.len(), &mut writer)?;
-        for uni in &self.gate_uni {
-            CustomGateWrapper::<C, 1> { custom_gate: *uni }.serialize_into(&mut writer)?;
-        }
-
-        Ok(())
+        serialize_uni_gates(&self.gate_uni, &mut writer)
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
@@ -85,13 +123,8 @@ impl<C: FieldEngine> ExpSerde for Segment<C> {
         let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
         let gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
         let gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
+        let gate_uni = deserialize_uni_gates(&mut reader)?;
 
-        let mut gate_uni = vec![];
-        let len = <usize as ExpSerde>::deserialize_from(&mut reader)?;
-        for _ in 0..len {
-            let uni = CustomGateWrapper::<C, 1>::deserialize_from(&mut reader)?.custom_gate;
-            gate_uni.push(uni);
-        }
         Ok(Segment {
             i_var_num: i_len.trailing_zeros() as usize,
             o_var_num: o_len.trailing_zeros() as usize,
@@ -141,22 +174,32 @@ impl<C: FieldEngine> ExpSerde for RecursiveCircuit<C> {
 }
 
 impl<C: FieldEngine> ExpSerde for Witness<C> {
-    fn serialize_into<W: std::io::Write>(&self, mut _writer: W) -> SerdeResult<()> {
-        todo!()
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.num_witnesses.serialize_into(&mut writer)?;
+        self.num_private_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        self.num_public_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        C::CircuitField::MODULUS.serialize_into(&mut writer)?;
+
+        self.values
+            .iter()
+            .try_for_each(|v| v.serialize_into(&mut writer))
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
-        let num_witnesses = <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
-        let num_private_inputs_per_witness =
-            <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
-        let num_public_inputs_per_witness =
-            <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
-        let _modulus = <[u64; 4]>::deserialize_from(&mut reader).unwrap();
-
+        let num_witnesses = <usize as ExpSerde>::deserialize_from(&mut reader)?;
+        let num_private_inputs_per_witness = <usize as ExpSerde>::deserialize_from(&mut reader)?;
+        let num_public_inputs_per_witness = <usize as ExpSerde>::deserialize_from(&mut reader)?;
+        let _modulus = <[u64; 4]>::deserialize_from(&mut reader)?;
+
+        let num_values = num_private_inputs_per_witness
+            .checked_add(num_public_inputs_per_witness)
+            .and_then(|total_size| total_size.checked_mul(num_witnesses))
+            .ok_or(SerdeError::DeserializeError)?;
         let mut values = vec![];
-        for _ in 0..num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness)
-        {
-            values.push(C::CircuitField::deserialize_from(&mut reader).unwrap());
+        for _ in 0..num_values {
+            values.push(C::CircuitField::deserialize_from(&mut reader)?);
         }
 
         Ok(Self {
@@ -0,0 +1,477 @@
+//! A line based text format for circuits, meant to be read and diffed by humans, e.g., across
+//! compiler versions. It holds everything the binary format does, so a circuit can be dumped,
+//! edited, and imported back.
+//!
+//! ```text
+//! recursive_circuit
+//! modulus 0x7fffffff
+//! num_public_inputs 1
+//! num_outputs 2
+//! expected_num_output_zeros 1
+//! layers 0
+//! segment 0
+//! i_var_num 2
+//! o_var_num 1
+//! child 1 0:0 2:1
+//! mul 0 1 -> 0 const 01000000
+//! add 2 -> 1 random
+//! const -> 1 public 0
+//! uni 12345 3 -> 0 const 01000000
+//! end
+//! ```
+//!
+//! A gate reads `<kind> [gate type, for uni gates] <input ids> -> <output id> <coefficient>`,
+//! where the coefficient is `random`, `public <index>`, or `const <value>`, and the gate type is
+//! one of `UNI_GATE_TYPES` or `GATE_TYPE_POW_BASE + d` for `x^d`. Field elements are written as
+//! the hex of their serialized bytes. Empty lines and anything after `#` are ignored.
+
+use std::{fmt::Write, io::Cursor, str::FromStr};
+
+use arith::Field;
+use ethnum::U256;
+use gkr_engine::FieldEngine;
+use serdes::ExpSerde;
+use thiserror::Error;
+
+use crate::{
+    Allocation, Circuit, CircuitLayer, CoefType, Gate, RecursiveCircuit, Segment, SegmentId,
+    UniGateType,
+};
+
+#[derive(Debug, Error)]
+pub enum CircuitTextError {
+    #[error("line {0}: {1}")]
+    Syntax(usize, String),
+
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+
+    #[error("the circuit is over the field of modulus {0:#x}, expected {1:#x}")]
+    ModulusMismatch(U256, U256),
+}
+
+fn field_to_text<F: Field>(f: &F) -> String {
+    let mut bytes = vec![];
+    f.serialize_into(&mut bytes).unwrap();
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn write_gate<C: FieldEngine, const INPUT_NUM: usize>(
+    text: &mut String,
+    kind: &str,
+    gate: &Gate<C, INPUT_NUM>,
+) {
+    write!(text, "{kind}").unwrap();
+    if kind == "uni" {
+        write!(text, " {}", gate.gate_type).unwrap();
+    }
+    gate.i_ids
+        .iter()
+        .for_each(|id| write!(text, " {id}").unwrap());
+    write!(text, " -> {}", gate.o_id).unwrap();
+    match gate.coef_type {
+        CoefType::Constant => writeln!(text, " const {}", field_to_text(&gate.coef)),
+        CoefType::Random => writeln!(text, " random"),
+        CoefType::PublicInput(idx) => writeln!(text, " public {idx}"),
+    }
+    .unwrap();
+}
+
+fn write_modulus<C: FieldEngine>(text: &mut String) {
+    writeln!(text, "modulus {:#x}", C::CircuitField::MODULUS).unwrap();
+}
+
+impl<C: FieldEngine> RecursiveCircuit<C> {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "recursive_circuit").unwrap();
+        write_modulus::<C>(&mut text);
+        writeln!(text, "num_public_inputs {}", self.num_public_inputs).unwrap();
+        writeln!(text, "num_outputs {}", self.num_outputs).unwrap();
+        writeln!(
+            text,
+            "expected_num_output_zeros {}",
+            self.expected_num_output_zeros
+        )
+        .unwrap();
+        write!(text, "layers").unwrap();
+        self.layers
+            .iter()
+            .for_each(|id| write!(text, " {id}").unwrap());
+        writeln!(text).unwrap();
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            writeln!(text, "segment {i}").unwrap();
+            text.push_str(&segment.to_text());
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, CircuitTextError> {
+        let mut lines = Lines::new(text);
+        lines.expect_header("recursive_circuit")?;
+        lines.expect_modulus::<C>()?;
+        let num_public_inputs = lines.expect_value("num_public_inputs")?;
+        let num_outputs = lines.expect_value("num_outputs")?;
+        let expected_num_output_zeros = lines.expect_value("expected_num_output_zeros")?;
+        let (line_num, tokens) = lines.expect_key("layers")?;
+        let layers = tokens[1..]
+            .iter()
+            .map(|token| parse(line_num, token))
+            .collect::<Result<Vec<SegmentId>, _>>()?;
+
+        let mut segments = vec![];
+        while let Some((line_num, tokens)) = lines.next() {
+            check_line(line_num, &tokens, "segment", 2)?;
+            let id: SegmentId = parse(line_num, tokens[1])?;
+            if id != segments.len() {
+                return Err(syntax_error(
+                    line_num,
+                    format!("expected segment {}, got {id}", segments.len()),
+                ));
+            }
+            segments.push(Segment::parse(&mut lines)?);
+        }
+
+        let num_segments = segments.len();
+        if let Some(id) = layers.iter().find(|&&id| id >= num_segments) {
+            return Err(syntax_error(
+                line_num,
+                format!("segment {id} does not exist"),
+            ));
+        }
+        Ok(Self {
+            num_public_inputs,
+            num_outputs,
+            expected_num_output_zeros,
+            segments,
+            layers,
+        })
+    }
+}
+
+impl<C: FieldEngine> Segment<C> {
+    /// The segment, without its header, i.e., from `i_var_num` to `end`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "i_var_num {}", self.i_var_num).unwrap();
+        writeln!(text, "o_var_num {}", self.o_var_num).unwrap();
+        for (id, allocations) in &self.child_segs {
+            write!(text, "child {id}").unwrap();
+            allocations
+                .iter()
+                .for_each(|a| write!(text, " {}:{}", a.i_offset, a.o_offset).unwrap());
+            writeln!(text).unwrap();
+        }
+        self.gate_muls
+            .iter()
+            .for_each(|gate| write_gate(&mut text, "mul", gate));
+        self.gate_adds
+            .iter()
+            .for_each(|gate| write_gate(&mut text, "add", gate));
+        self.gate_consts
+            .iter()
+            .for_each(|gate| write_gate(&mut text, "const", gate));
+        self.gate_uni
+            .iter()
+            .for_each(|gate| write_gate(&mut text, "uni", gate));
+        writeln!(text, "end").unwrap();
+        text
+    }
+
+    fn parse(lines: &mut Lines) -> Result<Self, CircuitTextError> {
+        let mut segment = Segment {
+            i_var_num: lines.expect_value("i_var_num")?,
+            o_var_num: lines.expect_value("o_var_num")?,
+            ..Default::default()
+        };
+        loop {
+            let (line_num, tokens) = lines.expect_line()?;
+            match tokens[0] {
+                "child" => {
+                    if tokens.len() < 2 {
+                        return Err(syntax_error(line_num, "missing child segment id".into()));
+                    }
+                    let allocations = tokens[2..]
+                        .iter()
+                        .map(|token| -> Result<Allocation, CircuitTextError> {
+                            let (i_offset, o_offset) = token.split_once(':').ok_or_else(|| {
+                                syntax_error(line_num, format!("invalid allocation {token}"))
+                            })?;
+                            Ok(Allocation {
+                                i_offset: parse(line_num, i_offset)?,
+                                o_offset: parse(line_num, o_offset)?,
+                            })
+                        })
+                        .collect::<Result<_, _>>()?;
+                    segment
+                        .child_segs
+                        .push((parse(line_num, tokens[1])?, allocations));
+                }
+                "mul" => segment.gate_muls.push(parse_gate(line_num, &tokens)?),
+                "add" => segment.gate_adds.push(parse_gate(line_num, &tokens)?),
+                "const" => segment.gate_consts.push(parse_gate(line_num, &tokens)?),
+                "uni" => segment.gate_uni.push(parse_gate(line_num, &tokens)?),
+                "end" => return Ok(segment),
+                token => {
+                    return Err(syntax_error(line_num, format!("unexpected {token}")));
+                }
+            }
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// The layers of the circuit and the number of outputs expected to be zero. Input values and
+    /// random coefficients are not part of the text.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "circuit").unwrap();
+        write_modulus::<C>(&mut text);
+        writeln!(
+            text,
+            "expected_num_output_zeros {}",
+            self.expected_num_output_zeros
+        )
+        .unwrap();
+        for (i, layer) in self.layers.iter().enumerate() {
+            writeln!(text, "layer {i}").unwrap();
+            writeln!(text, "input_var_num {}", layer.input_var_num).unwrap();
+            writeln!(text, "output_var_num {}", layer.output_var_num).unwrap();
+            layer
+                .mul
+                .iter()
+                .for_each(|gate| write_gate(&mut text, "mul", gate));
+            layer
+                .add
+                .iter()
+                .for_each(|gate| write_gate(&mut text, "add", gate));
+            layer
+                .const_
+                .iter()
+                .for_each(|gate| write_gate(&mut text, "const", gate));
+            layer
+                .uni
+                .iter()
+                .for_each(|gate| write_gate(&mut text, "uni", gate));
+            writeln!(text, "end").unwrap();
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, CircuitTextError> {
+        let mut lines = Lines::new(text);
+        lines.expect_header("circuit")?;
+        lines.expect_modulus::<C>()?;
+        let mut circuit = Circuit::<C> {
+            expected_num_output_zeros: lines.expect_value("expected_num_output_zeros")?,
+            ..Default::default()
+        };
+
+        while let Some((line_num, tokens)) = lines.next() {
+            check_line(line_num, &tokens, "layer", 2)?;
+            let id: usize = parse(line_num, tokens[1])?;
+            if id != circuit.layers.len() {
+                return Err(syntax_error(
+                    line_num,
+                    format!("expected layer {}, got {id}", circuit.layers.len()),
+                ));
+            }
+
+            let mut layer = CircuitLayer::<C> {
+                input_var_num: lines.expect_value("input_var_num")?,
+                output_var_num: lines.expect_value("output_var_num")?,
+                ..Default::default()
+            };
+            loop {
+                let (line_num, tokens) = lines.expect_line()?;
+                match tokens[0] {
+                    "mul" => layer.mul.push(parse_gate(line_num, &tokens)?),
+                    "add" => layer.add.push(parse_gate(line_num, &tokens)?),
+                    "const" => layer.const_.push(parse_gate(line_num, &tokens)?),
+                    "uni" => layer.uni.push(parse_gate(line_num, &tokens)?),
+                    "end" => break,
+                    token => {
+                        return Err(syntax_error(line_num, format!("unexpected {token}")));
+                    }
+                }
+            }
+            circuit.layers.push(layer);
+        }
+        Ok(circuit)
+    }
+}
+
+fn syntax_error(line_num: usize, message: String) -> CircuitTextError {
+    CircuitTextError::Syntax(line_num, message)
+}
+
+fn parse<T: FromStr>(line_num: usize, token: &str) -> Result<T, CircuitTextError> {
+    token
+        .parse()
+        .map_err(|_| syntax_error(line_num, format!("invalid number {token}")))
+}
+
+fn check_line(
+    line_num: usize,
+    tokens: &[&str],
+    key: &str,
+    len: usize,
+) -> Result<(), CircuitTextError> {
+    if tokens[0] != key || tokens.len() != len {
+        return Err(syntax_error(
+            line_num,
+            format!("expected {key}, got {}", tokens.join(" ")),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_field<F: Field>(line_num: usize, token: &str) -> Result<F, CircuitTextError> {
+    let invalid = || syntax_error(line_num, format!("invalid field element {token}"));
+    if token.len() % 2 != 0 {
+        return Err(invalid());
+    }
+    let bytes = (0..token.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&token[i..i + 2], 16).map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut cursor = Cursor::new(&bytes);
+    let f = F::deserialize_from(&mut cursor).map_err(|_| invalid())?;
+    if cursor.position() as usize != bytes.len() {
+        return Err(invalid());
+    }
+    Ok(f)
+}
+
+fn parse_gate<C: FieldEngine, const INPUT_NUM: usize>(
+    line_num: usize,
+    tokens: &[&str],
+) -> Result<Gate<C, INPUT_NUM>, CircuitTextError> {
+    let invalid = || syntax_error(line_num, format!("invalid gate {}", tokens.join(" ")));
+
+    let (gate_type, tokens) = match tokens[0] {
+        "uni" if tokens.len() > 1 => {
+            let gate_type = parse(line_num, tokens[1])?;
+            if UniGateType::from_gate_type(gate_type).is_none() {
+                return Err(syntax_error(
+                    line_num,
+                    format!("unknown uni gate type {gate_type}"),
+                ));
+            }
+            (gate_type, &tokens[2..])
+        }
+        _ => (0, &tokens[1..]),
+    };
+    if tokens.len() < INPUT_NUM + 3 || tokens[INPUT_NUM] != "->" {
+        return Err(invalid());
+    }
+    let mut i_ids = [0; INPUT_NUM];
+    for (id, token) in i_ids.iter_mut().zip(tokens) {
+        *id = parse(line_num, token)?;
+    }
+    let o_id = parse(line_num, tokens[INPUT_NUM + 1])?;
+
+    let (coef_type, coef) = match &tokens[INPUT_NUM + 2..] {
+        ["const", value] => (CoefType::Constant, parse_field(line_num, value)?),
+        ["random"] => (CoefType::Random, C::CircuitField::ZERO),
+        ["public", idx] if INPUT_NUM == 0 => (
+            CoefType::PublicInput(parse(line_num, idx)?),
+            C::CircuitField::ZERO,
+        ),
+        _ => return Err(invalid()),
+    };
+
+    Ok(Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef,
+        gate_type,
+    })
+}
+
+/// The non-empty lines of a text, split into tokens, with their line numbers.
+struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    line_num: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines().enumerate(),
+            line_num: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<(usize, Vec<&'a str>)> {
+        for (i, line) in self.lines.by_ref() {
+            self.line_num = i + 1;
+            let line = line.split('#').next().unwrap();
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            if !tokens.is_empty() {
+                return Some((self.line_num, tokens));
+            }
+        }
+        None
+    }
+
+    fn expect_line(&mut self) -> Result<(usize, Vec<&'a str>), CircuitTextError> {
+        self.next().ok_or(CircuitTextError::UnexpectedEof)
+    }
+
+    fn expect_key(&mut self, key: &str) -> Result<(usize, Vec<&'a str>), CircuitTextError> {
+        let (line_num, tokens) = self.expect_line()?;
+        if tokens[0] != key {
+            return Err(syntax_error(
+                line_num,
+                format!("expected {key}, got {}", tokens[0]),
+            ));
+        }
+        Ok((line_num, tokens))
+    }
+
+    fn expect_header(&mut self, header: &str) -> Result<(), CircuitTextError> {
+        let (line_num, tokens) = self.expect_line()?;
+        check_line(line_num, &tokens, header, 1)
+    }
+
+    /// A `key value` line.
+    fn expect_value<T: FromStr>(&mut self, key: &str) -> Result<T, CircuitTextError> {
+        let (line_num, tokens) = self.expect_line()?;
+        check_line(line_num, &tokens, key, 2)?;
+        parse(line_num, tokens[1])
+    }
+
+    fn expect_modulus<C: FieldEngine>(&mut self) -> Result<(), CircuitTextError> {
+        let (line_num, tokens) = self.expect_line()?;
+        check_line(line_num, &tokens, "modulus", 2)?;
+        let modulus = parse_modulus(line_num, tokens[1])?;
+        if modulus != C::CircuitField::MODULUS {
+            return Err(CircuitTextError::ModulusMismatch(
+                modulus,
+                C::CircuitField::MODULUS,
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn parse_modulus(line_num: usize, token: &str) -> Result<U256, CircuitTextError> {
+    token
+        .strip_prefix("0x")
+        .and_then(|hex| U256::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| syntax_error(line_num, format!("invalid modulus {token}")))
+}
+
+/// The modulus of the field a circuit in the text format is over, i.e., the `SENTINEL` of its
+/// `FieldEngine` in little endian bytes, to pick the config to import it with.
+pub fn circuit_text_modulus(text: &str) -> Result<[u8; 32], CircuitTextError> {
+    let mut lines = Lines::new(text);
+    lines.expect_line()?;
+    let (line_num, tokens) = lines.expect_line()?;
+    check_line(line_num, &tokens, "modulus", 2)?;
+    Ok(parse_modulus(line_num, tokens[1])?.to_le_bytes())
+}
@@ -1,4 +1,9 @@
-use gkr_engine::FieldEngine;
+use arith::{Field, SimdField};
+use gkr_engine::{FieldEngine, GKREngine};
+use serdes::SerdeError;
+use thiserror::Error;
+
+use crate::{Circuit, CoefType, RecursiveCircuit};
 
 // A direct copy of the witness struct from ecc
 #[derive(Debug, Clone)]
@@ -8,3 +13,211 @@ pub struct Witness<C: FieldEngine> {
     pub num_public_inputs_per_witness: usize,
     pub values: Vec<C::CircuitField>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WitnessSolverError {
+    #[error("no inputs to solve for")]
+    NoInputs,
+
+    #[error("got {0} sets of private inputs and {1} sets of public inputs")]
+    InputCountMismatch(usize, usize),
+
+    #[error("input {index} has {got} private inputs, the circuit takes at most {expected}")]
+    TooManyPrivateInputs {
+        index: usize,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("input {index} has {got} public inputs, expected {expected}")]
+    PublicInputSizeMismatch {
+        index: usize,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("input {index} does not satisfy the circuit: output {output} is not zero")]
+    Unsatisfied { index: usize, output: usize },
+}
+
+/// A witness that does not fit the circuit, see `Circuit::try_load_witness_bytes`.
+#[derive(Debug, Error)]
+pub enum WitnessLoadError {
+    #[error("malformed witness: {0}")]
+    Malformed(#[from] SerdeError),
+
+    #[error("{got} private inputs per witness, the circuit takes {expected}")]
+    PrivateInputSizeMismatch { expected: usize, got: usize },
+
+    #[error("{got} public inputs per witness, the circuit reads {expected}")]
+    NotEnoughPublicInputs { expected: usize, got: usize },
+
+    #[error("not enough witnesses, expected {expected}, got {got}")]
+    NotEnoughWitnesses { expected: usize, got: usize },
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Number of public inputs the circuit reads, i.e., one more than the largest public input
+    /// index of its constant gates.
+    pub fn num_public_inputs(&self) -> usize {
+        self.layers
+            .iter()
+            .flat_map(|layer| &layer.const_)
+            .filter_map(|gate| match gate.coef_type {
+                CoefType::PublicInput(idx) => Some(idx + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Compute the witness of the circuit on the given inputs, one set of private and public
+    /// inputs per witness.
+    ///
+    /// Private inputs shorter than the input layer are padded with zeros. Every layer is evaluated
+    /// with `CircuitLayer::evaluate`, `C::get_field_pack_size()` witnesses at a time, and the first
+    /// `expected_num_output_zeros` outputs are checked to be zero, with random coefficients
+    /// sampled afresh.
+    ///
+    /// The result is in the format of `witness.txt`, and can be loaded with
+    /// `Circuit::load_witness_bytes` once serialized.
+    pub fn solve_witness(
+        &self,
+        private_inputs: &[Vec<C::CircuitField>],
+        public_inputs: &[Vec<C::CircuitField>],
+    ) -> Result<Witness<C>, WitnessSolverError> {
+        if private_inputs.is_empty() {
+            return Err(WitnessSolverError::NoInputs);
+        }
+        if private_inputs.len() != public_inputs.len() {
+            return Err(WitnessSolverError::InputCountMismatch(
+                private_inputs.len(),
+                public_inputs.len(),
+            ));
+        }
+
+        let private_input_size = 1 << self.log_input_size();
+        let public_input_size = public_inputs[0].len().max(self.num_public_inputs());
+        for (index, (private_input, public_input)) in
+            private_inputs.iter().zip(public_inputs).enumerate()
+        {
+            if private_input.len() > private_input_size {
+                return Err(WitnessSolverError::TooManyPrivateInputs {
+                    index,
+                    expected: private_input_size,
+                    got: private_input.len(),
+                });
+            }
+            if public_input.len() != public_input_size {
+                return Err(WitnessSolverError::PublicInputSizeMismatch {
+                    index,
+                    expected: public_input_size,
+                    got: public_input.len(),
+                });
+            }
+        }
+
+        let mut values =
+            Vec::with_capacity(private_inputs.len() * (private_input_size + public_input_size));
+        for (private_input, public_input) in private_inputs.iter().zip(public_inputs) {
+            values.extend_from_slice(private_input);
+            values.resize(
+                values.len() + private_input_size - private_input.len(),
+                C::CircuitField::ZERO,
+            );
+            values.extend_from_slice(public_input);
+        }
+        let witness = Witness {
+            num_witnesses: private_inputs.len(),
+            num_private_inputs_per_witness: private_input_size,
+            num_public_inputs_per_witness: public_input_size,
+            values,
+        };
+
+        self.check_witness(&witness)?;
+        Ok(witness)
+    }
+
+    /// A copy of the circuit with random coefficients sampled afresh, to check constraints that
+    /// are combined with random coefficients without a transcript.
+    pub fn with_fresh_rnd_coefs(&self) -> Self {
+        let mut circuit = self.clone();
+        circuit.identify_rnd_coefs();
+        let mut rng = rand::thread_rng();
+        circuit
+            .rnd_coefs
+            .iter()
+            .for_each(|&r| unsafe { *r = C::CircuitField::random_unsafe(&mut rng) });
+        circuit
+    }
+
+    /// Check that the first `expected_num_output_zeros` outputs of the circuit are zero on every
+    /// piece of the witness, with random coefficients sampled afresh.
+    pub fn check_witness(&self, witness: &Witness<C>) -> Result<(), WitnessSolverError> {
+        let circuit = self.with_fresh_rnd_coefs();
+
+        let pack_size = C::get_field_pack_size();
+        let private_input_size = witness.num_private_inputs_per_witness;
+        let total_size = private_input_size + witness.num_public_inputs_per_witness;
+        for chunk_start in (0..witness.num_witnesses).step_by(pack_size) {
+            // the last chunk is padded with its last piece of witness
+            let lanes = (0..pack_size)
+                .map(|j| (chunk_start + j).min(witness.num_witnesses - 1) * total_size)
+                .collect::<Vec<_>>();
+            let pack = |offset: usize| {
+                C::SimdCircuitField::pack(
+                    &lanes
+                        .iter()
+                        .map(|lane| witness.values[lane + offset])
+                        .collect::<Vec<_>>(),
+                )
+            };
+            let mut input_vals = (0..private_input_size).map(pack).collect::<Vec<_>>();
+            let public_input = (private_input_size..total_size)
+                .map(pack)
+                .collect::<Vec<_>>();
+
+            let mut output_vals = vec![];
+            for layer in &circuit.layers {
+                layer.evaluate_with_input(&input_vals, &mut output_vals, &public_input);
+                std::mem::swap(&mut input_vals, &mut output_vals);
+            }
+
+            for (output, v) in input_vals[..circuit.expected_num_output_zeros]
+                .iter()
+                .enumerate()
+            {
+                // padded lanes repeat the last piece of witness, hence fail after it
+                if let Some(j) = v.unpack().iter().position(|x| !x.is_zero()) {
+                    return Err(WitnessSolverError::Unsatisfied {
+                        index: chunk_start + j,
+                        output,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C: FieldEngine> RecursiveCircuit<C> {
+    /// Same as [`Circuit::solve_witness`], on the flattened circuit.
+    pub fn solve_witness<Cfg: GKREngine<FieldConfig = C>>(
+        &self,
+        private_inputs: &[Vec<C::CircuitField>],
+        public_inputs: &[Vec<C::CircuitField>],
+    ) -> Result<Witness<C>, WitnessSolverError> {
+        let mismatch = public_inputs
+            .iter()
+            .position(|public_input| public_input.len() != self.num_public_inputs);
+        if let Some(index) = mismatch {
+            return Err(WitnessSolverError::PublicInputSizeMismatch {
+                index,
+                expected: self.num_public_inputs,
+                got: public_inputs[index].len(),
+            });
+        }
+        self.flatten::<Cfg>()
+            .solve_witness(private_inputs, public_inputs)
+    }
+}
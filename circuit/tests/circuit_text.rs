@@ -0,0 +1,287 @@
+use std::fs;
+
+use circuit::{
+    Allocation, Circuit, CircuitTextError, FlattenError, RecursiveCircuit, Segment, UniGateType,
+    GATE_TYPE_IDENTITY, GATE_TYPE_POW5, GATE_TYPE_POW_BASE,
+};
+use config_macros::declare_gkr_config;
+use gkr_engine::{
+    BN254Config, FieldEngine, FieldType, GKREngine, GKRScheme, M31x16Config, MPIConfig,
+};
+use gkr_hashers::SHA256hasher;
+use poly_commit::RawExpanderGKR;
+use serdes::ExpSerde;
+use transcript::BytesHashTranscript;
+
+const SMALL_M31_CIRCUIT: &str = "../scripts/small_circuit_m31.circ";
+/// Compiled by ExpanderCompilerCollection, downloaded by `dev-setup`
+const POSEIDON_M31_CIRCUIT: &str = "../data/poseidon_120_circuit_m31.txt";
+
+declare_gkr_config!(
+    pub M31x16ConfigSha2Raw,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+
+#[test]
+fn test_recursive_circuit_text_round_trip() {
+    let bytes = fs::read(SMALL_M31_CIRCUIT).unwrap();
+    let rc = RecursiveCircuit::<M31x16Config>::deserialize_from(&bytes[..]).unwrap();
+
+    let text = rc.to_text();
+    assert!(text.starts_with("recursive_circuit\nmodulus 0x7fffffff\n"));
+    assert!(text.contains("\nsegment 1\ni_var_num 0\no_var_num 0\nadd 0 -> 0 random\nend\n"));
+
+    let imported = RecursiveCircuit::<M31x16Config>::from_text(&text).unwrap();
+    assert_eq!(imported.to_text(), text);
+
+    let mut imported_bytes = vec![];
+    imported.serialize_into(&mut imported_bytes).unwrap();
+    assert_eq!(imported_bytes, bytes);
+}
+
+#[test]
+fn test_circuit_text_round_trip() {
+    let circuit =
+        Circuit::<M31x16Config>::verifier_load_circuit::<M31x16ConfigSha2Raw>(SMALL_M31_CIRCUIT);
+    let text = circuit.to_text();
+    let imported = Circuit::<M31x16Config>::from_text(&text).unwrap();
+    assert_eq!(imported.to_text(), text);
+    assert_eq!(
+        imported.expected_num_output_zeros,
+        circuit.expected_num_output_zeros
+    );
+}
+
+#[test]
+fn test_circuit_text_errors() {
+    let text = "\
+        recursive_circuit
+        modulus 0x7fffffff
+        num_public_inputs 1
+        num_outputs 2 # comments are ignored
+        expected_num_output_zeros 1
+
+        layers 0
+        segment 0
+        i_var_num 2
+        o_var_num 1
+        uni 12345 3 -> 0 const 01000000
+        const -> 1 public 0
+        end
+    ";
+    let rc = RecursiveCircuit::<M31x16Config>::from_text(text).unwrap();
+    assert_eq!(rc.segments[0].gate_uni[0].gate_type, 12345);
+
+    assert!(matches!(
+        RecursiveCircuit::<BN254Config>::from_text(text),
+        Err(CircuitTextError::ModulusMismatch(_, _))
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("public 0", "public")),
+        Err(CircuitTextError::Syntax(12, _))
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("-> 1 public", "public")),
+        Err(CircuitTextError::Syntax(12, _))
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("01000000", "010000")),
+        Err(CircuitTextError::Syntax(11, _))
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("end", "")),
+        Err(CircuitTextError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("layers 0", "layers 1")),
+        Err(CircuitTextError::Syntax(7, _))
+    ));
+    assert!(matches!(
+        RecursiveCircuit::<M31x16Config>::from_text(&text.replace("uni 12345", "uni 54321")),
+        Err(CircuitTextError::Syntax(11, _))
+    ));
+}
+
+#[test]
+fn test_uni_gate_types() {
+    assert_eq!(
+        UniGateType::from_gate_type(GATE_TYPE_POW5),
+        Some(UniGateType::Pow(5))
+    );
+    assert_eq!(UniGateType::Pow(5).gate_type(), GATE_TYPE_POW5);
+    assert_eq!(
+        UniGateType::from_gate_type(GATE_TYPE_POW_BASE + 4),
+        Some(UniGateType::Pow(4))
+    );
+    assert_eq!(UniGateType::Pow(4).gate_type(), GATE_TYPE_POW_BASE + 4);
+    assert_eq!(UniGateType::from_gate_type(GATE_TYPE_POW_BASE), None);
+    assert_eq!(UniGateType::from_gate_type(GATE_TYPE_POW_BASE + 8), None);
+
+    let text = format!(
+        "\
+        recursive_circuit
+        modulus 0x7fffffff
+        num_public_inputs 0
+        num_outputs 2
+        expected_num_output_zeros 0
+
+        layers 0
+        segment 0
+        i_var_num 1
+        o_var_num 1
+        uni {} 0 -> 0 const 01000000
+        uni {} 1 -> 1 const 01000000
+        end
+    ",
+        GATE_TYPE_POW5,
+        GATE_TYPE_POW_BASE + 4
+    );
+    let mut rc = RecursiveCircuit::<M31x16Config>::from_text(&text).unwrap();
+    let uni_types = rc.segments[0]
+        .gate_uni
+        .iter()
+        .map(|gate| gate.uni_type())
+        .collect::<Vec<_>>();
+    assert_eq!(uni_types, [UniGateType::Pow(5), UniGateType::Pow(4)]);
+
+    // the gate types survive both the recursive and the layered serialization
+    let mut bytes = vec![];
+    rc.serialize_into(&mut bytes).unwrap();
+    let deserialized = RecursiveCircuit::<M31x16Config>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(deserialized.to_text(), rc.to_text());
+
+    let circuit = rc.flatten::<M31x16ConfigSha2Raw>();
+    let mut layered_bytes = vec![];
+    circuit.serialize_into(&mut layered_bytes).unwrap();
+    let layered = Circuit::<M31x16Config>::deserialize_from(&layered_bytes[..]).unwrap();
+    let layered_types = layered.layers[0]
+        .uni
+        .iter()
+        .map(|gate| gate.uni_type())
+        .collect::<Vec<_>>();
+    assert_eq!(layered_types.len(), 2);
+    assert!(layered_types.contains(&UniGateType::Pow(5)));
+    assert!(layered_types.contains(&UniGateType::Pow(4)));
+
+    // unknown gate types are rejected at deserialization
+    rc.segments[0].gate_uni[1].gate_type = GATE_TYPE_POW_BASE + 8;
+    let mut bytes = vec![];
+    rc.serialize_into(&mut bytes).unwrap();
+    assert!(RecursiveCircuit::<M31x16Config>::deserialize_from(&bytes[..]).is_err());
+
+    let mut circuit = circuit;
+    circuit.layers[0].uni[0].gate_type = 54321;
+    let mut layered_bytes = vec![];
+    circuit.serialize_into(&mut layered_bytes).unwrap();
+    assert!(Circuit::<M31x16Config>::deserialize_from(&layered_bytes[..]).is_err());
+}
+
+#[test]
+fn test_compiler_uni_gate_types() {
+    let rc = RecursiveCircuit::<M31x16Config>::load(POSEIDON_M31_CIRCUIT).unwrap();
+    let gate_types = rc
+        .segments
+        .iter()
+        .flat_map(|segment| segment.gate_uni.iter().map(|gate| gate.gate_type))
+        .collect::<Vec<_>>();
+
+    // the S-box x^5 of Poseidon is compiled to uni gates of the compiler's gate types
+    assert!(gate_types.contains(&GATE_TYPE_POW5));
+    for gate_type in gate_types {
+        assert!([GATE_TYPE_POW5, GATE_TYPE_IDENTITY].contains(&gate_type));
+        let uni_type = UniGateType::from_gate_type(gate_type).unwrap();
+        assert_eq!(uni_type.gate_type(), gate_type);
+    }
+
+    // and survive the serialization
+    let mut bytes = vec![];
+    rc.serialize_into(&mut bytes).unwrap();
+    let deserialized = RecursiveCircuit::<M31x16Config>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(deserialized.to_text(), rc.to_text());
+}
+
+#[test]
+fn test_flatten_errors() {
+    let text = "\
+        recursive_circuit
+        modulus 0x7fffffff
+        num_public_inputs 0
+        num_outputs 2
+        expected_num_output_zeros 1
+
+        layers 0
+        segment 0
+        i_var_num 2
+        o_var_num 1
+        mul 0 1 -> 0 const 01000000
+        add 2 -> 1 const 01000000
+        end
+    ";
+    let rc = || RecursiveCircuit::<M31x16Config>::from_text(text).unwrap();
+    assert!(rc().try_flatten::<M31x16ConfigSha2Raw>().is_ok());
+
+    let mut circuit = rc();
+    circuit.segments[0].gate_muls[0].i_ids[1] = 4;
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::InvalidSegment { segment: 0, .. })
+    ));
+
+    let mut circuit = rc();
+    circuit.layers.clear();
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::NoLayers)
+    ));
+
+    let mut circuit = rc();
+    circuit.layers.push(1);
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::UnknownSegment(1))
+    ));
+
+    let mut circuit = rc();
+    circuit.layers.push(0);
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::LayerSizeMismatch {
+            layer: 1,
+            input_var_num: 2,
+            output_var_num: 1,
+        })
+    ));
+
+    // a segment containing itself would make flattening recurse forever
+    let mut circuit = rc();
+    circuit.segments.push(Segment {
+        i_var_num: 2,
+        o_var_num: 1,
+        child_segs: vec![(
+            1,
+            vec![Allocation {
+                i_offset: 0,
+                o_offset: 0,
+            }],
+        )],
+        ..Default::default()
+    });
+    circuit.layers = vec![1];
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::RecursiveSegment(1))
+    ));
+
+    let mut circuit = rc();
+    circuit.expected_num_output_zeros = 3;
+    assert!(matches!(
+        circuit.try_flatten::<M31x16ConfigSha2Raw>(),
+        Err(FlattenError::TooManyOutputZeros {
+            expected: 3,
+            num_outputs: 2,
+        })
+    ));
+}
@@ -0,0 +1,18 @@
+use circuit::{CoefType, Gate};
+use gkr_engine::{FieldEngine, M31x16Config};
+
+/// Gate of the M31 test circuits, with the default gate type.
+pub fn gate<const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef_type: CoefType,
+    coef: <M31x16Config as FieldEngine>::CircuitField,
+) -> Gate<M31x16Config, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef,
+        gate_type: 0,
+    }
+}
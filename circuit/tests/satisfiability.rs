@@ -0,0 +1,98 @@
+mod common;
+
+use arith::{Field, SimdField};
+use circuit::{Circuit, CircuitLayer, CoefType, GateKind};
+use gkr_engine::{FieldEngine, M31x16Config, MPIConfig};
+
+use common::gate;
+
+type F = <M31x16Config as FieldEngine>::CircuitField;
+type SimdF = <M31x16Config as FieldEngine>::SimdCircuitField;
+
+/// Checks x0 * x1 + p0 == x2 over two layers, with x0 * x1 as an unchecked output.
+fn test_circuit() -> Circuit<M31x16Config> {
+    let layer_0 = CircuitLayer::<M31x16Config> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![gate([0, 1], 0, CoefType::Constant, F::ONE)],
+        add: vec![gate([2], 1, CoefType::Constant, F::ONE)],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<M31x16Config> {
+        input_var_num: 1,
+        output_var_num: 1,
+        add: vec![
+            gate([0], 0, CoefType::Constant, F::ONE),
+            gate([1], 0, CoefType::Constant, -F::ONE),
+            gate([0], 1, CoefType::Constant, F::ONE),
+        ],
+        const_: vec![gate([], 0, CoefType::PublicInput(0), F::ZERO)],
+        ..Default::default()
+    };
+
+    Circuit {
+        layers: vec![layer_0, layer_1],
+        expected_num_output_zeros: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_check_satisfiability() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let pack_size = M31x16Config::get_field_pack_size();
+    let mut circuit = test_circuit();
+
+    let lanes = |f: &dyn Fn(u32) -> u32| {
+        SimdF::pack(
+            &(0..pack_size as u32)
+                .map(|j| F::from(f(j)))
+                .collect::<Vec<_>>(),
+        )
+    };
+    circuit.layers[0].input_vals = vec![
+        lanes(&|j| j),
+        lanes(&|j| j + 1),
+        lanes(&|j| j * (j + 1) + 2),
+        SimdF::ZERO,
+    ];
+    circuit.public_input = vec![lanes(&|_| 2)];
+    assert!(circuit.check_satisfiability(&mpi_config, 16).is_empty());
+
+    // break lane 3
+    circuit.layers[0].input_vals[2] = lanes(&|j| {
+        if j == 3 {
+            j * (j + 1) + 3
+        } else {
+            j * (j + 1) + 2
+        }
+    });
+    let failures = circuit.check_satisfiability(&mpi_config, 16);
+    assert_eq!(failures.len(), 1);
+    let failure = &failures[0];
+    assert_eq!((failure.shard, failure.lane, failure.output), (0, 3, 0));
+    assert_eq!(failure.value, -F::ONE);
+    assert!(!failure.trace_truncated);
+
+    // the output gates, then the gates of the first layer writing their inputs
+    let traced = failure
+        .trace
+        .iter()
+        .map(|gate| (gate.layer, gate.kind, gate.o_id, gate.value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        traced,
+        vec![
+            (1, GateKind::Add, 0, F::from(12u32)),
+            (1, GateKind::Add, 0, -F::from(15u32)),
+            (1, GateKind::Const, 0, F::from(2u32)),
+            (0, GateKind::Mul, 0, F::from(12u32)),
+            (0, GateKind::Add, 1, F::from(15u32)),
+        ]
+    );
+    assert_eq!(failure.trace[3].i_vals, vec![F::from(3u32), F::from(4u32)]);
+
+    let failures = circuit.check_satisfiability(&mpi_config, 2);
+    assert_eq!(failures[0].trace.len(), 2);
+    assert!(failures[0].trace_truncated);
+}
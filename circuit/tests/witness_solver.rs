@@ -0,0 +1,92 @@
+mod common;
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, Witness, WitnessSolverError};
+use gkr_engine::{FieldEngine, M31x16Config};
+use serdes::ExpSerde;
+
+use common::gate;
+
+type F = <M31x16Config as FieldEngine>::CircuitField;
+
+/// Checks x0 * x1 + p0 == x2, with x3 + r * x2 as an unchecked output.
+fn test_circuit() -> Circuit<M31x16Config> {
+    let minus_one = F::ZERO - F::ONE;
+    let layer = CircuitLayer::<M31x16Config> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![gate([0, 1], 0, CoefType::Constant, F::ONE)],
+        add: vec![
+            gate([2], 0, CoefType::Constant, minus_one),
+            gate([3], 1, CoefType::Constant, F::ONE),
+            gate([2], 1, CoefType::Random, F::ZERO),
+        ],
+        const_: vec![gate([], 0, CoefType::PublicInput(0), F::ZERO)],
+        ..Default::default()
+    };
+
+    Circuit {
+        layers: vec![layer],
+        expected_num_output_zeros: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_witness_solver() {
+    let circuit = test_circuit();
+    assert_eq!(circuit.num_public_inputs(), 1);
+
+    // more witnesses than a single simd pack, with shorter private inputs padded with zeros
+    let private_inputs = (0..20u32)
+        .map(|i| vec![F::from(i), F::from(i + 1), F::from(i * (i + 1) + 1)])
+        .collect::<Vec<_>>();
+    let public_inputs = vec![vec![F::ONE]; 20];
+    let witness = circuit
+        .solve_witness(&private_inputs, &public_inputs)
+        .unwrap();
+    assert_eq!(witness.num_witnesses, 20);
+    assert_eq!(witness.num_private_inputs_per_witness, 4);
+    assert_eq!(witness.num_public_inputs_per_witness, 1);
+    assert_eq!(witness.values.len(), 20 * 5);
+    assert_eq!(
+        witness.values[5..10],
+        [F::from(1u32), F::from(2u32), F::from(3u32), F::ZERO, F::ONE]
+    );
+
+    let mut bytes = vec![];
+    witness.serialize_into(&mut bytes).unwrap();
+    let deserialized = Witness::<M31x16Config>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(deserialized.values, witness.values);
+
+    // the 18th witness is wrong
+    let mut wrong_private_inputs = private_inputs.clone();
+    wrong_private_inputs[17][2] += F::ONE;
+    assert_eq!(
+        circuit
+            .solve_witness(&wrong_private_inputs, &public_inputs)
+            .unwrap_err(),
+        WitnessSolverError::Unsatisfied {
+            index: 17,
+            output: 0
+        }
+    );
+
+    let mut wrong_public_inputs = public_inputs.clone();
+    wrong_public_inputs[3] = vec![];
+    assert_eq!(
+        circuit
+            .solve_witness(&private_inputs, &wrong_public_inputs)
+            .unwrap_err(),
+        WitnessSolverError::PublicInputSizeMismatch {
+            index: 3,
+            expected: 1,
+            got: 0
+        }
+    );
+
+    assert_eq!(
+        circuit.solve_witness(&[], &[]).unwrap_err(),
+        WitnessSolverError::NoInputs
+    );
+}
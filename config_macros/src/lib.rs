@@ -85,6 +85,14 @@ fn parse_fiat_shamir_hash_type(
             "Poseidon".to_owned(),
             "BytesHashTranscript::<PoseidonFiatShamirHasher<M31x16>>".to_owned(),
         ),
+        ("Poseidon", "BabyBearx16") => (
+            "Poseidon".to_owned(),
+            "BytesHashTranscript::<Poseidon2FiatShamirHasher<Poseidon2BabyBear16>>".to_owned(),
+        ),
+        ("Poseidon", "Goldilocksx1" | "Goldilocksx8") => (
+            "Poseidon".to_owned(),
+            "BytesHashTranscript::<Poseidon2FiatShamirHasher<Poseidon2Goldilocks12>>".to_owned(),
+        ),
         ("MIMC5", "BN254") => (
             "MIMC5".to_owned(),
             format!("BytesHashTranscript::<MiMC5FiatShamirHasher<{challenge_f}>>").to_owned(),
@@ -111,6 +119,10 @@ fn parse_polynomial_commitment_type(
             format!("RawExpanderGKR::<{field_config}>").to_owned(),
         ),
         ("Hyrax", "BN254") => ("Hyrax".to_string(), "HyraxPCS::<G1Affine>".to_string()),
+        ("HidingHyrax", "BN254") => (
+            "HidingHyrax".to_owned(),
+            "HidingHyraxPCS::<G1Affine>".to_string(),
+        ),
         ("KZG", "BN254") => ("KZG".to_owned(), "HyperKZGPCS::<Bn256>".to_string()),
         ("Orion", "GF2Ext128") => (
             "Orion".to_owned(),
@@ -124,6 +136,10 @@ fn parse_polynomial_commitment_type(
             "Orion".to_owned(),
             format!("OrionPCSForGKR::<{field_config}, Goldilocksx8>").to_owned(),
         ),
+        ("FRI", "Goldilocksx8" | "BabyBearx16") => (
+            "FRI".to_owned(),
+            format!("BasefoldPCSForGKR::<{field_config}>").to_owned(),
+        ),
         _ => panic!(
             "Unknown polynomial commitment type in config macro expansion. PCS: '{pcs_type_str}', Field: '{field_type}'"
         ),
@@ -164,12 +180,13 @@ fn declare_gkr_config_impl(input: proc_macro::TokenStream) -> proc_macro::TokenS
     } = parse_macro_input!(input as ConfigLit);
 
     let (field_type, field_config) = parse_field_type(field_expr);
-    let (_fiat_shamir_hash_type, transcript_type) =
+    let (fiat_shamir_hash_type, transcript_type) =
         parse_fiat_shamir_hash_type(&field_type, &field_config, fiat_shamir_hash_type_expr);
     let (_polynomial_commitment_enum, polynomial_commitment_type) =
         parse_polynomial_commitment_type(&field_type, &field_config, polynomial_commitment_type);
 
     let field_config = format_ident!("{field_config}");
+    let fiat_shamir_hash_type = format_ident!("{fiat_shamir_hash_type}");
     let transcript_type_expr = syn::parse_str::<syn::Type>(&transcript_type).unwrap();
     let polynomial_commitment_type_expr =
         syn::parse_str::<syn::Type>(&polynomial_commitment_type).unwrap();
@@ -184,6 +201,8 @@ fn declare_gkr_config_impl(input: proc_macro::TokenStream) -> proc_macro::TokenS
             type FieldConfig = #field_config;
             type MPIConfig = MPIConfig<'a>;
             type TranscriptConfig = #transcript_type_expr;
+            const FIAT_SHAMIR_HASH: ::gkr_engine::FiatShamirHashType =
+                ::gkr_engine::FiatShamirHashType::#fiat_shamir_hash_type;
             type PCSConfig = #polynomial_commitment_type_expr;
             const SCHEME: GKRScheme = #scheme_config;
         }
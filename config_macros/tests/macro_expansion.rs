@@ -1,15 +1,20 @@
 use std::any::type_name;
 
+use babybear::Poseidon2BabyBear16;
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
     Goldilocksx8Config, M31x16Config, MPIConfig,
 };
-use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_hashers::{
+    Keccak256hasher, MiMC5FiatShamirHasher, Poseidon2FiatShamirHasher, PoseidonFiatShamirHasher,
+    SHA256hasher,
+};
+use goldilocks::Poseidon2Goldilocks12;
 use halo2curves::bn256::Bn256;
 use mersenne31::M31x16;
-use poly_commit::{HyperKZGPCS, OrionPCSForGKR, RawExpanderGKR};
+use poly_commit::{BasefoldPCSForGKR, HyperKZGPCS, OrionPCSForGKR, RawExpanderGKR};
 use transcript::BytesHashTranscript;
 
 fn print_type_name<Cfg: GKREngine>() {
@@ -81,6 +86,34 @@ fn main() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        GoldilocksExtSHA256FRIConfig,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::FRI,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        BabyBearExtSHA256FRIConfig,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::FRI,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        GoldilocksExtPoseidonConfig,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::Poseidon,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        BabyBearExtPoseidonConfig,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::Poseidon,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
 
     print_type_name::<M31ExtSha256Config>();
     print_type_name::<M31ExtPoseidonRawConfig>();
@@ -91,4 +124,8 @@ fn main() {
     print_type_name::<GF2ExtKeccak256OrionConfig>();
     print_type_name::<GoldilocksExtSHA256Config>();
     print_type_name::<BabyBearExtSHA256Config>();
+    print_type_name::<GoldilocksExtSHA256FRIConfig>();
+    print_type_name::<BabyBearExtSHA256FRIConfig>();
+    print_type_name::<GoldilocksExtPoseidonConfig>();
+    print_type_name::<BabyBearExtPoseidonConfig>();
 }
@@ -1,4 +1,4 @@
-use crate::gates::{SimpleGateAdd, SimpleGateCst, SimpleGateMul};
+use crate::gates::{CoefType, SimpleGateAdd, SimpleGateCst, SimpleGateMul};
 use arith::Field;
 use gkr_engine::FieldEngine;
 use rand::RngCore;
@@ -123,6 +123,8 @@ pub struct CrossLayerCircuitEvals<F: FieldEngine> {
 #[derive(Debug, Clone, Default)]
 pub struct CrossLayerCircuit<F: FieldEngine> {
     pub layers: Vec<GenericLayer<F>>,
+    /// Values of the `CoefType::PublicInput` constant gates.
+    pub public_input: Vec<F::SimdCircuitField>,
 }
 
 impl<F: FieldEngine> CrossLayerCircuit<F> {
@@ -186,7 +188,10 @@ impl<F: FieldEngine> CrossLayerCircuit<F> {
             }
 
             for gate in &layer.const_gates {
-                new_layer_vals[gate.o_id] += F::SimdCircuitField::from(gate.coef);
+                new_layer_vals[gate.o_id] += match gate.coef_type {
+                    CoefType::PublicInput(input_idx) => self.public_input[input_idx],
+                    _ => F::SimdCircuitField::from(gate.coef),
+                };
             }
 
             for gate in &layer.relay_gates {
@@ -199,6 +204,19 @@ impl<F: FieldEngine> CrossLayerCircuit<F> {
         CrossLayerCircuitEvals { vals }
     }
 
+    /// Number of public inputs the constant gates read from.
+    pub fn public_input_size(&self) -> usize {
+        self.layers
+            .iter()
+            .flat_map(|layer| &layer.const_gates)
+            .filter_map(|gate| match gate.coef_type {
+                CoefType::PublicInput(input_idx) => Some(input_idx + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn max_num_input_var(&self) -> usize {
         self.layers
             .iter()
@@ -0,0 +1,27 @@
+use config_macros::declare_gkr_config;
+use gkr_engine::{BN254Config, GF2ExtConfig, GKREngine, GKRScheme, M31x16Config, MPIConfig};
+use gkr_hashers::SHA256hasher;
+use poly_commit::raw::RawExpanderGKR;
+use transcript::BytesHashTranscript;
+
+declare_gkr_config!(
+    pub M31x16ConfigSha2RawCrossLayer,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::CrossLayer,
+);
+declare_gkr_config!(
+    pub BN254ConfigSha2RawCrossLayer,
+    FieldType::BN254,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::CrossLayer,
+);
+declare_gkr_config!(
+    pub GF2ExtConfigSha2RawCrossLayer,
+    FieldType::GF2Ext128,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::CrossLayer,
+);
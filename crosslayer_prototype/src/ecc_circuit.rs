@@ -1,5 +1,6 @@
 use std::{collections::HashMap, fs, io::Cursor};
 
+use arith::Field;
 use gkr_engine::{ExpErrors, FieldEngine};
 use serdes::ExpSerde;
 
@@ -102,7 +103,11 @@ impl<F: FieldEngine> CrossLayerRecursiveCircuit<F> {
     }
 
     pub fn flatten(&self) -> CrossLayerCircuit<F> {
-        let mut ret = CrossLayerCircuit::<F>::default();
+        let mut ret = CrossLayerCircuit::<F> {
+            // set along with the witness
+            public_input: vec![F::SimdCircuitField::zero(); self.num_public_inputs],
+            ..Default::default()
+        };
 
         // denote the input layer as layer 0 here
         assert!(self.segments[self.layers[0]].input_size.len() == 1);
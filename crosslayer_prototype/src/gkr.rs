@@ -1,72 +1,221 @@
-use arith::{Field, SimdField};
-use gkr_engine::{FieldEngine, Transcript};
-use polynomials::MultiLinearPoly;
+use std::{cmp::max, io::Read};
+
+use arith::Field;
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, MPIEngine, Transcript, VerifyError};
+use sumcheck::VerifierScratchPad;
 
 use crate::sumcheck::{sumcheck_prove_gather_layer, sumcheck_prove_scatter_layer};
-use crate::{circuit, scratchpad, CrossLayerCircuit};
+use crate::{
+    circuit, sumcheck_verify_gather_layer, sumcheck_verify_scatter_layer, CrossLayerCircuit,
+    LayerClaim, LayerClaimPoint,
+};
+
+/// Absorb the public inputs, right after the PCS commitment.
+///
+/// They are known to the verifier, hence absorbed with the proof locked.
+#[inline]
+pub(crate) fn absorb_public_input<C: FieldEngine, T: Transcript>(
+    public_input: &[C::SimdCircuitField],
+    transcript: &mut T,
+) {
+    transcript.lock_proof();
+    public_input
+        .iter()
+        .for_each(|x| transcript.append_field_element(x));
+    transcript.unlock_proof();
+}
+
+/// Sample the output challenge of a proof of `world_size` processes.
+#[inline]
+fn sample_output_challenge<C: FieldEngine, T: Transcript>(
+    circuit: &CrossLayerCircuit<C>,
+    transcript: &mut T,
+    world_size: usize,
+) -> ExpanderSingleVarChallenge<C> {
+    ExpanderSingleVarChallenge::sample_from_transcript(
+        transcript,
+        circuit.layers.last().unwrap().layer_size.trailing_zeros() as usize,
+        world_size,
+    )
+}
+
+/// Absorb the claimed output, right after the output challenge.
+#[inline]
+fn absorb_output_claim<C: FieldEngine, T: Transcript>(
+    challenge: ExpanderSingleVarChallenge<C>,
+    claimed_v: &C::ChallengeField,
+    transcript: &mut T,
+) -> LayerClaim<C> {
+    transcript.lock_proof();
+    transcript.append_field_element(claimed_v);
+    transcript.unlock_proof();
 
+    LayerClaim {
+        point: LayerClaimPoint::Direct(challenge.rz),
+        r_simd: challenge.r_simd,
+        r_mpi: challenge.r_mpi,
+        v: *claimed_v,
+    }
+}
+
+#[inline]
+fn input_challenge<C: FieldEngine>(claim: LayerClaim<C>) -> ExpanderSingleVarChallenge<C> {
+    let LayerClaimPoint::Direct(rz) = claim.point else {
+        unreachable!("a gathered claim is direct");
+    };
+    ExpanderSingleVarChallenge::new(rz, claim.r_simd, claim.r_mpi)
+}
+
+/// Prove the evaluation of a cross-layer circuit.
+///
+/// Starting from a claim on the output layer, each layer is scattered to claims on the layers
+/// it reads from, and the claims on each layer are gathered into one before it is scattered in
+/// turn. Returns the claimed output, the challenge on the input layer, and the claimed value of
+/// the inputs at the challenge.
+///
+/// With several MPI processes, each process evaluates the circuit on its own inputs, and the
+/// claimed values are only meaningful on the root, as in the vanilla GKR.
 pub fn prove_gkr<C: FieldEngine, T: Transcript>(
     circuit: &CrossLayerCircuit<C>,
     circuit_vals: &circuit::CrossLayerCircuitEvals<C>,
     connections: &circuit::CrossLayerConnections,
     transcript: &mut T,
-    sp: &mut scratchpad::CrossLayerProverScratchPad<C>,
-) -> (C::ChallengeField, Vec<C::ChallengeField>, C::ChallengeField) {
+    mpi_config: &impl MPIEngine,
+) -> (
+    C::ChallengeField,
+    ExpanderSingleVarChallenge<C>,
+    C::ChallengeField,
+) {
+    let n_layers = circuit.layers.len();
+    assert!(n_layers > 1);
+
     let final_layer_vals = circuit_vals.vals.last().unwrap();
-    let mut rz0 = transcript.generate_field_elements::<C::ChallengeField>(
-        final_layer_vals.len().trailing_zeros() as usize,
-    );
-    let r_simd = transcript.generate_field_elements::<C::ChallengeField>(
-        C::get_field_pack_size().trailing_zeros() as usize,
-    );
-    let output_claim = C::eval_circuit_vals_at_challenge(final_layer_vals, &rz0, &mut sp.v_evals);
-    let output_claim = MultiLinearPoly::<C::ChallengeField>::evaluate_with_buffer(
-        &output_claim.unpack(),
-        &r_simd,
-        &mut sp.eq_evals_at_r_simd,
+    let challenge = sample_output_challenge(circuit, transcript, mpi_config.world_size());
+    let output_claim = C::collectively_eval_circuit_vals_at_expander_challenge(
+        final_layer_vals,
+        &challenge,
+        &mut vec![C::Field::ZERO; final_layer_vals.len()],
+        &mut vec![C::ChallengeField::ZERO; 1 << max(challenge.r_simd.len(), challenge.r_mpi.len())],
+        mpi_config,
     );
 
-    // The second challenge from the previous layer
-    let mut rz1;
-
-    // r_relays is generated by the sumcheck_scatter function
-    // where r_relays[i] = [(input_layer_id, r)] is the relay claims produced by layer i
-    let mut r_relays;
-
-    // r_relays_all keeps track of all the r_relays generated in the previous layers
-    // where r_relays_all[i] = [(output_layer_id, r)] is all the relay claims for layer i
-    let mut r_relays_all = vec![vec![]; circuit.layers.len()];
-    let mut input_claim = C::ChallengeField::zero();
-
-    for i in (1..circuit.layers.len()).rev() {
-        let layer = &circuit.layers[i];
-        (rz0, rz1, r_relays) = sumcheck_prove_scatter_layer(
-            layer,
-            &rz0,
-            &r_simd,
-            connections,
+    // claims[i] keeps track of all the claims on layer i made by the layers above it
+    let mut claims = vec![vec![]; n_layers];
+    let mut claim = absorb_output_claim(challenge, &output_claim, transcript);
+
+    for i in (1..n_layers).rev() {
+        if i < n_layers - 1 {
+            claim = sumcheck_prove_gather_layer(
+                i,
+                &claims[i],
+                connections,
+                circuit_vals,
+                transcript,
+                mpi_config,
+            );
+        }
+        for (input_layer, input_claim) in sumcheck_prove_scatter_layer(
+            &circuit.layers[i],
+            &claim,
             circuit_vals,
             transcript,
-            sp,
-        );
-        for (input_layer, r) in r_relays {
-            r_relays_all[input_layer].push((i, r));
+            mpi_config,
+        ) {
+            claims[input_layer].push(input_claim);
         }
+    }
 
-        let layer_next = &circuit.layers[i - 1];
-        (rz0, input_claim) = sumcheck_prove_gather_layer(
-            layer_next,
-            &rz0,
-            &rz1,
-            &r_relays_all[i - 1],
-            connections,
-            circuit_vals,
+    let input_claim = sumcheck_prove_gather_layer(
+        0,
+        &claims[0],
+        connections,
+        circuit_vals,
+        transcript,
+        mpi_config,
+    );
+    let input_claim_v = input_claim.v;
+
+    (output_claim, input_challenge(input_claim), input_claim_v)
+}
+
+/// Verifier side of `prove_gkr` for a proof of `proving_time_mpi_size` processes, returning the
+/// challenge on the input layer along with the claimed value of the inputs at the challenge,
+/// which is left to the PCS.
+///
+/// Fails if the proof ends early or contains a malformed field element, or with
+/// `VerifyError::ClaimMismatch` at the first layer, from the output layer, whose scatter or
+/// gather sumcheck does not verify.
+#[allow(clippy::type_complexity)]
+pub fn verify_gkr<C: FieldEngine, T: Transcript>(
+    proving_time_mpi_size: usize,
+    circuit: &CrossLayerCircuit<C>,
+    public_input: &[C::SimdCircuitField],
+    claimed_v: &C::ChallengeField,
+    connections: &circuit::CrossLayerConnections,
+    transcript: &mut T,
+    mut proof_reader: impl Read,
+) -> Result<(ExpanderSingleVarChallenge<C>, C::ChallengeField), VerifyError> {
+    let n_layers = circuit.layers.len();
+    assert!(n_layers > 1);
+
+    let sp = VerifierScratchPad::<C>::with_max_num_var(
+        max(circuit.max_num_input_var(), circuit.max_num_output_var()),
+        proving_time_mpi_size,
+    );
+
+    let mut claims = vec![vec![]; n_layers];
+    let challenge = sample_output_challenge(circuit, transcript, proving_time_mpi_size);
+    let mut claim = absorb_output_claim(challenge, claimed_v, transcript);
+
+    for i in (1..n_layers).rev() {
+        if i < n_layers - 1 {
+            let (verified, gathered_claim) = sumcheck_verify_gather_layer(
+                proving_time_mpi_size,
+                i,
+                circuit.layers[i].layer_size,
+                &claims[i],
+                connections,
+                &mut proof_reader,
+                transcript,
+                &sp,
+            )?;
+            if !verified {
+                return Err(VerifyError::ClaimMismatch { layer: i });
+            }
+            claim = gathered_claim;
+        }
+
+        let (verified, input_claims) = sumcheck_verify_scatter_layer(
+            proving_time_mpi_size,
+            &circuit.layers[i],
+            public_input,
+            &claim,
+            &mut proof_reader,
             transcript,
-            sp,
-        );
+            &sp,
+        )?;
+        if !verified {
+            return Err(VerifyError::ClaimMismatch { layer: i });
+        }
+        for (input_layer, input_claim) in input_claims {
+            claims[input_layer].push(input_claim);
+        }
     }
 
-    let input_challenge = rz0;
+    let (verified, input_claim) = sumcheck_verify_gather_layer(
+        proving_time_mpi_size,
+        0,
+        circuit.layers[0].layer_size,
+        &claims[0],
+        connections,
+        &mut proof_reader,
+        transcript,
+        &sp,
+    )?;
+    if !verified {
+        return Err(VerifyError::ClaimMismatch { layer: 0 });
+    }
+    let input_claim_v = input_claim.v;
 
-    (output_claim, input_challenge, input_claim)
+    Ok((input_challenge(input_claim), input_claim_v))
 }
@@ -1,14 +1,31 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, cmp::max, marker::PhantomData};
 
 use crate::{
-    CrossLayerCircuitEvals, CrossLayerConnections, CrossLayerProverScratchPad, GenericLayer,
+    CrossLayerCircuitEvals, CrossLayerConnections, GenericLayer, LayerClaim, LayerClaimPoint,
 };
 
 use arith::{ExtensionField, Field, SimdField};
-use gkr_engine::{FieldEngine, FieldType};
+use gkr_engine::{FieldEngine, FieldType, MPIEngine};
 use polynomials::EqPolynomial;
 use sumcheck::unpack_and_combine;
 
+// Given p0 = f(0)h(0), p1 = f(1)h(1) and p2 = (f(0) + f(1))(h(0) + h(1)) summed over the
+// bookkeeping tables, return the evaluations of the round polynomial f(X)h(X) at the points
+// the verifier interpolates from
+#[inline]
+fn degree_2_evals<F: FieldEngine, E: ExtensionField>(p0: E, p1: E, p2: E) -> [E; 3] {
+    if F::FIELD_TYPE == FieldType::GF2Ext128 {
+        // over GF2_128, the three points are at 0, 1 and X
+        let p2x = p2.mul_by_x();
+        let p2x2 = p2x.mul_by_x();
+        let linear_term = p1 + p0 + p2;
+        [p0, p1, p2x2 + linear_term.mul_by_x() + p0]
+    } else {
+        // when Field size > 2, the three points are 0, 1, 2
+        [p0, p1, p1.mul_by_6() + p0.mul_by_3() - p2.double()]
+    }
+}
+
 pub(crate) struct MultilinearProductHelper<F: FieldEngine> {
     field: PhantomData<F>,
 }
@@ -64,18 +81,7 @@ impl<F: FieldEngine> MultilinearProductHelper<F> {
             }
         }
 
-        if F::FIELD_TYPE == FieldType::GF2Ext128 {
-            // over GF2_128, the three points are at 0, 1 and X
-            let p2x = p2.mul_by_x();
-            let p2x2 = p2x.mul_by_x();
-            let linear_term = p1 + p0 + p2;
-            p2 = p2x2 + linear_term.mul_by_x() + p0;
-        } else {
-            // when Field size > 2, the three points are 0, 1, -2
-            p2 = p1.mul_by_6() + p0.mul_by_3() - p2.double();
-        }
-
-        [p0, p1, p2]
+        degree_2_evals::<F, _>(p0, p1, p2)
     }
 
     // process the challenge and update the bookkeeping tables for f and h_g accordingly
@@ -116,9 +122,9 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
         var_num: usize,
         var_idx: usize,
         degree: usize,
-        bk_eq: &mut [F::ChallengeField],
-        bk_f: &mut [F::ChallengeField],
-        bk_hg: &mut [F::ChallengeField],
+        bk_eq: &[F::ChallengeField],
+        bk_f: &[F::ChallengeField],
+        bk_hg: &[F::ChallengeField],
     ) -> [F::ChallengeField; 4] {
         assert_eq!(degree, 3);
         let mut p0 = F::ChallengeField::zero();
@@ -178,506 +184,737 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
         [p0, p1, p2, p3]
     }
 
+    // Same as poly_eval_at, without the eq polynomial, hence of degree 2
+    #[inline]
+    pub(crate) fn product_poly_eval_at(
+        var_num: usize,
+        var_idx: usize,
+        bk_f: &[F::ChallengeField],
+        bk_hg: &[F::ChallengeField],
+    ) -> [F::ChallengeField; 3] {
+        let mut p0 = F::ChallengeField::zero();
+        let mut p1 = F::ChallengeField::zero();
+        let mut p2 = F::ChallengeField::zero();
+
+        let eval_size = 1 << (var_num - var_idx - 1);
+        for i in 0..eval_size {
+            let f_v_0 = bk_f[i * 2];
+            let f_v_1 = bk_f[i * 2 + 1];
+            let hg_v_0 = bk_hg[i * 2];
+            let hg_v_1 = bk_hg[i * 2 + 1];
+            p0 += f_v_0 * hg_v_0;
+            p1 += f_v_1 * hg_v_1;
+            p2 += (f_v_0 + f_v_1) * (hg_v_0 + hg_v_1);
+        }
+
+        degree_2_evals::<F, _>(p0, p1, p2)
+    }
+
+    // fix the current variable of a bookkeeping table to the challenge
     #[inline]
     pub(crate) fn receive_challenge(
         var_num: usize,
         var_idx: usize,
         r: F::ChallengeField,
-        bk_eq: &mut [F::ChallengeField],
-        bk_f: &mut [F::ChallengeField],
-        bk_hg: &mut [F::ChallengeField],
+        bk: &mut [F::ChallengeField],
     ) {
         assert!(var_idx < var_num);
 
         let eval_size = 1 << (var_num - var_idx - 1);
         for i in 0..eval_size {
-            bk_eq[i] = bk_eq[2 * i] + (bk_eq[2 * i + 1] - bk_eq[2 * i]) * r;
-            bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]) * r;
-            bk_hg[i] = bk_hg[2 * i] + (bk_hg[2 * i + 1] - bk_hg[2 * i]) * r;
+            bk[i] = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * r;
+        }
+    }
+}
+
+// The sum of the local evaluations of the round polynomial over all the processes, on the root;
+// the other processes get zeros, their transcript is not part of the proof
+#[inline]
+fn sum_over_processes<E: Field, const N: usize>(
+    mpi_config: &impl MPIEngine,
+    local_evals: [E; N],
+) -> [E; N] {
+    let evals = mpi_config.sum_vec(&local_evals);
+    if mpi_config.is_root() {
+        evals.try_into().unwrap()
+    } else {
+        [E::ZERO; N]
+    }
+}
+
+/// A term f(x, s) * hg(x, s) of the scatter sumcheck, with f and hg over the first `var_num`
+/// x variables. Over each of the remaining x variables x_t, the term is multiplied by x_t, so
+/// that it sums to the same value over the boolean hypercube, and once its own variables are
+/// fixed, it contributes c * X to the round polynomials.
+struct ScatterTerm<'a, F: FieldEngine> {
+    var_num: usize,
+    init_v: Cow<'a, [F::SimdCircuitField]>,
+    bk_f: Vec<F::Field>,
+    bk_hg: Vec<F::Field>,
+    // product of the challenges of the x variables beyond var_num
+    ext: F::ChallengeField,
+
+    simd_f: Vec<F::ChallengeField>,
+    simd_hg: Vec<F::ChallengeField>,
+
+    // the values of f and hg of each process, only gathered on the root
+    mpi_f: Vec<F::ChallengeField>,
+    mpi_hg: Vec<F::ChallengeField>,
+}
+
+impl<'a, F: FieldEngine> ScatterTerm<'a, F> {
+    fn new(init_v: Cow<'a, [F::SimdCircuitField]>, bk_hg: Vec<F::Field>) -> Self {
+        assert_eq!(init_v.len(), bk_hg.len());
+        assert!(init_v.len().is_power_of_two());
+
+        let var_num = init_v.len().trailing_zeros() as usize;
+        let mut bk_f = vec![F::Field::zero(); max(init_v.len() >> 1, 1)];
+        if var_num == 0 {
+            bk_f[0] = init_v[0].into();
+        }
+
+        ScatterTerm {
+            var_num,
+            init_v,
+            bk_f,
+            bk_hg,
+            ext: F::ChallengeField::ONE,
+            simd_f: vec![],
+            simd_hg: vec![],
+            mpi_f: vec![],
+            mpi_hg: vec![],
+        }
+    }
+
+    #[inline]
+    fn poly_evals_at_rx(
+        &self,
+        var_idx: usize,
+        eq_evals_at_r_simd: &[F::ChallengeField],
+    ) -> [F::ChallengeField; 3] {
+        if var_idx < self.var_num {
+            MultilinearProductHelper::<F>::poly_eval_at(
+                self.var_num,
+                var_idx,
+                2,
+                &self.bk_f,
+                &self.bk_hg,
+                &self.init_v,
+            )
+            .map(|p| unpack_and_combine(&p, eq_evals_at_r_simd))
+        } else {
+            let c =
+                unpack_and_combine(&(self.bk_f[0] * self.bk_hg[0]), eq_evals_at_r_simd) * self.ext;
+            if F::FIELD_TYPE == FieldType::GF2Ext128 {
+                [F::ChallengeField::ZERO, c, c.mul_by_x()]
+            } else {
+                [F::ChallengeField::ZERO, c, c.double()]
+            }
+        }
+    }
+
+    #[inline]
+    fn receive_rx(&mut self, var_idx: usize, r: F::ChallengeField) {
+        if var_idx < self.var_num {
+            MultilinearProductHelper::<F>::receive_challenge(
+                self.var_num,
+                var_idx,
+                r,
+                &mut self.bk_f,
+                &mut self.bk_hg,
+                &self.init_v,
+            );
+        } else {
+            self.ext *= r;
         }
     }
+
+    #[inline]
+    fn prepare_simd_var_vals(&mut self) {
+        self.simd_f = self.bk_f[0].unpack();
+        self.simd_hg = self.bk_hg[0]
+            .unpack()
+            .into_iter()
+            .map(|hg| hg * self.ext)
+            .collect();
+    }
+
+    #[inline]
+    fn prepare_mpi_var_vals(
+        &mut self,
+        eq_r_simd_r_simd_next: F::ChallengeField,
+        mpi_config: &impl MPIEngine,
+    ) {
+        self.mpi_f = vec![F::ChallengeField::ZERO; mpi_config.world_size()];
+        self.mpi_hg = vec![F::ChallengeField::ZERO; mpi_config.world_size()];
+        mpi_config.gather_vec(&[self.simd_f[0]], &mut self.mpi_f);
+        mpi_config.gather_vec(&[self.simd_hg[0] * eq_r_simd_r_simd_next], &mut self.mpi_hg);
+    }
 }
 
+/// Prover of the scatter sumcheck of a layer, reducing a claim on the layer to claims on the
+/// layer below it, and on the relay values of each earlier layer, see
+/// `sumcheck_prove_scatter_layer`.
+///
+/// As in the vanilla GKR, the round polynomials over the x and SIMD variables are combined
+/// across the MPI processes with eq(r_mpi, rank), and the root sums over the MPI variables.
 pub(crate) struct CrossLayerScatterHelper<'a, F: FieldEngine> {
+    pub(crate) var_num: usize,
     pub(crate) input_layer_var_num: usize,
+    pub(crate) mpi_var_num: usize,
     pub(crate) rx: Vec<F::ChallengeField>,
     pub(crate) ry: Vec<F::ChallengeField>,
     pub(crate) r_simd_next: Vec<F::ChallengeField>,
-    pub(crate) r_relays_next: Vec<(usize, Vec<F::ChallengeField>)>,
+    pub(crate) r_mpi_next: Vec<F::ChallengeField>,
 
     layer: &'a GenericLayer<F>,
-    rz0: &'a [F::ChallengeField],
-    r_simd: &'a [F::ChallengeField],
-    connections: &'a CrossLayerConnections,
     circuit_vals: &'a CrossLayerCircuitEvals<F>,
-    sp: &'a mut CrossLayerProverScratchPad<F>,
+    simd_var_num: usize,
+
+    main_term: ScatterTerm<'a, F>,
+    // (input layer, term) for the relays from layers other than the one below
+    relay_terms: Vec<(usize, ScatterTerm<'a, F>)>,
+
+    eq_evals_at_rz: Vec<F::ChallengeField>,
+    eq_evals_at_r_simd: Vec<F::ChallengeField>,
+    eq_evals_at_r_mpi: Vec<F::ChallengeField>,
+    eq_evals_at_r_simd_next: Vec<F::ChallengeField>,
+    eq_evals_at_r_mpi_next: Vec<F::ChallengeField>,
+
+    // phase two, over the second input of the mul gates
+    phase2_coef: F::ChallengeField,
+    bk_f_y: Vec<F::Field>,
+    bk_hg_y: Vec<F::Field>,
 }
 
-/// Helper functions to be called
-#[allow(clippy::too_many_arguments)]
 impl<'a, F: FieldEngine> CrossLayerScatterHelper<'a, F> {
     #[inline]
     pub(crate) fn new(
         layer: &'a GenericLayer<F>,
-        rz0: &'a [F::ChallengeField],
-        r_simd: &'a [F::ChallengeField],
-        connections: &'a CrossLayerConnections,
+        rz: &[F::ChallengeField],
+        r_simd: &[F::ChallengeField],
+        r_mpi: &[F::ChallengeField],
         circuit_vals: &'a CrossLayerCircuitEvals<F>,
-        sp: &'a mut CrossLayerProverScratchPad<F>,
     ) -> Self {
+        let layer_id = layer.layer_id;
+        assert!(layer_id > 0);
+        let input_vals = &circuit_vals.vals[layer_id - 1];
+        assert_eq!(input_vals.len(), layer.input_layer_size);
+        let eq_evals_at_rz = EqPolynomial::build_eq_x_r(rz);
+
+        // the add gates, and the relays from the layer below, which are the same as add gates
+        let mut bk_hg = vec![F::Field::zero(); input_vals.len()];
+        for g in &layer.add_gates {
+            bk_hg[g.i_ids[0]] += F::Field::from(eq_evals_at_rz[g.o_id] * g.coef);
+        }
+        for g in layer
+            .relay_gates
+            .iter()
+            .filter(|g| g.i_layer == layer_id - 1)
+        {
+            bk_hg[g.i_id] += F::Field::from(eq_evals_at_rz[g.o_id] * g.coef);
+        }
+        for g in &layer.mul_gates {
+            bk_hg[g.i_ids[0]] += (eq_evals_at_rz[g.o_id] * g.coef) * input_vals[g.i_ids[1]];
+        }
+        let main_term = ScatterTerm::new(Cow::Borrowed(input_vals.as_slice()), bk_hg);
+
+        // the relays from each earlier layer, as a table of the relayed values indexed by
+        // the relay gate, padded with zeros
+        let mut relay_vals = vec![vec![]; layer_id - 1];
+        let mut relay_hg = vec![vec![]; layer_id - 1];
+        for g in layer
+            .relay_gates
+            .iter()
+            .filter(|g| g.i_layer < layer_id - 1)
+        {
+            relay_vals[g.i_layer].push(circuit_vals.vals[g.i_layer][g.i_id]);
+            relay_hg[g.i_layer].push(F::Field::from(eq_evals_at_rz[g.o_id] * g.coef));
+        }
+        let relay_terms = relay_vals
+            .into_iter()
+            .zip(relay_hg)
+            .enumerate()
+            .filter(|(_, (vals, _))| !vals.is_empty())
+            .map(|(i_layer, (mut vals, mut hg))| {
+                let padded_size = vals.len().next_power_of_two();
+                vals.resize(padded_size, F::SimdCircuitField::zero());
+                hg.resize(padded_size, F::Field::zero());
+                (i_layer, ScatterTerm::new(Cow::Owned(vals), hg))
+            })
+            .collect::<Vec<_>>();
+
+        let var_num = relay_terms
+            .iter()
+            .map(|(_, term)| term.var_num)
+            .fold(main_term.var_num, max);
+
         CrossLayerScatterHelper {
-            input_layer_var_num: layer.input_layer_size.trailing_zeros() as usize,
+            var_num,
+            input_layer_var_num: main_term.var_num,
+            mpi_var_num: r_mpi.len(),
             rx: vec![],
             ry: vec![],
             r_simd_next: vec![],
-            r_relays_next: vec![],
+            r_mpi_next: vec![],
 
             layer,
-            rz0,
-            r_simd,
-            connections,
             circuit_vals,
-            sp,
-        }
-    }
+            simd_var_num: F::get_field_pack_size().trailing_zeros() as usize,
 
-    pub(crate) fn poly_evals_at_rx(
-        &mut self,
-        var_idx: usize,
-        degree: usize,
-    ) -> [F::ChallengeField; 3] {
-        assert!(degree == 2);
-        // layer_id - 1
-        let mut p3 = MultilinearProductHelper::<F>::poly_eval_at(
-            self.input_layer_var_num,
-            var_idx,
-            degree,
-            &self.sp.v_evals,
-            &self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id - 1],
-        );
+            main_term,
+            relay_terms,
 
-        // [0, layer_id - 2]
-        for i_layer in 0..(self.layer.layer_id - 1) {
-            let cross_layer_size = self.sp.cross_layer_sizes[i_layer];
-            if cross_layer_size > 0 {
-                if var_idx < cross_layer_size.trailing_zeros() as usize {
-                    let p3_at_layer_i = MultilinearProductHelper::<F>::poly_eval_at(
-                        cross_layer_size.trailing_zeros() as usize,
-                        var_idx,
-                        degree,
-                        &self.sp.cross_layer_evals[i_layer],
-                        &self.sp.cross_layer_hg_evals[i_layer],
-                        &self.sp.cross_layer_circuit_vals[i_layer],
-                    );
-                    for i in 0..3 {
-                        p3[i] += p3_at_layer_i[i];
-                    }
-                } else {
-                    for p in p3.iter_mut() {
-                        *p += self.sp.cross_layer_completed_values[i_layer];
-                    }
-                }
-            }
+            eq_evals_at_rz,
+            eq_evals_at_r_simd: EqPolynomial::build_eq_x_r(r_simd),
+            eq_evals_at_r_mpi: EqPolynomial::build_eq_x_r(r_mpi),
+            eq_evals_at_r_simd_next: vec![],
+            eq_evals_at_r_mpi_next: vec![],
+
+            phase2_coef: F::ChallengeField::ZERO,
+            bk_f_y: vec![],
+            bk_hg_y: vec![],
         }
+    }
 
-        p3.iter()
-            .map(|p| unpack_and_combine(p, &self.sp.eq_evals_at_r_simd))
-            .collect::<Vec<F::ChallengeField>>()
-            .try_into()
-            .unwrap()
+    #[inline]
+    fn terms(&self) -> impl Iterator<Item = &ScatterTerm<'a, F>> {
+        std::iter::once(&self.main_term).chain(self.relay_terms.iter().map(|(_, t)| t))
     }
 
-    pub(crate) fn poly_evals_at_r_simd_var(
-        &mut self,
-        var_idx: usize,
-        degree: usize,
-    ) -> [F::ChallengeField; 4] {
-        SumcheckSimdProdGateHelper::<F>::poly_eval_at(
-            F::get_field_pack_size().trailing_zeros() as usize,
-            var_idx,
-            degree,
-            &mut self.sp.eq_evals_at_r_simd,
-            &mut self.sp.simd_var_v_evals,
-            &mut self.sp.simd_var_hg_evals,
-        )
+    #[inline]
+    fn terms_mut(&mut self) -> impl Iterator<Item = &mut ScatterTerm<'a, F>> {
+        std::iter::once(&mut self.main_term).chain(self.relay_terms.iter_mut().map(|(_, t)| t))
     }
 
-    #[inline(always)]
-    pub(crate) fn poly_evals_at_ry(
-        &mut self,
+    #[inline]
+    pub(crate) fn poly_evals_at_rx(
+        &self,
         var_idx: usize,
-        degree: usize,
+        mpi_config: &impl MPIEngine,
     ) -> [F::ChallengeField; 3] {
-        assert!(degree == 2);
-        let p3 = MultilinearProductHelper::<F>::poly_eval_at(
-            self.input_layer_var_num,
-            var_idx,
-            degree,
-            &self.sp.v_evals,
-            &self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id - 1],
-        );
-        p3.iter()
-            .map(|p| unpack_and_combine(&(*p * self.sp.phase2_coef), &self.sp.eq_evals_at_r_simd))
-            .collect::<Vec<F::ChallengeField>>()
+        let mut local_evals = [F::ChallengeField::ZERO; 3];
+        for term in self.terms() {
+            let term_evals = term.poly_evals_at_rx(var_idx, &self.eq_evals_at_r_simd);
+            local_evals
+                .iter_mut()
+                .zip(term_evals)
+                .for_each(|(e, t)| *e += t);
+        }
+
+        mpi_config
+            .coef_combine_vec(&local_evals, &self.eq_evals_at_r_mpi)
             .try_into()
             .unwrap()
     }
 
-    // Returns which relay layer has ended, and the final claim, can be empty
     #[inline]
     pub(crate) fn receive_rx(&mut self, var_idx: usize, r: F::ChallengeField) {
-        MultilinearProductHelper::<F>::receive_challenge(
-            self.input_layer_var_num,
-            var_idx,
-            r,
-            &mut self.sp.v_evals,
-            &mut self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id - 1],
-        );
+        for term in self.terms_mut() {
+            term.receive_rx(var_idx, r);
+        }
+        self.rx.push(r);
+    }
 
-        for i_layer in 0..(self.layer.layer_id - 1) {
-            let cross_layer_size = self.sp.cross_layer_sizes[i_layer];
-            if cross_layer_size > 0 {
-                if var_idx < cross_layer_size.trailing_zeros() as usize {
-                    MultilinearProductHelper::<F>::receive_challenge(
-                        cross_layer_size.trailing_zeros() as usize,
-                        var_idx,
-                        r,
-                        &mut self.sp.cross_layer_evals[i_layer],
-                        &mut self.sp.cross_layer_hg_evals[i_layer],
-                        &self.circuit_vals.vals[i_layer],
-                    );
-
-                    if var_idx == cross_layer_size.trailing_zeros() as usize - 1 {
-                        // save the completed value
-                        self.r_relays_next.push((i_layer, self.rx.clone()));
-                        self.sp.cross_layer_completed_values[i_layer] = self.sp.cross_layer_evals
-                            [i_layer][0]
-                            * self.sp.cross_layer_hg_evals[i_layer][0];
-                    }
-                } else {
-                    // for extra bits in sumcheck, we require it to be 1
-                    self.sp.cross_layer_completed_values[i_layer] =
-                        self.sp.cross_layer_completed_values[i_layer] * r;
-                }
-            }
+    #[inline]
+    pub(crate) fn prepare_simd_var_vals(&mut self) {
+        for term in self.terms_mut() {
+            term.prepare_simd_var_vals();
         }
+    }
 
-        self.rx.push(r);
+    #[inline]
+    pub(crate) fn poly_evals_at_r_simd_var(
+        &self,
+        var_idx: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> [F::ChallengeField; 4] {
+        let mut local_evals = [F::ChallengeField::ZERO; 4];
+        for term in self.terms() {
+            let term_evals = SumcheckSimdProdGateHelper::<F>::poly_eval_at(
+                self.simd_var_num,
+                var_idx,
+                3,
+                &self.eq_evals_at_r_simd,
+                &term.simd_f,
+                &term.simd_hg,
+            );
+            local_evals
+                .iter_mut()
+                .zip(term_evals)
+                .for_each(|(e, t)| *e += t);
+        }
+
+        mpi_config
+            .coef_combine_vec(&local_evals, &self.eq_evals_at_r_mpi)
+            .try_into()
+            .unwrap()
     }
 
     #[inline]
     pub(crate) fn receive_r_simd_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        let simd_var_num = self.simd_var_num;
         SumcheckSimdProdGateHelper::<F>::receive_challenge(
-            F::get_field_pack_size().trailing_zeros() as usize,
+            simd_var_num,
             var_idx,
             r,
-            &mut self.sp.eq_evals_at_r_simd,
-            &mut self.sp.simd_var_v_evals,
-            &mut self.sp.simd_var_hg_evals,
+            &mut self.eq_evals_at_r_simd,
         );
+        for term in self.terms_mut() {
+            SumcheckSimdProdGateHelper::<F>::receive_challenge(
+                simd_var_num,
+                var_idx,
+                r,
+                &mut term.simd_f,
+            );
+            SumcheckSimdProdGateHelper::<F>::receive_challenge(
+                simd_var_num,
+                var_idx,
+                r,
+                &mut term.simd_hg,
+            );
+        }
         self.r_simd_next.push(r);
     }
 
     #[inline]
-    pub(crate) fn receive_ry(&mut self, var_idx: usize, r: F::ChallengeField) {
-        MultilinearProductHelper::<F>::receive_challenge(
-            self.input_layer_var_num,
-            var_idx,
-            r,
-            &mut self.sp.v_evals,
-            &mut self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id - 1],
-        );
-        self.ry.push(r);
-    }
-
-    pub(crate) fn vx_claims(&self) -> Vec<(usize, F::ChallengeField)> {
-        // TODO-Optimization: Maybe it's better to reduce simd for each relay layer individually and
-        // return the result
-        let mut claims = vec![(
-            self.layer.layer_id - 1,
-            unpack_and_combine(&self.sp.v_evals[0], &self.r_simd_next),
-        )];
-        for (i_layer, cross_layer_size) in self.sp.cross_layer_sizes.iter().enumerate() {
-            if *cross_layer_size > 0 {
-                claims.push((
-                    i_layer,
-                    unpack_and_combine(&self.sp.cross_layer_evals[i_layer][0], &self.r_simd_next),
-                ));
-            }
+    pub(crate) fn prepare_mpi_var_vals(&mut self, mpi_config: &impl MPIEngine) {
+        let eq_r_simd_r_simd_next = self.eq_evals_at_r_simd[0];
+        for term in self.terms_mut() {
+            term.prepare_mpi_var_vals(eq_r_simd_r_simd_next, mpi_config);
         }
-        claims
     }
 
-    #[inline(always)]
-    pub(crate) fn vy_claim(&self) -> F::ChallengeField {
-        unpack_and_combine(
-            &self.sp.v_evals[0],
-            self.sp.eq_evals_at_r_simd_at_layer[self.layer.layer_id].as_slice(),
-        )
+    /// The round polynomial over an MPI variable, only meaningful on the root.
+    #[inline]
+    pub(crate) fn poly_evals_at_r_mpi_var(&self, var_idx: usize) -> [F::ChallengeField; 4] {
+        let mut evals = [F::ChallengeField::ZERO; 4];
+        for term in self.terms() {
+            let term_evals = SumcheckSimdProdGateHelper::<F>::poly_eval_at(
+                self.mpi_var_num,
+                var_idx,
+                3,
+                &self.eq_evals_at_r_mpi,
+                &term.mpi_f,
+                &term.mpi_hg,
+            );
+            evals.iter_mut().zip(term_evals).for_each(|(e, t)| *e += t);
+        }
+        evals
     }
 
     #[inline]
-    pub(crate) fn prepare_simd(&mut self) {
-        if self.layer.layer_id == self.sp.eq_evals_at_r_simd_at_layer.len() - 1 {
-            EqPolynomial::<F::ChallengeField>::eq_eval_at(
-                self.r_simd,
-                &F::ChallengeField::one(),
-                &mut self.sp.eq_evals_at_r_simd,
-                &mut self.sp.eq_evals_first_half,
-                &mut self.sp.eq_evals_second_half,
+    pub(crate) fn receive_r_mpi_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        let mpi_var_num = self.mpi_var_num;
+        SumcheckSimdProdGateHelper::<F>::receive_challenge(
+            mpi_var_num,
+            var_idx,
+            r,
+            &mut self.eq_evals_at_r_mpi,
+        );
+        for term in self.terms_mut() {
+            SumcheckSimdProdGateHelper::<F>::receive_challenge(
+                mpi_var_num,
+                var_idx,
+                r,
+                &mut term.mpi_f,
+            );
+            SumcheckSimdProdGateHelper::<F>::receive_challenge(
+                mpi_var_num,
+                var_idx,
+                r,
+                &mut term.mpi_hg,
             );
-        } else {
-            // TODO: No need to actually clone
-            self.sp.eq_evals_at_r_simd =
-                self.sp.eq_evals_at_r_simd_at_layer[self.layer.layer_id].clone();
         }
+        self.r_mpi_next.push(r);
     }
 
+    /// The claim on the layer below at (rx, r_simd_next, r_mpi_next), broadcast from the root.
     #[inline]
-    pub(crate) fn prepare_x_vals(&mut self) {
-        let eq_evals_at_rz = &mut self.sp.eq_evals_at_rz0;
-        EqPolynomial::eq_eval_at(
-            self.rz0,
-            &F::ChallengeField::ONE,
-            eq_evals_at_rz,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
-        );
+    pub(crate) fn vx_claim(&self, mpi_config: &impl MPIEngine) -> F::ChallengeField {
+        let mut vx_claim = self.main_term.mpi_f[0];
+        mpi_config.root_broadcast_f(&mut vx_claim);
+        vx_claim
+    }
 
-        // processing the relay layers
-        let layers_connected_to = &self.connections.connections[self.layer.layer_id];
-
-        #[allow(clippy::needless_range_loop)]
-        for i_layer in 0..(self.layer.layer_id - 1) {
-            let connections_at_i_layer = &layers_connected_to[i_layer];
-            let cross_layer_size = &mut self.sp.cross_layer_sizes[i_layer];
-            let cir_vals = &mut self.sp.cross_layer_circuit_vals[i_layer];
-            let vals = &mut self.sp.cross_layer_evals[i_layer];
-            let hg_vals = &mut self.sp.cross_layer_hg_evals[i_layer];
-            *cross_layer_size = 0;
-            cir_vals.clear();
-            vals.clear();
-            hg_vals.clear();
-
-            if !connections_at_i_layer.is_empty() {
-                *cross_layer_size = connections_at_i_layer.len().next_power_of_two();
-                // TODO: Allocate this in scratchpad
-                cir_vals.resize(*cross_layer_size, F::SimdCircuitField::ZERO);
-                vals.resize(*cross_layer_size, F::Field::ZERO);
-                hg_vals.resize(*cross_layer_size, F::Field::ZERO);
-
-                for (idx, (o_id, i_id)) in connections_at_i_layer.iter().enumerate() {
-                    cir_vals[idx] = self.circuit_vals.vals[i_layer][*i_id];
-                    // Do nothing to vals[idx] here, it will be processed later in folding
-                    hg_vals[idx] = F::Field::from(eq_evals_at_rz[*o_id]);
-                }
-            }
-        }
+    /// The claims on the relay values of each earlier layer at (rx, r_simd_next, r_mpi_next),
+    /// restricted to the variables of the relay table. Only meaningful on the root.
+    #[inline]
+    pub(crate) fn relay_claims(&self) -> Vec<(usize, usize, F::ChallengeField)> {
+        self.relay_terms
+            .iter()
+            .map(|(i_layer, term)| (*i_layer, term.var_num, term.mpi_f[0]))
+            .collect()
+    }
 
-        // processing the input layer
-        let mul = &self.layer.mul_gates;
-        let add = &self.layer.add_gates;
-        let vals = &self.circuit_vals.vals[self.layer.layer_id - 1];
-        let hg_vals = &mut self.sp.hg_evals;
-        unsafe {
-            std::ptr::write_bytes(hg_vals.as_mut_ptr(), 0, vals.len());
+    #[inline]
+    pub(crate) fn prepare_y_vals(&mut self, vx_claim: F::ChallengeField) {
+        let input_vals = &self.circuit_vals.vals[self.layer.layer_id - 1];
+        let eq_evals_at_rx = EqPolynomial::build_eq_x_r(&self.rx[..self.input_layer_var_num]);
+
+        self.bk_hg_y = vec![F::Field::zero(); input_vals.len()];
+        for g in &self.layer.mul_gates {
+            self.bk_hg_y[g.i_ids[1]] +=
+                F::Field::from((self.eq_evals_at_rz[g.o_id] * eq_evals_at_rx[g.i_ids[0]]) * g.coef);
         }
-
-        for g in mul.iter() {
-            let r = eq_evals_at_rz[g.o_id] * g.coef;
-            hg_vals[g.i_ids[0]] += r * vals[g.i_ids[1]];
+        self.bk_f_y = vec![F::Field::zero(); max(input_vals.len() >> 1, 1)];
+        if self.input_layer_var_num == 0 {
+            self.bk_f_y[0] = input_vals[0].into();
         }
 
-        for g in add.iter() {
-            hg_vals[g.i_ids[0]] += F::Field::from(eq_evals_at_rz[g.o_id] * g.coef);
-        }
+        // the eq bookkeeping tables are down to eq(r_simd, r_simd_next) and eq(r_mpi, r_mpi_next)
+        self.phase2_coef =
+            self.eq_evals_at_r_mpi[0] * self.eq_evals_at_r_simd[0] * self.main_term.ext * vx_claim;
+        self.eq_evals_at_r_simd_next = EqPolynomial::build_eq_x_r(&self.r_simd_next);
+        self.eq_evals_at_r_mpi_next = EqPolynomial::build_eq_x_r(&self.r_mpi_next);
     }
 
     #[inline]
-    pub(crate) fn prepare_simd_var_vals(&mut self) {
-        self.sp.simd_var_v_evals = self.sp.v_evals[0].unpack();
-        self.sp.simd_var_hg_evals = self.sp.hg_evals[0].unpack();
-
-        for (i_layer, cross_layer_size) in self.sp.cross_layer_sizes.iter().enumerate() {
-            if *cross_layer_size > 0 {
-                let simd_var_v_evals = self.sp.cross_layer_evals[i_layer][0].unpack();
-                let simd_var_hg_evals = self.sp.cross_layer_hg_evals[i_layer][0].unpack();
+    pub(crate) fn poly_evals_at_ry(
+        &self,
+        var_idx: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> [F::ChallengeField; 3] {
+        let local_evals = MultilinearProductHelper::<F>::poly_eval_at(
+            self.input_layer_var_num,
+            var_idx,
+            2,
+            &self.bk_f_y,
+            &self.bk_hg_y,
+            &self.circuit_vals.vals[self.layer.layer_id - 1],
+        )
+        .map(|p| unpack_and_combine(&p, &self.eq_evals_at_r_simd_next) * self.phase2_coef);
 
-                for i in 0..simd_var_v_evals.len() {
-                    self.sp.simd_var_v_evals[i] += simd_var_v_evals[i];
-                    self.sp.simd_var_hg_evals[i] += simd_var_hg_evals[i];
-                }
-            }
-        }
+        mpi_config
+            .coef_combine_vec(&local_evals, &self.eq_evals_at_r_mpi_next)
+            .try_into()
+            .unwrap()
     }
 
     #[inline]
-    pub(crate) fn prepare_y_vals(&mut self) {
-        self.sp.phase2_coef = self.sp.simd_var_v_evals[0] * self.sp.eq_evals_at_r_simd[0];
-
-        let mul = &self.layer.mul_gates;
-        let eq_evals_at_rz = &self.sp.eq_evals_at_rz0;
-        let eq_evals_at_rx = &mut self.sp.eq_evals_at_rx;
-        let hg_vals = &mut self.sp.hg_evals;
-        let fill_len = 1 << self.rx.len();
-        // hg_vals[0..fill_len].fill(F::zero()); // FIXED: consider memset unsafe?
-        unsafe {
-            std::ptr::write_bytes(hg_vals.as_mut_ptr(), 0, fill_len);
-        }
-
-        EqPolynomial::<F::ChallengeField>::eq_eval_at(
-            &self.rx,
-            &F::ChallengeField::ONE,
-            eq_evals_at_rx,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
-        );
-
-        EqPolynomial::<F::ChallengeField>::eq_eval_at(
-            &self.r_simd_next,
-            &F::ChallengeField::ONE,
-            &mut self.sp.eq_evals_at_r_simd_at_layer[self.layer.layer_id],
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
+    pub(crate) fn receive_ry(&mut self, var_idx: usize, r: F::ChallengeField) {
+        MultilinearProductHelper::<F>::receive_challenge(
+            self.input_layer_var_num,
+            var_idx,
+            r,
+            &mut self.bk_f_y,
+            &mut self.bk_hg_y,
+            &self.circuit_vals.vals[self.layer.layer_id - 1],
         );
+        self.ry.push(r);
+    }
 
-        for g in mul.iter() {
-            hg_vals[g.i_ids[1]] +=
-                F::Field::from((eq_evals_at_rz[g.o_id] * eq_evals_at_rx[g.i_ids[0]]) * g.coef);
-        }
+    /// The claim on the layer below at (ry, r_simd_next, r_mpi_next). Only meaningful on the
+    /// root.
+    #[inline]
+    pub(crate) fn vy_claim(&self, mpi_config: &impl MPIEngine) -> F::ChallengeField {
+        let vy_local = unpack_and_combine(&self.bk_f_y[0], &self.eq_evals_at_r_simd_next);
+        mpi_config.coef_combine_vec(&[vy_local], &self.eq_evals_at_r_mpi_next)[0]
     }
 }
 
+/// Prover of the gather sumcheck of a layer, reducing all the claims on the layer, direct or
+/// on its relay values, to a single claim, see `sumcheck_prove_gather_layer`.
+///
+/// The claims may be at different MPI challenges, hence each process weighs its own claims
+/// with eq(r_mpi_k, rank), and the round polynomials are summed across the processes.
 pub(crate) struct CrossLayerGatherHelper<'a, F: FieldEngine> {
+    pub(crate) var_num: usize,
+    pub(crate) mpi_var_num: usize,
     pub(crate) rx: Vec<F::ChallengeField>,
+    pub(crate) r_simd: Vec<F::ChallengeField>,
+    pub(crate) r_mpi: Vec<F::ChallengeField>,
 
-    layer: &'a GenericLayer<F>,
-    rz0: &'a [F::ChallengeField],
-    rz1: &'a [F::ChallengeField],
-    r_relays: &'a [(usize, Vec<F::ChallengeField>)],
-    alpha: &'a F::ChallengeField, // alpha is the random value multiplied to V(rz1)
-    betas: &'a [F::ChallengeField], /* betas random value multiplied to the claims from the
-                                   * previous non-zero relay layer */
-    connections: &'a CrossLayerConnections,
-    circuit_vals: &'a CrossLayerCircuitEvals<F>,
+    simd_var_num: usize,
+    init_v: &'a [F::SimdCircuitField],
+    bk_f: Vec<F::Field>,
+    bk_hg: Vec<F::Field>,
 
-    sp: &'a mut CrossLayerProverScratchPad<F>,
+    simd_f: Vec<F::ChallengeField>,
+    simd_hg: Vec<F::ChallengeField>,
 
-    pub(crate) cur_layer_var_num: usize,
+    // the values of f and hg of each process, only gathered on the root
+    mpi_f: Vec<F::ChallengeField>,
+    mpi_hg: Vec<F::ChallengeField>,
 }
 
-#[allow(clippy::too_many_arguments)]
 impl<'a, F: FieldEngine> CrossLayerGatherHelper<'a, F> {
-    pub fn new(
-        layer: &'a GenericLayer<F>,
-        rz0: &'a [F::ChallengeField],
-        rz1: &'a [F::ChallengeField],
-        r_relays: &'a [(usize, Vec<F::ChallengeField>)],
-        alpha: &'a F::ChallengeField,
-        betas: &'a [F::ChallengeField],
-        connections: &'a CrossLayerConnections,
+    #[inline]
+    pub(crate) fn new(
+        layer_id: usize,
+        claims: &[LayerClaim<F>],
+        betas: &[F::ChallengeField],
+        connections: &CrossLayerConnections,
         circuit_vals: &'a CrossLayerCircuitEvals<F>,
-        sp: &'a mut CrossLayerProverScratchPad<F>,
+        mpi_config: &impl MPIEngine,
     ) -> Self {
+        assert_eq!(claims.len(), betas.len());
+
+        let init_v = circuit_vals.vals[layer_id].as_slice();
+        let var_num = init_v.len().trailing_zeros() as usize;
+
+        // hg(x, s) = sum_k beta_k * eq(r_mpi_k, rank) * eq(r_simd_k, s) * h_k(x), where h_k(x)
+        // is eq(rz_k, x) for a direct claim, and sum_g eq(rg_k, g) * [i_g == x] for a claim on
+        // the relay values
+        let mut bk_hg = vec![F::Field::zero(); init_v.len()];
+        let mut simd_weights = vec![F::ChallengeField::zero(); F::get_field_pack_size()];
+        for (claim, beta) in claims.iter().zip(betas) {
+            let weight = *beta * EqPolynomial::build_eq_x_r(&claim.r_mpi)[mpi_config.world_rank()];
+            EqPolynomial::build_eq_x_r_with_buf(&claim.r_simd, &weight, &mut simd_weights);
+            let packed_weights = F::Field::pack(&simd_weights);
+
+            match &claim.point {
+                LayerClaimPoint::Direct(rz) => {
+                    let eq_evals_at_rz = EqPolynomial::build_eq_x_r(rz);
+                    bk_hg
+                        .iter_mut()
+                        .zip(eq_evals_at_rz)
+                        .for_each(|(hg, eq)| *hg += packed_weights.scale(&eq));
+                }
+                LayerClaimPoint::Relay { o_layer, rg } => {
+                    let eq_evals_at_rg = EqPolynomial::build_eq_x_r(rg);
+                    for (g, (_, i_id)) in connections.connections[*o_layer][layer_id]
+                        .iter()
+                        .enumerate()
+                    {
+                        bk_hg[*i_id] += packed_weights.scale(&eq_evals_at_rg[g]);
+                    }
+                }
+            }
+        }
+
+        let mut bk_f = vec![F::Field::zero(); max(init_v.len() >> 1, 1)];
+        if var_num == 0 {
+            bk_f[0] = init_v[0].into();
+        }
+
         CrossLayerGatherHelper {
+            var_num,
+            mpi_var_num: mpi_config.world_size().trailing_zeros() as usize,
             rx: vec![],
-            layer,
-            rz0,
-            rz1,
-            r_relays,
-            alpha,
-            betas,
-            connections,
-            circuit_vals,
-            sp,
-            cur_layer_var_num: layer.layer_size.trailing_zeros() as usize,
+            r_simd: vec![],
+            r_mpi: vec![],
+
+            simd_var_num: F::get_field_pack_size().trailing_zeros() as usize,
+            init_v,
+            bk_f,
+            bk_hg,
+
+            simd_f: vec![],
+            simd_hg: vec![],
+            mpi_f: vec![],
+            mpi_hg: vec![],
         }
     }
 
+    #[inline]
     pub(crate) fn poly_evals_at_rx(
-        &mut self,
+        &self,
         var_idx: usize,
-        degree: usize,
+        mpi_config: &impl MPIEngine,
     ) -> [F::ChallengeField; 3] {
-        MultilinearProductHelper::<F>::poly_eval_at(
-            self.cur_layer_var_num,
+        let local_evals = MultilinearProductHelper::<F>::poly_eval_at(
+            self.var_num,
             var_idx,
-            degree,
-            &self.sp.v_evals,
-            &self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id],
+            2,
+            &self.bk_f,
+            &self.bk_hg,
+            self.init_v,
         )
-        .iter()
-        .map(|p| p.unpack()[0])
-        .collect::<Vec<F::ChallengeField>>()
-        .try_into()
-        .unwrap()
+        .map(|p| p.horizontal_sum());
+        sum_over_processes(mpi_config, local_evals)
     }
 
+    #[inline]
     pub(crate) fn receive_rx(&mut self, var_idx: usize, r: F::ChallengeField) {
         MultilinearProductHelper::<F>::receive_challenge(
-            self.cur_layer_var_num,
+            self.var_num,
             var_idx,
             r,
-            &mut self.sp.v_evals,
-            &mut self.sp.hg_evals,
-            &self.circuit_vals.vals[self.layer.layer_id],
+            &mut self.bk_f,
+            &mut self.bk_hg,
+            self.init_v,
         );
         self.rx.push(r);
     }
 
-    pub(crate) fn vx_claim(&self) -> F::ChallengeField {
-        self.sp.v_evals[0].unpack()[0]
+    #[inline]
+    pub(crate) fn prepare_simd_var_vals(&mut self) {
+        self.simd_f = self.bk_f[0].unpack();
+        self.simd_hg = self.bk_hg[0].unpack();
     }
 
     #[inline]
-    pub(crate) fn prepare_x_vals(&mut self) {
-        let hg_vals = &mut self.sp.hg_evals;
-        let eq_evals_at_rz = &mut self.sp.eq_evals_at_rz0;
-
-        EqPolynomial::eq_eval_at(
-            self.rz0,
-            &F::ChallengeField::ONE,
-            eq_evals_at_rz,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
+    pub(crate) fn poly_evals_at_r_simd_var(
+        &self,
+        var_idx: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> [F::ChallengeField; 3] {
+        let local_evals = SumcheckSimdProdGateHelper::<F>::product_poly_eval_at(
+            self.simd_var_num,
+            var_idx,
+            &self.simd_f,
+            &self.simd_hg,
         );
-        for i in 0..self.rz0.len() {
-            hg_vals[i] += F::Field::from(eq_evals_at_rz[i]);
-        }
+        sum_over_processes(mpi_config, local_evals)
+    }
 
-        // second claim from the previous layer
-        EqPolynomial::eq_eval_at(
-            self.rz1,
-            self.alpha,
-            eq_evals_at_rz,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
+    #[inline]
+    pub(crate) fn receive_r_simd_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        SumcheckSimdProdGateHelper::<F>::receive_challenge(
+            self.simd_var_num,
+            var_idx,
+            r,
+            &mut self.simd_f,
         );
-        for i in 0..self.rz0.len() {
-            hg_vals[i] += F::Field::from(eq_evals_at_rz[i]);
-        }
+        SumcheckSimdProdGateHelper::<F>::receive_challenge(
+            self.simd_var_num,
+            var_idx,
+            r,
+            &mut self.simd_hg,
+        );
+        self.r_simd.push(r);
+    }
 
-        for (layer_idx, (out_layer_id, claim)) in self.r_relays.iter().enumerate() {
-            EqPolynomial::eq_eval_at(
-                claim,
-                &self.betas[layer_idx],
-                eq_evals_at_rz,
-                &mut self.sp.eq_evals_first_half,
-                &mut self.sp.eq_evals_second_half,
-            );
+    #[inline]
+    pub(crate) fn prepare_mpi_var_vals(&mut self, mpi_config: &impl MPIEngine) {
+        self.mpi_f = vec![F::ChallengeField::ZERO; mpi_config.world_size()];
+        self.mpi_hg = vec![F::ChallengeField::ZERO; mpi_config.world_size()];
+        mpi_config.gather_vec(&[self.simd_f[0]], &mut self.mpi_f);
+        mpi_config.gather_vec(&[self.simd_hg[0]], &mut self.mpi_hg);
+    }
 
-            for (gate_idx, (_o_id, i_id)) in self.connections.connections[*out_layer_id]
-                [self.layer.layer_id]
-                .iter()
-                .enumerate()
-            {
-                hg_vals[*i_id] += F::Field::from(eq_evals_at_rz[gate_idx]);
-            }
-        }
+    /// The round polynomial over an MPI variable, only meaningful on the root.
+    #[inline]
+    pub(crate) fn poly_evals_at_r_mpi_var(&self, var_idx: usize) -> [F::ChallengeField; 3] {
+        SumcheckSimdProdGateHelper::<F>::product_poly_eval_at(
+            self.mpi_var_num,
+            var_idx,
+            &self.mpi_f,
+            &self.mpi_hg,
+        )
+    }
+
+    #[inline]
+    pub(crate) fn receive_r_mpi_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        SumcheckSimdProdGateHelper::<F>::receive_challenge(
+            self.mpi_var_num,
+            var_idx,
+            r,
+            &mut self.mpi_f,
+        );
+        SumcheckSimdProdGateHelper::<F>::receive_challenge(
+            self.mpi_var_num,
+            var_idx,
+            r,
+            &mut self.mpi_hg,
+        );
+        self.r_mpi.push(r);
+    }
+
+    /// The claim on the layer at (rx, r_simd, r_mpi). Only meaningful on the root.
+    #[inline]
+    pub(crate) fn vx_claim(&self) -> F::ChallengeField {
+        self.mpi_f[0]
     }
 }
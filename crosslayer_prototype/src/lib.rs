@@ -13,11 +13,20 @@ pub use circuit::*;
 mod helper;
 pub(crate) use helper::*;
 
-mod scratchpad;
-pub use scratchpad::*;
-
 mod sumcheck;
 pub use sumcheck::*;
 
+mod sumcheck_verifier;
+pub use sumcheck_verifier::*;
+
 mod gkr;
 pub use gkr::*;
+
+mod prover;
+pub use prover::*;
+
+mod verifier;
+pub use verifier::*;
+
+mod configs;
+pub use configs::*;
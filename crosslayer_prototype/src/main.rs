@@ -1,39 +1,61 @@
 use arith::Field;
-use crosslayer_prototype::{prove_gkr, CrossLayerConnections, CrossLayerRecursiveCircuit};
-use gkr_engine::{FieldEngine, GF2ExtConfig, Transcript};
-use gkr_hashers::SHA256hasher;
-use transcript::BytesHashTranscript;
-
-fn test_sumcheck_cross_layered_helper<F: FieldEngine>() {
-    let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+use crosslayer_prototype::{
+    CrossLayerProver, CrossLayerRecursiveCircuit, CrossLayerVerifier, GF2ExtConfigSha2RawCrossLayer,
+};
+use gkr_engine::{FieldEngine, GKREngine, MPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
 
+fn prove_and_verify_sha256<Cfg: GKREngine>()
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
     let mut rng = rand::thread_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
 
-    let circuit = CrossLayerRecursiveCircuit::<F>::load("./data/sha256_circuit_gf2.txt")
-        .unwrap()
-        .flatten();
+    let circuit =
+        CrossLayerRecursiveCircuit::<Cfg::FieldConfig>::load("./data/sha256_circuit_gf2.txt")
+            .unwrap()
+            .flatten();
     circuit.print_stats();
 
     let inputs = (0..circuit.layers[0].layer_size)
-        .map(|_| F::SimdCircuitField::random_unsafe(&mut rng))
+        .map(|_| <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::random_unsafe(&mut rng))
         .collect::<Vec<_>>();
-    let connections = CrossLayerConnections::parse_circuit(&circuit);
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+            circuit.layers[0].layer_size.trailing_zeros() as usize,
+            &mpi_config,
+        );
 
     let start_time = std::time::Instant::now();
-    let evals = circuit.evaluate(&inputs);
-    let mut sp = crosslayer_prototype::CrossLayerProverScratchPad::<F>::new(
-        circuit.layers.len(),
-        circuit.max_num_input_var(),
-        circuit.max_num_output_var(),
-        1,
+    let mut prover = CrossLayerProver::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove(
+        &circuit,
+        &inputs,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+    println!(
+        "Proving time {} ms, proof size {} bytes",
+        start_time.elapsed().as_millis(),
+        proof.bytes.len()
     );
-    let (_output_claim, _input_challenge, _input_claim) =
-        prove_gkr(&circuit, &evals, &connections, &mut transcript, &mut sp);
-    let stop_time = std::time::Instant::now();
-    let duration = stop_time.duration_since(start_time);
-    println!("Time elapsed {} ms", duration.as_millis());
+
+    let start_time = std::time::Instant::now();
+    let verifier = CrossLayerVerifier::<Cfg>::new(mpi_config);
+    assert!(verifier.verify(
+        &circuit,
+        &circuit.public_input,
+        &claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        &proof
+    ));
+    println!("Verification time {} ms", start_time.elapsed().as_millis());
 }
 
 fn main() {
-    test_sumcheck_cross_layered_helper::<GF2ExtConfig>();
+    prove_and_verify_sha256::<GF2ExtConfigSha2RawCrossLayer>();
 }
@@ -0,0 +1,138 @@
+//! The cross-layer GKR prover, including the PCS commitment to and the opening of the inputs.
+
+use arith::Field;
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, GKRScheme, MPIConfig, MPIEngine, Proof,
+    StructuredReferenceString, Transcript,
+};
+use polynomials::RefMultiLinearPoly;
+use serdes::ExpSerde;
+use transcript::transcript_root_broadcast;
+use utils::timer::Timer;
+
+use crate::{gkr::absorb_public_input, prove_gkr, CrossLayerCircuit, CrossLayerConnections};
+
+/// Prover of `GKRScheme::CrossLayer`.
+///
+/// As in the vanilla GKR, each MPI process proves the circuit on its own inputs, and the root
+/// writes the proof.
+#[derive(Default)]
+pub struct CrossLayerProver<'a, Cfg: GKREngine> {
+    pub mpi_config: MPIConfig<'a>,
+    connections: CrossLayerConnections,
+    phantom: std::marker::PhantomData<Cfg>,
+}
+
+impl<'a, Cfg: GKREngine> CrossLayerProver<'a, Cfg> {
+    pub fn new(mpi_config: MPIConfig<'a>) -> Self {
+        CrossLayerProver {
+            mpi_config,
+            connections: CrossLayerConnections::default(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    pub fn prepare_mem(&mut self, c: &CrossLayerCircuit<Cfg::FieldConfig>) {
+        self.connections = CrossLayerConnections::parse_circuit(c);
+    }
+
+    /// Prove the evaluation of the circuit on the inputs, returning the claimed output at the
+    /// output challenge and the proof, both only meaningful on the root.
+    ///
+    /// The transcript is bound to the public inputs of the circuit of all the processes, in rank
+    /// order, and to the claimed output. The number of processes must be a power of two.
+    pub fn prove(
+        &mut self,
+        c: &CrossLayerCircuit<Cfg::FieldConfig>,
+        inputs: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::ScratchPad,
+    ) -> (<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)
+    where
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        const { assert!(matches!(Cfg::SCHEME, GKRScheme::CrossLayer)) };
+        assert!(
+            self.mpi_config.world_size().is_power_of_two(),
+            "the number of processes must be a power of two"
+        );
+        assert_eq!(inputs.len(), c.layers[0].layer_size);
+        if self.connections.connections.len() != c.layers.len() {
+            self.prepare_mem(c);
+        }
+
+        let is_root = self.mpi_config.is_root();
+        let proving_timer = Timer::new("cross-layer prover", is_root);
+        let mut transcript = Cfg::TranscriptConfig::new();
+
+        let pcs_commit_timer = Timer::new("pcs commit", is_root);
+        let inputs_poly = RefMultiLinearPoly::from_ref(inputs);
+        let commitment = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::commit(
+            pcs_params,
+            &self.mpi_config,
+            pcs_proving_key,
+            &inputs_poly,
+            pcs_scratch,
+        );
+
+        // the verifier is given the public inputs of all the MPI processes, in rank order
+        let mut public_input = vec![];
+        if is_root {
+            public_input = vec![
+                <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO;
+                c.public_input.len() * self.mpi_config.world_size()
+            ];
+        }
+        self.mpi_config
+            .gather_vec(&c.public_input, &mut public_input);
+
+        if is_root {
+            let mut buffer = vec![];
+            commitment.unwrap().serialize_into(&mut buffer).unwrap(); // TODO: error propagation
+            transcript.append_commitment(&buffer);
+            absorb_public_input::<Cfg::FieldConfig, _>(&public_input, &mut transcript);
+        }
+        pcs_commit_timer.stop();
+
+        let gkr_prove_timer = Timer::new("gkr prove", is_root);
+        transcript_root_broadcast(&mut transcript, &self.mpi_config);
+        let circuit_vals = c.evaluate(inputs);
+        let (claimed_v, challenge, _input_claim) = prove_gkr(
+            c,
+            &circuit_vals,
+            &self.connections,
+            &mut transcript,
+            &self.mpi_config,
+        );
+        gkr_prove_timer.stop();
+
+        transcript_root_broadcast(&mut transcript, &self.mpi_config);
+
+        let pcs_open_timer = Timer::new("pcs open", is_root);
+        transcript.lock_proof();
+        let opening = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::open(
+            pcs_params,
+            &self.mpi_config,
+            pcs_proving_key,
+            &inputs_poly,
+            &challenge,
+            &mut transcript,
+            pcs_scratch,
+        );
+        transcript.unlock_proof();
+
+        if is_root {
+            let mut buffer = vec![];
+            opening.unwrap().serialize_into(&mut buffer).unwrap(); // TODO: error propagation
+            transcript.append_u8_slice(&buffer);
+        }
+        pcs_open_timer.stop();
+
+        let proof = transcript.finalize_and_get_proof();
+        proving_timer.print(&format!("Proof size {} bytes", proof.bytes.len()));
+        proving_timer.stop();
+
+        (claimed_v, proof)
+    }
+}
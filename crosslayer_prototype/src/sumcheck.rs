@@ -1,111 +1,186 @@
-use arith::ExtensionField;
-use gkr_engine::{FieldEngine, Transcript};
+use gkr_engine::{FieldEngine, MPIEngine, Transcript};
+use sumcheck::transcript_io;
 
 use crate::{
-    CrossLayerCircuitEvals, CrossLayerConnections, CrossLayerGatherHelper,
-    CrossLayerProverScratchPad, CrossLayerScatterHelper, GenericLayer,
+    CrossLayerCircuitEvals, CrossLayerConnections, CrossLayerGatherHelper, CrossLayerScatterHelper,
+    GenericLayer,
 };
 
-#[inline]
-pub fn transcript_io<F: ExtensionField, T: Transcript>(ps: &[F], transcript: &mut T) -> F {
-    assert!(ps.len() == 3 || ps.len() == 4);
-    for p in ps {
-        transcript.append_field_element(p);
-    }
-    transcript.generate_field_element::<F>()
+/// Where a claim on the values V(x, s) of a layer is made.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerClaimPoint<F: FieldEngine> {
+    /// A claim on V(rz, r_simd).
+    Direct(Vec<F::ChallengeField>),
+    /// A claim on R(rg, r_simd), where R(g, s) = V(i_g, s) for the g-th relay gate of layer
+    /// `o_layer` that reads from this layer, in the order of `CrossLayerConnections`, padded
+    /// with zeros to a power of two.
+    Relay {
+        o_layer: usize,
+        rg: Vec<F::ChallengeField>,
+    },
+}
+
+/// A claim that the values of a layer evaluate to `v` at `point`, `r_simd` and `r_mpi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerClaim<F: FieldEngine> {
+    pub point: LayerClaimPoint<F>,
+    pub r_simd: Vec<F::ChallengeField>,
+    pub r_mpi: Vec<F::ChallengeField>,
+    pub v: F::ChallengeField,
 }
 
-// FIXME
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::type_complexity)]
+/// Reduce a claim on the values of `layer` to claims on the layers it reads from:
+/// - one or two direct claims on the layer below, at the inputs of the add/mul gates and of the
+///   relays from that layer,
+/// - one claim on the relay values of each earlier layer the layer relays from.
+///
+/// The claims are returned along with the id of the layer they are on. With several MPI
+/// processes, each process holds its own values of the layers, and the claimed values are only
+/// meaningful on the root, as in the vanilla GKR.
 pub fn sumcheck_prove_scatter_layer<F: FieldEngine, T: Transcript>(
     layer: &GenericLayer<F>,
-    rz0: &[F::ChallengeField],
-    r_simd: &[F::ChallengeField],
-    connections: &CrossLayerConnections,
+    claim: &LayerClaim<F>,
     circuit_vals: &CrossLayerCircuitEvals<F>,
     transcript: &mut T,
-    sp: &mut CrossLayerProverScratchPad<F>,
-) -> (
-    Vec<F::ChallengeField>,
-    Vec<F::ChallengeField>,
-    Vec<(usize, Vec<F::ChallengeField>)>,
-) {
+    mpi_config: &impl MPIEngine,
+) -> Vec<(usize, LayerClaim<F>)> {
+    let LayerClaimPoint::Direct(rz) = &claim.point else {
+        panic!("a layer is scattered from a direct claim");
+    };
     let mut helper =
-        CrossLayerScatterHelper::new(layer, rz0, r_simd, connections, circuit_vals, sp);
-
-    helper.prepare_simd();
+        CrossLayerScatterHelper::new(layer, rz, &claim.r_simd, &claim.r_mpi, circuit_vals);
 
-    // gkr phase 1 over variable x
-    helper.prepare_x_vals();
-    for i_var in 0..helper.input_layer_var_num {
-        let evals = helper.poly_evals_at_rx(i_var, 2);
-        let r = transcript_io::<F::ChallengeField, T>(&evals, transcript);
+    // gkr phase 1 over variable x, and the variables of the relay tables
+    for i_var in 0..helper.var_num {
+        let evals = helper.poly_evals_at_rx(i_var, mpi_config);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
         helper.receive_rx(i_var, r);
     }
 
     helper.prepare_simd_var_vals();
     for i_var in 0..F::get_field_pack_size().trailing_zeros() as usize {
-        let evals = helper.poly_evals_at_r_simd_var(i_var, 3);
-        let r = transcript_io::<F::ChallengeField, T>(&evals, transcript);
+        let evals = helper.poly_evals_at_r_simd_var(i_var, mpi_config);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
         helper.receive_r_simd_var(i_var, r);
     }
 
-    let vx_claims: Vec<(usize, F::ChallengeField)> = helper.vx_claims();
-    for (_i_layer, claim) in vx_claims {
-        transcript.append_field_element(&claim);
+    helper.prepare_mpi_var_vals(mpi_config);
+    for i_var in 0..helper.mpi_var_num {
+        let evals = helper.poly_evals_at_r_mpi_var(i_var);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        helper.receive_r_mpi_var(i_var, r);
     }
 
+    let vx_claim = helper.vx_claim(mpi_config);
+    transcript.append_field_element(&vx_claim);
+    let relay_claims = helper.relay_claims();
+    for (_, _, claim) in &relay_claims {
+        transcript.append_field_element(claim);
+    }
+
+    let mut claims = vec![(
+        layer.layer_id - 1,
+        LayerClaim {
+            point: LayerClaimPoint::Direct(helper.rx[..helper.input_layer_var_num].to_vec()),
+            r_simd: helper.r_simd_next.clone(),
+            r_mpi: helper.r_mpi_next.clone(),
+            v: vx_claim,
+        },
+    )];
+
     // gkr phase 2 over variable y
-    helper.prepare_y_vals();
-    for i_var in 0..helper.input_layer_var_num {
-        let evals = helper.poly_evals_at_ry(i_var, 2);
-        let r = transcript_io::<F::ChallengeField, T>(&evals, transcript);
-        helper.receive_ry(i_var, r);
+    if !layer.mul_gates.is_empty() {
+        helper.prepare_y_vals(vx_claim);
+        for i_var in 0..helper.input_layer_var_num {
+            let evals = helper.poly_evals_at_ry(i_var, mpi_config);
+            let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+            helper.receive_ry(i_var, r);
+        }
+        let vy_claim = helper.vy_claim(mpi_config);
+        transcript.append_field_element(&vy_claim);
+
+        claims.push((
+            layer.layer_id - 1,
+            LayerClaim {
+                point: LayerClaimPoint::Direct(helper.ry.clone()),
+                r_simd: helper.r_simd_next.clone(),
+                r_mpi: helper.r_mpi_next.clone(),
+                v: vy_claim,
+            },
+        ));
     }
-    let vy_claim = helper.vy_claim();
-    transcript.append_field_element(&vy_claim);
 
-    (helper.rx, helper.ry, helper.r_relays_next)
+    claims.extend(relay_claims.into_iter().map(|(i_layer, var_num, v)| {
+        (
+            i_layer,
+            LayerClaim {
+                point: LayerClaimPoint::Relay {
+                    o_layer: layer.layer_id,
+                    rg: helper.rx[..var_num].to_vec(),
+                },
+                r_simd: helper.r_simd_next.clone(),
+                r_mpi: helper.r_mpi_next.clone(),
+                v,
+            },
+        )
+    }));
+
+    claims
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Reduce all the claims on the values of layer `layer_id` to a single direct claim, by a
+/// sumcheck on a random linear combination of them.
+///
+/// The claimed value is only meaningful on the root, see `sumcheck_prove_scatter_layer`.
 pub fn sumcheck_prove_gather_layer<F: FieldEngine, T: Transcript>(
-    layer: &GenericLayer<F>,
-    rz0: &[F::ChallengeField],
-    rz1: &[F::ChallengeField],
-    r_relays: &[(usize, Vec<F::ChallengeField>)],
+    layer_id: usize,
+    claims: &[LayerClaim<F>],
     connections: &CrossLayerConnections,
     circuit_vals: &CrossLayerCircuitEvals<F>,
     transcript: &mut T,
-    sp: &mut CrossLayerProverScratchPad<F>,
-) -> (Vec<F::ChallengeField>, F::ChallengeField) {
-    let alpha = transcript.generate_field_element::<F::ChallengeField>();
-    let betas = transcript.generate_field_elements::<F::ChallengeField>(r_relays.len());
+    mpi_config: &impl MPIEngine,
+) -> LayerClaim<F> {
+    let mut betas = transcript.generate_field_elements::<F::ChallengeField>(claims.len());
+    betas
+        .iter_mut()
+        .for_each(|beta| mpi_config.root_broadcast_f(beta));
 
     let mut helper = CrossLayerGatherHelper::new(
-        layer,
-        rz0,
-        rz1,
-        r_relays,
-        &alpha,
+        layer_id,
+        claims,
         &betas,
         connections,
         circuit_vals,
-        sp,
+        mpi_config,
     );
 
-    helper.prepare_x_vals();
-    for i_var in 0..helper.cur_layer_var_num {
-        let evals = helper.poly_evals_at_rx(i_var, 2);
-        let r = transcript_io::<F::ChallengeField, T>(&evals, transcript);
+    for i_var in 0..helper.var_num {
+        let evals = helper.poly_evals_at_rx(i_var, mpi_config);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
         helper.receive_rx(i_var, r);
     }
 
+    helper.prepare_simd_var_vals();
+    for i_var in 0..F::get_field_pack_size().trailing_zeros() as usize {
+        let evals = helper.poly_evals_at_r_simd_var(i_var, mpi_config);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        helper.receive_r_simd_var(i_var, r);
+    }
+
+    helper.prepare_mpi_var_vals(mpi_config);
+    for i_var in 0..helper.mpi_var_num {
+        let evals = helper.poly_evals_at_r_mpi_var(i_var);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        helper.receive_r_mpi_var(i_var, r);
+    }
+
     let vx_claim = helper.vx_claim();
     transcript.append_field_element(&vx_claim);
 
-    // No variable Y in gathering layer
-
-    (helper.rx, vx_claim)
+    LayerClaim {
+        point: LayerClaimPoint::Direct(helper.rx),
+        r_simd: helper.r_simd,
+        r_mpi: helper.r_mpi,
+        v: vx_claim,
+    }
 }
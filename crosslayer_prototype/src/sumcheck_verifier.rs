@@ -0,0 +1,362 @@
+use std::{cmp::max, io::Read};
+
+use arith::Field;
+use gkr_engine::{FieldEngine, Transcript, VerifyError};
+use polynomials::EqPolynomial;
+use serdes::ExpSerde;
+use sumcheck::{
+    unpack_and_combine, GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_DEGREE,
+    SUMCHECK_GKR_SIMD_MPI_DEGREE,
+};
+
+use crate::{CoefType, CrossLayerConnections, GenericLayer, LayerClaim, LayerClaimPoint};
+
+#[inline(always)]
+fn verify_sumcheck_step<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    degree: usize,
+    transcript: &mut impl Transcript,
+    claimed_sum: &mut F::ChallengeField,
+    randomness_vec: &mut Vec<F::ChallengeField>,
+    sp: &VerifierScratchPad<F>,
+) -> Result<bool, VerifyError> {
+    let mut ps = vec![];
+    for i in 0..(degree + 1) {
+        ps.push(
+            F::ChallengeField::deserialize_from(&mut proof_reader)
+                .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedSumcheckMessage))?,
+        );
+        transcript.append_field_element(&ps[i]);
+    }
+
+    let r = transcript.generate_field_element::<F::ChallengeField>();
+    randomness_vec.push(r);
+
+    let verified = (ps[0] + ps[1]) == *claimed_sum;
+
+    if degree == SUMCHECK_GKR_DEGREE {
+        *claimed_sum = GKRVerifierHelper::degree_2_eval(&ps, r, sp);
+    } else if degree == SUMCHECK_GKR_SIMD_MPI_DEGREE {
+        *claimed_sum = GKRVerifierHelper::degree_3_eval(&ps, r, sp);
+    } else {
+        unreachable!("unsupported degree");
+    }
+
+    Ok(verified)
+}
+
+#[inline(always)]
+fn read_claim<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+) -> Result<F::ChallengeField, VerifyError> {
+    let claim = F::ChallengeField::deserialize_from(&mut proof_reader)
+        .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedSumcheckMessage))?;
+    transcript.append_field_element(&claim);
+    Ok(claim)
+}
+
+/// Verifier side of `sumcheck_prove_scatter_layer`, returning whether the sumcheck verifies,
+/// and the claims on the layers `layer` reads from.
+///
+/// The public inputs are those of all the `proving_time_mpi_size` processes, in rank order.
+///
+/// Fails if the proof ends early or contains a malformed field element.
+pub fn sumcheck_verify_scatter_layer<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    layer: &GenericLayer<F>,
+    public_input: &[F::SimdCircuitField],
+    claim: &LayerClaim<F>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &VerifierScratchPad<F>,
+) -> Result<(bool, Vec<(usize, LayerClaim<F>)>), VerifyError> {
+    let LayerClaimPoint::Direct(rz) = &claim.point else {
+        unreachable!("a layer is scattered from a direct claim");
+    };
+    let layer_id = layer.layer_id;
+    assert!(layer_id > 0);
+    let eq_evals_at_rz = EqPolynomial::build_eq_x_r(rz);
+
+    let input_layer_var_num = layer.input_layer_size.trailing_zeros() as usize;
+    let mut relay_gates = vec![vec![]; layer_id - 1];
+    for g in layer
+        .relay_gates
+        .iter()
+        .filter(|g| g.i_layer < layer_id - 1)
+    {
+        relay_gates[g.i_layer].push(g);
+    }
+    // (input layer, number of variables of the relay table, relay gates)
+    let relay_tables = relay_gates
+        .into_iter()
+        .enumerate()
+        .filter(|(_, gates)| !gates.is_empty())
+        .map(|(i_layer, gates)| {
+            let var_num = gates.len().next_power_of_two().trailing_zeros() as usize;
+            (i_layer, var_num, gates)
+        })
+        .collect::<Vec<_>>();
+    let var_num = relay_tables
+        .iter()
+        .map(|(_, var_num, _)| *var_num)
+        .fold(input_layer_var_num, max);
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let mpi_var_num = proving_time_mpi_size.trailing_zeros() as usize;
+
+    let eq_evals_at_r_simd = EqPolynomial::build_eq_x_r(&claim.r_simd);
+    let eq_evals_at_r_mpi = EqPolynomial::build_eq_x_r(&claim.r_mpi);
+    let local_input_size = public_input.len() / proving_time_mpi_size;
+    let mut sum = claim.v;
+    for g in &layer.const_gates {
+        sum -= match g.coef_type {
+            CoefType::PublicInput(input_idx) => {
+                let input_mpi_combined: F::Field = eq_evals_at_r_mpi
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, eq)| *eq * public_input[rank * local_input_size + input_idx])
+                    .sum();
+                eq_evals_at_rz[g.o_id]
+                    * unpack_and_combine::<F::Field>(&input_mpi_combined, &eq_evals_at_r_simd)
+            }
+            _ => eq_evals_at_rz[g.o_id] * g.coef,
+        };
+    }
+
+    let mut rx = vec![];
+    let mut r_simd_next = vec![];
+    let mut r_mpi_next = vec![];
+    let mut verified = true;
+    for _i_var in 0..var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            transcript,
+            &mut sum,
+            &mut rx,
+            sp,
+        )?;
+    }
+    for _i_var in 0..simd_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            transcript,
+            &mut sum,
+            &mut r_simd_next,
+            sp,
+        )?;
+    }
+    for _i_var in 0..mpi_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            transcript,
+            &mut sum,
+            &mut r_mpi_next,
+            sp,
+        )?;
+    }
+
+    let vx_claim = read_claim::<F>(&mut proof_reader, transcript)?;
+    let relay_claims = relay_tables
+        .iter()
+        .map(|_| read_claim::<F>(&mut proof_reader, transcript))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // a term over k < var_num variables is multiplied by the remaining variables of rx
+    let ext = |k: usize| rx[k..].iter().copied().product::<F::ChallengeField>();
+    let eq_r_simd_mpi_next = EqPolynomial::eq_vec(&claim.r_simd, &r_simd_next)
+        * EqPolynomial::eq_vec(&claim.r_mpi, &r_mpi_next);
+
+    let rx_main = &rx[..input_layer_var_num];
+    let eq_evals_at_rx = EqPolynomial::build_eq_x_r(rx_main);
+    let mut eval_add = F::ChallengeField::ZERO;
+    for g in &layer.add_gates {
+        eval_add += eq_evals_at_rz[g.o_id] * eq_evals_at_rx[g.i_ids[0]] * g.coef;
+    }
+    for g in layer
+        .relay_gates
+        .iter()
+        .filter(|g| g.i_layer == layer_id - 1)
+    {
+        eval_add += eq_evals_at_rz[g.o_id] * eq_evals_at_rx[g.i_id] * g.coef;
+    }
+    let main_coef = eq_r_simd_mpi_next * ext(input_layer_var_num);
+    sum -= main_coef * vx_claim * eval_add;
+
+    for ((_, relay_var_num, gates), relay_claim) in relay_tables.iter().zip(&relay_claims) {
+        let eq_evals_at_rg = EqPolynomial::build_eq_x_r(&rx[..*relay_var_num]);
+        let eval_relay = gates
+            .iter()
+            .zip(eq_evals_at_rg)
+            .map(|(g, eq)| eq_evals_at_rz[g.o_id] * eq * g.coef)
+            .sum::<F::ChallengeField>();
+        sum -= eq_r_simd_mpi_next * ext(*relay_var_num) * *relay_claim * eval_relay;
+    }
+
+    let mut claims = vec![(
+        layer_id - 1,
+        LayerClaim {
+            point: LayerClaimPoint::Direct(rx_main.to_vec()),
+            r_simd: r_simd_next.clone(),
+            r_mpi: r_mpi_next.clone(),
+            v: vx_claim,
+        },
+    )];
+
+    if !layer.mul_gates.is_empty() {
+        let mut ry = vec![];
+        for _i_var in 0..input_layer_var_num {
+            verified &= verify_sumcheck_step::<F>(
+                &mut proof_reader,
+                SUMCHECK_GKR_DEGREE,
+                transcript,
+                &mut sum,
+                &mut ry,
+                sp,
+            )?;
+        }
+        let vy_claim = read_claim::<F>(&mut proof_reader, transcript)?;
+
+        let eq_evals_at_ry = EqPolynomial::build_eq_x_r(&ry);
+        let mut eval_mul = F::ChallengeField::ZERO;
+        for g in &layer.mul_gates {
+            eval_mul += eq_evals_at_rz[g.o_id]
+                * eq_evals_at_rx[g.i_ids[0]]
+                * eq_evals_at_ry[g.i_ids[1]]
+                * g.coef;
+        }
+        verified &= sum == main_coef * vx_claim * vy_claim * eval_mul;
+
+        claims.push((
+            layer_id - 1,
+            LayerClaim {
+                point: LayerClaimPoint::Direct(ry),
+                r_simd: r_simd_next.clone(),
+                r_mpi: r_mpi_next.clone(),
+                v: vy_claim,
+            },
+        ));
+    } else {
+        verified &= sum == F::ChallengeField::ZERO;
+    }
+
+    claims.extend(
+        relay_tables
+            .iter()
+            .zip(relay_claims)
+            .map(|((i_layer, relay_var_num, _), v)| {
+                (
+                    *i_layer,
+                    LayerClaim {
+                        point: LayerClaimPoint::Relay {
+                            o_layer: layer_id,
+                            rg: rx[..*relay_var_num].to_vec(),
+                        },
+                        r_simd: r_simd_next.clone(),
+                        r_mpi: r_mpi_next.clone(),
+                        v,
+                    },
+                )
+            }),
+    );
+
+    Ok((verified, claims))
+}
+
+/// Verifier side of `sumcheck_prove_gather_layer`, returning whether the sumcheck verifies,
+/// and the single claim on the layer it is reduced to.
+///
+/// Fails if the proof ends early or contains a malformed field element.
+#[allow(clippy::too_many_arguments)]
+pub fn sumcheck_verify_gather_layer<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    layer_id: usize,
+    layer_size: usize,
+    claims: &[LayerClaim<F>],
+    connections: &CrossLayerConnections,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &VerifierScratchPad<F>,
+) -> Result<(bool, LayerClaim<F>), VerifyError> {
+    let betas = transcript.generate_field_elements::<F::ChallengeField>(claims.len());
+
+    let var_num = layer_size.trailing_zeros() as usize;
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let mpi_var_num = proving_time_mpi_size.trailing_zeros() as usize;
+
+    let mut sum = claims
+        .iter()
+        .zip(&betas)
+        .map(|(claim, beta)| claim.v * beta)
+        .sum::<F::ChallengeField>();
+
+    let mut rx = vec![];
+    let mut r_simd = vec![];
+    let mut r_mpi = vec![];
+    let mut verified = true;
+    for _i_var in 0..var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            transcript,
+            &mut sum,
+            &mut rx,
+            sp,
+        )?;
+    }
+    for _i_var in 0..simd_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            transcript,
+            &mut sum,
+            &mut r_simd,
+            sp,
+        )?;
+    }
+    for _i_var in 0..mpi_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            transcript,
+            &mut sum,
+            &mut r_mpi,
+            sp,
+        )?;
+    }
+
+    let vx_claim = read_claim::<F>(&mut proof_reader, transcript)?;
+
+    let eq_evals_at_rx = EqPolynomial::build_eq_x_r(&rx);
+    let mut eval_hg = F::ChallengeField::ZERO;
+    for (claim, beta) in claims.iter().zip(&betas) {
+        let eval_h = match &claim.point {
+            LayerClaimPoint::Direct(rz) => EqPolynomial::eq_vec(rz, &rx),
+            LayerClaimPoint::Relay { o_layer, rg } => {
+                let eq_evals_at_rg = EqPolynomial::build_eq_x_r(rg);
+                connections.connections[*o_layer][layer_id]
+                    .iter()
+                    .zip(eq_evals_at_rg)
+                    .map(|((_, i_id), eq)| eq * eq_evals_at_rx[*i_id])
+                    .sum()
+            }
+        };
+        eval_hg += *beta
+            * EqPolynomial::eq_vec(&claim.r_simd, &r_simd)
+            * EqPolynomial::eq_vec(&claim.r_mpi, &r_mpi)
+            * eval_h;
+    }
+    verified &= sum == vx_claim * eval_hg;
+
+    Ok((
+        verified,
+        LayerClaim {
+            point: LayerClaimPoint::Direct(rx),
+            r_simd,
+            r_mpi,
+            v: vx_claim,
+        },
+    ))
+}
@@ -0,0 +1,147 @@
+//! The cross-layer GKR verifier, including the PCS opening of the inputs.
+
+use std::{io::Cursor, marker::PhantomData};
+
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, GKRScheme, MPIConfig, MPIEngine, Proof,
+    StructuredReferenceString, Transcript, VerifyError,
+};
+use serdes::ExpSerde;
+use transcript::transcript_verifier_sync;
+use utils::timer::Timer;
+
+use crate::{gkr::absorb_public_input, verify_gkr, CrossLayerCircuit, CrossLayerConnections};
+
+/// Verifier of `GKRScheme::CrossLayer`.
+///
+/// As for the vanilla GKR, the verifier runs in a single process, and `mpi_config` gives the
+/// number of processes the proofs are generated by.
+#[derive(Default)]
+pub struct CrossLayerVerifier<'a, Cfg: GKREngine> {
+    pub mpi_config: MPIConfig<'a>,
+    phantom: PhantomData<Cfg>,
+}
+
+impl<'a, Cfg: GKREngine> CrossLayerVerifier<'a, Cfg> {
+    pub fn new(mpi_config: MPIConfig<'a>) -> Self {
+        Self {
+            mpi_config,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Verify a proof of `CrossLayerProver::prove` for the public inputs of all the processes, in
+    /// rank order, and the claimed output.
+    ///
+    /// Malformed proofs are rejected, see `try_verify` for the reason.
+    pub fn verify(
+        &self,
+        circuit: &CrossLayerCircuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> bool {
+        let timer = Timer::new("cross-layer verify", true);
+
+        let verified = matches!(
+            self.try_verify(
+                circuit,
+                public_input,
+                claimed_v,
+                pcs_params,
+                pcs_verification_key,
+                proof,
+            ),
+            Ok(true)
+        );
+
+        timer.stop();
+        verified
+    }
+
+    /// Same as [`Self::verify`], but reports why a proof is rejected.
+    ///
+    /// A proof that is truncated, has trailing bytes, or contains an item that does not
+    /// deserialize is rejected with the corresponding [`VerifyError`], as is a proof whose GKR
+    /// or PCS checks fail, see [`VerifyError::is_rejection`]. Returns
+    /// `VerifyError::UnsupportedWorldSize` if the number of processes is not a power of two, and
+    /// `VerifyError::PublicInputSize` if the public inputs of a process are fewer than the circuit
+    /// reads.
+    pub fn try_verify(
+        &self,
+        circuit: &CrossLayerCircuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> Result<bool, VerifyError> {
+        const { assert!(matches!(Cfg::SCHEME, GKRScheme::CrossLayer)) };
+        let proving_time_mpi_size = self.mpi_config.world_size();
+        if !proving_time_mpi_size.is_power_of_two() {
+            return Err(VerifyError::UnsupportedWorldSize(proving_time_mpi_size));
+        }
+        if public_input.len() % proving_time_mpi_size != 0
+            || public_input.len() / proving_time_mpi_size < circuit.public_input_size()
+        {
+            return Err(VerifyError::PublicInputSize(public_input.len()));
+        }
+
+        let connections = CrossLayerConnections::parse_circuit(circuit);
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut cursor = Cursor::new(&proof.bytes);
+
+        let commitment =
+            <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Commitment as ExpSerde>::deserialize_from(
+                &mut cursor,
+            )
+            .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedCommitment))?;
+        let mut buffer = vec![];
+        commitment
+            .serialize_into(&mut buffer)
+            .map_err(|_| VerifyError::MalformedCommitment)?;
+        transcript.append_commitment(&buffer);
+        absorb_public_input::<Cfg::FieldConfig, _>(public_input, &mut transcript);
+        transcript_verifier_sync(&mut transcript, proving_time_mpi_size);
+
+        let (challenge, input_claim) = verify_gkr(
+            proving_time_mpi_size,
+            circuit,
+            public_input,
+            claimed_v,
+            &connections,
+            &mut transcript,
+            &mut cursor,
+        )?;
+        transcript_verifier_sync(&mut transcript, proving_time_mpi_size);
+
+        let opening =
+            <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Opening::deserialize_from(
+                &mut cursor,
+            )
+            .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedOpening))?;
+        let remaining = proof.bytes.len() - cursor.position() as usize;
+        if remaining != 0 {
+            return Err(VerifyError::TrailingBytes(remaining));
+        }
+
+        transcript.lock_proof();
+        let verified = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::verify(
+            pcs_params,
+            pcs_verification_key,
+            &commitment,
+            &challenge,
+            input_claim,
+            &mut transcript,
+            &opening,
+        );
+        transcript.unlock_proof();
+        if !verified {
+            return Err(VerifyError::PcsOpeningFailed);
+        }
+
+        Ok(true)
+    }
+}
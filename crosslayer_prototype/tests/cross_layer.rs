@@ -1,10 +1,22 @@
+use std::io::Cursor;
+
 use arith::Field;
-use crosslayer_prototype::{prove_gkr, CrossLayerCircuit, CrossLayerConnections};
-use gkr_engine::{BN254Config, FieldEngine, GF2ExtConfig, M31x16Config, Transcript};
+use crosslayer_prototype::{
+    prove_gkr, verify_gkr, BN254ConfigSha2RawCrossLayer, CoefType, CrossLayerCircuit,
+    CrossLayerConnections, CrossLayerProver, CrossLayerVerifier, GF2ExtConfigSha2RawCrossLayer,
+    M31x16ConfigSha2RawCrossLayer, SimpleGateCst,
+};
+use gkr_engine::{
+    BN254Config, FieldEngine, GF2ExtConfig, GKREngine, M31x16Config, MPIConfig, MPIEngine,
+    ThreadCommunicator, Transcript, VerifyError,
+};
 use gkr_hashers::SHA256hasher;
+use poly_commit::expander_pcs_init_testing_only;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use transcript::BytesHashTranscript;
 
 fn test_sumcheck_cross_layered_helper<F: FieldEngine>() {
+    let mpi_config = MPIConfig::prover_new(None, None);
     let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
 
     let mut rng = rand::thread_rng();
@@ -16,15 +28,45 @@ fn test_sumcheck_cross_layered_helper<F: FieldEngine>() {
         .collect::<Vec<_>>();
     let evals = circuit.evaluate(&inputs);
     let connections = CrossLayerConnections::parse_circuit(&circuit);
-    let mut sp = crosslayer_prototype::CrossLayerProverScratchPad::<F>::new(
-        circuit.layers.len(),
-        circuit.max_num_input_var(),
-        circuit.max_num_output_var(),
-        1,
+
+    let (output_claim, input_challenge, input_claim) =
+        prove_gkr(&circuit, &evals, &connections, &mut transcript, &mpi_config);
+    assert_eq!(
+        input_claim,
+        F::single_core_eval_circuit_vals_at_expander_challenge(&inputs, &input_challenge)
     );
 
-    let (_output_claim, _input_challenge, _input_claim) =
-        prove_gkr(&circuit, &evals, &connections, &mut transcript, &mut sp);
+    let proof = transcript.finalize_and_get_proof();
+    let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+    let (verifier_challenge, verifier_claim) = verify_gkr(
+        1,
+        &circuit,
+        &circuit.public_input,
+        &output_claim,
+        &connections,
+        &mut transcript,
+        Cursor::new(&proof.bytes),
+    )
+    .unwrap();
+    assert_eq!(verifier_challenge.local_xs(), input_challenge.local_xs());
+    assert_eq!(verifier_claim, input_claim);
+
+    let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+    assert_eq!(
+        verify_gkr(
+            1,
+            &circuit,
+            &circuit.public_input,
+            &(output_claim + F::ChallengeField::ONE),
+            &connections,
+            &mut transcript,
+            Cursor::new(&proof.bytes),
+        )
+        .err(),
+        Some(VerifyError::ClaimMismatch {
+            layer: n_layers - 1
+        })
+    );
 }
 
 #[test]
@@ -33,3 +75,223 @@ fn test_sumcheck_cross_layered() {
     test_sumcheck_cross_layered_helper::<GF2ExtConfig>();
     test_sumcheck_cross_layered_helper::<BN254Config>();
 }
+
+/// A random circuit with a constant gate reading a random public input
+fn snark_test_circuit<F: FieldEngine>(
+    circuit_rng: impl RngCore,
+    mut public_input_rng: impl RngCore,
+) -> CrossLayerCircuit<F> {
+    let mut circuit = CrossLayerCircuit::<F>::random_for_testing(circuit_rng, 6);
+    circuit.layers[1].const_gates.push(SimpleGateCst {
+        i_ids: [],
+        o_id: 0,
+        coef_type: CoefType::PublicInput(0),
+        coef: F::CircuitField::ZERO,
+    });
+    circuit.public_input = vec![F::SimdCircuitField::random_unsafe(&mut public_input_rng)];
+    circuit
+}
+
+fn test_cross_layer_snark_helper<Cfg: GKREngine>()
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    let mut rng = rand::thread_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let circuit = snark_test_circuit::<Cfg::FieldConfig>(&mut rng, rand::thread_rng());
+
+    let inputs = (0..circuit.layers[0].layer_size)
+        .map(|_| <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+            circuit.layers[0].layer_size.trailing_zeros() as usize,
+            &mpi_config,
+        );
+
+    let mut prover = CrossLayerProver::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove(
+        &circuit,
+        &inputs,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let verifier = CrossLayerVerifier::<Cfg>::new(mpi_config);
+    let try_verify = |circuit: &CrossLayerCircuit<_>, public_input: &[_], claimed_v, proof| {
+        verifier.try_verify(
+            circuit,
+            public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+    let verify = |circuit: &CrossLayerCircuit<_>, claimed_v, proof| {
+        verifier.verify(
+            circuit,
+            &circuit.public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+    assert!(verify(&circuit, &claimed_v, &proof));
+    assert!(!verify(
+        &circuit,
+        &(claimed_v + <Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE),
+        &proof
+    ));
+
+    // the transcript is bound to the public inputs
+    let other_public_input =
+        vec![circuit.public_input[0] + <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ONE];
+    assert!(matches!(
+        try_verify(&circuit, &other_public_input, &claimed_v, &proof),
+        Err(VerifyError::ClaimMismatch { .. })
+    ));
+    assert_eq!(
+        try_verify(&circuit, &[], &claimed_v, &proof),
+        Err(VerifyError::PublicInputSize(0))
+    );
+
+    let mut other_circuit = circuit.clone();
+    let gate = other_circuit
+        .layers
+        .iter_mut()
+        .flat_map(|layer| layer.add_gates.iter_mut())
+        .next()
+        .unwrap();
+    gate.coef += <Cfg::FieldConfig as FieldEngine>::CircuitField::ONE;
+    assert!(!verify(&other_circuit, &claimed_v, &proof));
+
+    let mut truncated_proof = proof.clone();
+    truncated_proof.bytes.truncate(proof.bytes.len() / 2);
+    assert!(!verify(&circuit, &claimed_v, &truncated_proof));
+    assert_eq!(
+        try_verify(
+            &circuit,
+            &circuit.public_input,
+            &claimed_v,
+            &truncated_proof
+        ),
+        Err(VerifyError::TruncatedProof)
+    );
+
+    let mut extended_proof = proof.clone();
+    extended_proof.bytes.push(0);
+    assert_eq!(
+        try_verify(&circuit, &circuit.public_input, &claimed_v, &extended_proof),
+        Err(VerifyError::TrailingBytes(1))
+    );
+
+    let verifier = CrossLayerVerifier::<Cfg>::new(MPIConfig::verifier_new(3));
+    assert_eq!(
+        verifier.try_verify(
+            &circuit,
+            &circuit.public_input,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &proof,
+        ),
+        Err(VerifyError::UnsupportedWorldSize(3))
+    );
+}
+
+#[test]
+fn test_cross_layer_snark() {
+    test_cross_layer_snark_helper::<M31x16ConfigSha2RawCrossLayer>();
+    test_cross_layer_snark_helper::<GF2ExtConfigSha2RawCrossLayer>();
+    test_cross_layer_snark_helper::<BN254ConfigSha2RawCrossLayer>();
+}
+
+fn test_cross_layer_snark_mpi_helper<Cfg: GKREngine>(mpi_config: &MPIConfig)
+where
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    // the processes prove the same circuit, on their own inputs and public inputs
+    let mut rng = rand::thread_rng();
+    let circuit =
+        snark_test_circuit::<Cfg::FieldConfig>(StdRng::seed_from_u64(0), rand::thread_rng());
+
+    let inputs = (0..circuit.layers[0].layer_size)
+        .map(|_| <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+            circuit.layers[0].layer_size.trailing_zeros() as usize,
+            mpi_config,
+        );
+
+    let mut prover = CrossLayerProver::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove(
+        &circuit,
+        &inputs,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let mut public_input = if mpi_config.is_root() {
+        vec![
+            <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO;
+            circuit.public_input.len() * mpi_config.world_size()
+        ]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&circuit.public_input, &mut public_input);
+
+    if mpi_config.is_root() {
+        let verifier = CrossLayerVerifier::<Cfg>::new(mpi_config.clone());
+        let try_verify = |public_input: &[_], claimed_v| {
+            verifier.try_verify(
+                &circuit,
+                public_input,
+                claimed_v,
+                &pcs_params,
+                &pcs_verification_key,
+                &proof,
+            )
+        };
+        assert_eq!(try_verify(&public_input, &claimed_v), Ok(true));
+        assert!(matches!(
+            try_verify(
+                &public_input,
+                &(claimed_v + <Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE)
+            ),
+            Err(VerifyError::ClaimMismatch { .. })
+        ));
+
+        // the public inputs of the last process are bound to the proof as well
+        let mut other_public_input = public_input.clone();
+        *other_public_input.last_mut().unwrap() +=
+            <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ONE;
+        assert!(matches!(
+            try_verify(&other_public_input, &claimed_v),
+            Err(VerifyError::ClaimMismatch { .. })
+        ));
+        assert_eq!(
+            try_verify(&public_input[1..], &claimed_v),
+            Err(VerifyError::PublicInputSize(public_input.len() - 1))
+        );
+    }
+}
+
+/// The distributed code paths without `mpiexec`, with the processes simulated by threads
+#[test]
+fn test_cross_layer_snark_mpi_threads() {
+    for world_size in [2, 4] {
+        ThreadCommunicator::run_world(world_size, |mpi_config| {
+            test_cross_layer_snark_mpi_helper::<M31x16ConfigSha2RawCrossLayer>(&mpi_config);
+            test_cross_layer_snark_mpi_helper::<GF2ExtConfigSha2RawCrossLayer>(&mpi_config);
+            test_cross_layer_snark_mpi_helper::<BN254ConfigSha2RawCrossLayer>(&mpi_config);
+        });
+    }
+}
@@ -0,0 +1,212 @@
+#ifndef EXPANDER_H
+#define EXPANDER_H
+
+/* Generated by cbindgen from the expander_ffi crate, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status returned by every function of the C API.
+ */
+typedef enum ExpanderStatus {
+  EXPANDER_STATUS_OK = 0,
+  /**
+   * A required pointer argument is null
+   */
+  EXPANDER_STATUS_NULL_POINTER = 1,
+  /**
+   * A string argument is not a known name, or handles of different configs are mixed
+   */
+  EXPANDER_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * The field, Fiat-Shamir hash and PCS do not form a config served by `expander-exec`
+   */
+  EXPANDER_STATUS_UNSUPPORTED_CONFIG = 3,
+  EXPANDER_STATUS_MALFORMED_CIRCUIT = 4,
+  /**
+   * The witness does not deserialize or does not fit the circuit
+   */
+  EXPANDER_STATUS_MALFORMED_WITNESS = 5,
+  /**
+   * The PCS key does not deserialize or was generated for another PCS, field or size
+   */
+  EXPANDER_STATUS_PCS_SETUP_MISMATCH = 6,
+  /**
+   * No witness was loaded into the circuit before proving
+   */
+  EXPANDER_STATUS_NO_WITNESS = 7,
+  /**
+   * The output buffer is too small, the required size is written to the length argument
+   */
+  EXPANDER_STATUS_BUFFER_TOO_SMALL = 8,
+  /**
+   * The proof does not deserialize, or was generated with another config or circuit
+   */
+  EXPANDER_STATUS_PROOF_MISMATCH = 9,
+  /**
+   * A panic was caught, this is a bug
+   */
+  EXPANDER_STATUS_INTERNAL = 10,
+} ExpanderStatus;
+
+/**
+ * A circuit loaded for a config, along with its witness.
+ */
+typedef struct ExpanderCircuit ExpanderCircuit;
+
+/**
+ * A prover, bound to the config of the circuit it was created for.
+ */
+typedef struct ExpanderProver ExpanderProver;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns the message of the last error on the calling thread, or null if the last call
+ * succeeded. The message is valid until the next call on the same thread.
+ */
+const char *expander_last_error_message(void);
+
+/**
+ * Loads a circuit for the config given by the field of the circuit, the Fiat-Shamir hash, e.g.
+ * "SHA256", and the PCS, e.g. "Raw", as named by the `-f` and `-p` flags of `expander-exec`.
+ *
+ * # Safety
+ * `circuit` must point to `circuit_len` readable bytes, the names must be nul-terminated strings,
+ * and `out` must be a writable pointer. The circuit is freed with `expander_circuit_free`.
+ */
+ExpanderStatus expander_circuit_load(const uint8_t *circuit,
+                                     size_t circuit_len,
+                                     const char *fiat_shamir_hash,
+                                     const char *pcs,
+                                     ExpanderCircuit **out);
+
+/**
+ * Loads a witness into the circuit, replacing the previous one.
+ *
+ * # Safety
+ * `circuit` must be a circuit handle, and `witness` must point to `witness_len` readable bytes.
+ */
+ExpanderStatus expander_circuit_load_witness(ExpanderCircuit *circuit,
+                                             const uint8_t *witness,
+                                             size_t witness_len);
+
+/**
+ * Frees a circuit, null is ignored.
+ *
+ * # Safety
+ * `circuit` must be null or a circuit handle that is not used afterwards.
+ */
+void expander_circuit_free(ExpanderCircuit *circuit);
+
+/**
+ * Creates a prover for the config of the circuit, with the `pcs.pk` file written by
+ * `expander-exec setup` for a single process. `pcs_proving_key` may only be null for the `Raw`
+ * PCS, which has no setup, otherwise `EXPANDER_STATUS_NULL_POINTER` is returned.
+ *
+ * # Safety
+ * `circuit` must be a circuit handle, `pcs_proving_key` must be null or point to
+ * `pcs_proving_key_len` readable bytes, and `out` must be a writable pointer. The prover is freed
+ * with `expander_prover_free`.
+ */
+ExpanderStatus expander_prover_new(const ExpanderCircuit *circuit,
+                                   const uint8_t *pcs_proving_key,
+                                   size_t pcs_proving_key_len,
+                                   ExpanderProver **out);
+
+/**
+ * Creates a prover for the config of the circuit with the insecure testing setup, derived from a
+ * fixed seed. Proofs can be forged against this setup, it is only meant for testing.
+ *
+ * # Safety
+ * `circuit` must be a circuit handle, and `out` must be a writable pointer. The prover is freed
+ * with `expander_prover_free`.
+ */
+ExpanderStatus expander_prover_new_insecure_testing_only(const ExpanderCircuit *circuit,
+                                                         ExpanderProver **out);
+
+/**
+ * Proves the witness loaded into the circuit, and writes the proof, in the format of
+ * `expander-exec prove`, into `proof`. The size of the proof is written to `proof_len`.
+ *
+ * If the proof does not fit in `proof_capacity` bytes, returns `EXPANDER_STATUS_BUFFER_TOO_SMALL`,
+ * and the proof can be copied into a larger buffer with `expander_prover_last_proof`, without
+ * proving again.
+ *
+ * # Safety
+ * `prover` and `circuit` must be handles, `proof` must point to `proof_capacity` writable bytes,
+ * or be null if `proof_capacity` is 0, and `proof_len` must be a writable pointer.
+ */
+ExpanderStatus expander_prove(ExpanderProver *prover,
+                              ExpanderCircuit *circuit,
+                              uint8_t *proof,
+                              size_t proof_capacity,
+                              size_t *proof_len);
+
+/**
+ * Copies the last proof of the prover into `proof`, see `expander_prove`.
+ *
+ * # Safety
+ * Same as `expander_prove`.
+ */
+ExpanderStatus expander_prover_last_proof(const ExpanderProver *prover,
+                                          uint8_t *proof,
+                                          size_t proof_capacity,
+                                          size_t *proof_len);
+
+/**
+ * Frees a prover, null is ignored.
+ *
+ * # Safety
+ * `prover` must be null or a prover handle that is not used afterwards.
+ */
+void expander_prover_free(ExpanderProver *prover);
+
+/**
+ * Verifies a proof of the circuit, in the format of `expander-exec prove`, against the public
+ * inputs of `public_input`, a witness in the format of `witness.txt`. `pcs_verifying_key` is the
+ * `pcs.vk` file written by `expander-exec setup`. It may only be null for the `Raw` PCS, which
+ * has no setup, otherwise `EXPANDER_STATUS_NULL_POINTER` is returned.
+ *
+ * Writes whether the proof verifies to `valid`. Malformed proofs and proofs of another config or
+ * circuit are errors, proofs that do not pass the checks of the verifier are not.
+ *
+ * # Safety
+ * `circuit` must be a circuit handle, the byte arguments must point to as many readable bytes as
+ * their lengths, `pcs_verifying_key` may be null, and `valid` must be a writable pointer.
+ */
+ExpanderStatus expander_verify(const ExpanderCircuit *circuit,
+                               const uint8_t *public_input,
+                               size_t public_input_len,
+                               const uint8_t *proof,
+                               size_t proof_len,
+                               const uint8_t *pcs_verifying_key,
+                               size_t pcs_verifying_key_len,
+                               bool *valid);
+
+/**
+ * Same as `expander_verify`, with the insecure testing setup, derived from a fixed seed. Proofs
+ * can be forged against this setup, it is only meant for testing.
+ *
+ * # Safety
+ * `circuit` must be a circuit handle, the byte arguments must point to as many readable bytes as
+ * their lengths, and `valid` must be a writable pointer.
+ */
+ExpanderStatus expander_verify_insecure_testing_only(const ExpanderCircuit *circuit,
+                                                     const uint8_t *public_input,
+                                                     size_t public_input_len,
+                                                     const uint8_t *proof,
+                                                     size_t proof_len,
+                                                     bool *valid);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* EXPANDER_H */
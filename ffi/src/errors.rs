@@ -0,0 +1,134 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use gkr::ProofInputError;
+use thiserror::Error;
+
+/// Status returned by every function of the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpanderStatus {
+    Ok = 0,
+    /// A required pointer argument is null
+    NullPointer = 1,
+    /// A string argument is not a known name, or handles of different configs are mixed
+    InvalidArgument = 2,
+    /// The field, Fiat-Shamir hash and PCS do not form a config served by `expander-exec`
+    UnsupportedConfig = 3,
+    MalformedCircuit = 4,
+    /// The witness does not deserialize or does not fit the circuit
+    MalformedWitness = 5,
+    /// The PCS key does not deserialize or was generated for another PCS, field or size
+    PcsSetupMismatch = 6,
+    /// No witness was loaded into the circuit before proving
+    NoWitness = 7,
+    /// The output buffer is too small, the required size is written to the length argument
+    BufferTooSmall = 8,
+    /// The proof does not deserialize, or was generated with another config or circuit
+    ProofMismatch = 9,
+    /// A panic was caught, this is a bug
+    Internal = 10,
+}
+
+#[derive(Debug, Error)]
+pub enum FfiError {
+    #[error("`{0}` is a null pointer")]
+    NullPointer(&'static str),
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("unsupported config: {0}")]
+    UnsupportedConfig(String),
+
+    #[error("malformed circuit: {0}")]
+    MalformedCircuit(String),
+
+    #[error("malformed witness: {0}")]
+    MalformedWitness(String),
+
+    #[error("PCS setup mismatch: {0}")]
+    PcsSetupMismatch(String),
+
+    #[error("no witness loaded")]
+    NoWitness,
+
+    #[error("the buffer holds {capacity} bytes, {required} are needed")]
+    BufferTooSmall { capacity: usize, required: usize },
+
+    #[error("proof mismatch: {0}")]
+    ProofMismatch(String),
+
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl FfiError {
+    pub fn status(&self) -> ExpanderStatus {
+        match self {
+            Self::NullPointer(_) => ExpanderStatus::NullPointer,
+            Self::InvalidArgument(_) => ExpanderStatus::InvalidArgument,
+            Self::UnsupportedConfig(_) => ExpanderStatus::UnsupportedConfig,
+            Self::MalformedCircuit(_) => ExpanderStatus::MalformedCircuit,
+            Self::MalformedWitness(_) => ExpanderStatus::MalformedWitness,
+            Self::PcsSetupMismatch(_) => ExpanderStatus::PcsSetupMismatch,
+            Self::NoWitness => ExpanderStatus::NoWitness,
+            Self::BufferTooSmall { .. } => ExpanderStatus::BufferTooSmall,
+            Self::ProofMismatch(_) => ExpanderStatus::ProofMismatch,
+            Self::Internal(_) => ExpanderStatus::Internal,
+        }
+    }
+}
+
+impl From<ProofInputError> for FfiError {
+    fn from(e: ProofInputError) -> Self {
+        match e {
+            ProofInputError::UnsupportedConfig(msg) => Self::UnsupportedConfig(msg),
+            ProofInputError::MalformedCircuit(msg) => Self::MalformedCircuit(msg),
+            ProofInputError::InvalidWitness(e) => Self::MalformedWitness(e.to_string()),
+            ProofInputError::PcsSetupMismatch(msg) => Self::PcsSetupMismatch(msg),
+            ProofInputError::ProofMismatch(msg) => Self::ProofMismatch(msg),
+            ProofInputError::MalformedProof(e) => Self::ProofMismatch(e.to_string()),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The message of the last error on the calling thread, or null if the last call succeeded.
+pub(crate) fn last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Run the body of a C API function, turning errors and panics into a status, and recording the
+/// error message for `expander_last_error_message`.
+pub(crate) fn ffi_call(f: impl FnOnce() -> Result<(), FfiError>) -> ExpanderStatus {
+    let result = panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(FfiError::Internal(panic_message(payload))));
+
+    let (status, message) = match result {
+        Ok(()) => (ExpanderStatus::Ok, None),
+        Err(e) => (
+            e.status(),
+            // a C string cannot hold nul bytes, which may come from a panic payload
+            Some(CString::new(e.to_string().replace('\0', " ")).unwrap()),
+        ),
+    };
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+    status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
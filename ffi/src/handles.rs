@@ -0,0 +1,217 @@
+use std::any::Any;
+
+use circuit::Circuit;
+use gkr::{
+    detect_field_type, dispatch_gkr_config, load_circuit_bytes, open_proof_envelope,
+    prover_pcs_setup, verifier_pcs_setup, verifier_pcs_setup_insecure_testing_only,
+    verify_proof_envelope, GKRConfigVisitor, Prover,
+};
+use gkr_engine::{
+    ExpanderPCS, FiatShamirHashType, FieldEngine, GKREngine, MPIConfig, PCSParamsOf,
+    PCSProvingKeyOf, PCSScratchPadOf, PolynomialCommitmentType, ProofEnvelope, ProofHeader,
+};
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+
+use crate::FfiError;
+
+/// A circuit loaded for a config, with the witness last loaded into it, if any.
+pub(crate) trait CircuitOps {
+    fn pcs_type(&self) -> PolynomialCommitmentType;
+
+    fn load_witness(&mut self, witness: &[u8]) -> Result<(), FfiError>;
+
+    /// A prover for the config of the circuit, with the given proving key or, if `None`, the
+    /// insecure testing setup.
+    fn new_prover(&self, pcs_proving_key: Option<&[u8]>) -> Result<Box<dyn ProverOps>, FfiError>;
+
+    /// Verify a proof of the circuit on the public inputs of `public_input`, a serialized witness,
+    /// with the given verifying key or, if `None`, the insecure testing setup.
+    fn verify(
+        &self,
+        public_input: &[u8],
+        proof: &[u8],
+        pcs_verifying_key: Option<&[u8]>,
+    ) -> Result<bool, FfiError>;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub(crate) trait ProverOps {
+    /// Prove the witness loaded into `circuit`, returning the proof envelope.
+    fn prove(&mut self, circuit: &mut dyn CircuitOps) -> Result<Vec<u8>, FfiError>;
+}
+
+/// Load a circuit for the config picked by the field of the circuit, the Fiat-Shamir hash and
+/// the PCS.
+pub(crate) fn load_circuit(
+    circuit: &[u8],
+    fiat_shamir_hash_type: FiatShamirHashType,
+    pcs_type: PolynomialCommitmentType,
+) -> Result<Box<dyn CircuitOps>, FfiError> {
+    let field_type = detect_field_type(circuit).ok_or_else(|| {
+        FfiError::MalformedCircuit("truncated header or unknown field".to_string())
+    })?;
+
+    dispatch_gkr_config(
+        fiat_shamir_hash_type.clone(),
+        pcs_type.clone(),
+        field_type.clone(),
+        LoadCircuit { circuit },
+    )
+    .unwrap_or_else(|| {
+        Err(FfiError::UnsupportedConfig(format!(
+            "FS: {fiat_shamir_hash_type:?}, PCS: {pcs_type:?}, Field: {field_type:?}"
+        )))
+    })
+}
+
+struct LoadCircuit<'a> {
+    circuit: &'a [u8],
+}
+
+impl GKRConfigVisitor for LoadCircuit<'_> {
+    type Output = Result<Box<dyn CircuitOps>, FfiError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let circuit = load_circuit_bytes::<Cfg>(self.circuit)?;
+
+        let mut circuit_hash = [0u8; 32];
+        SHA256hasher::new().hash(&mut circuit_hash, self.circuit);
+
+        Ok(Box::new(ConfigCircuit::<Cfg> {
+            circuit,
+            circuit_hash,
+            has_witness: false,
+        }))
+    }
+}
+
+struct ConfigCircuit<Cfg: GKREngine> {
+    circuit: Circuit<Cfg::FieldConfig>,
+    circuit_hash: [u8; 32],
+    has_witness: bool,
+}
+
+impl<Cfg> CircuitOps for ConfigCircuit<Cfg>
+where
+    Cfg: GKREngine + 'static,
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    fn pcs_type(&self) -> PolynomialCommitmentType {
+        <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::PCS_TYPE
+    }
+
+    fn load_witness(&mut self, witness: &[u8]) -> Result<(), FfiError> {
+        self.circuit
+            .try_load_witness_bytes(witness, &MPIConfig::default(), true, false)
+            .map_err(|e| FfiError::MalformedWitness(e.to_string()))?;
+        self.has_witness = true;
+        Ok(())
+    }
+
+    fn new_prover(&self, pcs_proving_key: Option<&[u8]>) -> Result<Box<dyn ProverOps>, FfiError> {
+        let n_input_vars = self.circuit.log_input_size();
+        let mpi_config = MPIConfig::default();
+
+        let (pcs_params, pcs_proving_key, pcs_scratch) = match pcs_proving_key {
+            Some(bytes) => prover_pcs_setup::<Cfg>(n_input_vars, &mpi_config, bytes)?,
+            None => {
+                let (pcs_params, pcs_proving_key, _, pcs_scratch) = expander_pcs_init_testing_only::<
+                    Cfg::FieldConfig,
+                    Cfg::PCSField,
+                    Cfg::PCSConfig,
+                >(
+                    n_input_vars, &mpi_config
+                );
+                (pcs_params, pcs_proving_key, pcs_scratch)
+            }
+        };
+
+        let mut prover = Prover::<Cfg>::new(mpi_config);
+        prover.prepare_mem(&self.circuit);
+
+        Ok(Box::new(ConfigProver::<Cfg> {
+            prover,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        }))
+    }
+
+    fn verify(
+        &self,
+        public_input: &[u8],
+        proof: &[u8],
+        pcs_verifying_key: Option<&[u8]>,
+    ) -> Result<bool, FfiError> {
+        let envelope = open_proof_envelope::<Cfg>(proof, &self.circuit_hash)?;
+        let n_input_vars = self.circuit.log_input_size();
+        let world_size = envelope.header.proving_time_mpi_size;
+        let (pcs_params, pcs_verification_key) = match pcs_verifying_key {
+            Some(bytes) => verifier_pcs_setup::<Cfg>(n_input_vars, world_size, bytes)?,
+            None => verifier_pcs_setup_insecure_testing_only::<Cfg>(n_input_vars, world_size),
+        };
+        Ok(verify_proof_envelope::<Cfg>(
+            &self.circuit,
+            envelope,
+            public_input,
+            &pcs_params,
+            &pcs_verification_key,
+        )?)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+struct ConfigProver<Cfg: GKREngine + 'static> {
+    prover: Prover<'static, Cfg>,
+    pcs_params: PCSParamsOf<Cfg>,
+    pcs_proving_key: PCSProvingKeyOf<Cfg>,
+    pcs_scratch: PCSScratchPadOf<Cfg>,
+}
+
+impl<Cfg> ProverOps for ConfigProver<Cfg>
+where
+    Cfg: GKREngine + 'static,
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    fn prove(&mut self, circuit: &mut dyn CircuitOps) -> Result<Vec<u8>, FfiError> {
+        let circuit = circuit
+            .as_any_mut()
+            .downcast_mut::<ConfigCircuit<Cfg>>()
+            .ok_or_else(|| {
+                FfiError::InvalidArgument(
+                    "the circuit was loaded with another config than the prover".to_string(),
+                )
+            })?;
+        if !circuit.has_witness {
+            return Err(FfiError::NoWitness);
+        }
+
+        let (claimed_v, proof) = self.prover.prove(
+            &mut circuit.circuit,
+            &self.pcs_params,
+            &self.pcs_proving_key,
+            &mut self.pcs_scratch,
+        );
+
+        let envelope = ProofEnvelope {
+            header: ProofHeader::new::<Cfg>(1, circuit.circuit_hash),
+            proof,
+            claimed_v,
+        };
+        let mut bytes = vec![];
+        envelope
+            .serialize_into(&mut bytes)
+            .map_err(|e| FfiError::Internal(e.to_string()))?;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,386 @@
+//! C API to prove and verify with Expander, for services that cannot link Rust directly.
+//!
+//! - [`expander_circuit_load`] loads a circuit, in the binary format of `circuit.txt`, for the
+//!   config given by the field of the circuit, the Fiat-Shamir hash and the PCS, among the configs
+//!   served by `expander-exec`.
+//! - [`expander_circuit_load_witness`] loads a witness, in the format of `witness.txt`.
+//! - [`expander_prover_new`] creates a prover for the config of a circuit, with a PCS proving key
+//!   written by `expander-exec setup`, and [`expander_prove`] proves into a caller-provided buffer.
+//! - [`expander_verify`] verifies a proof against the public inputs of a witness, with the PCS
+//!   verifying key written by `expander-exec setup`.
+//!
+//! The PCS keys may only be null for the `Raw` PCS, which has no setup. Tests can use
+//! [`expander_prover_new_insecure_testing_only`] and [`expander_verify_insecure_testing_only`]
+//! instead, with a setup derived from a fixed seed, against which proofs can be forged.
+//!
+//! Circuits and provers are opaque handles, freed with [`expander_circuit_free`] and
+//! [`expander_prover_free`]. Every function returns an [`ExpanderStatus`] instead of panicking,
+//! with a message for [`expander_last_error_message`]. Proofs are in the format of
+//! `expander-exec prove`, proven by a single process.
+//!
+//! The C header is generated with cbindgen, see `cbindgen.toml`.
+
+mod errors;
+mod handles;
+
+use std::{
+    ffi::{c_char, CStr},
+    ptr, slice,
+    str::FromStr,
+};
+
+pub use errors::*;
+use gkr_engine::{FiatShamirHashType, PolynomialCommitmentType};
+use handles::{load_circuit, CircuitOps, ProverOps};
+
+/// A circuit loaded for a config, along with its witness.
+pub struct ExpanderCircuit {
+    inner: Box<dyn CircuitOps>,
+}
+
+/// A prover, bound to the config of the circuit it was created for.
+pub struct ExpanderProver {
+    inner: Box<dyn ProverOps>,
+    /// The last proof, kept so that it can be copied again into a larger buffer
+    last_proof: Vec<u8>,
+}
+
+/// Returns the message of the last error on the calling thread, or null if the last call
+/// succeeded. The message is valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn expander_last_error_message() -> *const c_char {
+    last_error_message()
+}
+
+/// Loads a circuit for the config given by the field of the circuit, the Fiat-Shamir hash, e.g.
+/// "SHA256", and the PCS, e.g. "Raw", as named by the `-f` and `-p` flags of `expander-exec`.
+///
+/// # Safety
+/// `circuit` must point to `circuit_len` readable bytes, the names must be nul-terminated strings,
+/// and `out` must be a writable pointer. The circuit is freed with `expander_circuit_free`.
+#[no_mangle]
+pub unsafe extern "C" fn expander_circuit_load(
+    circuit: *const u8,
+    circuit_len: usize,
+    fiat_shamir_hash: *const c_char,
+    pcs: *const c_char,
+    out: *mut *mut ExpanderCircuit,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = bytes_arg(circuit, circuit_len, "circuit")?;
+        let fiat_shamir_hash_type =
+            FiatShamirHashType::from_str(str_arg(fiat_shamir_hash, "fiat_shamir_hash")?)
+                .map_err(|e| FfiError::InvalidArgument(e.to_string()))?;
+        let pcs_type = PolynomialCommitmentType::from_str(str_arg(pcs, "pcs")?)
+            .map_err(|e| FfiError::InvalidArgument(e.to_string()))?;
+        let out = mut_arg(out, "out")?;
+
+        let inner = load_circuit(circuit, fiat_shamir_hash_type, pcs_type)?;
+        *out = Box::into_raw(Box::new(ExpanderCircuit { inner }));
+        Ok(())
+    })
+}
+
+/// Loads a witness into the circuit, replacing the previous one.
+///
+/// # Safety
+/// `circuit` must be a circuit handle, and `witness` must point to `witness_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn expander_circuit_load_witness(
+    circuit: *mut ExpanderCircuit,
+    witness: *const u8,
+    witness_len: usize,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = mut_arg(circuit, "circuit")?;
+        let witness = bytes_arg(witness, witness_len, "witness")?;
+        circuit.inner.load_witness(witness)
+    })
+}
+
+/// Frees a circuit, null is ignored.
+///
+/// # Safety
+/// `circuit` must be null or a circuit handle that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn expander_circuit_free(circuit: *mut ExpanderCircuit) {
+    if !circuit.is_null() {
+        drop(Box::from_raw(circuit));
+    }
+}
+
+/// Creates a prover for the config of the circuit, with the `pcs.pk` file written by
+/// `expander-exec setup` for a single process. `pcs_proving_key` may only be null for the `Raw`
+/// PCS, which has no setup, otherwise `EXPANDER_STATUS_NULL_POINTER` is returned.
+///
+/// # Safety
+/// `circuit` must be a circuit handle, `pcs_proving_key` must be null or point to
+/// `pcs_proving_key_len` readable bytes, and `out` must be a writable pointer. The prover is freed
+/// with `expander_prover_free`.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prover_new(
+    circuit: *const ExpanderCircuit,
+    pcs_proving_key: *const u8,
+    pcs_proving_key_len: usize,
+    out: *mut *mut ExpanderProver,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = circuit.as_ref().ok_or(FfiError::NullPointer("circuit"))?;
+        let pcs_proving_key = pcs_key_arg(
+            circuit,
+            pcs_proving_key,
+            pcs_proving_key_len,
+            "pcs_proving_key",
+        )?;
+        new_prover(circuit, pcs_proving_key, out)
+    })
+}
+
+/// Creates a prover for the config of the circuit with the insecure testing setup, derived from a
+/// fixed seed. Proofs can be forged against this setup, it is only meant for testing.
+///
+/// # Safety
+/// `circuit` must be a circuit handle, and `out` must be a writable pointer. The prover is freed
+/// with `expander_prover_free`.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prover_new_insecure_testing_only(
+    circuit: *const ExpanderCircuit,
+    out: *mut *mut ExpanderProver,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = circuit.as_ref().ok_or(FfiError::NullPointer("circuit"))?;
+        new_prover(circuit, None, out)
+    })
+}
+
+/// Proves the witness loaded into the circuit, and writes the proof, in the format of
+/// `expander-exec prove`, into `proof`. The size of the proof is written to `proof_len`.
+///
+/// If the proof does not fit in `proof_capacity` bytes, returns `EXPANDER_STATUS_BUFFER_TOO_SMALL`,
+/// and the proof can be copied into a larger buffer with `expander_prover_last_proof`, without
+/// proving again.
+///
+/// # Safety
+/// `prover` and `circuit` must be handles, `proof` must point to `proof_capacity` writable bytes,
+/// or be null if `proof_capacity` is 0, and `proof_len` must be a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prove(
+    prover: *mut ExpanderProver,
+    circuit: *mut ExpanderCircuit,
+    proof: *mut u8,
+    proof_capacity: usize,
+    proof_len: *mut usize,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let prover = mut_arg(prover, "prover")?;
+        let circuit = mut_arg(circuit, "circuit")?;
+        let proof_len = mut_arg(proof_len, "proof_len")?;
+
+        prover.last_proof = prover.inner.prove(circuit.inner.as_mut())?;
+        copy_proof(prover, proof, proof_capacity, proof_len)
+    })
+}
+
+/// Copies the last proof of the prover into `proof`, see `expander_prove`.
+///
+/// # Safety
+/// Same as `expander_prove`.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prover_last_proof(
+    prover: *const ExpanderProver,
+    proof: *mut u8,
+    proof_capacity: usize,
+    proof_len: *mut usize,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let prover = prover.as_ref().ok_or(FfiError::NullPointer("prover"))?;
+        let proof_len = mut_arg(proof_len, "proof_len")?;
+
+        copy_proof(prover, proof, proof_capacity, proof_len)
+    })
+}
+
+/// Frees a prover, null is ignored.
+///
+/// # Safety
+/// `prover` must be null or a prover handle that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prover_free(prover: *mut ExpanderProver) {
+    if !prover.is_null() {
+        drop(Box::from_raw(prover));
+    }
+}
+
+/// Verifies a proof of the circuit, in the format of `expander-exec prove`, against the public
+/// inputs of `public_input`, a witness in the format of `witness.txt`. `pcs_verifying_key` is the
+/// `pcs.vk` file written by `expander-exec setup`. It may only be null for the `Raw` PCS, which
+/// has no setup, otherwise `EXPANDER_STATUS_NULL_POINTER` is returned.
+///
+/// Writes whether the proof verifies to `valid`. Malformed proofs and proofs of another config or
+/// circuit are errors, proofs that do not pass the checks of the verifier are not.
+///
+/// # Safety
+/// `circuit` must be a circuit handle, the byte arguments must point to as many readable bytes as
+/// their lengths, `pcs_verifying_key` may be null, and `valid` must be a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn expander_verify(
+    circuit: *const ExpanderCircuit,
+    public_input: *const u8,
+    public_input_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+    pcs_verifying_key: *const u8,
+    pcs_verifying_key_len: usize,
+    valid: *mut bool,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = circuit.as_ref().ok_or(FfiError::NullPointer("circuit"))?;
+        let pcs_verifying_key = pcs_key_arg(
+            circuit,
+            pcs_verifying_key,
+            pcs_verifying_key_len,
+            "pcs_verifying_key",
+        )?;
+        verify(
+            circuit,
+            public_input,
+            public_input_len,
+            proof,
+            proof_len,
+            pcs_verifying_key,
+            valid,
+        )
+    })
+}
+
+/// Same as `expander_verify`, with the insecure testing setup, derived from a fixed seed. Proofs
+/// can be forged against this setup, it is only meant for testing.
+///
+/// # Safety
+/// `circuit` must be a circuit handle, the byte arguments must point to as many readable bytes as
+/// their lengths, and `valid` must be a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn expander_verify_insecure_testing_only(
+    circuit: *const ExpanderCircuit,
+    public_input: *const u8,
+    public_input_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+    valid: *mut bool,
+) -> ExpanderStatus {
+    ffi_call(|| {
+        let circuit = circuit.as_ref().ok_or(FfiError::NullPointer("circuit"))?;
+        verify(
+            circuit,
+            public_input,
+            public_input_len,
+            proof,
+            proof_len,
+            None,
+            valid,
+        )
+    })
+}
+
+unsafe fn new_prover(
+    circuit: &ExpanderCircuit,
+    pcs_proving_key: Option<&[u8]>,
+    out: *mut *mut ExpanderProver,
+) -> Result<(), FfiError> {
+    let out = mut_arg(out, "out")?;
+
+    let inner = circuit.inner.new_prover(pcs_proving_key)?;
+    *out = Box::into_raw(Box::new(ExpanderProver {
+        inner,
+        last_proof: vec![],
+    }));
+    Ok(())
+}
+
+unsafe fn verify(
+    circuit: &ExpanderCircuit,
+    public_input: *const u8,
+    public_input_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+    pcs_verifying_key: Option<&[u8]>,
+    valid: *mut bool,
+) -> Result<(), FfiError> {
+    let public_input = bytes_arg(public_input, public_input_len, "public_input")?;
+    let proof = bytes_arg(proof, proof_len, "proof")?;
+    let valid = mut_arg(valid, "valid")?;
+
+    *valid = circuit
+        .inner
+        .verify(public_input, proof, pcs_verifying_key)?;
+    Ok(())
+}
+
+unsafe fn copy_proof(
+    prover: &ExpanderProver,
+    proof: *mut u8,
+    proof_capacity: usize,
+    proof_len: &mut usize,
+) -> Result<(), FfiError> {
+    let required = prover.last_proof.len();
+    *proof_len = required;
+    if proof_capacity < required {
+        return Err(FfiError::BufferTooSmall {
+            capacity: proof_capacity,
+            required,
+        });
+    }
+    if required > 0 {
+        if proof.is_null() {
+            return Err(FfiError::NullPointer("proof"));
+        }
+        ptr::copy_nonoverlapping(prover.last_proof.as_ptr(), proof, required);
+    }
+    Ok(())
+}
+
+unsafe fn bytes_arg<'a>(
+    bytes: *const u8,
+    len: usize,
+    name: &'static str,
+) -> Result<&'a [u8], FfiError> {
+    if bytes.is_null() {
+        return if len == 0 {
+            Ok(&[])
+        } else {
+            Err(FfiError::NullPointer(name))
+        };
+    }
+    Ok(slice::from_raw_parts(bytes, len))
+}
+
+/// A PCS key, which may only be null for the `Raw` PCS: `None` then stands for its setup, which
+/// has no secret. For the other PCS, the testing setup is only used through the functions named
+/// `insecure_testing_only`.
+unsafe fn pcs_key_arg<'a>(
+    circuit: &ExpanderCircuit,
+    bytes: *const u8,
+    len: usize,
+    name: &'static str,
+) -> Result<Option<&'a [u8]>, FfiError> {
+    if !bytes.is_null() {
+        return Ok(Some(slice::from_raw_parts(bytes, len)));
+    }
+    if circuit.inner.pcs_type() != PolynomialCommitmentType::Raw {
+        return Err(FfiError::NullPointer(name));
+    }
+    Ok(None)
+}
+
+unsafe fn str_arg<'a>(s: *const c_char, name: &'static str) -> Result<&'a str, FfiError> {
+    if s.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| FfiError::InvalidArgument(format!("`{name}`: {e}")))
+}
+
+unsafe fn mut_arg<'a, T>(p: *mut T, name: &'static str) -> Result<&'a mut T, FfiError> {
+    p.as_mut().ok_or(FfiError::NullPointer(name))
+}
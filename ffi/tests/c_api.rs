@@ -0,0 +1,302 @@
+use std::{ffi::CStr, ptr};
+
+use arith::Field;
+use circuit::RecursiveCircuit;
+use expander_ffi::*;
+use gkr::M31x16ConfigKeccakRawVanilla;
+use gkr_engine::{FieldEngine, M31x16Config};
+use serdes::ExpSerde;
+
+type Cfg = M31x16ConfigKeccakRawVanilla<'static>;
+type F = <M31x16Config as FieldEngine>::CircuitField;
+
+/// Checks x0 * x1 + p0 == x2, with x3 + r * x2 as an unchecked output.
+const CIRCUIT_TEXT: &str = "\
+    recursive_circuit
+    modulus 0x7fffffff
+    num_public_inputs 1
+    num_outputs 2
+    expected_num_output_zeros 1
+    layers 0
+    segment 0
+    i_var_num 2
+    o_var_num 1
+    mul 0 1 -> 0 const 01000000
+    add 2 -> 0 const feffff7f
+    add 3 -> 1 const 01000000
+    add 2 -> 1 random
+    const -> 0 public 0
+    end
+";
+
+/// Serialized circuit and witness of the test circuit, as `expander-exec` reads them.
+fn circuit_and_witness() -> (Vec<u8>, Vec<u8>) {
+    let rc = RecursiveCircuit::<M31x16Config>::from_text(CIRCUIT_TEXT).unwrap();
+    let mut circuit_bytes = vec![];
+    rc.serialize_into(&mut circuit_bytes).unwrap();
+
+    let num_witnesses = M31x16Config::get_field_pack_size();
+    let private_inputs = (0..num_witnesses as u32)
+        .map(|i| vec![F::from(i), F::from(i + 1), F::from(i * (i + 1) + 1)])
+        .collect::<Vec<_>>();
+    let public_inputs = vec![vec![F::ONE]; num_witnesses];
+    let witness = rc
+        .solve_witness::<Cfg>(&private_inputs, &public_inputs)
+        .unwrap();
+    let mut witness_bytes = vec![];
+    witness.serialize_into(&mut witness_bytes).unwrap();
+
+    (circuit_bytes, witness_bytes)
+}
+
+fn last_error_message() -> String {
+    let message = expander_last_error_message();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_c_api_prove_and_verify() {
+    let (circuit_bytes, witness) = circuit_and_witness();
+
+    unsafe {
+        let mut circuit = ptr::null_mut();
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len(),
+                c"Keccak256".as_ptr(),
+                c"Raw".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::Ok
+        );
+        assert!(expander_last_error_message().is_null());
+
+        let mut prover = ptr::null_mut();
+        assert_eq!(
+            expander_prover_new(circuit, ptr::null(), 0, &mut prover),
+            ExpanderStatus::Ok
+        );
+
+        let mut proof_len = 0;
+        assert_eq!(
+            expander_prove(prover, circuit, ptr::null_mut(), 0, &mut proof_len),
+            ExpanderStatus::NoWitness
+        );
+        assert_eq!(last_error_message(), "no witness loaded");
+
+        assert_eq!(
+            expander_circuit_load_witness(circuit, witness.as_ptr(), witness.len()),
+            ExpanderStatus::Ok
+        );
+
+        // query the size of the proof, then copy it without proving again
+        assert_eq!(
+            expander_prove(prover, circuit, ptr::null_mut(), 0, &mut proof_len),
+            ExpanderStatus::BufferTooSmall
+        );
+        assert!(proof_len > 0);
+        let mut proof = vec![0u8; proof_len];
+        assert_eq!(
+            expander_prover_last_proof(prover, proof.as_mut_ptr(), proof.len(), &mut proof_len),
+            ExpanderStatus::Ok
+        );
+        assert_eq!(proof_len, proof.len());
+
+        let mut valid = false;
+        assert_eq!(
+            expander_verify(
+                circuit,
+                witness.as_ptr(),
+                witness.len(),
+                proof.as_ptr(),
+                proof.len(),
+                ptr::null(),
+                0,
+                &mut valid,
+            ),
+            ExpanderStatus::Ok
+        );
+        assert!(valid);
+
+        let mut tampered = proof.clone();
+        tampered[proof.len() / 2] ^= 1;
+        assert_eq!(
+            expander_verify(
+                circuit,
+                witness.as_ptr(),
+                witness.len(),
+                tampered.as_ptr(),
+                tampered.len(),
+                ptr::null(),
+                0,
+                &mut valid,
+            ),
+            ExpanderStatus::Ok
+        );
+        assert!(!valid);
+
+        // the Raw PCS has no setup, the testing setup is the same
+        assert_eq!(
+            expander_verify_insecure_testing_only(
+                circuit,
+                witness.as_ptr(),
+                witness.len(),
+                proof.as_ptr(),
+                proof.len(),
+                &mut valid,
+            ),
+            ExpanderStatus::Ok
+        );
+        assert!(valid);
+
+        assert_eq!(
+            expander_verify(
+                circuit,
+                witness.as_ptr(),
+                witness.len(),
+                proof.as_ptr(),
+                proof.len() / 2,
+                ptr::null(),
+                0,
+                &mut valid,
+            ),
+            ExpanderStatus::ProofMismatch
+        );
+
+        assert_eq!(
+            expander_circuit_load_witness(circuit, witness.as_ptr(), witness.len() / 2),
+            ExpanderStatus::MalformedWitness
+        );
+
+        expander_prover_free(prover);
+        expander_circuit_free(circuit);
+    }
+}
+
+#[test]
+fn test_c_api_errors() {
+    let (circuit_bytes, _) = circuit_and_witness();
+
+    unsafe {
+        let mut circuit = ptr::null_mut();
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len(),
+                c"SHA3".as_ptr(),
+                c"Raw".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::InvalidArgument
+        );
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len(),
+                c"MIMC5".as_ptr(),
+                c"KZG".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::UnsupportedConfig
+        );
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len() / 2,
+                c"Keccak256".as_ptr(),
+                c"Raw".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::MalformedCircuit
+        );
+
+        // circuits that do not flatten are rejected instead of panicking
+        let mut rc = RecursiveCircuit::<M31x16Config>::from_text(CIRCUIT_TEXT).unwrap();
+        rc.layers.push(1);
+        let mut unknown_segment_circuit = vec![];
+        rc.serialize_into(&mut unknown_segment_circuit).unwrap();
+        assert_eq!(
+            expander_circuit_load(
+                unknown_segment_circuit.as_ptr(),
+                unknown_segment_circuit.len(),
+                c"Keccak256".as_ptr(),
+                c"Raw".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::MalformedCircuit
+        );
+        assert_eq!(
+            last_error_message(),
+            "malformed circuit: segment 1 does not exist"
+        );
+
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len(),
+                c"Keccak256".as_ptr(),
+                c"Raw".as_ptr(),
+                ptr::null_mut(),
+            ),
+            ExpanderStatus::NullPointer
+        );
+        assert_eq!(last_error_message(), "`out` is a null pointer");
+        assert!(circuit.is_null());
+
+        expander_circuit_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_c_api_requires_pcs_keys() {
+    let (circuit_bytes, witness) = circuit_and_witness();
+
+    unsafe {
+        let mut circuit = ptr::null_mut();
+        assert_eq!(
+            expander_circuit_load(
+                circuit_bytes.as_ptr(),
+                circuit_bytes.len(),
+                c"SHA256".as_ptr(),
+                c"Orion".as_ptr(),
+                &mut circuit,
+            ),
+            ExpanderStatus::Ok
+        );
+
+        // only the Raw PCS may go without a setup, the testing one is never picked implicitly
+        let mut prover = ptr::null_mut();
+        assert_eq!(
+            expander_prover_new(circuit, ptr::null(), 0, &mut prover),
+            ExpanderStatus::NullPointer
+        );
+        assert_eq!(last_error_message(), "`pcs_proving_key` is a null pointer");
+        assert!(prover.is_null());
+
+        let mut valid = true;
+        assert_eq!(
+            expander_verify(
+                circuit,
+                witness.as_ptr(),
+                witness.len(),
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                &mut valid,
+            ),
+            ExpanderStatus::NullPointer
+        );
+        assert_eq!(
+            last_error_message(),
+            "`pcs_verifying_key` is a null pointer"
+        );
+
+        expander_circuit_free(circuit);
+    }
+}
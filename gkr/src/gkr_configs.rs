@@ -1,14 +1,21 @@
+use babybear::Poseidon2BabyBear16;
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
-    BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
-    Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
+    BN254Config, BabyBearx16Config, FiatShamirHashType, FieldEngine, FieldType, GF2ExtConfig,
+    GKREngine, GKRScheme, Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config,
+    MPIConfig, PolynomialCommitmentType,
 };
-use gkr_hashers::{MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
-use goldilocks::Goldilocksx8;
+use gkr_hashers::{
+    Keccak256hasher, MiMC5FiatShamirHasher, Poseidon2FiatShamirHasher, PoseidonFiatShamirHasher,
+    SHA256hasher,
+};
+use goldilocks::{Goldilocksx8, Poseidon2Goldilocks12};
 use halo2curves::bn256::{Bn256, G1Affine};
 use mersenne31::M31x16;
-use poly_commit::{raw::RawExpanderGKR, HyperKZGPCS, HyraxPCS, OrionPCSForGKR};
+use poly_commit::{
+    raw::RawExpanderGKR, BasefoldPCSForGKR, HidingHyraxPCS, HyperKZGPCS, HyraxPCS, OrionPCSForGKR,
+};
 use transcript::BytesHashTranscript;
 
 // ============== M31 ==============
@@ -62,6 +69,13 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::GkrSquare,
 );
+declare_gkr_config!(
+    pub M31x16ConfigKeccakRawVanilla,
+    FieldType::M31x16,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
 
 // ============== BN254 ==============
 declare_gkr_config!(
@@ -85,6 +99,13 @@ declare_gkr_config!(
     PolynomialCommitmentType::Hyrax,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub BN254ConfigSha2HidingHyraxZk,
+    FieldType::BN254,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::HidingHyrax,
+    GKRScheme::ZkVanilla,
+);
 declare_gkr_config!(
     pub BN254ConfigSha2KZG,
     FieldType::BN254,
@@ -99,6 +120,20 @@ declare_gkr_config!(
     PolynomialCommitmentType::KZG,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub BN254ConfigKeccakRaw,
+    FieldType::BN254,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub BN254ConfigKeccakKZG,
+    FieldType::BN254,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::KZG,
+    GKRScheme::Vanilla,
+);
 
 // ============== GF2 ==============
 declare_gkr_config!(
@@ -115,6 +150,27 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub GF2ExtConfigSha2OrionSquare,
+    FieldType::GF2Ext128,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Orion,
+    GKRScheme::GkrSquare,
+);
+declare_gkr_config!(
+    pub GF2ExtConfigSha2RawSquare,
+    FieldType::GF2Ext128,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::GkrSquare,
+);
+declare_gkr_config!(
+    pub GF2ExtConfigKeccakRaw,
+    FieldType::GF2Ext128,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
 
 // ============== Goldilocks ==============
 declare_gkr_config!(
@@ -141,6 +197,27 @@ declare_gkr_config!(
     PolynomialCommitmentType::Orion,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub Goldilocksx8ConfigSha2FRI,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::FRI,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub Goldilocksx8ConfigKeccakRaw,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub Goldilocksx8ConfigPoseidonRaw,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::Poseidon,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
 
 // ============== Babybear ==============
 declare_gkr_config!(
@@ -150,3 +227,103 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub BabyBearx16ConfigSha2FRI,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::FRI,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub BabyBearx16ConfigKeccakRaw,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub BabyBearx16ConfigPoseidonRaw,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::Poseidon,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+
+/// A computation generic over the GKR config, run by [`dispatch_gkr_config`] on a config picked at
+/// runtime, e.g. from a proof header
+pub trait GKRConfigVisitor {
+    type Output;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>;
+}
+
+/// Declares [`dispatch_gkr_config`], [`dispatch_gkr_config_name`] and [`GKR_CONFIG_NAMES`] from
+/// a single table of the configs served by `expander-exec`.
+macro_rules! served_gkr_configs {
+    ($(($hash:ident, $pcs:ident, $field:ident) => $cfg:ident),* $(,)?) => {
+        /// Names of the configs served by `expander-exec`, i.e., the ones of
+        /// [`dispatch_gkr_config`], so that every proof made with them can be verified from its
+        /// header.
+        pub const GKR_CONFIG_NAMES: &[&str] = &[$(stringify!($cfg)),*];
+
+        /// Runs `visitor` on the vanilla GKR config of the given Fiat-Shamir hash, PCS and field,
+        /// among the configs served by `expander-exec`. Returns `None` if there is no such config.
+        pub fn dispatch_gkr_config<V: GKRConfigVisitor>(
+            fiat_shamir_hash_type: FiatShamirHashType,
+            pcs_type: PolynomialCommitmentType,
+            field_type: FieldType,
+            visitor: V,
+        ) -> Option<V::Output> {
+            let output = match (fiat_shamir_hash_type, pcs_type, field_type) {
+                $(
+                    (
+                        FiatShamirHashType::$hash,
+                        PolynomialCommitmentType::$pcs,
+                        FieldType::$field,
+                    ) => visitor.visit::<$cfg<'static>>(),
+                )*
+                _ => return None,
+            };
+            Some(output)
+        }
+
+        /// Runs `visitor` on the config named `name`, one of [`GKR_CONFIG_NAMES`]. Returns `None`
+        /// if there is no such config.
+        pub fn dispatch_gkr_config_name<V: GKRConfigVisitor>(
+            name: &str,
+            visitor: V,
+        ) -> Option<V::Output> {
+            let output = match name {
+                $(stringify!($cfg) => visitor.visit::<$cfg<'static>>(),)*
+                _ => return None,
+            };
+            Some(output)
+        }
+    };
+}
+
+served_gkr_configs!(
+    (SHA256, Orion, M31x16) => M31x16ConfigSha2OrionVanilla,
+    (Poseidon, Raw, M31x16) => M31x16ConfigPoseidonRawVanilla,
+    (Keccak256, Raw, M31x16) => M31x16ConfigKeccakRawVanilla,
+    (MIMC5, Raw, BN254) => BN254ConfigMIMC5Raw,
+    (SHA256, Raw, BN254) => BN254ConfigSha2Raw,
+    (SHA256, Hyrax, BN254) => BN254ConfigSha2Hyrax,
+    (MIMC5, KZG, BN254) => BN254ConfigMIMC5KZG,
+    (Keccak256, Raw, BN254) => BN254ConfigKeccakRaw,
+    (Keccak256, KZG, BN254) => BN254ConfigKeccakKZG,
+    (SHA256, Orion, GF2Ext128) => GF2ExtConfigSha2Orion,
+    (SHA256, Raw, GF2Ext128) => GF2ExtConfigSha2Raw,
+    (Keccak256, Raw, GF2Ext128) => GF2ExtConfigKeccakRaw,
+    (SHA256, Orion, Goldilocksx8) => Goldilocksx8ConfigSha2Orion,
+    (SHA256, FRI, Goldilocksx8) => Goldilocksx8ConfigSha2FRI,
+    (Keccak256, Raw, Goldilocksx8) => Goldilocksx8ConfigKeccakRaw,
+    (Poseidon, Raw, Goldilocksx8) => Goldilocksx8ConfigPoseidonRaw,
+    (SHA256, Raw, BabyBearx16) => BabyBearx16ConfigSha2Raw,
+    (SHA256, FRI, BabyBearx16) => BabyBearx16ConfigSha2FRI,
+    (Keccak256, Raw, BabyBearx16) => BabyBearx16ConfigKeccakRaw,
+    (Poseidon, Raw, BabyBearx16) => BabyBearx16ConfigPoseidonRaw,
+);
@@ -0,0 +1,312 @@
+//! Typed view of the GKR proof bytes.
+//!
+//! The prover writes its proof as an opaque byte stream through the transcript, and the verifier
+//! re-derives the structure of that stream while it verifies. [`GkrProof`] parses the same stream
+//! into its components, so that a proof can be inspected field by field without running the
+//! verifier, and converts back into exactly the same bytes.
+
+use std::{fmt, io::Cursor};
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use gkr_engine::{ExpanderPCS, FieldEngine, GKREngine, GKRScheme, Proof, VerifyError};
+use serdes::{ExpSerde, SerdeResult};
+use sumcheck::{sumcheck_gkr_square_degree, SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SIMD_MPI_DEGREE};
+
+pub type PCSCommitmentOf<Cfg> = <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::Commitment;
+pub type PCSOpeningOf<Cfg> = <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::Opening;
+
+/// Sumcheck messages of a single layer.
+///
+/// Each round is the list of evaluations of the round polynomial at `0, 1, ..., degree`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GkrLayerProof<F: Field> {
+    pub x_rounds: Vec<Vec<F>>,
+    pub simd_rounds: Vec<Vec<F>>,
+    pub mpi_rounds: Vec<Vec<F>>,
+    pub vx_claim: F,
+    /// Phase two of the sumcheck, absent if the layer skips it or the scheme is GKR square
+    pub y_rounds: Option<Vec<Vec<F>>>,
+    pub vy_claim: Option<F>,
+}
+
+/// Structured GKR proof of config `Cfg`.
+///
+/// `layers` is indexed like `circuit.layers`, i.e., `layers[0]` is the input layer; in the proof
+/// bytes the layers appear in reverse order, starting from the output layer.
+///
+/// Grinding does not add anything to the proof: the grinding hash chain is computed with the
+/// proof locked and is recomputed by the verifier, hence there is no nonce to record here.
+pub struct GkrProof<Cfg: GKREngine> {
+    pub commitment: PCSCommitmentOf<Cfg>,
+    pub layers: Vec<GkrLayerProof<<Cfg::FieldConfig as FieldEngine>::ChallengeField>>,
+    /// PCS opening of the input layer at the x challenge
+    pub opening_x: PCSOpeningOf<Cfg>,
+    /// PCS opening of the input layer at the y challenge, present iff the input layer runs
+    /// phase two of the sumcheck
+    pub opening_y: Option<PCSOpeningOf<Cfg>>,
+}
+
+impl<Cfg: GKREngine> Clone for GkrProof<Cfg> {
+    fn clone(&self) -> Self {
+        Self {
+            commitment: self.commitment.clone(),
+            layers: self.layers.clone(),
+            opening_x: self.opening_x.clone(),
+            opening_y: self.opening_y.clone(),
+        }
+    }
+}
+
+impl<Cfg: GKREngine> fmt::Debug for GkrProof<Cfg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GkrProof")
+            .field("commitment", &self.commitment)
+            .field("layers", &self.layers)
+            .field("opening_x", &self.opening_x)
+            .field("opening_y", &self.opening_y)
+            .finish()
+    }
+}
+
+/// Degrees of the round polynomials of a layer in the (x/y, simd/mpi) variables.
+#[inline]
+pub(crate) fn round_degrees<Cfg: GKREngine>(
+    layer: &CircuitLayer<Cfg::FieldConfig>,
+) -> (usize, usize) {
+    const { assert_generic_scheme::<Cfg>() };
+    match Cfg::SCHEME {
+        GKRScheme::Vanilla => (SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SIMD_MPI_DEGREE),
+        GKRScheme::GkrSquare => {
+            let degree = sumcheck_gkr_square_degree(layer);
+            (degree, degree)
+        }
+        GKRScheme::ZkVanilla | GKRScheme::CrossLayer => unreachable!(),
+    }
+}
+
+/// Fails to compile when called in `Prover::prove`, `Verifier::verify` or `GkrProof::from_proof`
+/// for a config whose proofs have another layout, i.e., the zero-knowledge GKR of
+/// `Prover::prove_zk` and `Verifier::verify_zk`, and the cross-layer GKR of the
+/// `crosslayer_prototype` crate.
+pub(crate) const fn assert_generic_scheme<Cfg: GKREngine>() {
+    assert!(
+        !matches!(Cfg::SCHEME, GKRScheme::ZkVanilla),
+        "the zero-knowledge GKR is proven by Prover::prove_zk and verified by Verifier::verify_zk"
+    );
+    assert!(
+        !matches!(Cfg::SCHEME, GKRScheme::CrossLayer),
+        "the cross-layer GKR is proven by CrossLayerProver and verified by CrossLayerVerifier"
+    );
+}
+
+/// Deserialize the next item of the proof, reporting a decoding failure as `malformed`.
+fn read_item<T: ExpSerde>(
+    reader: &mut Cursor<&[u8]>,
+    malformed: VerifyError,
+) -> Result<T, VerifyError> {
+    T::deserialize_from(&mut *reader).map_err(|e| VerifyError::from_serde(e, malformed))
+}
+
+#[inline]
+fn read_field<F: Field>(reader: &mut Cursor<&[u8]>) -> Result<F, VerifyError> {
+    let offset = reader.position() as usize;
+    read_item(reader, VerifyError::MalformedFieldElement(offset))
+}
+
+fn read_rounds<F: Field>(
+    reader: &mut Cursor<&[u8]>,
+    n_rounds: usize,
+    degree: usize,
+) -> Result<Vec<Vec<F>>, VerifyError> {
+    (0..n_rounds)
+        .map(|_| (0..degree + 1).map(|_| read_field(reader)).collect())
+        .collect()
+}
+
+fn write_rounds<F: Field>(rounds: &[Vec<F>], bytes: &mut Vec<u8>) -> SerdeResult<()> {
+    rounds
+        .iter()
+        .flatten()
+        .try_for_each(|eval| eval.serialize_into(&mut *bytes))
+}
+
+fn to_hex(value: &impl ExpSerde) -> String {
+    let mut bytes = vec![];
+    value.serialize_into(&mut bytes).unwrap();
+    let digits: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{digits}")
+}
+
+fn rounds_to_json<F: Field>(rounds: &[Vec<F>]) -> String {
+    let rounds = rounds
+        .iter()
+        .map(|evals| {
+            let evals = evals
+                .iter()
+                .map(|e| format!("\"{}\"", to_hex(e)))
+                .collect::<Vec<_>>();
+            format!("[{}]", evals.join(", "))
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", rounds.join(", "))
+}
+
+fn fmt_rounds<F: Field>(f: &mut fmt::Formatter<'_>, name: &str, rounds: &[Vec<F>]) -> fmt::Result {
+    rounds.iter().enumerate().try_for_each(|(i, evals)| {
+        let evals = evals.iter().map(to_hex).collect::<Vec<_>>();
+        writeln!(f, "    {name} round {i}: [{}]", evals.join(", "))
+    })
+}
+
+fn option_to_json<T>(value: &Option<T>, to_json: impl Fn(&T) -> String) -> String {
+    value.as_ref().map_or("null".to_string(), to_json)
+}
+
+impl<Cfg: GKREngine> GkrProof<Cfg> {
+    /// Parse the proof of `circuit` generated by `proving_time_mpi_size` processes.
+    ///
+    /// Fails if the proof is truncated, has trailing bytes, or contains a field element, a
+    /// commitment or an opening that does not deserialize.
+    pub fn from_proof(
+        proof: &Proof,
+        circuit: &Circuit<Cfg::FieldConfig>,
+        proving_time_mpi_size: usize,
+    ) -> Result<Self, VerifyError> {
+        let mut reader = Cursor::new(proof.bytes.as_slice());
+        let n_simd_vars = Cfg::FieldConfig::get_field_pack_size().trailing_zeros() as usize;
+        let n_mpi_vars = proving_time_mpi_size.trailing_zeros() as usize;
+
+        let commitment = read_item(&mut reader, VerifyError::MalformedCommitment)?;
+
+        let mut layers = vec![GkrLayerProof::default(); circuit.layers.len()];
+        for (layer, layer_proof) in circuit.layers.iter().zip(layers.iter_mut()).rev() {
+            let n_vars = layer.input_var_num;
+            let (xy_degree, simd_mpi_degree) = round_degrees::<Cfg>(layer);
+            layer_proof.x_rounds = read_rounds(&mut reader, n_vars, xy_degree)?;
+            layer_proof.simd_rounds = read_rounds(&mut reader, n_simd_vars, simd_mpi_degree)?;
+            layer_proof.mpi_rounds = read_rounds(&mut reader, n_mpi_vars, simd_mpi_degree)?;
+            layer_proof.vx_claim = read_field(&mut reader)?;
+
+            if Cfg::SCHEME == GKRScheme::Vanilla && !layer.structure_info.skip_sumcheck_phase_two {
+                layer_proof.y_rounds = Some(read_rounds(&mut reader, n_vars, xy_degree)?);
+                layer_proof.vy_claim = Some(read_field(&mut reader)?);
+            }
+        }
+
+        let opening_x = read_item(&mut reader, VerifyError::MalformedOpening)?;
+        let opening_y = match layers.first() {
+            Some(input_layer) if input_layer.y_rounds.is_some() => {
+                Some(read_item(&mut reader, VerifyError::MalformedOpening)?)
+            }
+            _ => None,
+        };
+
+        let n_trailing_bytes = proof.bytes.len() - reader.position() as usize;
+        if n_trailing_bytes != 0 {
+            return Err(VerifyError::TrailingBytes(n_trailing_bytes));
+        }
+
+        Ok(Self {
+            commitment,
+            layers,
+            opening_x,
+            opening_y,
+        })
+    }
+
+    /// Serialize back into the proof bytes, in the order the prover writes them.
+    pub fn to_proof(&self) -> SerdeResult<Proof> {
+        let mut bytes = vec![];
+        self.commitment.serialize_into(&mut bytes)?;
+
+        for layer_proof in self.layers.iter().rev() {
+            write_rounds(&layer_proof.x_rounds, &mut bytes)?;
+            write_rounds(&layer_proof.simd_rounds, &mut bytes)?;
+            write_rounds(&layer_proof.mpi_rounds, &mut bytes)?;
+            layer_proof.vx_claim.serialize_into(&mut bytes)?;
+
+            if let Some(y_rounds) = &layer_proof.y_rounds {
+                write_rounds(y_rounds, &mut bytes)?;
+            }
+            if let Some(vy_claim) = &layer_proof.vy_claim {
+                vy_claim.serialize_into(&mut bytes)?;
+            }
+        }
+
+        self.opening_x.serialize_into(&mut bytes)?;
+        if let Some(opening_y) = &self.opening_y {
+            opening_y.serialize_into(&mut bytes)?;
+        }
+
+        Ok(Proof { bytes })
+    }
+
+    /// JSON dump of the proof. Field elements, the commitment and the openings are written as the
+    /// hex encoding of their serialized (little endian) bytes.
+    pub fn to_json(&self) -> String {
+        let layers = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer_proof)| {
+                format!(
+                    "{{\"layer\": {i}, \"x_rounds\": {}, \"simd_rounds\": {}, \"mpi_rounds\": {}, \
+                     \"vx_claim\": \"{}\", \"y_rounds\": {}, \"vy_claim\": {}}}",
+                    rounds_to_json(&layer_proof.x_rounds),
+                    rounds_to_json(&layer_proof.simd_rounds),
+                    rounds_to_json(&layer_proof.mpi_rounds),
+                    to_hex(&layer_proof.vx_claim),
+                    option_to_json(&layer_proof.y_rounds, |r| rounds_to_json(r)),
+                    option_to_json(&layer_proof.vy_claim, |v| format!("\"{}\"", to_hex(v))),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "{{\"scheme\": \"{:?}\", \"commitment\": \"{}\", \"layers\": [{}], \
+             \"opening_x\": \"{}\", \"opening_y\": {}}}",
+            Cfg::SCHEME,
+            to_hex(&self.commitment),
+            layers.join(", "),
+            to_hex(&self.opening_x),
+            option_to_json(&self.opening_y, |o| format!("\"{}\"", to_hex(o))),
+        )
+    }
+}
+
+impl<Cfg: GKREngine> fmt::Display for GkrProof<Cfg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "GKR proof ({:?}, {} layers)",
+            Cfg::SCHEME,
+            self.layers.len()
+        )?;
+        writeln!(f, "commitment: {}", to_hex(&self.commitment))?;
+        for (i, layer_proof) in self.layers.iter().enumerate().rev() {
+            writeln!(f, "layer {i}:")?;
+            fmt_rounds(f, "x", &layer_proof.x_rounds)?;
+            fmt_rounds(f, "simd", &layer_proof.simd_rounds)?;
+            fmt_rounds(f, "mpi", &layer_proof.mpi_rounds)?;
+            writeln!(f, "    vx claim: {}", to_hex(&layer_proof.vx_claim))?;
+            if let Some(y_rounds) = &layer_proof.y_rounds {
+                fmt_rounds(f, "y", y_rounds)?;
+            }
+            if let Some(vy_claim) = &layer_proof.vy_claim {
+                writeln!(f, "    vy claim: {}", to_hex(vy_claim))?;
+            }
+        }
+        writeln!(f, "opening x: {}", to_hex(&self.opening_x))?;
+        if let Some(opening_y) = &self.opening_y {
+            writeln!(f, "opening y: {}", to_hex(opening_y))?;
+        }
+        Ok(())
+    }
+}
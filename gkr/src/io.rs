@@ -0,0 +1,282 @@
+use circuit::{Circuit, FlattenError, RecursiveCircuit, WitnessLoadError};
+use gkr_engine::{
+    load_pcs_setup_bytes, BN254Config, BabyBearx16Config, ExpErrors, ExpanderPCS, FieldEngine,
+    FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config, M31x16Config, MPIConfig, MPIEngine,
+    PCSParamsOf, PCSProvingKeyOf, PCSScratchPadOf, PCSSetupFileKind, PCSSetupHeader,
+    PCSVerifyingKeyOf, ProofEnvelope, ProofHeader, VerifyError,
+};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+use thiserror::Error;
+
+use crate::{dispatch_gkr_config, GKRConfigVisitor, Verifier};
+
+/// The field of a circuit given by the sentinel of its header, see [`detect_field_type`]
+pub fn field_type_from_sentinel(sentinel: &[u8; 32]) -> Option<FieldType> {
+    match *sentinel {
+        M31x16Config::SENTINEL => Some(FieldType::M31x16),
+        BN254Config::SENTINEL => Some(FieldType::BN254),
+        GF2ExtConfig::SENTINEL => Some(FieldType::GF2Ext128),
+        Goldilocksx8Config::SENTINEL => Some(FieldType::Goldilocksx8),
+        BabyBearx16Config::SENTINEL => Some(FieldType::BabyBearx16),
+        _ => None,
+    }
+}
+
+/// The field of a serialized `RecursiveCircuit`, i.e., of a `circuit.txt` file, read from the
+/// sentinel of the field that follows its 8-byte version. Returns `None` if the circuit is
+/// truncated or the field is not one of the configs of [`dispatch_gkr_config`].
+pub fn detect_field_type(circuit: &[u8]) -> Option<FieldType> {
+    field_type_from_sentinel(circuit.get(8..8 + 32)?.try_into().unwrap())
+}
+
+/// Serialized circuits, witnesses, PCS keys and proofs rejected by [`load_circuit_bytes`] and
+/// the proof envelope verification
+#[derive(Debug, Error)]
+pub enum ProofInputError {
+    #[error("unsupported config: {0}")]
+    UnsupportedConfig(String),
+
+    #[error("malformed circuit: {0}")]
+    MalformedCircuit(String),
+
+    #[error("invalid witness: {0}")]
+    InvalidWitness(#[from] WitnessLoadError),
+
+    /// The PCS key does not deserialize or was generated for another PCS, field or size
+    #[error("PCS setup mismatch: {0}")]
+    PcsSetupMismatch(String),
+
+    /// The proof envelope does not deserialize, or was generated with another config or circuit
+    #[error("proof mismatch: {0}")]
+    ProofMismatch(String),
+
+    /// The GKR proof in the envelope does not deserialize
+    #[error("malformed proof: {0}")]
+    MalformedProof(#[from] VerifyError),
+}
+
+impl From<FlattenError> for ProofInputError {
+    fn from(e: FlattenError) -> Self {
+        Self::MalformedCircuit(e.to_string())
+    }
+}
+
+/// Deserialize a `RecursiveCircuit`, i.e., a `circuit.txt` file, and flatten it for `Cfg`, ready
+/// for GKR. Circuits whose segments do not fit together are rejected, see
+/// `RecursiveCircuit::validate`.
+pub fn load_circuit_bytes<Cfg: GKREngine>(
+    circuit: &[u8],
+) -> Result<Circuit<Cfg::FieldConfig>, ProofInputError> {
+    let rc = RecursiveCircuit::<Cfg::FieldConfig>::deserialize_from(circuit)
+        .map_err(|e| ProofInputError::MalformedCircuit(e.to_string()))?;
+    let mut circuit = rc.try_flatten::<Cfg>()?;
+    circuit.pre_process_gkr::<Cfg>();
+    Ok(circuit)
+}
+
+/// Deserialize a proof envelope, as written by `expander-exec prove`, and check that its header
+/// is the one of a proof of the circuit hashed to `circuit_hash` with config `Cfg`.
+pub fn open_proof_envelope<Cfg: GKREngine>(
+    proof: &[u8],
+    circuit_hash: &[u8; 32],
+) -> Result<ProofEnvelope<<Cfg::FieldConfig as FieldEngine>::ChallengeField>, ProofInputError> {
+    let envelope =
+        ProofEnvelope::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>::deserialize_from(proof)
+            .map_err(|e| ProofInputError::ProofMismatch(e.to_string()))?;
+
+    let world_size = envelope.header.proving_time_mpi_size;
+    if !world_size.is_power_of_two() {
+        return Err(ProofInputError::ProofMismatch(format!(
+            "proving time MPI size {world_size} is not a power of two"
+        )));
+    }
+    envelope
+        .header
+        .check::<Cfg>(world_size, circuit_hash)
+        .map_err(|e| match e {
+            ExpErrors::ProofMismatchError(msg) => ProofInputError::ProofMismatch(msg),
+            e => ProofInputError::ProofMismatch(e.to_string()),
+        })?;
+    Ok(envelope)
+}
+
+/// PCS parameters, proving key and scratch pad of the current MPI rank for a circuit with
+/// `n_input_vars` input variables, from the `pcs.pk` file of the rank written by
+/// `expander-exec setup`.
+pub fn prover_pcs_setup<Cfg: GKREngine>(
+    n_input_vars: usize,
+    mpi_config: &MPIConfig,
+    pcs_proving_key: &[u8],
+) -> Result<(PCSParamsOf<Cfg>, PCSProvingKeyOf<Cfg>, PCSScratchPadOf<Cfg>), ProofInputError> {
+    let pcs_proving_key = load_pcs_setup_bytes(
+        pcs_proving_key,
+        &PCSSetupHeader::new::<Cfg>(
+            PCSSetupFileKind::ProvingKey,
+            n_input_vars,
+            mpi_config.world_size(),
+            mpi_config.world_rank(),
+        ),
+    )
+    .map_err(pcs_setup_mismatch)?;
+    let pcs_params = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::gen_params(
+        n_input_vars,
+        mpi_config.world_size(),
+    );
+    let pcs_scratch =
+        <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::init_scratch_pad(
+            &pcs_params,
+            mpi_config,
+        );
+    Ok((pcs_params, pcs_proving_key, pcs_scratch))
+}
+
+/// PCS parameters and verifying key for a circuit with `n_input_vars` input variables, proven by
+/// `world_size` processes, from the `pcs.vk` file written by `expander-exec setup`.
+pub fn verifier_pcs_setup<Cfg: GKREngine>(
+    n_input_vars: usize,
+    world_size: usize,
+    pcs_verifying_key: &[u8],
+) -> Result<(PCSParamsOf<Cfg>, PCSVerifyingKeyOf<Cfg>), ProofInputError> {
+    let pcs_verification_key = load_pcs_setup_bytes(
+        pcs_verifying_key,
+        &PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::VerifyingKey, n_input_vars, world_size, 0),
+    )
+    .map_err(pcs_setup_mismatch)?;
+    let pcs_params = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::gen_params(
+        n_input_vars,
+        world_size,
+    );
+    Ok((pcs_params, pcs_verification_key))
+}
+
+/// Same as [`verifier_pcs_setup`], but from the insecure testing setup, whose trapdoor is derived
+/// from a fixed seed: anyone can forge proofs against it, unless the PCS is `Raw`, which has no
+/// setup.
+pub fn verifier_pcs_setup_insecure_testing_only<Cfg: GKREngine>(
+    n_input_vars: usize,
+    world_size: usize,
+) -> (PCSParamsOf<Cfg>, PCSVerifyingKeyOf<Cfg>) {
+    let (pcs_params, _, pcs_verification_key, _) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+            n_input_vars,
+            &MPIConfig::verifier_new(world_size as i32),
+        );
+    (pcs_params, pcs_verification_key)
+}
+
+fn pcs_setup_mismatch(e: ExpErrors) -> ProofInputError {
+    match e {
+        ExpErrors::PCSSetupError(msg) => ProofInputError::PcsSetupMismatch(msg),
+        e => ProofInputError::PcsSetupMismatch(e.to_string()),
+    }
+}
+
+/// Verify an opened proof envelope of `circuit`, as loaded by [`load_circuit_bytes`], against
+/// the public inputs of `public_input`, a serialized witness of which only the public inputs are
+/// read, as for `expander-exec verify`.
+///
+/// Proofs that do not pass the checks of the verifier are rejected with `Ok(false)`, proofs that
+/// do not deserialize are errors.
+pub fn verify_proof_envelope<Cfg>(
+    circuit: &Circuit<Cfg::FieldConfig>,
+    envelope: ProofEnvelope<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
+    public_input: &[u8],
+    pcs_params: &PCSParamsOf<Cfg>,
+    pcs_verification_key: &PCSVerifyingKeyOf<Cfg>,
+) -> Result<bool, ProofInputError>
+where
+    Cfg: GKREngine,
+    Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+{
+    let ProofEnvelope {
+        header,
+        proof,
+        claimed_v,
+    } = envelope;
+
+    let mpi_config = MPIConfig::verifier_new(header.proving_time_mpi_size as i32);
+    let mut circuit = circuit.clone();
+    circuit.try_load_witness_bytes(public_input, &mpi_config, false, false)?;
+
+    let mut verifier = Verifier::<Cfg>::new(mpi_config);
+    verifier.statement_binding = header.statement_binding();
+    let public_input = circuit.public_input.clone();
+    match verifier.try_verify(
+        &mut circuit,
+        &public_input,
+        &claimed_v,
+        pcs_params,
+        pcs_verification_key,
+        &proof,
+    ) {
+        Err(e) if e.is_rejection() => Ok(false),
+        verified => Ok(verified?),
+    }
+}
+
+/// Verify a proof envelope of a serialized circuit, with the config of the proof header, see
+/// [`verify_proof_envelope`]. `pcs_verifying_key` is the `pcs.vk` file written by
+/// `expander-exec setup`.
+pub fn verify_serialized_proof(
+    circuit: &[u8],
+    circuit_hash: &[u8; 32],
+    public_input: &[u8],
+    proof: &[u8],
+    pcs_verifying_key: &[u8],
+) -> Result<bool, ProofInputError> {
+    let header = ProofHeader::deserialize_from(proof)
+        .map_err(|e| ProofInputError::ProofMismatch(e.to_string()))?;
+
+    dispatch_gkr_config(
+        header.fiat_shamir_hash_type.clone(),
+        header.pcs_type.clone(),
+        header.field_type.clone(),
+        VerifySerializedProof {
+            circuit,
+            circuit_hash,
+            public_input,
+            proof,
+            pcs_verifying_key,
+        },
+    )
+    .unwrap_or_else(|| {
+        Err(ProofInputError::UnsupportedConfig(format!(
+            "FS: {:?}, PCS: {:?}, Field: {:?}",
+            header.fiat_shamir_hash_type, header.pcs_type, header.field_type
+        )))
+    })
+}
+
+struct VerifySerializedProof<'a> {
+    circuit: &'a [u8],
+    circuit_hash: &'a [u8; 32],
+    public_input: &'a [u8],
+    proof: &'a [u8],
+    pcs_verifying_key: &'a [u8],
+}
+
+impl GKRConfigVisitor for VerifySerializedProof<'_> {
+    type Output = Result<bool, ProofInputError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let envelope = open_proof_envelope::<Cfg>(self.proof, self.circuit_hash)?;
+        let circuit = load_circuit_bytes::<Cfg>(self.circuit)?;
+        let (pcs_params, pcs_verification_key) = verifier_pcs_setup::<Cfg>(
+            circuit.log_input_size(),
+            envelope.header.proving_time_mpi_size,
+            self.pcs_verifying_key,
+        )?;
+        verify_proof_envelope::<Cfg>(
+            &circuit,
+            envelope,
+            self.public_input,
+            &pcs_params,
+            &pcs_verification_key,
+        )
+    }
+}
@@ -6,11 +6,23 @@ pub use prover::*;
 pub mod verifier;
 pub use verifier::*;
 
+pub mod gkr_proof;
+pub use gkr_proof::*;
+
 pub mod utils;
 
+mod statement;
+pub(crate) use statement::*;
+
+mod zk;
+pub(crate) use zk::*;
+
 pub mod gkr_configs;
 pub use gkr_configs::*;
 
+pub mod io;
+pub use io::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -4,5 +4,8 @@ pub use gkr_vanilla::*;
 pub mod gkr_square;
 pub use gkr_square::*;
 
+pub mod gkr_zk;
+pub use gkr_zk::*;
+
 pub mod snark;
 pub use snark::*;
@@ -4,9 +4,11 @@
 use ark_std::{end_timer, start_timer};
 use circuit::Circuit;
 use gkr_engine::{
-    ExpanderSingleVarChallenge, FieldEngine, FieldType, MPIConfig, MPIEngine, Transcript,
+    ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine, StatementBinding, Transcript,
 };
-use sumcheck::{sumcheck_prove_gkr_square_layer, ProverScratchPad};
+use sumcheck::{gkr_square_supports_layer, sumcheck_prove_gkr_square_layer, ProverScratchPad};
+
+use crate::absorb_claimed_v;
 
 #[allow(clippy::type_complexity)]
 pub fn gkr_square_prove<F: FieldEngine>(
@@ -14,11 +16,11 @@ pub fn gkr_square_prove<F: FieldEngine>(
     sp: &mut ProverScratchPad<F>,
     transcript: &mut impl Transcript,
     mpi_config: &MPIConfig,
+    statement_binding: StatementBinding,
 ) -> (F::ChallengeField, ExpanderSingleVarChallenge<F>) {
-    assert_ne!(
-        F::FIELD_TYPE,
-        FieldType::GF2Ext128,
-        "GF2 is not supported in GKR^2"
+    assert!(
+        circuit.layers.iter().all(gkr_square_supports_layer),
+        "only the identity and square uni gates are supported over GF2 in GKR^2"
     );
     let timer = start_timer!(|| "gkr^2 prove");
     let layer_num = circuit.layers.len();
@@ -38,6 +40,8 @@ pub fn gkr_square_prove<F: FieldEngine>(
         mpi_config,
     );
 
+    absorb_claimed_v::<F>(statement_binding, &claimed_v, transcript);
+
     log::trace!("Claimed v: {claimed_v:?}");
 
     for i in (0..layer_num).rev() {
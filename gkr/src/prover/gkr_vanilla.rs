@@ -3,17 +3,20 @@
 use circuit::Circuit;
 use gkr_engine::{
     ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
-    Transcript,
+    StatementBinding, Transcript,
 };
 use sumcheck::{sumcheck_prove_gkr_layer, ProverScratchPad};
 use utils::timer::Timer;
 
+use crate::absorb_claimed_v;
+
 #[allow(clippy::type_complexity)]
 pub fn gkr_prove<F: FieldEngine>(
     circuit: &Circuit<F>,
     sp: &mut ProverScratchPad<F>,
     transcript: &mut impl Transcript,
     mpi_config: &MPIConfig,
+    statement_binding: StatementBinding,
 ) -> (F::ChallengeField, ExpanderDualVarChallenge<F>) {
     let layer_num = circuit.layers.len();
 
@@ -35,6 +38,7 @@ pub fn gkr_prove<F: FieldEngine>(
         &mut sp.eq_evals_first_half, // confusing name here..
         mpi_config,
     );
+    absorb_claimed_v::<F>(statement_binding, &claimed_v, transcript);
 
     for i in (0..layer_num).rev() {
         let timer = Timer::new(
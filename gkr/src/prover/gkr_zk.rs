@@ -0,0 +1,252 @@
+//! This module implements the zero-knowledge GKR IOP, see `crate::zk`.
+
+use arith::{ExtensionField, Field};
+use circuit::Circuit;
+use gkr_engine::{
+    ExpErrors, ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig,
+    MPIEngine, StatementBinding, Transcript,
+};
+use halo2curves::{
+    ff::PrimeField,
+    group::{Curve, UncompressedEncoding},
+    CurveAffine,
+};
+use poly_commit::{HidingPedersenParams, ProductProof, ZeroProof};
+use rand::RngCore;
+use serdes::ExpSerde;
+use sumcheck::{
+    sumcheck_prove_gkr_layer_with_io, GKRVerifierHelper, ProverScratchPad, SumcheckProverIO,
+    VerifierScratchPad,
+};
+use utils::timer::Timer;
+
+use crate::{
+    absorb_claimed_v, append_point, coeffs_from_evals, next_claim_blind, with_proof_locked,
+};
+
+/// Sends the sumcheck messages as Pedersen commitments, keeping track of their blinding factors.
+struct ZkProverIO<'a, C, T, R>
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    params: &'a HidingPedersenParams<C>,
+    transcript: &'a mut T,
+    rng: &'a mut R,
+    /// The blinding factors of the coefficients of degree >= 1 and the challenge, of each round
+    rounds: Vec<(Vec<C::Scalar>, C::Scalar)>,
+    /// The claims sent, with their blinding factors
+    claims: Vec<(C::Scalar, C::Scalar)>,
+    /// The first error writing the proof, as the sumcheck does not expect the IO to fail
+    result: Result<(), ExpErrors>,
+}
+
+impl<C, T, R> ZkProverIO<'_, C, T, R>
+where
+    C: CurveAffine + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    T: Transcript,
+{
+    fn append_point(&mut self, point: &C) {
+        if self.result.is_ok() {
+            self.result = append_point(&mut *self.transcript, point);
+        }
+    }
+}
+
+impl<C, T, R> SumcheckProverIO<C::Scalar> for ZkProverIO<'_, C, T, R>
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    T: Transcript,
+    R: RngCore,
+{
+    fn send_round_poly(&mut self, evals: &[C::Scalar]) -> C::Scalar {
+        let coeffs = coeffs_from_evals(evals);
+
+        let blinds: Vec<C::Scalar> = coeffs[1..]
+            .iter()
+            .map(|c| {
+                let blind = C::Scalar::random_unsafe(&mut *self.rng);
+                let comm = self.params.commit_scalar(*c, blind).to_affine();
+                self.append_point(&comm);
+                blind
+            })
+            .collect();
+
+        let r = self.transcript.generate_field_element::<C::Scalar>();
+        self.rounds.push((blinds, r));
+        r
+    }
+
+    fn send_claim(&mut self, claim: &C::Scalar) {
+        let blind = C::Scalar::random_unsafe(&mut *self.rng);
+        let comm = self.params.commit_scalar(*claim, blind).to_affine();
+        self.append_point(&comm);
+        self.claims.push((*claim, blind));
+    }
+}
+
+/// Returns the claimed output, the challenge on the input layer, and the blinding factors of
+/// the commitments to the claims at the challenge.
+#[allow(clippy::type_complexity)]
+pub fn gkr_zk_prove<C, F>(
+    circuit: &Circuit<F>,
+    sp: &mut ProverScratchPad<F>,
+    transcript: &mut impl Transcript,
+    mpi_config: &MPIConfig,
+    statement_binding: StatementBinding,
+    pedersen_params: &HidingPedersenParams<C>,
+    mut rng: impl RngCore,
+) -> Result<
+    (
+        F::ChallengeField,
+        ExpanderDualVarChallenge<F>,
+        C::Scalar,
+        Option<C::Scalar>,
+    ),
+    ExpErrors,
+>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    F: FieldEngine<ChallengeField = C::Scalar>,
+{
+    assert!(
+        mpi_config.is_single_process(),
+        "zero-knowledge GKR is only supported in a single process"
+    );
+    let layer_num = circuit.layers.len();
+
+    let mut challenge: ExpanderDualVarChallenge<F> =
+        ExpanderSingleVarChallenge::sample_from_transcript(
+            transcript,
+            circuit.layers.last().unwrap().output_var_num,
+            mpi_config.world_size(),
+        )
+        .into();
+
+    let output_vals = &circuit.layers.last().unwrap().output_vals;
+    let claimed_v = F::collectively_eval_circuit_vals_at_expander_challenge(
+        output_vals,
+        &challenge.challenge_x(),
+        &mut sp.hg_evals,
+        &mut sp.eq_evals_first_half,
+        mpi_config,
+    );
+    absorb_claimed_v::<F>(statement_binding, &claimed_v, transcript);
+
+    // the gate evaluations of the verifier are needed to blind its final checks consistently
+    let mut verifier_sp = VerifierScratchPad::<F>::new(circuit, mpi_config.world_size());
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+
+    // the claimed output is public, hence committed to with a zero blinding factor
+    let mut alpha = None;
+    let mut claim_blind = <C::Scalar as Field>::ZERO;
+    let mut vx_blind = <C::Scalar as Field>::ZERO;
+    let mut vy_blind = None;
+    for i in (0..layer_num).rev() {
+        let timer = Timer::new(&format!("ZK sumcheck layer {i}"), mpi_config.is_root());
+        let layer = &circuit.layers[i];
+
+        GKRVerifierHelper::prepare_layer(
+            layer,
+            &alpha,
+            &challenge,
+            &mut verifier_sp,
+            i == layer_num - 1,
+        );
+
+        let mut io = ZkProverIO {
+            params: pedersen_params,
+            transcript: &mut *transcript,
+            rng: &mut rng,
+            rounds: vec![],
+            claims: vec![],
+            result: Ok(()),
+        };
+        sumcheck_prove_gkr_layer_with_io(
+            layer,
+            &mut challenge,
+            alpha,
+            &mut io,
+            sp,
+            mpi_config,
+            i == layer_num - 1,
+        );
+        let ZkProverIO {
+            rounds,
+            claims,
+            result,
+            ..
+        } = io;
+        result?;
+
+        // phase x, over the x, SIMD and MPI variables
+        let (rounds_x, rounds_y) = rounds.split_at(layer.input_var_num + simd_var_num);
+        claim_blind = rounds_x
+            .iter()
+            .fold(claim_blind, |blind, (coeff_blinds, r)| {
+                next_claim_blind(blind, coeff_blinds, *r)
+            });
+        GKRVerifierHelper::set_rx(&challenge.rz_0, &mut verifier_sp);
+        GKRVerifierHelper::set_r_simd_xy(&challenge.r_simd, &mut verifier_sp);
+        GKRVerifierHelper::set_r_mpi_xy(&challenge.r_mpi, &mut verifier_sp);
+
+        let (vx, bx) = claims[0];
+        claim_blind -= GKRVerifierHelper::eval_add(&layer.add, &verifier_sp) * bx;
+        vx_blind = bx;
+
+        // phase y
+        vy_blind = None;
+        if !layer.structure_info.skip_sumcheck_phase_two {
+            claim_blind = rounds_y
+                .iter()
+                .fold(claim_blind, |blind, (coeff_blinds, r)| {
+                    next_claim_blind(blind, coeff_blinds, *r)
+                });
+            GKRVerifierHelper::set_ry(challenge.rz_1.as_ref().unwrap(), &mut verifier_sp);
+
+            let (vy, by) = claims[1];
+            let vxy_blind = C::Scalar::random_unsafe(&mut rng);
+            append_point(
+                transcript,
+                &pedersen_params
+                    .commit_scalar(vx * vy, vxy_blind)
+                    .to_affine(),
+            )?;
+            with_proof_locked(transcript, |transcript| {
+                ProductProof::prove(
+                    pedersen_params,
+                    (vx, bx),
+                    (vy, by),
+                    vxy_blind,
+                    transcript,
+                    &mut rng,
+                )
+            })?;
+
+            claim_blind -= GKRVerifierHelper::eval_mul(&layer.mul, &verifier_sp) * vxy_blind;
+            vy_blind = Some(by);
+        }
+
+        // what is left of the claim commits to zero
+        with_proof_locked(transcript, |transcript| {
+            ZeroProof::prove(pedersen_params, claim_blind, transcript, &mut rng)
+        })?;
+
+        if challenge.rz_1.is_some() {
+            let a = transcript.generate_field_element::<F::ChallengeField>();
+            claim_blind = vx_blind + a * vy_blind.unwrap();
+            alpha = Some(a);
+        } else {
+            claim_blind = vx_blind;
+            alpha = None;
+        }
+        timer.stop();
+    }
+
+    Ok((claimed_v, challenge, vx_blind, vy_blind))
+}
@@ -1,10 +1,15 @@
 //! This module implements the whole GKR prover, including the IOP and PCS.
 
-use arith::Field;
+use arith::{ExtensionField, Field};
 use circuit::Circuit;
 use gkr_engine::{
-    ExpanderDualVarChallenge, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine,
-    GKRScheme, MPIConfig, MPIEngine, Proof, StructuredReferenceString, Transcript,
+    ExpErrors, ExpanderDualVarChallenge, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine,
+    GKREngine, GKRScheme, MPIConfig, MPIEngine, Proof, StatementBinding, StructuredReferenceString,
+    Transcript,
+};
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
+use poly_commit::{
+    hiding_hyrax_prove_eval, HidingHyraxPCS, HidingHyraxScratchPad, HidingPedersenParams,
 };
 use polynomials::{
     MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension, RefMultiLinearPoly,
@@ -14,7 +19,10 @@ use sumcheck::ProverScratchPad;
 use transcript::transcript_root_broadcast;
 use utils::timer::Timer;
 
-use crate::{gkr_prove, gkr_square_prove};
+use crate::{
+    absorb_public_input, assert_generic_scheme, gkr_prove, gkr_square_prove, gkr_zk_prove,
+    with_proof_locked,
+};
 
 #[cfg(feature = "grinding")]
 pub(crate) fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, mpi_config: &MPIConfig) {
@@ -52,6 +60,9 @@ pub(crate) fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, mpi_config
 #[derive(Default)]
 pub struct Prover<'a, Cfg: GKREngine> {
     pub mpi_config: MPIConfig<'a>,
+    /// What the transcript is bound to, `StatementBinding::Legacy` is only kept to reproduce
+    /// old proofs
+    pub statement_binding: StatementBinding,
     sp: ProverScratchPad<Cfg::FieldConfig>,
 }
 
@@ -59,6 +70,7 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
     pub fn new(mpi_config: MPIConfig<'a>) -> Self {
         Prover {
             mpi_config,
+            statement_binding: StatementBinding::default(),
             sp: ProverScratchPad::default(),
         }
     }
@@ -93,6 +105,8 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
     where
         Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
     {
+        const { assert_generic_scheme::<Cfg>() };
+
         let proving_timer = Timer::new("prover", self.mpi_config.is_root());
         let mut transcript = Cfg::TranscriptConfig::new();
 
@@ -106,10 +120,28 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
             pcs_scratch,
         );
 
+        // the verifier is given the public inputs of all the MPI processes, in rank order
+        let mut public_input = vec![];
+        if self.statement_binding == StatementBinding::Full {
+            if self.mpi_config.is_root() {
+                public_input = vec![
+                    <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO;
+                    c.public_input.len() * self.mpi_config.world_size()
+                ];
+            }
+            self.mpi_config
+                .gather_vec(&c.public_input, &mut public_input);
+        }
+
         if self.mpi_config.is_root() {
             let mut buffer = vec![];
             commitment.unwrap().serialize_into(&mut buffer).unwrap(); // TODO: error propagation
             transcript.append_commitment(&buffer);
+            absorb_public_input::<Cfg::FieldConfig>(
+                self.statement_binding,
+                &public_input,
+                &mut transcript,
+            );
         }
         pcs_commit_timer.stop();
 
@@ -126,12 +158,24 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         transcript_root_broadcast(&mut transcript, &self.mpi_config);
 
         let (claimed_v, challenge) = match Cfg::SCHEME {
-            GKRScheme::Vanilla => gkr_prove(c, &mut self.sp, &mut transcript, &self.mpi_config),
+            GKRScheme::Vanilla => gkr_prove(
+                c,
+                &mut self.sp,
+                &mut transcript,
+                &self.mpi_config,
+                self.statement_binding,
+            ),
             GKRScheme::GkrSquare => {
-                let (claimed_v, challenge_x) =
-                    gkr_square_prove(c, &mut self.sp, &mut transcript, &self.mpi_config);
+                let (claimed_v, challenge_x) = gkr_square_prove(
+                    c,
+                    &mut self.sp,
+                    &mut transcript,
+                    &self.mpi_config,
+                    self.statement_binding,
+                );
                 (claimed_v, ExpanderDualVarChallenge::from(&challenge_x))
             }
+            GKRScheme::ZkVanilla | GKRScheme::CrossLayer => unreachable!(),
         };
         gkr_prove_timer.stop();
 
@@ -171,6 +215,101 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
 
         (claimed_v, proof)
     }
+
+    /// Prove with the zero-knowledge GKR, see `GKRScheme::ZkVanilla`, opening the hiding Hyrax
+    /// commitment to the inputs against the committed claims on the input layer.
+    ///
+    /// Only a single process is supported.
+    pub fn prove_zk<C>(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        pcs_params: &usize,
+        pcs_proving_key: &HidingPedersenParams<C>,
+        pcs_scratch: &mut HidingHyraxScratchPad<C>,
+    ) -> Result<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof), ExpErrors>
+    where
+        C: CurveAffine + ExpSerde + UncompressedEncoding,
+        C::Scalar: ExtensionField + PrimeField,
+        C::ScalarExt: ExtensionField + PrimeField,
+        C::Base: PrimeField<Repr = [u8; 32]>,
+        Cfg: GKREngine<PCSField = C::Scalar, PCSConfig = HidingHyraxPCS<C>>,
+        Cfg::FieldConfig: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
+    {
+        const { assert!(matches!(Cfg::SCHEME, GKRScheme::ZkVanilla)) };
+        assert!(
+            self.mpi_config.is_single_process(),
+            "zero-knowledge GKR is only supported in a single process"
+        );
+
+        let proving_timer = Timer::new("zk prover", true);
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut rng = rand::thread_rng();
+
+        let pcs_commit_timer = Timer::new("pcs commit", true);
+        let commitment = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::commit(
+            pcs_params,
+            &self.mpi_config,
+            pcs_proving_key,
+            &RefMultiLinearPoly::from_ref(&c.layers[0].input_vals),
+            pcs_scratch,
+        )
+        .unwrap();
+
+        let mut buffer = vec![];
+        commitment.serialize_into(&mut buffer)?;
+        transcript.append_commitment(&buffer);
+        absorb_public_input::<Cfg::FieldConfig>(
+            self.statement_binding,
+            &c.public_input,
+            &mut transcript,
+        );
+        pcs_commit_timer.stop();
+
+        #[cfg(feature = "grinding")]
+        grind::<Cfg>(&mut transcript, &self.mpi_config);
+
+        c.fill_rnd_coefs(&mut transcript);
+        c.evaluate();
+
+        let gkr_prove_timer = Timer::new("gkr zk prove", true);
+        let (claimed_v, challenge, vx_blind, vy_blind) = gkr_zk_prove(
+            c,
+            &mut self.sp,
+            &mut transcript,
+            &self.mpi_config,
+            self.statement_binding,
+            pcs_proving_key,
+            &mut rng,
+        )?;
+        gkr_prove_timer.stop();
+
+        let pcs_open_timer = Timer::new("pcs open", true);
+        let inputs = RefMultiLinearPoly::from_ref(&c.layers[0].input_vals);
+        let mut open_at = vec![(challenge.challenge_x(), vx_blind)];
+        if let Some(challenge_y) = challenge.challenge_y() {
+            open_at.push((challenge_y, vy_blind.unwrap()));
+        }
+        for (challenge, eval_blind) in open_at {
+            with_proof_locked(&mut transcript, |transcript| {
+                hiding_hyrax_prove_eval(
+                    pcs_proving_key,
+                    &inputs,
+                    &pcs_scratch.row_blinds,
+                    &challenge.local_xs(),
+                    eval_blind,
+                    transcript,
+                    &mut rng,
+                )
+            })?;
+        }
+        pcs_open_timer.stop();
+
+        let proof = transcript.finalize_and_get_proof();
+        proving_timer.print(&format!("Proof size {} bytes", proof.bytes.len()));
+        proving_timer.stop();
+
+        Ok((claimed_v, proof))
+    }
 }
 
 impl<Cfg: GKREngine> Prover<'_, Cfg> {
@@ -0,0 +1,41 @@
+//! Binding of the statement, i.e., the public inputs and the claimed output of the circuit, into
+//! the Fiat-Shamir transcript.
+//!
+//! Both are known to the verifier, hence they are absorbed with the proof locked, s.t., they are
+//! hashed into the transcript state without being written into the proof.
+
+use gkr_engine::{FieldEngine, StatementBinding, Transcript};
+
+/// Absorb the public inputs, right after the PCS commitment.
+#[inline(always)]
+pub(crate) fn absorb_public_input<F: FieldEngine>(
+    statement_binding: StatementBinding,
+    public_input: &[F::SimdCircuitField],
+    transcript: &mut impl Transcript,
+) {
+    if statement_binding == StatementBinding::Legacy {
+        return;
+    }
+
+    transcript.lock_proof();
+    public_input
+        .iter()
+        .for_each(|x| transcript.append_field_element(x));
+    transcript.unlock_proof();
+}
+
+/// Absorb the claimed output, right after the output challenge is sampled.
+#[inline(always)]
+pub(crate) fn absorb_claimed_v<F: FieldEngine>(
+    statement_binding: StatementBinding,
+    claimed_v: &F::ChallengeField,
+    transcript: &mut impl Transcript,
+) {
+    if statement_binding == StatementBinding::Legacy {
+        return;
+    }
+
+    transcript.lock_proof();
+    transcript.append_field_element(claimed_v);
+    transcript.unlock_proof();
+}
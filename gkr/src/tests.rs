@@ -1,2 +1,8 @@
+mod batch_verify;
 mod gkr_correctness;
+mod gkr_square;
+mod headers;
+mod simd_backends;
+mod statement_binding;
 mod system;
+mod zk;
@@ -0,0 +1,88 @@
+use config_macros::declare_gkr_config;
+use gkr_engine::{BN254Config, GKREngine, GKRScheme, MPIConfig, VerifyError};
+use gkr_hashers::SHA256hasher;
+use halo2curves::bn256::Bn256;
+use poly_commit::{expander_pcs_init_testing_only, HyperKZGPCS};
+use transcript::BytesHashTranscript;
+
+use crate::{utils::statement_binding_test_circuit, GkrProof, Prover, Verifier};
+
+#[test]
+fn test_verify_batch_rejects_tampered_deferred_opening() {
+    declare_gkr_config!(
+        Cfg,
+        FieldType::BN254,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::KZG,
+        GKRScheme::Vanilla,
+    );
+    type FieldConfig = <Cfg<'static> as GKREngine>::FieldConfig;
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit = statement_binding_test_circuit::<FieldConfig>();
+    let public_input = circuit.public_input.clone();
+
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<
+            FieldConfig,
+            <Cfg as GKREngine>::PCSField,
+            <Cfg as GKREngine>::PCSConfig,
+        >(circuit.log_input_size(), &mpi_config);
+
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    // the quotient commitment only enters the final pairing check, which is deferred to the
+    // end of the batch, hence the tampered proof passes everything but the batch check
+    let mut gkr_proof = GkrProof::<Cfg>::from_proof(&proof, &circuit, 1).unwrap();
+    assert_ne!(
+        gkr_proof.opening_x.quotient_delta_x_commitment,
+        gkr_proof.opening_x.beta_x_commitment
+    );
+    gkr_proof.opening_x.quotient_delta_x_commitment = gkr_proof.opening_x.beta_x_commitment;
+    let tampered_proof = gkr_proof.to_proof().unwrap();
+
+    let verifier = Verifier::<Cfg>::new(mpi_config);
+    assert!(verifier
+        .verify_with_deferred_pcs_checks(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &tampered_proof,
+        )
+        .is_some());
+    assert_eq!(
+        verifier.try_verify(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &tampered_proof,
+        ),
+        Err(VerifyError::PcsOpeningFailed)
+    );
+
+    let valid = (public_input.as_slice(), claimed_v, &proof);
+    let tampered = (public_input.as_slice(), claimed_v, &tampered_proof);
+    assert!(verifier.verify_batch(
+        &circuit,
+        &[valid, valid],
+        &pcs_params,
+        &pcs_verification_key
+    ));
+    assert!(!verifier.verify_batch(
+        &circuit,
+        &[valid, tampered],
+        &pcs_params,
+        &pcs_verification_key
+    ));
+}
@@ -1,29 +1,34 @@
+use std::fs;
 use std::io::Write;
-use std::panic::AssertUnwindSafe;
 use std::time::Instant;
-use std::{fs, panic};
 
 use arith::Field;
+use babybear::Poseidon2BabyBear16;
 use circuit::Circuit;
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     root_println, BN254Config, BabyBearx16Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine,
     GKRScheme, Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
-    MPIEngine, SharedMemory,
+    MPIEngine, Proof, SharedMemory, ThreadCommunicator, VerifyError,
 };
-use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_hashers::{
+    Keccak256hasher, MiMC5FiatShamirHasher, Poseidon2FiatShamirHasher, PoseidonFiatShamirHasher,
+    SHA256hasher,
+};
+use goldilocks::Poseidon2Goldilocks12;
 use halo2curves::bn256::{Bn256, G1Affine};
 use mersenne31::M31x16;
 use poly_commit::{
-    expander_pcs_init_testing_only, HyperKZGPCS, HyraxPCS, OrionPCSForGKR, RawExpanderGKR,
+    expander_pcs_init_testing_only, BasefoldPCSForGKR, HyperKZGPCS, HyraxPCS, OrionPCSForGKR,
+    RawExpanderGKR,
 };
 use rand::Rng;
 use serdes::ExpSerde;
 use sha2::Digest;
 use transcript::BytesHashTranscript;
 
-use crate::{utils::*, Prover, Verifier};
+use crate::{utils::*, GkrProof, Prover, Verifier};
 
 #[test]
 fn test_gkr_correctness() {
@@ -33,6 +38,20 @@ fn test_gkr_correctness() {
     let world = universe.world();
     let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
 
+    test_gkr_correctness_all_configs(&mpi_config);
+}
+
+/// The distributed code paths without `mpiexec`, with the processes simulated by threads
+#[test]
+fn test_gkr_correctness_mpi_threads() {
+    for world_size in [2, 4, 8] {
+        ThreadCommunicator::run_world(world_size, |mpi_config| {
+            test_gkr_correctness_all_configs(&mpi_config);
+        });
+    }
+}
+
+fn test_gkr_correctness_all_configs(mpi_config: &MPIConfig) {
     declare_gkr_config!(
         C0,
         FieldType::GF2Ext128,
@@ -145,6 +164,55 @@ fn test_gkr_correctness() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        C16,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::Keccak256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C17,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::Keccak256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C18,
+        FieldType::BN254,
+        FiatShamirHashType::Keccak256,
+        PolynomialCommitmentType::KZG,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C19,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::FRI,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C20,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::FRI,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C21,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::Poseidon,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C22,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::Poseidon,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
     test_gkr_correctness_helper::<C0>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C1>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C2>(mpi_config.clone(), None);
@@ -161,6 +229,13 @@ fn test_gkr_correctness() {
     test_gkr_correctness_helper::<C13>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C14>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C15>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C16>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C17>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C18>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C19>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C20>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C21>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C22>(mpi_config.clone(), None);
 }
 
 #[allow(unreachable_patterns)]
@@ -316,38 +391,137 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         );
         println!("Correct proof verified.");
 
-        let mut bad_proof = proof.clone();
-        let rng = &mut rand::thread_rng();
-        let random_idx = rng.gen_range(0..bad_proof.bytes.len());
-        let random_change = rng.gen_range(1..256) as u8;
-        bad_proof.bytes[random_idx] ^= random_change;
+        let gkr_proof =
+            GkrProof::<Cfg>::from_proof(&proof, &circuit, mpi_config.world_size()).unwrap();
+        assert_eq!(gkr_proof.to_proof().unwrap().bytes, proof.bytes);
+        let truncated_proof = Proof {
+            bytes: proof.bytes[..proof.bytes.len() - 1].to_vec(),
+        };
+        assert!(matches!(
+            GkrProof::<Cfg>::from_proof(&truncated_proof, &circuit, mpi_config.world_size()),
+            Err(VerifyError::TruncatedProof)
+        ));
+        let mut padded_proof = proof.clone();
+        padded_proof.bytes.push(0);
+        assert!(matches!(
+            GkrProof::<Cfg>::from_proof(&padded_proof, &circuit, mpi_config.world_size()),
+            Err(VerifyError::TrailingBytes(1))
+        ));
+        assert!(!verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &padded_proof
+        ));
 
-        // Catch the panic and treat it as returning `false`
-        let result = panic::catch_unwind(AssertUnwindSafe(|| {
-            verifier.verify(
+        assert_eq!(
+            verifier.try_verify(
                 &mut circuit,
                 &public_input_gathered,
                 &claimed_v,
                 &pcs_params,
                 &pcs_verification_key,
-                &bad_proof,
-            )
-        }));
-        let final_result = result.unwrap_or_default();
-        assert!(!final_result,);
+                &proof
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            verifier.try_verify(
+                &mut circuit,
+                &public_input_gathered,
+                &claimed_v,
+                &pcs_params,
+                &pcs_verification_key,
+                &truncated_proof
+            ),
+            Err(VerifyError::TruncatedProof)
+        );
+        assert_eq!(
+            verifier.try_verify(
+                &mut circuit,
+                &public_input_gathered,
+                &claimed_v,
+                &pcs_params,
+                &pcs_verification_key,
+                &padded_proof
+            ),
+            Err(VerifyError::TrailingBytes(1))
+        );
+        assert_eq!(
+            verifier.try_verify(
+                &mut circuit,
+                &public_input_gathered,
+                &(claimed_v + <Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE),
+                &pcs_params,
+                &pcs_verification_key,
+                &proof
+            ),
+            Err(VerifyError::ClaimMismatch {
+                layer: circuit.layers.len() - 1
+            })
+        );
+
+        let mut bad_proof = proof.clone();
+        let rng = &mut rand::thread_rng();
+        let random_idx = rng.gen_range(0..bad_proof.bytes.len());
+        let random_change = rng.gen_range(1..256) as u8;
+        bad_proof.bytes[random_idx] ^= random_change;
 
-        let par_result = panic::catch_unwind(AssertUnwindSafe(|| {
-            verifier.par_verify(
+        // the bad proof is either malformed or fails the checks, and is rejected without panicking
+        assert!(!verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &bad_proof,
+        ));
+        assert_ne!(
+            verifier.try_verify(
                 &mut circuit,
                 &public_input_gathered,
                 &claimed_v,
                 &pcs_params,
                 &pcs_verification_key,
                 &bad_proof,
-            )
-        }));
-        let final_par_result = par_result.unwrap_or_default();
-        assert!(!final_par_result,);
+            ),
+            Ok(true)
+        );
+
+        let batch_verification_start = Instant::now();
+        assert!(verifier.verify_batch(
+            &circuit,
+            &[
+                (&public_input_gathered[..], claimed_v, &proof),
+                (&public_input_gathered[..], claimed_v, &proof),
+            ],
+            &pcs_params,
+            &pcs_verification_key,
+        ));
+        println!(
+            "Batch verification time for 2 proofs: {} μs",
+            batch_verification_start.elapsed().as_micros()
+        );
+        assert!(!verifier.verify_batch(
+            &circuit,
+            &[
+                (&public_input_gathered[..], claimed_v, &proof),
+                (&public_input_gathered[..], claimed_v, &bad_proof),
+            ],
+            &pcs_params,
+            &pcs_verification_key,
+        ));
+
+        assert!(!verifier.par_verify(
+            &mut circuit,
+            &public_input_gathered,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &bad_proof,
+        ));
 
         println!("Bad proof rejected.");
         println!("============== end ===============");
@@ -1,12 +1,16 @@
 use arith::SimdField;
-use circuit::{Circuit, CircuitLayer, CoefType, GateConst, GateUni};
+use circuit::{Circuit, CircuitLayer, CoefType, GateConst, GateUni, UniGateType};
 use config_macros::declare_gkr_config;
-use gkr_engine::{FieldEngine, GKREngine, GKRScheme, M31x16Config, MPIConfig, MPIEngine};
+use gkr_engine::{
+    FieldEngine, GKREngine, GKRScheme, M31x16Config, MPIConfig, MPIEngine, StatementBinding,
+    ThreadCommunicator, Transcript, VerifyError,
+};
 use gkr_hashers::SHA256hasher;
 use poly_commit::{expander_pcs_init_testing_only, RawExpanderGKR};
+use sumcheck::sumcheck_gkr_square_degree;
 use transcript::BytesHashTranscript;
 
-use crate::{Prover, Verifier};
+use crate::{gkr_square_verify, GF2ExtConfigSha2RawSquare, Prover, Verifier};
 
 /// A simple GKR2 test circuit:
 /// ```text
@@ -134,13 +138,13 @@ pub fn gkr_square_test_circuit<C: FieldEngine>() -> Circuit<C> {
 fn gkr_square_correctness_test() {
     declare_gkr_config!(
         GkrConfigType,
-        FieldType::M31,
+        FieldType::M31x16,
         FiatShamirHashType::SHA256,
         PolynomialCommitmentType::Raw,
         GKRScheme::GkrSquare
     );
-    type GkrFieldConfigType = <GkrConfigType as GKREngine>::FieldConfig;
-let universe = MPIConfig::init().unwrap(); let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    type GkrFieldConfigType = <GkrConfigType<'static> as GKREngine>::FieldConfig;
+    let mpi_config = MPIConfig::prover_new(None, None);
     let mut circuit = gkr_square_test_circuit::<GkrFieldConfigType>();
     // Set input layers with N_2_0 = 3, N_2_1 = 5, N_2_2 = 7,
     // and N_2_3 varying from 0 to 15
@@ -153,7 +157,91 @@ let universe = MPIConfig::init().unwrap(); let mpi_config = MPIConfig::prover_ne
     circuit.public_input = vec![13.into()];
 
     do_prove_verify::<GkrConfigType>(&mpi_config, &mut circuit);
-   
+}
+
+/// A GKR2 test circuit mixing uni gates of several degrees in a layer:
+/// ```text
+/// N_0_0 = N_1_0^3 + N_1_1
+/// N_0_1 = N_1_2 + 2 * N_1_3^7
+///
+/// N_1_0 = N_2_0^2
+/// N_1_1 = N_2_1^3 + N_2_0
+/// N_1_2 = 3 * N_2_2^7
+/// N_1_3 = N_2_3^5 + N_2_2^3
+/// ```
+pub fn gkr_square_mixed_degree_test_circuit<C: FieldEngine>() -> Circuit<C> {
+    let uni = |i_id: usize, o_id: usize, coef: u32, uni_type: UniGateType| GateUni {
+        i_ids: [i_id],
+        o_id,
+        coef: C::CircuitField::from(coef),
+        coef_type: CoefType::Constant,
+        gate_type: uni_type.gate_type(),
+    };
+
+    let mut circuit = Circuit::default();
+
+    let mut l1 = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 2,
+        ..Default::default()
+    };
+    l1.uni = vec![
+        uni(0, 0, 1, UniGateType::Pow(2)),
+        uni(1, 1, 1, UniGateType::Pow(3)),
+        uni(0, 1, 1, UniGateType::Pow(1)),
+        uni(2, 2, 3, UniGateType::Pow(7)),
+        uni(3, 3, 1, UniGateType::Pow(5)),
+        uni(2, 3, 1, UniGateType::Pow(3)),
+    ];
+    circuit.layers.push(l1);
+
+    let mut output_layer = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    output_layer.uni = vec![
+        uni(0, 0, 1, UniGateType::Pow(3)),
+        uni(1, 0, 1, UniGateType::Pow(1)),
+        uni(2, 1, 1, UniGateType::Pow(1)),
+        uni(3, 1, 2, UniGateType::Pow(7)),
+    ];
+    circuit.layers.push(output_layer);
+
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn gkr_square_mixed_degree_test() {
+    declare_gkr_config!(
+        GkrConfigType,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::GkrSquare
+    );
+    type GkrFieldConfigType = <GkrConfigType<'static> as GKREngine>::FieldConfig;
+
+    let circuit = gkr_square_mixed_degree_test_circuit::<GkrFieldConfigType>();
+    assert_eq!(circuit.layers[0].uni_degrees(), vec![1, 2, 3, 5, 7]);
+    assert_eq!(sumcheck_gkr_square_degree(&circuit.layers[0]), 8);
+    assert_eq!(sumcheck_gkr_square_degree(&circuit.layers[1]), 8);
+
+    ThreadCommunicator::run_world(2, |mpi_config| {
+        let mut circuit = gkr_square_mixed_degree_test_circuit::<GkrFieldConfigType>();
+        let input_vals = (0..4)
+            .map(|i| {
+                let vals = (0..16)
+                    .map(|j| (i * 16 + j + 3 * mpi_config.world_rank() as u32).into())
+                    .collect::<Vec<_>>();
+                <GkrFieldConfigType as FieldEngine>::SimdCircuitField::pack(&vals)
+            })
+            .collect();
+        circuit.layers[0].input_vals = input_vals;
+
+        do_prove_verify::<GkrConfigType>(&mpi_config, &mut circuit);
+    });
 }
 
 fn do_prove_verify<Cfg: GKREngine>(
@@ -163,7 +251,7 @@ fn do_prove_verify<Cfg: GKREngine>(
     circuit.evaluate();
 
     let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
-        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
             circuit.log_input_size(),
             mpi_config,
         );
@@ -187,3 +275,104 @@ fn do_prove_verify<Cfg: GKREngine>(
         ))
     }
 }
+
+/// A GKR2 test circuit over GF2, with the identity and square gates:
+/// ```text
+/// N_0_0 = N_1_0^2 + N_1_1
+/// N_0_1 = N_1_2 + N_1_3^2
+///
+/// N_1_0 = N_2_0^2 + N_2_1
+/// N_1_1 = N_2_1
+/// N_1_2 = N_2_2^2 + N_2_3^2
+/// N_1_3 = N_2_3
+/// ```
+pub fn gkr_square_gf2_test_circuit<C: FieldEngine>() -> Circuit<C> {
+    let uni = |i_id: usize, o_id: usize, uni_type: UniGateType| GateUni {
+        i_ids: [i_id],
+        o_id,
+        coef: C::CircuitField::from(1),
+        coef_type: CoefType::Constant,
+        gate_type: uni_type.gate_type(),
+    };
+
+    let mut circuit = Circuit::default();
+
+    let mut l1 = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 2,
+        ..Default::default()
+    };
+    l1.uni = vec![
+        uni(0, 0, UniGateType::Pow(2)),
+        uni(1, 0, UniGateType::Pow(1)),
+        uni(1, 1, UniGateType::Pow(1)),
+        uni(2, 2, UniGateType::Pow(2)),
+        uni(3, 2, UniGateType::Pow(2)),
+        uni(3, 3, UniGateType::Pow(1)),
+    ];
+    circuit.layers.push(l1);
+
+    let mut output_layer = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    output_layer.uni = vec![
+        uni(0, 0, UniGateType::Pow(2)),
+        uni(1, 0, UniGateType::Pow(1)),
+        uni(2, 1, UniGateType::Pow(1)),
+        uni(3, 1, UniGateType::Pow(2)),
+    ];
+    circuit.layers.push(output_layer);
+
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn gkr_square_gf2_test() {
+    type GF2FieldConfig = <GF2ExtConfigSha2RawSquare<'static> as GKREngine>::FieldConfig;
+
+    let circuit = gkr_square_gf2_test_circuit::<GF2FieldConfig>();
+    assert_eq!(sumcheck_gkr_square_degree(&circuit.layers[0]), 3);
+
+    for world_size in [1, 2] {
+        ThreadCommunicator::run_world(world_size, |mpi_config| {
+            let mut circuit = gkr_square_gf2_test_circuit::<GF2FieldConfig>();
+            let input_vals = (0..4)
+                .map(|i| {
+                    let bits = (0..8)
+                        .map(|j| ((i * 3 + j * 5 + mpi_config.world_rank() as u32) % 3).into())
+                        .collect::<Vec<_>>();
+                    <GF2FieldConfig as FieldEngine>::SimdCircuitField::pack(&bits)
+                })
+                .collect();
+            circuit.layers[0].input_vals = input_vals;
+
+            do_prove_verify::<GF2ExtConfigSha2RawSquare>(&mpi_config, &mut circuit);
+        });
+    }
+}
+
+#[test]
+fn gkr_square_gf2_rejects_unsupported_gates() {
+    type GF2FieldConfig = <GF2ExtConfigSha2RawSquare<'static> as GKREngine>::FieldConfig;
+
+    // x^3 is not linear over GF(2)
+    let mut circuit = gkr_square_gf2_test_circuit::<GF2FieldConfig>();
+    circuit.layers[1].uni[0].gate_type = UniGateType::Pow(3).gate_type();
+
+    let result = gkr_square_verify(
+        1,
+        &circuit,
+        &[],
+        &Default::default(),
+        &mut BytesHashTranscript::<SHA256hasher>::new(),
+        &[0u8; 0][..],
+        StatementBinding::Full,
+    );
+    assert_eq!(
+        result.err(),
+        Some(VerifyError::UnsupportedCircuit { layer: 1 })
+    );
+}
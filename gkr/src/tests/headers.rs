@@ -0,0 +1,188 @@
+use std::any::TypeId;
+
+use gkr_engine::{
+    load_pcs_setup_bytes, ExpErrors, FieldEngine, FieldType, GKREngine, GKRScheme,
+    PCSSetupFileKind, PCSSetupHeader, Proof, ProofEnvelope, ProofHeader, StatementBinding,
+    PROOF_ENVELOPE_LEGACY_VERSION,
+};
+use mersenne31::M31Ext3;
+use serdes::ExpSerde;
+
+use crate::{
+    dispatch_gkr_config, dispatch_gkr_config_name, BN254ConfigMIMC5KZG, BN254ConfigSha2Hyrax,
+    GKRConfigVisitor, M31x16ConfigPoseidonRawVanilla, M31x16ConfigSha2OrionVanilla,
+    M31x16ConfigSha2RawSquare, M31x16ConfigSha2RawVanilla, GKR_CONFIG_NAMES,
+};
+
+type Cfg = M31x16ConfigSha2RawVanilla<'static>;
+
+const CIRCUIT_HASH: [u8; 32] = [7u8; 32];
+
+fn assert_proof_mismatch(result: Result<(), ExpErrors>, expected: &str) {
+    match result {
+        Err(ExpErrors::ProofMismatchError(msg)) => {
+            assert!(msg.contains(expected), "{msg} does not contain {expected}")
+        }
+        result => panic!("expected a proof mismatch, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_proof_header_check() {
+    let header = ProofHeader::new::<Cfg>(2, CIRCUIT_HASH);
+    assert_eq!(header.field_type, FieldType::M31x16);
+    assert_eq!(header.gkr_scheme, GKRScheme::Vanilla);
+    header.check::<Cfg>(2, &CIRCUIT_HASH).unwrap();
+    assert_eq!(header.statement_binding(), StatementBinding::Full);
+
+    assert_proof_mismatch(header.check::<Cfg>(4, &CIRCUIT_HASH), "MPI size");
+    assert_proof_mismatch(header.check::<Cfg>(2, &[0u8; 32]), "different circuit");
+    assert_proof_mismatch(
+        header.check::<M31x16ConfigPoseidonRawVanilla>(2, &CIRCUIT_HASH),
+        "Fiat-Shamir hash",
+    );
+    assert_proof_mismatch(
+        header.check::<M31x16ConfigSha2OrionVanilla>(2, &CIRCUIT_HASH),
+        "PCS",
+    );
+    assert_proof_mismatch(
+        header.check::<M31x16ConfigSha2RawSquare>(2, &CIRCUIT_HASH),
+        "GKR scheme",
+    );
+    assert_proof_mismatch(
+        header.check::<BN254ConfigMIMC5KZG>(2, &CIRCUIT_HASH),
+        "field",
+    );
+
+    let mut bad_magic = header.clone();
+    bad_magic.magic ^= 1;
+    assert_proof_mismatch(
+        bad_magic.check::<Cfg>(2, &CIRCUIT_HASH),
+        "not an expander proof",
+    );
+
+    // version 1 proofs are still accepted, with the legacy transcript
+    let mut legacy = header.clone();
+    legacy.version = PROOF_ENVELOPE_LEGACY_VERSION;
+    legacy.check::<Cfg>(2, &CIRCUIT_HASH).unwrap();
+    assert_eq!(legacy.statement_binding(), StatementBinding::Legacy);
+
+    let mut future = header;
+    future.version += 1;
+    assert_proof_mismatch(future.check::<Cfg>(2, &CIRCUIT_HASH), "version");
+}
+
+#[test]
+fn test_proof_envelope_round_trip() {
+    let envelope = ProofEnvelope {
+        header: ProofHeader::new::<Cfg>(1, CIRCUIT_HASH),
+        proof: Proof {
+            bytes: (0..100).collect(),
+        },
+        claimed_v: M31Ext3::from(42u32),
+    };
+    let mut bytes = vec![];
+    envelope.serialize_into(&mut bytes).unwrap();
+
+    // the header can be read before the config is known
+    assert_eq!(
+        ProofHeader::deserialize_from(&bytes[..]).unwrap(),
+        envelope.header
+    );
+    assert_eq!(
+        ProofEnvelope::<M31Ext3>::deserialize_from(&bytes[..]).unwrap(),
+        envelope
+    );
+    assert!(ProofEnvelope::<M31Ext3>::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn test_pcs_setup_header() {
+    type KZGCfg = BN254ConfigMIMC5KZG<'static>;
+    let header = PCSSetupHeader::new::<KZGCfg>(PCSSetupFileKind::VerifyingKey, 10, 2, 0);
+
+    let payload = vec![1u64, 2, 3];
+    let mut bytes = vec![];
+    header.serialize_into(&mut bytes).unwrap();
+    payload.serialize_into(&mut bytes).unwrap();
+    assert_eq!(
+        load_pcs_setup_bytes::<Vec<u64>>(&bytes, &header).unwrap(),
+        payload
+    );
+
+    let assert_setup_error =
+        |expected_header: PCSSetupHeader, expected: &str| match load_pcs_setup_bytes::<Vec<u64>>(
+            &bytes,
+            &expected_header,
+        ) {
+            Err(ExpErrors::PCSSetupError(msg)) => {
+                assert!(msg.contains(expected), "{msg} does not contain {expected}")
+            }
+            result => panic!("expected a PCS setup error, got {result:?}"),
+        };
+    assert_setup_error(
+        PCSSetupHeader::new::<KZGCfg>(PCSSetupFileKind::ProvingKey, 10, 2, 0),
+        "file kind",
+    );
+    assert_setup_error(
+        PCSSetupHeader::new::<BN254ConfigSha2Hyrax>(PCSSetupFileKind::VerifyingKey, 10, 2, 0),
+        "PCS",
+    );
+    assert_setup_error(
+        PCSSetupHeader::new::<KZGCfg>(PCSSetupFileKind::VerifyingKey, 11, 2, 0),
+        "number of input variables",
+    );
+    assert_setup_error(
+        PCSSetupHeader::new::<KZGCfg>(PCSSetupFileKind::VerifyingKey, 10, 4, 0),
+        "MPI world size",
+    );
+    assert_setup_error(
+        PCSSetupHeader::new::<KZGCfg>(PCSSetupFileKind::VerifyingKey, 10, 2, 1),
+        "MPI world rank",
+    );
+
+    let mut future = header.clone();
+    future.version += 1;
+    assert_setup_error(future, "unsupported version");
+
+    assert!(matches!(
+        load_pcs_setup_bytes::<Vec<u64>>(&bytes[..4], &header),
+        Err(ExpErrors::PCSSetupError(_))
+    ));
+}
+
+/// The header of the proofs of a config, along with the config
+struct ConfigHeader;
+
+impl GKRConfigVisitor for ConfigHeader {
+    type Output = (TypeId, ProofHeader);
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        (
+            TypeId::of::<Cfg>(),
+            ProofHeader::new::<Cfg>(1, CIRCUIT_HASH),
+        )
+    }
+}
+
+#[test]
+fn test_config_names_dispatch_from_proof_headers() {
+    assert_eq!(GKR_CONFIG_NAMES.len(), 20);
+    for name in GKR_CONFIG_NAMES {
+        let (config, header) = dispatch_gkr_config_name(name, ConfigHeader).unwrap();
+        assert_eq!(header.gkr_scheme, GKRScheme::Vanilla, "{name}");
+        let (dispatched, _) = dispatch_gkr_config(
+            header.fiat_shamir_hash_type,
+            header.pcs_type,
+            header.field_type,
+            ConfigHeader,
+        )
+        .unwrap();
+        assert_eq!(dispatched, config, "{name}");
+    }
+    assert!(dispatch_gkr_config_name("M31x16ConfigSha2RawSquare", ConfigHeader).is_none());
+}
@@ -0,0 +1,141 @@
+//! The proofs on the AVX2 fields and on their AVX-512 twins dispatched at runtime. The backend
+//! is chosen once per process, so each backend proves in a child process of the test binary.
+
+use std::{env, fs, process::Command};
+
+use arith::{SimdBackend, SimdField, SIMD_BACKEND_ENV};
+use circuit::Circuit;
+use gkr_engine::{FieldEngine, GF2ExtConfig, GKREngine, M31x16Config, MPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+
+use super::gkr_square::{gkr_square_gf2_test_circuit, gkr_square_mixed_degree_test_circuit};
+use crate::{
+    utils::*, GF2ExtConfigSha2Orion, GF2ExtConfigSha2RawSquare, M31x16ConfigSha2OrionVanilla,
+    M31x16ConfigSha2RawSquare, Prover,
+};
+
+/// The file `prove_on_env_backend` writes the proofs to
+const PROOF_OUT_ENV: &str = "EXPANDER_TEST_PROOF_OUT";
+
+fn prove<Cfg: GKREngine>(
+    circuit: &mut Circuit<Cfg::FieldConfig>,
+    mpi_config: &MPIConfig,
+) -> Vec<u8> {
+    circuit.evaluate();
+
+    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            mpi_config,
+        );
+
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(circuit);
+    let (_, proof) = prover.prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch);
+    proof.bytes
+}
+
+fn prove_keccak<Cfg: GKREngine>(
+    circuit_path: &str,
+    witness_path: &str,
+    mpi_config: &MPIConfig,
+) -> Vec<u8> {
+    let mut circuit = Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
+        &("../".to_owned() + circuit_path),
+    );
+    circuit.load_witness_allow_padding_testing_only(&("../".to_owned() + witness_path), mpi_config);
+    prove::<Cfg>(&mut circuit, mpi_config)
+}
+
+/// The proofs of circuits running all the dispatched hot paths
+fn simd_backend_test_proofs() -> Vec<u8> {
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut proofs = vec![];
+
+    // the circuit evaluation, the product gates and the Orion encoding
+    proofs.extend(prove_keccak::<M31x16ConfigSha2OrionVanilla>(
+        KECCAK_M31_CIRCUIT,
+        KECCAK_M31_WITNESS,
+        &mpi_config,
+    ));
+    proofs.extend(prove_keccak::<GF2ExtConfigSha2Orion>(
+        KECCAK_GF2_CIRCUIT,
+        KECCAK_GF2_WITNESS,
+        &mpi_config,
+    ));
+
+    // the power gates and the SIMD gates of GKR square
+    let mut circuit = gkr_square_mixed_degree_test_circuit::<M31x16Config>();
+    circuit.layers[0].input_vals = (0..4u32)
+        .map(|i| {
+            let vals = (0..16).map(|j| (i * 16 + j + 3).into()).collect::<Vec<_>>();
+            <M31x16Config as FieldEngine>::SimdCircuitField::pack(&vals)
+        })
+        .collect();
+    proofs.extend(prove::<M31x16ConfigSha2RawSquare>(
+        &mut circuit,
+        &mpi_config,
+    ));
+
+    let mut circuit = gkr_square_gf2_test_circuit::<GF2ExtConfig>();
+    circuit.layers[0].input_vals = (0..4u32)
+        .map(|i| {
+            let bits = (0..8)
+                .map(|j| ((i * 3 + j * 5) % 3).into())
+                .collect::<Vec<_>>();
+            <GF2ExtConfig as FieldEngine>::SimdCircuitField::pack(&bits)
+        })
+        .collect();
+    proofs.extend(prove::<GF2ExtConfigSha2RawSquare>(
+        &mut circuit,
+        &mpi_config,
+    ));
+
+    proofs
+}
+
+/// Run by `test_simd_backends_prove_the_same` in a child process, on the backend of
+/// `SIMD_BACKEND_ENV`
+#[test]
+#[ignore]
+fn prove_on_env_backend() {
+    let Ok(path) = env::var(PROOF_OUT_ENV) else {
+        return;
+    };
+    fs::write(path, simd_backend_test_proofs()).unwrap();
+}
+
+#[test]
+fn test_simd_backends_prove_the_same() {
+    if !SimdBackend::AVX512_DISPATCH || SimdBackend::detect() != SimdBackend::Avx512 {
+        println!("Skipped: the AVX-512 fields are not dispatched at runtime on this build or CPU");
+        return;
+    }
+
+    let proofs = |backend: SimdBackend| {
+        let path = env::temp_dir().join(format!(
+            "expander_{}_{}.proof",
+            backend.name(),
+            std::process::id()
+        ));
+        let status = Command::new(env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tests::simd_backends::prove_on_env_backend",
+                "--ignored",
+            ])
+            .env(SIMD_BACKEND_ENV, backend.name())
+            .env(PROOF_OUT_ENV, &path)
+            .status()
+            .unwrap();
+        assert!(status.success(), "proving on {backend} failed");
+
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        bytes
+    };
+
+    let avx256_proofs = proofs(SimdBackend::Avx256);
+    assert!(!avx256_proofs.is_empty());
+    assert_eq!(avx256_proofs, proofs(SimdBackend::Avx512));
+}
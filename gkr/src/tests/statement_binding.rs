@@ -0,0 +1,156 @@
+use arith::Field;
+use config_macros::declare_gkr_config;
+use gkr_engine::{
+    FieldEngine, GKREngine, GKRScheme, M31x16Config, MPIConfig, Proof, StatementBinding, Transcript,
+};
+use gkr_hashers::SHA256hasher;
+use poly_commit::{expander_pcs_init_testing_only, RawExpanderGKR};
+use transcript::BytesHashTranscript;
+
+use crate::{
+    absorb_claimed_v, absorb_public_input, utils::statement_binding_test_circuit, Prover, Verifier,
+};
+
+#[test]
+fn test_statement_binding() {
+    declare_gkr_config!(
+        Cfg,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    type FieldConfig = <Cfg<'static> as GKREngine>::FieldConfig;
+    type SimdF = <FieldConfig as FieldEngine>::SimdCircuitField;
+    type ChallengeF = <FieldConfig as FieldEngine>::ChallengeField;
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit = statement_binding_test_circuit::<FieldConfig>();
+    let public_input = circuit.public_input.clone();
+    let wrong_public_input = vec![public_input[0] + SimdF::ONE];
+
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<
+            FieldConfig,
+            <Cfg as GKREngine>::PCSField,
+            <Cfg as GKREngine>::PCSConfig,
+        >(circuit.log_input_size(), &mpi_config);
+
+    let mut prove = |circuit: &mut Circuit<_>, statement_binding| {
+        let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+        prover.statement_binding = statement_binding;
+        prover.prepare_mem(circuit);
+        prover.prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch)
+    };
+    let verify = |circuit: &mut Circuit<_>,
+                  statement_binding,
+                  public_input: &[SimdF],
+                  claimed_v: &ChallengeF,
+                  proof: &Proof| {
+        let mut verifier = Verifier::<Cfg>::new(mpi_config.clone());
+        verifier.statement_binding = statement_binding;
+        verifier.verify(
+            circuit,
+            public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+
+    let (claimed_v, proof) = prove(&mut circuit, StatementBinding::Full);
+    let (legacy_claimed_v, legacy_proof) = prove(&mut circuit, StatementBinding::Legacy);
+    assert_eq!(proof.bytes.len(), legacy_proof.bytes.len());
+    assert_ne!(proof.bytes, legacy_proof.bytes);
+
+    assert!(verify(
+        &mut circuit,
+        StatementBinding::Full,
+        &public_input,
+        &claimed_v,
+        &proof
+    ));
+    assert!(!verify(
+        &mut circuit,
+        StatementBinding::Full,
+        &wrong_public_input,
+        &claimed_v,
+        &proof
+    ));
+    assert!(!verify(
+        &mut circuit,
+        StatementBinding::Full,
+        &public_input,
+        &(claimed_v + ChallengeF::ONE),
+        &proof
+    ));
+
+    // old proofs only verify with the legacy binding
+    assert!(verify(
+        &mut circuit,
+        StatementBinding::Legacy,
+        &public_input,
+        &legacy_claimed_v,
+        &legacy_proof
+    ));
+    assert!(!verify(
+        &mut circuit,
+        StatementBinding::Full,
+        &public_input,
+        &legacy_claimed_v,
+        &legacy_proof
+    ));
+    assert!(!verify(
+        &mut circuit,
+        StatementBinding::Legacy,
+        &public_input,
+        &claimed_v,
+        &proof
+    ));
+}
+
+#[test]
+fn test_statement_is_absorbed_into_transcript() {
+    type FieldConfig = M31x16Config;
+    type SimdF = <FieldConfig as FieldEngine>::SimdCircuitField;
+    type ChallengeF = <FieldConfig as FieldEngine>::ChallengeField;
+
+    // the challenge following the statement, and the proof bytes written along the way
+    let challenge_after = |statement_binding, public_input: &[SimdF], claimed_v: &ChallengeF| {
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        transcript.append_u8_slice(&[1, 2, 3]);
+        absorb_public_input::<FieldConfig>(statement_binding, public_input, &mut transcript);
+        let r = transcript.generate_field_element::<ChallengeF>();
+        absorb_claimed_v::<FieldConfig>(statement_binding, claimed_v, &mut transcript);
+        let challenges = (r, transcript.generate_field_element::<ChallengeF>());
+        (challenges, transcript.finalize_and_get_proof().bytes)
+    };
+
+    let public_input = [SimdF::from(13)];
+    let wrong_public_input = [SimdF::from(14)];
+    let claimed_v = ChallengeF::from(5);
+    let wrong_claimed_v = ChallengeF::from(6);
+
+    let (full, full_proof) = challenge_after(StatementBinding::Full, &public_input, &claimed_v);
+    let (legacy, legacy_proof) =
+        challenge_after(StatementBinding::Legacy, &public_input, &claimed_v);
+    // the statement is hashed into the transcript without being written into the proof
+    assert_eq!(full_proof, legacy_proof);
+    assert_ne!(full, legacy);
+
+    // under the full binding, the challenges depend on the public input and the claimed value
+    let (r, _) = challenge_after(StatementBinding::Full, &wrong_public_input, &claimed_v);
+    assert_ne!(r.0, full.0);
+    let (r, _) = challenge_after(StatementBinding::Full, &public_input, &wrong_claimed_v);
+    assert_eq!(r.0, full.0);
+    assert_ne!(r.1, full.1);
+
+    // under the legacy binding, they do not
+    let (r, _) = challenge_after(
+        StatementBinding::Legacy,
+        &wrong_public_input,
+        &wrong_claimed_v,
+    );
+    assert_eq!(r, legacy);
+}
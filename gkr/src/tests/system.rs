@@ -2,17 +2,38 @@
 fn test_mutually_exclusive_flags() {
     let mut enabled_ctr = 0;
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
     {
         enabled_ctr += 1;
     }
 
-    #[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f"),
+        not(feature = "portable")
+    ))]
     {
         enabled_ctr += 1;
     }
 
-    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        target_feature = "avx512f",
+        not(feature = "portable")
+    ))]
+    {
+        enabled_ctr += 1;
+    }
+
+    #[cfg(any(
+        feature = "portable",
+        not(any(
+            target_arch = "aarch64",
+            all(target_arch = "x86_64", target_feature = "avx2")
+        ))
+    ))]
     {
         enabled_ctr += 1;
     }
@@ -0,0 +1,193 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul};
+use config_macros::declare_gkr_config;
+use gkr_engine::{BN254Config, FieldEngine, GKREngine, GKRScheme, MPIConfig, Proof, VerifyError};
+use gkr_hashers::SHA256hasher;
+use halo2curves::bn256::G1Affine;
+use poly_commit::{expander_pcs_init_testing_only, HidingHyraxPCS};
+use transcript::BytesHashTranscript;
+
+use crate::{Prover, Verifier};
+
+/// A two layer circuit with a public input, the output layer having no mul gates:
+/// ```text
+///   N_1_0 = N_2_0 * N_2_1
+///   N_1_1 = N_2_2 + PI[0]
+///   N_1_2 = N_2_3
+///   N_1_3 = N_2_0 * N_2_3
+///
+///   N_0_0 = N_1_0 + N_1_3
+///   N_0_1 = N_1_2
+/// ```
+fn zk_test_circuit<C: FieldEngine>() -> Circuit<C> {
+    let mut circuit = Circuit::default();
+
+    let mut layer = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 2,
+        ..Default::default()
+    };
+    for (i_ids, o_id) in [([0, 1], 0), ([0, 3], 3)] {
+        layer.mul.push(GateMul {
+            i_ids,
+            o_id,
+            coef: C::CircuitField::ONE,
+            coef_type: CoefType::Constant,
+            gate_type: 0,
+        });
+    }
+    for (i_id, o_id) in [(2, 1), (3, 2)] {
+        layer.add.push(GateAdd {
+            i_ids: [i_id],
+            o_id,
+            coef: C::CircuitField::ONE,
+            coef_type: CoefType::Constant,
+            gate_type: 1,
+        });
+    }
+    layer.const_.push(GateConst {
+        i_ids: [],
+        o_id: 1,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::PublicInput(0),
+        gate_type: 2,
+    });
+    circuit.layers.push(layer);
+
+    let mut layer = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    for (i_id, o_id) in [(0, 0), (3, 0), (2, 1)] {
+        layer.add.push(GateAdd {
+            i_ids: [i_id],
+            o_id,
+            coef: C::CircuitField::ONE,
+            coef_type: CoefType::Constant,
+            gate_type: 1,
+        });
+    }
+    circuit.layers.push(layer);
+
+    circuit.layers[0].input_vals = (0..4).map(|i| C::SimdCircuitField::from(i + 2)).collect();
+    circuit.public_input = vec![C::SimdCircuitField::from(13)];
+    circuit.identify_rnd_coefs();
+    circuit.identify_structure_info();
+    circuit
+}
+
+#[test]
+fn test_zk_gkr() {
+    declare_gkr_config!(
+        Cfg,
+        FieldType::BN254,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::HidingHyrax,
+        GKRScheme::ZkVanilla,
+    );
+    type FieldConfig = <Cfg<'static> as GKREngine>::FieldConfig;
+    type SimdF = <FieldConfig as FieldEngine>::SimdCircuitField;
+    type ChallengeF = <FieldConfig as FieldEngine>::ChallengeField;
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit = zk_test_circuit::<FieldConfig>();
+    let public_input = circuit.public_input.clone();
+
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<
+            FieldConfig,
+            <Cfg as GKREngine>::PCSField,
+            <Cfg as GKREngine>::PCSConfig,
+        >(circuit.log_input_size(), &mpi_config);
+
+    let mut prove = |circuit: &mut Circuit<_>| {
+        let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+        prover.prepare_mem(circuit);
+        prover
+            .prove_zk(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch)
+            .unwrap()
+    };
+    let verify = |circuit: &mut Circuit<_>,
+                  public_input: &[SimdF],
+                  claimed_v: &ChallengeF,
+                  proof: &Proof| {
+        let verifier = Verifier::<Cfg>::new(mpi_config.clone());
+        verifier.verify_zk(
+            circuit,
+            public_input,
+            claimed_v,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+
+    let (claimed_v, proof) = prove(&mut circuit);
+    assert_eq!(
+        verify(&mut circuit, &public_input, &claimed_v, &proof),
+        Ok(true)
+    );
+    assert!(matches!(
+        verify(
+            &mut circuit,
+            &[public_input[0] + SimdF::ONE],
+            &claimed_v,
+            &proof
+        ),
+        Err(e) if e.is_rejection()
+    ));
+    assert_eq!(
+        verify(
+            &mut circuit,
+            &public_input,
+            &(claimed_v + ChallengeF::ONE),
+            &proof
+        ),
+        Err(VerifyError::ClaimMismatch {
+            layer: circuit.layers.len() - 1
+        })
+    );
+
+    // the proof is randomized by the blinding factors
+    let (other_claimed_v, other_proof) = prove(&mut circuit);
+    assert_eq!(
+        verify(&mut circuit, &public_input, &other_claimed_v, &other_proof),
+        Ok(true)
+    );
+    assert_ne!(proof.bytes, other_proof.bytes);
+
+    let mut tampered_proof = proof.clone();
+    let n_bytes = tampered_proof.bytes.len();
+    tampered_proof.bytes[n_bytes - 40] ^= 1;
+    assert_ne!(
+        verify(&mut circuit, &public_input, &claimed_v, &tampered_proof),
+        Ok(true)
+    );
+
+    // malformed proofs are rejected
+    let mut trailing_proof = proof.clone();
+    trailing_proof.bytes.push(0);
+    assert_eq!(
+        verify(&mut circuit, &public_input, &claimed_v, &trailing_proof),
+        Err(VerifyError::TrailingBytes(1))
+    );
+    let truncated_proof = Proof {
+        bytes: proof.bytes[..n_bytes - 1].to_vec(),
+    };
+    assert_eq!(
+        verify(&mut circuit, &public_input, &claimed_v, &truncated_proof),
+        Err(VerifyError::TruncatedProof)
+    );
+
+    // the zero-knowledge GKR is only proven in a single process
+    assert_eq!(
+        Verifier::<Cfg>::new(MPIConfig::verifier_new(2)).verify_zk(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &pcs_verification_key,
+            &proof,
+        ),
+        Err(VerifyError::UnsupportedWorldSize(2))
+    );
+}
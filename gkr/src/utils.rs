@@ -1,6 +1,10 @@
 use std::fs;
 use std::process::Command;
 
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul};
+use gkr_engine::FieldEngine;
+
 const DATA_PREFIX: &str = "data/";
 
 // circuit for repeating Keccak for 2 times
@@ -132,3 +136,46 @@ pub fn dev_env_data_setup() {
     download_and_store(POSEIDON_CIRCUIT_M31_URL, POSEIDON_M31_CIRCUIT);
     download_and_store(POSEIDON_WITNESS_M31_URL, POSEIDON_M31_WITNESS);
 }
+
+/// A single layer circuit with a public input, with its inputs set, shared by the tests of the
+/// statement binding, of the batch verification and of the proving service:
+/// ```text
+///   N_0_0 = N_1_0 * N_1_1
+///   N_0_1 = N_1_2 + PI[0]
+/// ```
+pub fn statement_binding_test_circuit<C: FieldEngine>() -> Circuit<C> {
+    let mut circuit = Circuit::default();
+
+    let mut layer = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    layer.mul.push(GateMul {
+        i_ids: [0, 1],
+        o_id: 0,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    });
+    layer.add.push(GateAdd {
+        i_ids: [2],
+        o_id: 1,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 1,
+    });
+    layer.const_.push(GateConst {
+        i_ids: [],
+        o_id: 1,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::PublicInput(0),
+        gate_type: 2,
+    });
+    circuit.layers.push(layer);
+
+    circuit.layers[0].input_vals = (0..4).map(|i| C::SimdCircuitField::from(i + 2)).collect();
+    circuit.public_input = vec![C::SimdCircuitField::from(13)];
+    circuit.identify_rnd_coefs();
+    circuit
+}
@@ -10,5 +10,8 @@ pub use gkr_vanilla::gkr_verify;
 mod gkr_square;
 pub use gkr_square::gkr_square_verify;
 
+mod gkr_zk;
+pub use gkr_zk::gkr_zk_verify;
+
 mod snark;
 pub use snark::Verifier;
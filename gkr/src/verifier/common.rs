@@ -2,13 +2,20 @@ use std::{io::Read, vec};
 
 use arith::Field;
 use circuit::CircuitLayer;
-use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, Transcript};
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, Transcript, VerifyError};
 use serdes::ExpSerde;
 use sumcheck::{
     GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SIMD_MPI_DEGREE,
-    SUMCHECK_GKR_SQUARE_DEGREE,
+    SUMCHECK_GKR_SQUARE_MAX_DEGREE,
 };
 
+/// Read the next field element of a sumcheck from the proof.
+#[inline(always)]
+pub fn read_sumcheck_field<F: Field>(proof_reader: impl Read) -> Result<F, VerifyError> {
+    F::deserialize_from(proof_reader)
+        .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedSumcheckMessage))
+}
+
 #[inline(always)]
 pub fn verify_sumcheck_step<F: FieldEngine>(
     mut proof_reader: impl Read,
@@ -17,10 +24,10 @@ pub fn verify_sumcheck_step<F: FieldEngine>(
     claimed_sum: &mut F::ChallengeField,
     randomness_vec: &mut Vec<F::ChallengeField>,
     sp: &VerifierScratchPad<F>,
-) -> bool {
+) -> Result<bool, VerifyError> {
     let mut ps = vec![];
     for i in 0..(degree + 1) {
-        ps.push(F::ChallengeField::deserialize_from(&mut proof_reader).unwrap());
+        ps.push(read_sumcheck_field::<F::ChallengeField>(&mut proof_reader)?);
         transcript.append_field_element(&ps[i]);
     }
 
@@ -29,19 +36,19 @@ pub fn verify_sumcheck_step<F: FieldEngine>(
 
     let verified = (ps[0] + ps[1]) == *claimed_sum;
 
-    // This assumes SUMCHECK_GKR_DEGREE == 2, SUMCHECK_GKR_SIMD_MPI_DEGREE == 3,
-    // SUMCHECK_GKR_SQUARE_DEGREE == 6
+    // This assumes SUMCHECK_GKR_DEGREE == 2, SUMCHECK_GKR_SIMD_MPI_DEGREE == 3, the GKR square
+    // layers having any degree up to SUMCHECK_GKR_SQUARE_MAX_DEGREE
     if degree == SUMCHECK_GKR_DEGREE {
         *claimed_sum = GKRVerifierHelper::degree_2_eval(&ps, r, sp);
     } else if degree == SUMCHECK_GKR_SIMD_MPI_DEGREE {
         *claimed_sum = GKRVerifierHelper::degree_3_eval(&ps, r, sp);
-    } else if degree == SUMCHECK_GKR_SQUARE_DEGREE {
-        *claimed_sum = GKRVerifierHelper::degree_6_eval(&ps, r, sp);
+    } else if degree <= SUMCHECK_GKR_SQUARE_MAX_DEGREE {
+        *claimed_sum = GKRVerifierHelper::degree_n_eval(&ps, r, sp);
     } else {
         panic!("unsupported degree");
     }
 
-    verified
+    Ok(verified)
 }
 
 // todo: FIXME
@@ -61,7 +68,7 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
     sp: &mut VerifierScratchPad<F>,
     is_output_layer: bool,
     parallel_verify: bool,
-) -> bool {
+) -> Result<bool, VerifyError> {
     assert_eq!(challenge.rz_1.is_none(), claimed_v1.is_none());
     assert_eq!(challenge.rz_1.is_none(), alpha.is_none());
 
@@ -96,7 +103,7 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
             &mut sum,
             &mut rx,
             sp,
-        );
+        )?;
         // println!("x {} var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_rx(&rx, sp);
@@ -109,7 +116,7 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
             &mut sum,
             &mut r_simd_xy,
             sp,
-        );
+        )?;
         // println!("{} simd var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
@@ -122,12 +129,12 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
             &mut sum,
             &mut r_mpi_xy,
             sp,
-        );
+        )?;
         // println!("{} mpi var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_mpi_xy(&r_mpi_xy, sp);
 
-    let vx_claim = F::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+    let vx_claim = read_sumcheck_field::<F::ChallengeField>(&mut proof_reader)?;
 
     sum -= vx_claim * GKRVerifierHelper::eval_add(&layer.add, sp);
     transcript.append_field_element(&vx_claim);
@@ -142,12 +149,12 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
                 &mut sum,
                 ry.as_mut().unwrap(),
                 sp,
-            );
+            )?;
             // println!("y {} var, verified? {}", _i_var, verified);
         }
         GKRVerifierHelper::set_ry(ry.as_ref().unwrap(), sp);
 
-        let vy_claim = F::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+        let vy_claim = read_sumcheck_field::<F::ChallengeField>(&mut proof_reader)?;
         transcript.append_field_element(&vy_claim);
         verified &= sum == vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, sp);
         Some(vy_claim)
@@ -160,5 +167,5 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
     *claimed_v0 = vx_claim;
     *claimed_v1 = vy_claim;
 
-    verified
+    Ok(verified)
 }
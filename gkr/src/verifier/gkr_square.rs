@@ -1,13 +1,32 @@
-use super::verify_sumcheck_step;
+use super::{read_sumcheck_field, verify_sumcheck_step};
 use arith::Field;
 use ark_std::{end_timer, start_timer};
 use circuit::{Circuit, CircuitLayer};
 use gkr_engine::{
-    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, FieldType, Transcript,
+    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, StatementBinding,
+    Transcript, VerifyError,
 };
-use serdes::ExpSerde;
 use std::io::Read;
-use sumcheck::{GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_SQUARE_DEGREE};
+use sumcheck::{
+    gkr_square_supports_layer, sumcheck_gkr_square_degree, GKRVerifierHelper, VerifierScratchPad,
+};
+
+use crate::absorb_claimed_v;
+
+/// Fails with `VerifyError::UnsupportedCircuit` on the first layer whose uni gates GKR square
+/// does not support, i.e., the gates other than the identity and the square over GF2.
+pub(crate) fn gkr_square_check_circuit<C: FieldEngine>(
+    circuit: &Circuit<C>,
+) -> Result<(), VerifyError> {
+    match circuit
+        .layers
+        .iter()
+        .position(|layer| !gkr_square_supports_layer(layer))
+    {
+        Some(layer) => Err(VerifyError::UnsupportedCircuit { layer }),
+        None => Ok(()),
+    }
+}
 
 #[allow(clippy::type_complexity)]
 pub fn gkr_square_verify<C: FieldEngine>(
@@ -17,12 +36,9 @@ pub fn gkr_square_verify<C: FieldEngine>(
     claimed_v: &C::ChallengeField,
     transcript: &mut impl Transcript,
     mut proof_reader: impl Read,
-) -> (bool, ExpanderSingleVarChallenge<C>, C::ChallengeField) {
-    assert_ne!(
-        C::FIELD_TYPE,
-        FieldType::GF2Ext128,
-        "GF2 is not supported in GKR^2"
-    );
+    statement_binding: StatementBinding,
+) -> Result<(ExpanderSingleVarChallenge<C>, C::ChallengeField), VerifyError> {
+    gkr_square_check_circuit(circuit)?;
 
     let timer = start_timer!(|| "gkr verify");
     let mut sp = VerifierScratchPad::<C>::new(circuit, proving_time_mpi_size);
@@ -34,16 +50,16 @@ pub fn gkr_square_verify<C: FieldEngine>(
         circuit.layers.last().unwrap().output_var_num,
         proving_time_mpi_size,
     );
+    absorb_claimed_v::<C>(statement_binding, claimed_v, transcript);
 
     log::trace!("Initial rz0: {:?}", challenge.rz);
     log::trace!("Initial r_simd: {:?}", challenge.r_simd);
     log::trace!("Initial r_mpi: {:?}", challenge.r_mpi);
 
-    let mut verified = true;
     let mut current_claim = *claimed_v;
     log::trace!("Starting claim: {current_claim:?}",);
     for i in (0..layer_num).rev() {
-        let cur_verified = sumcheck_verify_gkr_square_layer(
+        let verified = sumcheck_verify_gkr_square_layer(
             proving_time_mpi_size,
             &circuit.layers[i],
             public_input,
@@ -54,12 +70,14 @@ pub fn gkr_square_verify<C: FieldEngine>(
             &mut sp,
             i == layer_num - 1,
             false,
-        );
-        log::trace!("Layer {i} verified? {cur_verified}");
-        verified &= cur_verified;
+        )?;
+        log::trace!("Layer {i} verified? {verified}");
+        if !verified {
+            return Err(VerifyError::ClaimMismatch { layer: i });
+        }
     }
     end_timer!(timer);
-    (verified, challenge, current_claim)
+    Ok((challenge, current_claim))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -76,9 +94,9 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
     sp: &mut VerifierScratchPad<C>,
     is_output_layer: bool,
     parallel_verify: bool,
-) -> bool {
-    // GKR2 with Power5 gate has degree 6 polynomial
-    let degree = SUMCHECK_GKR_SQUARE_DEGREE;
+) -> Result<bool, VerifyError> {
+    // e.g. GKR2 with Power5 gates has degree 6 polynomials
+    let degree = sumcheck_gkr_square_degree(layer);
 
     let dual_challenge = ExpanderDualVarChallenge::from(challenge.clone());
 
@@ -103,7 +121,7 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
             &mut sum,
             &mut challenge.rz,
             sp,
-        );
+        )?;
         log::trace!("x {i_var} var, verified? {verified}");
     }
     GKRVerifierHelper::set_rx(&challenge.rz, sp);
@@ -116,7 +134,7 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
             &mut sum,
             &mut challenge.r_simd,
             sp,
-        );
+        )?;
         log::trace!("simd {i_var} var, verified? {verified}");
     }
     GKRVerifierHelper::set_r_simd_xy(&challenge.r_simd, sp);
@@ -129,20 +147,22 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
             &mut sum,
             &mut challenge.r_mpi,
             sp,
-        );
+        )?;
         log::trace!("{_i_var} mpi var, verified? {verified}");
     }
     GKRVerifierHelper::set_r_mpi_xy(&challenge.r_mpi, sp);
 
-    let v_claim = C::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+    let v_claim = read_sumcheck_field::<C::ChallengeField>(&mut proof_reader)?;
     log::trace!("v_claim: {v_claim:?}");
 
-    sum -= v_claim * GKRVerifierHelper::eval_pow_1(&layer.uni, sp)
-        + v_claim.exp(5) * GKRVerifierHelper::eval_pow_5(&layer.uni, sp);
+    for uni_degree in layer.uni_degrees() {
+        sum -= v_claim.exp(uni_degree as u128)
+            * GKRVerifierHelper::eval_uni(&layer.uni, uni_degree, sp);
+    }
     transcript.append_field_element(&v_claim);
 
     verified &= sum == C::ChallengeField::ZERO;
 
     *current_claim = v_claim;
-    verified
+    Ok(verified)
 }
@@ -1,12 +1,21 @@
 use std::io::Read;
 
 use circuit::Circuit;
-use gkr_engine::{ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, Transcript};
+use gkr_engine::{
+    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, StatementBinding,
+    Transcript, VerifyError,
+};
 use sumcheck::VerifierScratchPad;
 use utils::timer::Timer;
 
 use super::common::sumcheck_verify_gkr_layer;
+use crate::absorb_claimed_v;
 
+/// Verify the GKR sumchecks of all the layers, reading them from `proof_reader`.
+///
+/// Fails if the proof ends early or contains a malformed field element, or with
+/// `VerifyError::ClaimMismatch` at the first layer, from the output layer, whose sumcheck does not
+/// verify.
 #[allow(clippy::type_complexity)]
 pub fn gkr_verify<F: FieldEngine>(
     proving_time_mpi_size: usize,
@@ -15,12 +24,15 @@ pub fn gkr_verify<F: FieldEngine>(
     claimed_v: &F::ChallengeField,
     transcript: &mut impl Transcript,
     mut proof_reader: impl Read,
-) -> (
-    bool,
-    ExpanderDualVarChallenge<F>,
-    F::ChallengeField,
-    Option<F::ChallengeField>,
-) {
+    statement_binding: StatementBinding,
+) -> Result<
+    (
+        ExpanderDualVarChallenge<F>,
+        F::ChallengeField,
+        Option<F::ChallengeField>,
+    ),
+    VerifyError,
+> {
     let timer = Timer::new("gkr_verify", true);
     let mut sp = VerifierScratchPad::<F>::new(circuit, proving_time_mpi_size);
 
@@ -32,14 +44,14 @@ pub fn gkr_verify<F: FieldEngine>(
         proving_time_mpi_size,
     )
     .into();
+    absorb_claimed_v::<F>(statement_binding, claimed_v, transcript);
 
     let mut alpha = None;
     let mut claimed_v0 = *claimed_v;
     let mut claimed_v1 = None;
 
-    let mut verified = true;
     for i in (0..layer_num).rev() {
-        let cur_verified = sumcheck_verify_gkr_layer(
+        let verified = sumcheck_verify_gkr_layer(
             proving_time_mpi_size,
             &circuit.layers[i],
             public_input,
@@ -52,9 +64,11 @@ pub fn gkr_verify<F: FieldEngine>(
             &mut sp,
             i == layer_num - 1,
             false,
-        );
+        )?;
+        if !verified {
+            return Err(VerifyError::ClaimMismatch { layer: i });
+        }
 
-        verified &= cur_verified;
         alpha = if challenge.rz_1.is_some() {
             Some(transcript.generate_field_element::<F::ChallengeField>())
         } else {
@@ -69,5 +83,5 @@ pub fn gkr_verify<F: FieldEngine>(
         challenge.r_mpi,
     );
 
-    (verified, challenge, claimed_v0, claimed_v1)
+    Ok((challenge, claimed_v0, claimed_v1))
 }
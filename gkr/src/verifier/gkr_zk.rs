@@ -0,0 +1,207 @@
+use std::io::Read;
+
+use arith::ExtensionField;
+use circuit::{Circuit, CircuitLayer};
+use gkr_engine::{
+    ExpErrors, ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, StatementBinding,
+    Transcript, VerifyError,
+};
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
+use poly_commit::{HidingPedersenParams, ProductProof, ZeroProof};
+use serdes::ExpSerde;
+use sumcheck::{
+    GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SIMD_MPI_DEGREE,
+};
+use utils::timer::Timer;
+
+use crate::{absorb_claimed_v, next_claim_commitment, read_point, verify_with_proof_locked};
+
+/// Read the commitments to a round polynomial, and update the commitment to the claim.
+/// Returns the challenge of the round.
+#[inline(always)]
+fn verify_zk_sumcheck_step<C: CurveAffine + ExpSerde>(
+    mut proof_reader: impl Read,
+    degree: usize,
+    transcript: &mut impl Transcript,
+    claim_comm: &mut C::Curve,
+) -> Result<C::Scalar, ExpErrors>
+where
+    C::Scalar: ExtensionField + PrimeField,
+{
+    let coeff_comms = (0..degree)
+        .map(|_| read_point::<C>(&mut proof_reader, transcript))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let r = transcript.generate_field_element::<C::Scalar>();
+    *claim_comm = next_claim_commitment(claim_comm, &coeff_comms, r);
+    Ok(r)
+}
+
+/// Verify the sumcheck of a layer, given the commitment to its claim.
+/// Returns the commitments to the claims on the next layer, or `None` if the proof is rejected,
+/// and fails if the proof is malformed.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn sumcheck_zk_verify_gkr_layer<C, F>(
+    layer: &CircuitLayer<F>,
+    public_input: &[F::SimdCircuitField],
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    mut claim_comm: C::Curve,
+    alpha: Option<F::ChallengeField>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &mut VerifierScratchPad<F>,
+    pedersen_params: &HidingPedersenParams<C>,
+    is_output_layer: bool,
+) -> Result<Option<(C::Curve, Option<C::Curve>)>, ExpErrors>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    F: FieldEngine<ChallengeField = C::Scalar>,
+{
+    GKRVerifierHelper::prepare_layer(layer, &alpha, challenge, sp, is_output_layer);
+
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    claim_comm -=
+        pedersen_params.value_base * GKRVerifierHelper::eval_cst(&layer.const_, public_input, sp);
+
+    let rx = (0..layer.input_var_num)
+        .map(|_| {
+            verify_zk_sumcheck_step::<C>(
+                &mut proof_reader,
+                SUMCHECK_GKR_DEGREE,
+                transcript,
+                &mut claim_comm,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    GKRVerifierHelper::set_rx(&rx, sp);
+
+    let r_simd_xy = (0..simd_var_num)
+        .map(|_| {
+            verify_zk_sumcheck_step::<C>(
+                &mut proof_reader,
+                SUMCHECK_GKR_SIMD_MPI_DEGREE,
+                transcript,
+                &mut claim_comm,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
+    GKRVerifierHelper::set_r_mpi_xy(&[], sp);
+
+    let vx_comm = read_point::<C>(&mut proof_reader, transcript)?.to_curve();
+    claim_comm -= vx_comm * GKRVerifierHelper::eval_add(&layer.add, sp);
+
+    let mut verified = true;
+    let mut ry = None;
+    let mut vy_comm = None;
+    if !layer.structure_info.skip_sumcheck_phase_two {
+        let r = (0..layer.input_var_num)
+            .map(|_| {
+                verify_zk_sumcheck_step::<C>(
+                    &mut proof_reader,
+                    SUMCHECK_GKR_DEGREE,
+                    transcript,
+                    &mut claim_comm,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        GKRVerifierHelper::set_ry(&r, sp);
+        ry = Some(r);
+
+        let vy = read_point::<C>(&mut proof_reader, transcript)?.to_curve();
+        let vxy_comm = read_point::<C>(&mut proof_reader, transcript)?.to_curve();
+        verified &= verify_with_proof_locked(
+            &mut proof_reader,
+            transcript,
+            |proof: &ProductProof<C>, transcript| {
+                proof.verify(pedersen_params, &vx_comm, &vy, &vxy_comm, transcript)
+            },
+        )?;
+
+        claim_comm -= vxy_comm * GKRVerifierHelper::eval_mul(&layer.mul, sp);
+        vy_comm = Some(vy);
+    }
+
+    // what is left of the claim commits to zero
+    verified &= verify_with_proof_locked(
+        &mut proof_reader,
+        transcript,
+        |proof: &ZeroProof<C>, transcript| proof.verify(pedersen_params, &claim_comm, transcript),
+    )?;
+
+    *challenge = ExpanderDualVarChallenge::new(rx, ry, r_simd_xy, vec![]);
+    Ok(verified.then_some((vx_comm, vy_comm)))
+}
+
+/// Verify the zero-knowledge GKR IOP, see `crate::zk`.
+/// Returns the challenge on the input layer and the commitments to the claims at the challenge,
+/// and fails if the proof is malformed, or with `VerifyError::ClaimMismatch` at the first layer,
+/// from the output layer, that does not verify.
+#[allow(clippy::type_complexity)]
+pub fn gkr_zk_verify<C, F>(
+    circuit: &Circuit<F>,
+    public_input: &[F::SimdCircuitField],
+    claimed_v: &F::ChallengeField,
+    transcript: &mut impl Transcript,
+    mut proof_reader: impl Read,
+    statement_binding: StatementBinding,
+    pedersen_params: &HidingPedersenParams<C>,
+) -> Result<(ExpanderDualVarChallenge<F>, C::Curve, Option<C::Curve>), ExpErrors>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    F: FieldEngine<ChallengeField = C::Scalar>,
+{
+    let timer = Timer::new("gkr_zk_verify", true);
+    let mut sp = VerifierScratchPad::<F>::new(circuit, 1);
+
+    let layer_num = circuit.layers.len();
+
+    let mut challenge = ExpanderSingleVarChallenge::sample_from_transcript(
+        transcript,
+        circuit.layers.last().unwrap().output_var_num,
+        1,
+    )
+    .into();
+    absorb_claimed_v::<F>(statement_binding, claimed_v, transcript);
+
+    // the claimed output is public, hence committed to with a zero blinding factor
+    let mut alpha = None;
+    let mut claim_comm = pedersen_params.value_base * *claimed_v;
+    let mut claim_comms = (claim_comm, None);
+    for i in (0..layer_num).rev() {
+        let Some(comms) = sumcheck_zk_verify_gkr_layer(
+            &circuit.layers[i],
+            public_input,
+            &mut challenge,
+            claim_comm,
+            alpha,
+            &mut proof_reader,
+            transcript,
+            &mut sp,
+            pedersen_params,
+            i == layer_num - 1,
+        )?
+        else {
+            return Err(VerifyError::ClaimMismatch { layer: i }.into());
+        };
+        claim_comms = comms;
+
+        let (vx_comm, vy_comm) = claim_comms;
+        if let Some(vy_comm) = vy_comm {
+            let a = transcript.generate_field_element::<F::ChallengeField>();
+            claim_comm = vx_comm + vy_comm * a;
+            alpha = Some(a);
+        } else {
+            claim_comm = vx_comm;
+            alpha = None;
+        }
+    }
+    timer.stop();
+
+    Ok((challenge, claim_comms.0, claim_comms.1))
+}
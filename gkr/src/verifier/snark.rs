@@ -4,27 +4,47 @@ use std::{
     vec,
 };
 
-use super::gkr_square::sumcheck_verify_gkr_square_layer;
+#[cfg(feature = "rayon")]
+use super::gkr_square::{gkr_square_check_circuit, sumcheck_verify_gkr_square_layer};
+use arith::ExtensionField;
 use circuit::Circuit;
 use gkr_engine::{
     ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, MPIConfig,
-    MPIEngine, Proof, StructuredReferenceString, Transcript,
+    MPIEngine, Proof, StatementBinding, StructuredReferenceString, Transcript, VerifyError,
 };
-use rayon::iter::{
-    IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
+use poly_commit::{
+    hiding_hyrax_verify_eval, HidingHyraxEvalProof, HidingHyraxPCS, HidingPedersenParams,
+};
+#[cfg(feature = "rayon")]
+use rayon::{
+    iter::{
+        IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+        ParallelIterator,
+    },
+    slice::ParallelSlice,
 };
 use serdes::ExpSerde;
-use sumcheck::{VerifierScratchPad, SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SQUARE_DEGREE};
+#[cfg(feature = "rayon")]
+use sumcheck::VerifierScratchPad;
 use transcript::transcript_verifier_sync;
 use utils::timer::Timer;
 
 #[cfg(feature = "grinding")]
 use crate::grind;
-use crate::{gkr_square_verify, gkr_verify, parse_proof, sumcheck_verify_gkr_layer};
+use crate::{
+    absorb_public_input, assert_generic_scheme, gkr_square_verify, gkr_verify, gkr_zk_verify,
+    verify_with_proof_locked, zk_verify_error,
+};
+#[cfg(feature = "rayon")]
+use crate::{gkr_proof::round_degrees, parse_proof, sumcheck_verify_gkr_layer};
 
 #[derive(Default)]
 pub struct Verifier<'a, Cfg: GKREngine> {
     pub mpi_config: MPIConfig<'a>,
+    /// What the transcript is bound to, set it to `StatementBinding::Legacy` to verify proofs of
+    /// envelope version 1, see `ProofHeader::statement_binding`
+    pub statement_binding: StatementBinding,
     phantom: PhantomData<Cfg>,
 }
 
@@ -32,30 +52,40 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
     pub fn new(mpi_config: MPIConfig<'a>) -> Self {
         Self {
             mpi_config,
+            statement_binding: StatementBinding::default(),
             phantom: PhantomData,
         }
     }
 
     /// Prior to GKR, we need to do the following:
     /// 1. Parse the commitment from the proof reader and use that to initialize the transcript.
-    /// 2. (Optionally) grinding.
-    /// 3. Fill the circuit with random coefficients.
+    /// 2. Absorb the public inputs, unless the statement binding is legacy.
+    /// 3. (Optionally) grinding.
+    /// 4. Fill the circuit with random coefficients.
+    ///
+    /// Fails if the commitment does not deserialize.
     #[inline(always)]
     pub(crate) fn pre_gkr(
         &self,
         mut proof_reader: impl Read,
         circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
         transcript: &mut Cfg::TranscriptConfig,
         proving_time_mpi_size: usize,
-    ) -> <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Commitment {
+    ) -> Result<
+        <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Commitment,
+        VerifyError,
+    > {
         let timer = Timer::new("pre_gkr", true);
         let commitment =
             <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Commitment as ExpSerde>::deserialize_from(
                 &mut proof_reader,
             )
-            .unwrap();
+            .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedCommitment))?;
         let mut buffer = vec![];
-        commitment.serialize_into(&mut buffer).unwrap();
+        commitment
+            .serialize_into(&mut buffer)
+            .map_err(|_| VerifyError::MalformedCommitment)?;
 
         // this function will iteratively hash the commitment, and append the
         // final hash output to the transcript.
@@ -64,6 +94,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         // note that this function is almost identical to grind, except that grind uses a
         // fixed hasher, where as this function uses the transcript hasher
         transcript.append_commitment(&buffer);
+        absorb_public_input::<Cfg::FieldConfig>(self.statement_binding, public_input, transcript);
 
         // ZZ: shall we use probabilistic grinding so the verifier can avoid this cost?
         // (and also be recursion friendly)
@@ -75,13 +106,16 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
 
         timer.stop();
 
-        commitment
+        Ok(commitment)
     }
 
     /// Main body of the GKR verification.
     /// We have two schemes:
     /// 1. Vanilla GKR
     /// 2. GKR square: This is a dedicated scheme for the circuit that only contains pow gates.
+    ///
+    /// Fails if the proof ends early or contains a malformed field element, or with
+    /// `VerifyError::ClaimMismatch` if the sumcheck of a layer does not verify.
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
@@ -93,27 +127,31 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         proving_time_mpi_size: usize,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
-    ) -> (
-        bool,
-        ExpanderSingleVarChallenge<Cfg::FieldConfig>,
-        Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
-        <Cfg::FieldConfig as FieldEngine>::ChallengeField,
-        Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
-    ) {
+    ) -> Result<
+        (
+            ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+            Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
+            <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+            Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
+        ),
+        VerifyError,
+    > {
+        const { assert_generic_scheme::<Cfg>() };
+
         let timer = Timer::new("gkr", true);
-        let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
+        let (challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
             GKRScheme::Vanilla => {
-                let (gkr_verified, challenge, claim_x, claim_y) = gkr_verify(
+                let (challenge, claim_x, claim_y) = gkr_verify(
                     proving_time_mpi_size,
                     circuit,
                     public_input,
                     claimed_v,
                     transcript,
                     &mut proof_reader,
-                );
+                    self.statement_binding,
+                )?;
 
                 (
-                    gkr_verified,
                     challenge.challenge_x(),
                     challenge.challenge_y(),
                     claim_x,
@@ -121,27 +159,28 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                 )
             }
             GKRScheme::GkrSquare => {
-                let (gkr_verified, challenge_x, claim_x) = gkr_square_verify(
+                let (challenge_x, claim_x) = gkr_square_verify(
                     proving_time_mpi_size,
                     circuit,
                     public_input,
                     claimed_v,
                     transcript,
                     &mut proof_reader,
-                );
+                    self.statement_binding,
+                )?;
 
-                (gkr_verified, challenge_x, None, claim_x, None)
+                (challenge_x, None, claim_x, None)
             }
+            GKRScheme::ZkVanilla | GKRScheme::CrossLayer => unreachable!(),
         };
         transcript_verifier_sync(transcript, proving_time_mpi_size);
 
-        log::info!("GKR verification: {verified}");
-
         timer.stop();
-        (verified, challenge_x, challenge_y, claim_x, claim_y)
+        Ok((challenge_x, challenge_y, claim_x, claim_y))
     }
 
     /// Parallel version of the GKR verification.
+    #[cfg(feature = "rayon")]
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
@@ -153,73 +192,66 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         proving_time_mpi_size: usize,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
-    ) -> (
-        bool,
-        ExpanderSingleVarChallenge<Cfg::FieldConfig>,
-        Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
-        <Cfg::FieldConfig as FieldEngine>::ChallengeField,
-        Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
-    ) {
-        let parse_proof_timer = Timer::new("parse_proof", true);
-        let xy_var_degree = match Cfg::SCHEME {
-            GKRScheme::Vanilla => SUMCHECK_GKR_DEGREE,
-            GKRScheme::GkrSquare => SUMCHECK_GKR_SQUARE_DEGREE,
-        };
+    ) -> Result<
+        (
+            ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+            Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
+            <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+            Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
+        ),
+        VerifyError,
+    > {
+        const { assert_generic_scheme::<Cfg>() };
+        if matches!(Cfg::SCHEME, GKRScheme::GkrSquare) {
+            gkr_square_check_circuit(circuit)?;
+        }
 
+        let parse_proof_timer = Timer::new("parse_proof", true);
         let (mut verification_units, challenge, claim_x, claim_y) = parse_proof(
             &mut proof_reader,
             circuit,
             proving_time_mpi_size,
-            xy_var_degree,
+            round_degrees::<Cfg>,
             *claimed_v,
             transcript,
-        );
+            self.statement_binding,
+        )?;
         parse_proof_timer.stop();
 
         let gkr_parallel_timer = Timer::new("gkr_parallel", true);
         let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, proving_time_mpi_size);
-        let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
-            GKRScheme::Vanilla => {
-                let gkr_verified = verification_units
-                    .par_iter_mut()
-                    .zip(circuit.layers.par_iter())
-                    .map(|(verification_unit, layer)| {
-                        let mut challenge = verification_unit.claim.challenge.clone();
-                        let alpha = verification_unit.claim.alpha;
-                        let mut claim_x = verification_unit.claim.claim_x;
-                        let mut claim_y = verification_unit.claim.claim_y;
-
-                        let mut sp = sp.clone();
-                        sumcheck_verify_gkr_layer(
-                            proving_time_mpi_size,
-                            layer,
-                            public_input,
-                            &mut challenge,
-                            &mut claim_x,
-                            &mut claim_y,
-                            alpha,
-                            &mut Cursor::new(verification_unit.proof.clone()),
-                            &mut verification_unit.random_tape,
-                            &mut sp,
-                            false,
-                            true,
-                        )
-                    })
-                    .all(|verified| verified);
-
-                (
-                    gkr_verified,
-                    challenge.challenge_x(),
-                    challenge.challenge_y(),
-                    claim_x,
-                    claim_y,
-                )
-            }
+        let layers_verified = match Cfg::SCHEME {
+            GKRScheme::Vanilla => verification_units
+                .par_iter_mut()
+                .zip(circuit.layers.par_iter())
+                .map(|(verification_unit, layer)| {
+                    let mut challenge = verification_unit.claim.challenge.clone();
+                    let alpha = verification_unit.claim.alpha;
+                    let mut claim_x = verification_unit.claim.claim_x;
+                    let mut claim_y = verification_unit.claim.claim_y;
+
+                    let mut sp = sp.clone();
+                    sumcheck_verify_gkr_layer(
+                        proving_time_mpi_size,
+                        layer,
+                        public_input,
+                        &mut challenge,
+                        &mut claim_x,
+                        &mut claim_y,
+                        alpha,
+                        &mut Cursor::new(verification_unit.proof.clone()),
+                        &mut verification_unit.random_tape,
+                        &mut sp,
+                        false,
+                        true,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?,
             GKRScheme::GkrSquare => {
                 assert!(challenge.challenge_y().is_none());
                 assert!(claim_y.is_none());
 
-                let gkr_verified = verification_units
+                verification_units
                     .par_iter_mut()
                     .zip(circuit.layers.par_iter())
                     .map(|(verification_unit, layer)| {
@@ -240,18 +272,29 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                             true,
                         )
                     })
-                    .all(|verified| verified);
-
-                (gkr_verified, challenge.challenge_x(), None, claim_x, None)
+                    .collect::<Result<Vec<_>, _>>()?
             }
+            GKRScheme::ZkVanilla | GKRScheme::CrossLayer => unreachable!(),
         };
+        // report the first failing layer from the output layer, as the sequential verifier does
+        if let Some(layer) = layers_verified.iter().rposition(|verified| !verified) {
+            return Err(VerifyError::ClaimMismatch { layer });
+        }
         gkr_parallel_timer.stop();
         transcript_verifier_sync(transcript, proving_time_mpi_size);
 
-        (verified, challenge_x, challenge_y, claim_x, claim_y)
+        Ok((
+            challenge.challenge_x(),
+            challenge.challenge_y(),
+            claim_x,
+            claim_y,
+        ))
     }
 
     /// Verify the PCS opening against the commitment and the claim from GKR.
+    ///
+    /// Fails if an opening does not deserialize, or with `VerifyError::PcsOpeningFailed` if it does
+    /// not verify.
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
@@ -266,9 +309,9 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         claim_y: &Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
         transcript: &mut impl Transcript,
         mut proof_reader: impl Read,
-    ) -> bool {
+    ) -> Result<(), VerifyError> {
         let timer = Timer::new("post_gkr", true);
-        let mut verified = self.get_pcs_opening_from_proof_and_verify(
+        self.get_pcs_opening_from_proof_and_verify(
             pcs_params,
             pcs_verification_key,
             commitment,
@@ -276,10 +319,10 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             claim_x,
             transcript,
             &mut proof_reader,
-        );
+        )?;
 
         if let Some(challenge_y) = challenge_y {
-            verified &= self.get_pcs_opening_from_proof_and_verify(
+            self.get_pcs_opening_from_proof_and_verify(
                 pcs_params,
                 pcs_verification_key,
                 commitment,
@@ -287,13 +330,14 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                 claim_y.as_ref().unwrap(),
                 transcript,
                 &mut proof_reader,
-            );
+            )?;
         }
 
         timer.stop();
-        verified
+        Ok(())
     }
 
+    /// Verify a proof, see [`Self::try_verify`] for why a proof is rejected.
     pub fn verify(
         &self,
         circuit: &mut Circuit<Cfg::FieldConfig>,
@@ -303,24 +347,62 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
         proof: &Proof,
     ) -> bool {
+        matches!(
+            self.try_verify(
+                circuit,
+                public_input,
+                claimed_v,
+                pcs_params,
+                pcs_verification_key,
+                proof,
+            ),
+            Ok(true)
+        )
+    }
+
+    /// Same as [`Self::verify`], but reports why a proof is rejected.
+    ///
+    /// The proof is read as it is verified, and is rejected with
+    /// - `VerifyError::TruncatedProof`, `VerifyError::TrailingBytes` or one of the `Malformed*`
+    ///   errors if it does not deserialize, see [`crate::GkrProof`] for the layout it is read with,
+    /// - `VerifyError::ClaimMismatch` if the sumcheck of a layer does not verify,
+    /// - `VerifyError::PcsOpeningFailed` if the opening of the input layer does not verify.
+    ///
+    /// Returns `Ok(true)` if the proof is accepted, use [`VerifyError::is_rejection`] to tell the
+    /// proofs that fail the checks from the malformed ones.
+    pub fn try_verify(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> Result<bool, VerifyError> {
         let timer = Timer::new("snark verify", true);
 
         let proving_time_mpi_size = self.mpi_config.world_size();
         let mut transcript = Cfg::TranscriptConfig::new();
         let mut cursor = Cursor::new(&proof.bytes);
 
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        let commitment = self.pre_gkr(
+            &mut cursor,
+            circuit,
+            public_input,
+            &mut transcript,
+            proving_time_mpi_size,
+        )?;
 
-        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr(
+        let (mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr(
             circuit,
             public_input,
             claimed_v,
             proving_time_mpi_size,
             &mut transcript,
             &mut cursor,
-        );
+        )?;
 
-        verified &= self.post_gkr(
+        self.post_gkr(
             pcs_params,
             pcs_verification_key,
             &commitment,
@@ -330,13 +412,88 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             &claim_y,
             &mut transcript,
             &mut cursor,
-        );
+        )?;
+        check_proof_consumed(&cursor)?;
 
         timer.stop();
+        Ok(true)
+    }
 
-        verified
+    /// Verify a proof of `Prover::prove_zk`, see `GKRScheme::ZkVanilla`.
+    ///
+    /// Fails with `VerifyError::UnsupportedWorldSize` unless the verifier is configured for a
+    /// single process, as the zero-knowledge GKR is only proven in a single process. Otherwise the
+    /// proof is rejected as in [`Self::try_verify`], and `Ok(true)` is returned if it is accepted.
+    pub fn verify_zk<C>(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_verification_key: &HidingPedersenParams<C>,
+        proof: &Proof,
+    ) -> Result<bool, VerifyError>
+    where
+        C: CurveAffine + ExpSerde + UncompressedEncoding,
+        C::Scalar: ExtensionField + PrimeField,
+        C::ScalarExt: ExtensionField + PrimeField,
+        C::Base: PrimeField<Repr = [u8; 32]>,
+        Cfg: GKREngine<PCSField = C::Scalar, PCSConfig = HidingHyraxPCS<C>>,
+        Cfg::FieldConfig: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
+    {
+        const { assert!(matches!(Cfg::SCHEME, GKRScheme::ZkVanilla)) };
+        let world_size = self.mpi_config.world_size();
+        if world_size != 1 {
+            return Err(VerifyError::UnsupportedWorldSize(world_size));
+        }
+
+        let timer = Timer::new("snark verify zk", true);
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut cursor = Cursor::new(&proof.bytes);
+
+        let commitment = self.pre_gkr(&mut cursor, circuit, public_input, &mut transcript, 1)?;
+
+        let (challenge, vx_comm, vy_comm) = gkr_zk_verify(
+            circuit,
+            public_input,
+            claimed_v,
+            &mut transcript,
+            &mut cursor,
+            self.statement_binding,
+            pcs_verification_key,
+        )
+        .map_err(zk_verify_error)?;
+
+        let mut open_at = vec![(challenge.challenge_x(), vx_comm)];
+        if let (Some(challenge_y), Some(vy_comm)) = (challenge.challenge_y(), vy_comm) {
+            open_at.push((challenge_y, vy_comm));
+        }
+        for (challenge, eval_comm) in &open_at {
+            let verified = verify_with_proof_locked(
+                &mut cursor,
+                &mut transcript,
+                |proof: &HidingHyraxEvalProof<C>, transcript| {
+                    hiding_hyrax_verify_eval(
+                        pcs_verification_key,
+                        &commitment,
+                        &challenge.local_xs(),
+                        eval_comm,
+                        transcript,
+                        proof,
+                    )
+                },
+            )
+            .map_err(zk_verify_error)?;
+            if !verified {
+                return Err(VerifyError::PcsOpeningFailed);
+            }
+        }
+        check_proof_consumed(&cursor)?;
+
+        timer.stop();
+        Ok(true)
     }
 
+    #[cfg(feature = "rayon")]
     pub fn par_verify(
         &self,
         circuit: &mut Circuit<Cfg::FieldConfig>,
@@ -346,24 +503,54 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
         proof: &Proof,
     ) -> bool {
-        let timer = Timer::new("snark verify", true);
+        matches!(
+            self.try_par_verify(
+                circuit,
+                public_input,
+                claimed_v,
+                pcs_params,
+                pcs_verification_key,
+                proof,
+            ),
+            Ok(true)
+        )
+    }
+
+    /// Same as [`Self::try_verify`], verifying the layers in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn try_par_verify(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> Result<bool, VerifyError> {
+        let timer = Timer::new("snark par verify", true);
 
         let proving_time_mpi_size = self.mpi_config.world_size();
         let mut transcript = Cfg::TranscriptConfig::new();
         let mut cursor = Cursor::new(&proof.bytes);
 
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        let commitment = self.pre_gkr(
+            &mut cursor,
+            circuit,
+            public_input,
+            &mut transcript,
+            proving_time_mpi_size,
+        )?;
 
-        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr_parallel(
+        let (mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr_parallel(
             circuit,
             public_input,
             claimed_v,
             proving_time_mpi_size,
             &mut transcript,
             &mut cursor,
-        );
+        )?;
 
-        verified &= self.post_gkr(
+        self.post_gkr(
             pcs_params,
             pcs_verification_key,
             &commitment,
@@ -373,7 +560,94 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             &claim_y,
             &mut transcript,
             &mut cursor,
-        );
+        )?;
+        check_proof_consumed(&cursor)?;
+
+        timer.stop();
+        Ok(true)
+    }
+
+    /// Verify a batch of proofs for the same circuit, each given as
+    /// (public input, claimed value, proof).
+    ///
+    /// GKR is verified for the proofs in parallel, and the PCS checks that the PCS allows to
+    /// defer, see `ExpanderPCS::verify_deferred`, are run once for the whole batch, e.g., as a
+    /// single pairing check for KZG or a single MSM for Hyrax.
+    /// Malformed proofs are rejected rather than panicking, as in `try_verify`.
+    ///
+    /// Returns true iff all the proofs verify.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_batch(
+        &self,
+        circuit: &Circuit<Cfg::FieldConfig>,
+        instances: &[(
+            &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+            <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+            &Proof,
+        )],
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+    ) -> bool {
+        if instances.is_empty() {
+            return true;
+        }
+
+        let timer = Timer::new("snark batch verify", true);
+        let proving_time_mpi_size = self.mpi_config.world_size();
+
+        // the circuit is not Sync, hence each thread verifies a chunk of the batch
+        // on its own copy of the circuit
+        #[cfg(feature = "rayon")]
+        let num_threads = rayon::current_num_threads();
+        #[cfg(not(feature = "rayon"))]
+        let num_threads = 1;
+        let chunk_size = instances.len().div_ceil(num_threads);
+        let mut circuits = vec![circuit.clone(); instances.len().div_ceil(chunk_size)];
+
+        #[cfg(feature = "rayon")]
+        let chunks = instances
+            .par_chunks(chunk_size)
+            .zip(circuits.par_iter_mut());
+        #[cfg(not(feature = "rayon"))]
+        let chunks = instances.chunks(chunk_size).zip(circuits.iter_mut());
+
+        let deferred_checks = chunks
+            .map(|(chunk, circuit)| {
+                let mut verifier =
+                    Verifier::<Cfg>::new(MPIConfig::verifier_new(proving_time_mpi_size as i32));
+                verifier.statement_binding = self.statement_binding;
+                chunk
+                    .iter()
+                    .map(|(public_input, claimed_v, proof)| {
+                        verifier.verify_with_deferred_pcs_checks(
+                            circuit,
+                            public_input,
+                            claimed_v,
+                            pcs_params,
+                            pcs_verification_key,
+                            proof,
+                        )
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>();
+
+        let verified = match deferred_checks {
+            Some(deferred_checks) => {
+                let deferred_checks = deferred_checks
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                Cfg::PCSConfig::batch_check(
+                    pcs_params,
+                    pcs_verification_key,
+                    &deferred_checks,
+                    rand::thread_rng(),
+                )
+            }
+            None => false,
+        };
 
         timer.stop();
         verified
@@ -381,6 +655,107 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
 }
 
 impl<Cfg: GKREngine> Verifier<'_, Cfg> {
+    /// Verify a single proof of a batch, returning the PCS checks deferred to the end of the batch,
+    /// or `None` if the proof is rejected.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn verify_with_deferred_pcs_checks(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> Option<Vec<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::DeferredCheck>>
+    {
+        let proving_time_mpi_size = self.mpi_config.world_size();
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut cursor = Cursor::new(&proof.bytes);
+
+        let commitment = self
+            .pre_gkr(
+                &mut cursor,
+                circuit,
+                public_input,
+                &mut transcript,
+                proving_time_mpi_size,
+            )
+            .ok()?;
+
+        let (mut challenge_x, mut challenge_y, claim_x, claim_y) = self
+            .gkr(
+                circuit,
+                public_input,
+                claimed_v,
+                proving_time_mpi_size,
+                &mut transcript,
+                &mut cursor,
+            )
+            .ok()?;
+
+        let mut deferred_checks = vec![self.get_pcs_opening_from_proof_and_verify_deferred(
+            pcs_params,
+            pcs_verification_key,
+            &commitment,
+            &mut challenge_x,
+            &claim_x,
+            &mut transcript,
+            &mut cursor,
+        )?];
+        if let Some(challenge_y) = challenge_y.as_mut() {
+            deferred_checks.push(self.get_pcs_opening_from_proof_and_verify_deferred(
+                pcs_params,
+                pcs_verification_key,
+                &commitment,
+                challenge_y,
+                claim_y.as_ref().unwrap(),
+                &mut transcript,
+                &mut cursor,
+            )?);
+        }
+        check_proof_consumed(&cursor).ok()?;
+
+        Some(deferred_checks)
+    }
+
+    /// Same as `get_pcs_opening_from_proof_and_verify`, but defers the PCS check that can be
+    /// batched with other openings.
+    #[allow(clippy::too_many_arguments)]
+    fn get_pcs_opening_from_proof_and_verify_deferred(
+        &self,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::SRS as StructuredReferenceString>::VKey,
+        commitment: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Commitment,
+        open_at: &mut ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+        v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        transcript: &mut impl Transcript,
+        proof_reader: impl Read,
+    ) -> Option<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::DeferredCheck>
+    {
+        let opening = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Opening::deserialize_from(
+            proof_reader,
+        )
+        .ok()?;
+
+        transcript.lock_proof();
+        let deferred_check = Cfg::PCSConfig::verify_deferred(
+            pcs_params,
+            pcs_verification_key,
+            commitment,
+            open_at,
+            *v,
+            transcript,
+            &opening,
+        );
+        transcript.unlock_proof();
+
+        let mut buffer = vec![];
+        opening.serialize_into(&mut buffer).ok()?;
+        transcript.append_u8_slice(&buffer);
+
+        deferred_check
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn get_pcs_opening_from_proof_and_verify(
         &self,
@@ -391,11 +766,11 @@ impl<Cfg: GKREngine> Verifier<'_, Cfg> {
         v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
         transcript: &mut impl Transcript,
         proof_reader: impl Read,
-    ) -> bool {
+    ) -> Result<(), VerifyError> {
         let opening = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::Opening::deserialize_from(
             proof_reader,
         )
-        .unwrap();
+        .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedOpening))?;
 
         transcript.lock_proof();
         let verified = Cfg::PCSConfig::verify(
@@ -410,9 +785,24 @@ impl<Cfg: GKREngine> Verifier<'_, Cfg> {
         transcript.unlock_proof();
 
         let mut buffer = vec![];
-        opening.serialize_into(&mut buffer).unwrap(); // TODO: error propagation
+        opening
+            .serialize_into(&mut buffer)
+            .map_err(|_| VerifyError::MalformedOpening)?;
         transcript.append_u8_slice(&buffer);
 
-        verified
+        if verified {
+            Ok(())
+        } else {
+            Err(VerifyError::PcsOpeningFailed)
+        }
+    }
+}
+
+/// Fails with `VerifyError::TrailingBytes` if the verifier did not read the proof to the end.
+fn check_proof_consumed(cursor: &Cursor<&Vec<u8>>) -> Result<(), VerifyError> {
+    let remaining = cursor.get_ref().len() - cursor.position() as usize;
+    if remaining != 0 {
+        return Err(VerifyError::TrailingBytes(remaining));
     }
+    Ok(())
 }
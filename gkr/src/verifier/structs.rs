@@ -1,11 +1,16 @@
 use std::io::{Cursor, Read};
 
 use arith::{ExtensionField, SimdField};
-use circuit::Circuit;
-use gkr_engine::{ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, Transcript};
-use sumcheck::SUMCHECK_GKR_SIMD_MPI_DEGREE;
+use circuit::{Circuit, CircuitLayer};
+use gkr_engine::{
+    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, StatementBinding,
+    Transcript, VerifyError,
+};
 use transcript::RandomTape;
 
+use super::read_sumcheck_field;
+use crate::absorb_claimed_v;
+
 // ================ Structured Claims ================
 #[derive(Clone, Debug, Default)]
 pub struct SumcheckClaim<F: FieldEngine> {
@@ -31,13 +36,15 @@ pub fn parse_challenge_field<ChallengeF: ExtensionField>(
     mut proof_reader: impl Read,
     transcript: &mut impl Transcript,
     proof_bytes: &mut Vec<u8>,
-) -> ChallengeF {
+) -> Result<ChallengeF, VerifyError> {
     let mut buffer = vec![0; ChallengeF::SIZE];
-    proof_reader.read_exact(&mut buffer).unwrap();
+    proof_reader
+        .read_exact(&mut buffer)
+        .map_err(|_| VerifyError::TruncatedProof)?;
     proof_bytes.extend_from_slice(&buffer);
-    let challenge = ChallengeF::deserialize_from(Cursor::new(buffer)).unwrap();
+    let challenge = read_sumcheck_field::<ChallengeF>(Cursor::new(buffer))?;
     transcript.append_field_element(&challenge);
-    challenge
+    Ok(challenge)
 }
 
 pub fn parse_sumcheck_rounds<F: FieldEngine>(
@@ -48,33 +55,41 @@ pub fn parse_sumcheck_rounds<F: FieldEngine>(
     challenge_vec: &mut Vec<F::ChallengeField>,
     proof_bytes: &mut Vec<u8>,
     random_tape: &mut RandomTape<F::ChallengeField>,
-) {
+) -> Result<(), VerifyError> {
     challenge_vec.clear();
-    (0..n_rounds).for_each(|_| {
-        (0..degree + 1).for_each(|_| {
-            parse_challenge_field::<F::ChallengeField>(&mut proof_reader, transcript, proof_bytes);
-        });
+    for _ in 0..n_rounds {
+        for _ in 0..degree + 1 {
+            parse_challenge_field::<F::ChallengeField>(&mut proof_reader, transcript, proof_bytes)?;
+        }
 
         challenge_vec.push(transcript.generate_field_element());
-    });
+    }
     random_tape.tape.extend_from_slice(challenge_vec);
+    Ok(())
 }
 
 #[allow(clippy::type_complexity)]
-/// Parse the proof into a vector of verification units.
+/// Parse the proof into a vector of verification units, `round_degrees` giving the degrees of the
+/// round polynomials of a layer in the (x/y, simd/mpi) variables.
+///
+/// Fails if the proof ends early or contains a malformed field element.
 pub fn parse_proof<F: FieldEngine>(
     mut proof_reader: impl Read,
     circuit: &Circuit<F>,
     proving_time_mpi_size: usize,
-    xy_var_degree: usize,
+    round_degrees: impl Fn(&CircuitLayer<F>) -> (usize, usize),
     claimed_v: F::ChallengeField,
     transcript: &mut impl Transcript,
-) -> (
-    Vec<SumcheckVerificationUnit<F>>,
-    ExpanderDualVarChallenge<F>,
-    F::ChallengeField,
-    Option<F::ChallengeField>,
-) {
+    statement_binding: StatementBinding,
+) -> Result<
+    (
+        Vec<SumcheckVerificationUnit<F>>,
+        ExpanderDualVarChallenge<F>,
+        F::ChallengeField,
+        Option<F::ChallengeField>,
+    ),
+    VerifyError,
+> {
     let mut verification_units =
         vec![SumcheckVerificationUnit::<F>::default(); circuit.layers.len()];
     let n_output_vars = circuit.layers.last().unwrap().output_var_num;
@@ -88,6 +103,8 @@ pub fn parse_proof<F: FieldEngine>(
             proving_time_mpi_size,
         )
         .into();
+    absorb_claimed_v::<F>(statement_binding, &claimed_v, transcript);
+
     let mut claim_x = claimed_v;
     let mut alpha = None;
     let mut claim_y = None;
@@ -106,6 +123,7 @@ pub fn parse_proof<F: FieldEngine>(
         let sumcheck_proof = &mut verification_unit.proof;
         let random_tape = &mut verification_unit.random_tape;
         let n_vars = layer.input_var_num;
+        let (xy_var_degree, simd_mpi_var_degree) = round_degrees(layer);
 
         parse_sumcheck_rounds::<F>(
             &mut proof_reader,
@@ -115,33 +133,33 @@ pub fn parse_proof<F: FieldEngine>(
             &mut challenge.rz_0,
             sumcheck_proof,
             random_tape,
-        );
+        )?;
 
         parse_sumcheck_rounds::<F>(
             &mut proof_reader,
             n_simd_vars,
-            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            simd_mpi_var_degree,
             transcript,
             &mut challenge.r_simd,
             sumcheck_proof,
             random_tape,
-        );
+        )?;
 
         parse_sumcheck_rounds::<F>(
             &mut proof_reader,
             n_mpi_vars,
-            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            simd_mpi_var_degree,
             transcript,
             &mut challenge.r_mpi,
             sumcheck_proof,
             random_tape,
-        );
+        )?;
 
         claim_x = parse_challenge_field::<F::ChallengeField>(
             &mut proof_reader,
             transcript,
             sumcheck_proof,
-        );
+        )?;
 
         if !layer.structure_info.skip_sumcheck_phase_two {
             challenge.rz_1 = Some(vec![]);
@@ -153,12 +171,12 @@ pub fn parse_proof<F: FieldEngine>(
                 challenge.rz_1.as_mut().unwrap(),
                 sumcheck_proof,
                 random_tape,
-            );
+            )?;
             claim_y = Some(parse_challenge_field::<F::ChallengeField>(
                 &mut proof_reader,
                 transcript,
                 sumcheck_proof,
-            ));
+            )?);
         } else {
             claim_y = None;
         }
@@ -172,5 +190,5 @@ pub fn parse_proof<F: FieldEngine>(
         };
     }
 
-    (verification_units, challenge, claim_x, claim_y)
+    Ok((verification_units, challenge, claim_x, claim_y))
 }
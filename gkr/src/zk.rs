@@ -0,0 +1,169 @@
+//! Common parts of the zero-knowledge GKR, see `GKRScheme::ZkVanilla`.
+//!
+//! Each round polynomial of the sumchecks is sent as Pedersen commitments to its coefficients
+//! of degree >= 1. The commitment to the constant coefficient is derived by the verifier from the
+//! commitment to the claim `p(0) + p(1)`, and the commitment to the next claim `p(r)` follows by
+//! homomorphism as well, so the sumcheck relations hold by construction. The claims `vx`, `vy`
+//! on the next layer are sent as commitments too, and the final check of each layer is proven
+//! with a proof of product and a proof of opening to zero.
+//!
+//! This is the commit-and-prove approach of Hyrax (Wahby et al., S&P 2018), not the masking
+//! polynomials of Libra and Virgo. Masking each sumcheck with a random polynomial needs a second,
+//! zero-knowledge PCS to commit to and open the masking polynomials, and a low-degree extension of
+//! the input layer padded with random evaluations to hide the final claims. Here every message is
+//! a Pedersen commitment with the generators of the hiding Hyrax PCS the input layer is committed
+//! with, so that PCS is the only addition to the vanilla GKR. In exchange, the verifier does a
+//! few group operations per sumcheck round, and each round costs a few curve points in the proof.
+
+use std::io::Read;
+
+use arith::{ExtensionField, Field};
+use gkr_engine::{ExpErrors, Transcript, VerifyError};
+use halo2curves::{ff::PrimeField, group::Group, CurveAffine};
+use serdes::ExpSerde;
+
+#[inline(always)]
+pub(crate) fn append_point<C: CurveAffine + ExpSerde>(
+    transcript: &mut impl Transcript,
+    point: &C,
+) -> Result<(), ExpErrors> {
+    let mut buffer = vec![];
+    point.serialize_into(&mut buffer)?;
+    transcript.append_u8_slice(&buffer);
+    Ok(())
+}
+
+/// Lock the proof around a sigma protocol, and write the proof afterwards, as for PCS openings.
+#[inline(always)]
+pub(crate) fn with_proof_locked<T: Transcript, P: ExpSerde>(
+    transcript: &mut T,
+    f: impl FnOnce(&mut T) -> P,
+) -> Result<P, ExpErrors> {
+    transcript.lock_proof();
+    let proof = f(transcript);
+    transcript.unlock_proof();
+
+    let mut buffer = vec![];
+    proof.serialize_into(&mut buffer)?;
+    transcript.append_u8_slice(&buffer);
+    Ok(proof)
+}
+
+/// Read a point from the proof, and append it to the transcript.
+#[inline(always)]
+pub(crate) fn read_point<C: CurveAffine + ExpSerde>(
+    proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+) -> Result<C, ExpErrors> {
+    let point = C::deserialize_from(proof_reader)
+        .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedSumcheckMessage))?;
+    append_point(transcript, &point)?;
+    Ok(point)
+}
+
+/// Error of a zero-knowledge proof that is rejected by the verifier.
+///
+/// The only errors of the verifier that are not a `VerifyError` come from serializing a point
+/// read from the proof into the transcript, i.e., from a malformed sumcheck message.
+pub(crate) fn zk_verify_error(e: ExpErrors) -> VerifyError {
+    match e {
+        ExpErrors::VerifyError(e) => e,
+        _ => VerifyError::MalformedSumcheckMessage,
+    }
+}
+
+/// Read a sigma protocol proof written by `with_proof_locked`, and verify it with the proof locked.
+#[inline(always)]
+pub(crate) fn verify_with_proof_locked<T: Transcript, P: ExpSerde>(
+    proof_reader: impl Read,
+    transcript: &mut T,
+    verify: impl FnOnce(&P, &mut T) -> bool,
+) -> Result<bool, ExpErrors> {
+    let proof = P::deserialize_from(proof_reader)
+        .map_err(|e| VerifyError::from_serde(e, VerifyError::MalformedOpening))?;
+
+    transcript.lock_proof();
+    let verified = verify(&proof, transcript);
+    transcript.unlock_proof();
+
+    let mut buffer = vec![];
+    proof.serialize_into(&mut buffer)?;
+    transcript.append_u8_slice(&buffer);
+    Ok(verified)
+}
+
+/// Coefficients of the polynomial of degree `evals.len() - 1` with the evaluations `evals` at
+/// 0, 1, ..., from its Newton form over the forward differences.
+pub(crate) fn coeffs_from_evals<F: Field>(evals: &[F]) -> Vec<F> {
+    let n = evals.len();
+
+    let mut diffs = evals.to_vec();
+    for k in 1..n {
+        for i in (k..n).rev() {
+            diffs[i] = diffs[i] - diffs[i - 1];
+        }
+    }
+
+    // binomial(X, k) = prod_{j < k} (X - j) / k!
+    let mut coeffs = vec![F::ZERO; n];
+    let mut binomial = vec![F::ONE];
+    for (k, diff) in diffs.iter().enumerate() {
+        coeffs
+            .iter_mut()
+            .zip(binomial.iter())
+            .for_each(|(c, b)| *c += *diff * b);
+
+        let k_inv = F::from(k as u32 + 1).inv().unwrap();
+        let k = F::from(k as u32);
+        let mut next = vec![F::ZERO; binomial.len() + 1];
+        binomial.iter().enumerate().for_each(|(i, b)| {
+            next[i + 1] += *b * k_inv;
+            next[i] -= *b * k * k_inv;
+        });
+        binomial = next;
+    }
+
+    coeffs
+}
+
+/// The blinding factor of the claim `p(r)`, from the blinding factor of the claim `p(0) + p(1)`
+/// and the ones of the coefficients of degree >= 1 of `p`.
+#[inline(always)]
+pub(crate) fn next_claim_blind<F: Field>(claim_blind: F, coeff_blinds: &[F], r: F) -> F {
+    let inv_2 = F::from(2u32).inv().unwrap();
+    let sum: F = coeff_blinds.iter().sum();
+
+    let mut next = (claim_blind - sum) * inv_2;
+    let mut r_pow = r;
+    for b in coeff_blinds {
+        next += r_pow * b;
+        r_pow *= r;
+    }
+    next
+}
+
+/// The commitment to the claim `p(r)`, from the commitment to the claim `p(0) + p(1)` and the
+/// ones to the coefficients of degree >= 1 of `p`.
+#[inline(always)]
+pub(crate) fn next_claim_commitment<C>(
+    claim_comm: &C::Curve,
+    coeff_comms: &[C],
+    r: C::Scalar,
+) -> C::Curve
+where
+    C: CurveAffine,
+    C::Scalar: ExtensionField + PrimeField,
+{
+    let inv_2 = C::Scalar::from(2u32).inv().unwrap();
+    let sum = coeff_comms
+        .iter()
+        .fold(C::Curve::identity(), |acc, c| acc + c.to_curve());
+
+    let mut next = (*claim_comm - sum) * inv_2;
+    let mut r_pow = r;
+    for c in coeff_comms {
+        next += *c * r_pow;
+        r_pow *= r;
+    }
+    next
+}
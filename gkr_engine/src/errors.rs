@@ -12,9 +12,80 @@ pub enum ExpErrors {
     #[error("Unknown string `{0}` for FiatShamir Hash Type")]
     FiatShamirHashTypeError(String),
 
+    #[error("PCS setup mismatch: {0}")]
+    PCSSetupError(String),
+
+    #[error("proof mismatch: {0}")]
+    ProofMismatchError(String),
+
+    #[error("verification error: {0}")]
+    VerifyError(#[from] VerifyError),
+
     #[error("field serde error: {0:?}")]
     SerdeError(#[from] SerdeError),
 
     #[error("other error: {0:?}")]
     OtherError(#[from] std::io::Error),
 }
+
+/// Reasons for a verifier to reject a proof.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerifyError {
+    #[error("proof is truncated")]
+    TruncatedProof,
+
+    #[error("proof has {0} trailing bytes")]
+    TrailingBytes(usize),
+
+    #[error("malformed field element at byte {0} of the proof")]
+    MalformedFieldElement(usize),
+
+    #[error("malformed sumcheck message")]
+    MalformedSumcheckMessage,
+
+    #[error("malformed PCS commitment")]
+    MalformedCommitment,
+
+    #[error("malformed PCS opening")]
+    MalformedOpening,
+
+    /// The sumcheck of layer `layer`, indexed like `circuit.layers`, does not reduce the claim
+    /// on its output to a claim on its input
+    #[error("sumcheck claim mismatch at layer {layer}")]
+    ClaimMismatch { layer: usize },
+
+    #[error("PCS opening does not verify")]
+    PcsOpeningFailed,
+
+    #[error("proofs of {0} processes are not supported by this scheme")]
+    UnsupportedWorldSize(usize),
+
+    /// The gates of layer `layer`, indexed like `circuit.layers`, are not supported by the GKR
+    /// scheme over the field of the circuit
+    #[error("layer {layer} of the circuit is not supported by this scheme")]
+    UnsupportedCircuit { layer: usize },
+
+    /// The public inputs of each process are fewer than the circuit reads, or the public inputs
+    /// do not split evenly between the processes
+    #[error("{0} public inputs do not match the circuit")]
+    PublicInputSize(usize),
+}
+
+impl VerifyError {
+    /// Error of a proof item that does not deserialize: the proof is truncated if it ends
+    /// before the item, otherwise the item is `malformed`.
+    pub fn from_serde(e: SerdeError, malformed: Self) -> Self {
+        match e {
+            SerdeError::IOError(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                Self::TruncatedProof
+            }
+            _ => malformed,
+        }
+    }
+
+    /// Whether the proof is well-formed but does not pass the checks of the verifier, as opposed
+    /// to a proof that does not even deserialize.
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Self::ClaimMismatch { .. } | Self::PcsOpeningFailed)
+    }
+}
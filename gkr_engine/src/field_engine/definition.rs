@@ -4,10 +4,11 @@ use std::ops::{Add, Mul};
 
 use arith::{ExtensionField, Field, SimdField};
 use polynomials::MultiLinearPoly;
+use serdes::ExpSerde;
 
 use crate::{ExpanderSingleVarChallenge, MPIEngine};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, ExpSerde)]
 pub enum FieldType {
     #[default]
     M31x16, // M31Ext3x16
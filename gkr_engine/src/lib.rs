@@ -17,16 +17,22 @@
 mod errors;
 mod field_engine;
 mod mpi_engine;
+mod pcs_setup_header;
 mod poly_commit;
+mod proof_envelope;
 mod scheme;
+mod simd_dispatch;
 mod transcript;
 
 use arith::Field;
 pub use errors::*;
 pub use field_engine::*;
 pub use mpi_engine::*;
+pub use pcs_setup_header::*;
 pub use poly_commit::*;
+pub use proof_envelope::*;
 pub use scheme::*;
+pub use simd_dispatch::*;
 pub use transcript::*;
 
 /// Core trait defining the configuration types for a GKR protocol implementation.
@@ -41,6 +47,7 @@ pub use transcript::*;
 /// * `MPIConfig` - Configuration for distributed computing operations, implementing `MPIEngine`
 /// * `TranscriptConfig` - Configuration for transcript generation, implementing `Transcript` over
 ///   the challenge field
+/// * `FIAT_SHAMIR_HASH` - Identifier for the hash used by `TranscriptConfig`
 /// * `PCSConfig` - Configuration for polynomial commitment scheme, implementing `PCSForExpanderGKR`
 /// * `Scheme` - Identifier for the GKR scheme, candidates are `GKRScheme::Vanilla` and
 ///   `GKRScheme::GkrSquare`
@@ -58,6 +65,7 @@ pub use transcript::*;
 ///     type FieldConfig = M31Ext3Config;
 ///     type MPIConfig = MPIConfig;
 ///     type TranscriptConfig = BytesHashTranscript<M31Ext3, Sha2hasher>;
+///     const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::SHA256;
 ///     type PCSConfig = RawPCS<M31Ext3>;
 ///     const SCHEME: GKRScheme = GKRScheme::Vanilla;
 /// }
@@ -72,6 +80,9 @@ pub trait GKREngine: Send + Sync {
     /// Configuration for transcript generation over the challenge field
     type TranscriptConfig: Transcript;
 
+    /// Hash function used by the transcript
+    const FIAT_SHAMIR_HASH: FiatShamirHashType;
+
     /// Configuration for polynomial commitment scheme
     type PCSField: Field = <<Self as GKREngine>::FieldConfig as FieldEngine>::SimdCircuitField;
     type PCSConfig: ExpanderPCS<Self::FieldConfig, Self::PCSField>;
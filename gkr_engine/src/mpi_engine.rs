@@ -1,10 +1,28 @@
+mod backend;
 mod definition;
 mod engine;
 mod shared_mem;
+#[cfg(feature = "socket")]
+mod socket;
+mod thread;
 
+pub use backend::CommBackend;
 pub use definition::*;
 pub use engine::*;
 pub use shared_mem::SharedMemory;
+#[cfg(feature = "socket")]
+pub use socket::*;
+pub use thread::ThreadCommunicator;
+
+/// The handle of a shared memory segment, see `MPIEngine::create_shared_mem`
+#[cfg(feature = "mpi")]
+pub use mpi::ffi::ompi_win_t;
+
+/// The handle of a shared memory segment, see `MPIEngine::create_shared_mem`. Without MPI, the
+/// handle is the address of the memory in the communication backend.
+#[cfg(not(feature = "mpi"))]
+#[allow(non_camel_case_types)]
+pub type ompi_win_t = u8;
 
 #[cfg(test)]
 mod tests;
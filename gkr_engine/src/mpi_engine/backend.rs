@@ -0,0 +1,129 @@
+use std::io;
+
+/// A communication backend replacing MPI in `MPIConfig`, see `MPIConfig::backend_new`.
+///
+/// A backend only provides point-to-point messages between the root and the other processes, and
+/// shared memory. The collectives are built on top of them, with every message going through the
+/// root, which is enough for the gather/scatter pattern of the prover.
+///
+/// The failures of the backend, e.g. a process that exited or a shared memory file that cannot be
+/// created, are returned as errors. As with the default error handler of MPI, `MPIConfig` aborts
+/// on them, since the collectives of `MPIEngine` cannot fail.
+pub trait CommBackend: Send + Sync {
+    /// Get the total number of processes
+    fn world_size(&self) -> usize;
+
+    /// Get the rank of the current process, the root being 0
+    fn world_rank(&self) -> usize;
+
+    /// Send `bytes` to process `rank`, the messages between two processes are received in the
+    /// order they are sent
+    fn send(&self, rank: usize, bytes: &[u8]) -> io::Result<()>;
+
+    /// Receive the next message from process `rank`
+    fn recv(&self, rank: usize) -> io::Result<Vec<u8>>;
+
+    /// Create `n_bytes` of memory written by the root and shared with all processes, the `n_bytes`
+    /// of non-root processes being ignored as in `MPIEngine::create_shared_mem`.
+    ///
+    /// Returns the address of the memory, which is also the handle to pass to `free_shared_mem`.
+    fn create_shared_mem(&self, n_bytes: usize) -> io::Result<*mut u8>;
+
+    /// Discard the shared memory at `ptr`, called by all processes
+    fn free_shared_mem(&self, ptr: *mut u8) -> io::Result<()>;
+
+    #[inline(always)]
+    fn is_root(&self) -> bool {
+        self.world_rank() == 0
+    }
+
+    /// Receive the next message from process `rank` into `buf`, which must be of its length
+    #[inline]
+    fn recv_into(&self, rank: usize, buf: &mut [u8]) -> io::Result<()> {
+        let bytes = self.recv(rank)?;
+        if bytes.len() != buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unexpected message length from process {rank}"),
+            ));
+        }
+        buf.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Gather the bytes of all processes into the root, indexed by rank.
+    /// Non-root processes get an empty vector.
+    fn gather(&self, local: &[u8]) -> io::Result<Vec<Vec<u8>>> {
+        if !self.is_root() {
+            self.send(0, local)?;
+            return Ok(vec![]);
+        }
+
+        let mut all = Vec::with_capacity(self.world_size());
+        all.push(local.to_vec());
+        for rank in 1..self.world_size() {
+            all.push(self.recv(rank)?);
+        }
+        Ok(all)
+    }
+
+    /// Scatter the root's `send`, made of `world_size` chunks of the length of `recv`, into the
+    /// `recv` of all processes.
+    fn scatter(&self, send: &[u8], recv: &mut [u8]) -> io::Result<()> {
+        if !self.is_root() {
+            return self.recv_into(0, recv);
+        }
+
+        assert_eq!(send.len(), recv.len() * self.world_size());
+        let len = recv.len();
+        for rank in 1..self.world_size() {
+            self.send(rank, &send[rank * len..(rank + 1) * len])?;
+        }
+        recv.copy_from_slice(&send[..len]);
+        Ok(())
+    }
+
+    /// Broadcast the root's `bytes` into the `bytes` of the same length of all processes.
+    fn broadcast(&self, bytes: &mut [u8]) -> io::Result<()> {
+        if !self.is_root() {
+            return self.recv_into(0, bytes);
+        }
+
+        for rank in 1..self.world_size() {
+            self.send(rank, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Transpose the matrix whose rows are the `row`s of the processes, each row being made of
+    /// `world_size` chunks of equal length.
+    fn all_to_all(&self, row: &mut [u8]) -> io::Result<()> {
+        assert_eq!(row.len() % self.world_size(), 0);
+        let chunk_len = row.len() / self.world_size();
+
+        let rows = self.gather(row)?;
+        if !self.is_root() {
+            return self.recv_into(0, row);
+        }
+
+        for rank in 0..self.world_size() {
+            let transposed_row = rows
+                .iter()
+                .flat_map(|r| &r[rank * chunk_len..(rank + 1) * chunk_len])
+                .copied()
+                .collect::<Vec<_>>();
+            if rank == 0 {
+                row.copy_from_slice(&transposed_row);
+            } else {
+                self.send(rank, &transposed_row)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Synchronize all processes at this point
+    fn barrier(&self) -> io::Result<()> {
+        self.gather(&[])?;
+        self.broadcast(&mut [])
+    }
+}
@@ -1,11 +1,9 @@
 use arith::Field;
-use mpi::{
-    ffi::{ompi_win_t, MPI_Win_free},
-    topology::Process,
-};
+#[cfg(feature = "mpi")]
+use mpi::topology::Process;
 use serdes::ExpSerde;
 
-use super::SharedMemory;
+use super::{ompi_win_t, SharedMemory};
 
 /// MPI APIs for distributed computing operations
 pub trait MPIEngine {
@@ -133,6 +131,7 @@ pub trait MPIEngine {
     }
 
     /// Get the root process handle
+    #[cfg(feature = "mpi")]
     fn root_process(&self) -> Process;
 
     /// Synchronize all processes at this point
@@ -164,9 +163,5 @@ pub trait MPIEngine {
     }
 
     /// Discard the control of shared memory segment
-    fn free_shared_mem(&self, window: &mut *mut ompi_win_t) {
-        unsafe {
-            MPI_Win_free(window as *mut *mut ompi_win_t);
-        }
-    }
+    fn free_shared_mem(&self, window: &mut *mut ompi_win_t);
 }
@@ -1,9 +1,12 @@
-use std::os::raw::c_void;
-use std::{cmp, fmt::Debug, slice};
+#[cfg(feature = "mpi")]
+use std::{cmp, os::raw::c_void};
+use std::{fmt::Debug, io, slice};
 
 use arith::Field;
 use itertools::izip;
+#[cfg(feature = "mpi")]
 use mpi::environment::Universe;
+#[cfg(feature = "mpi")]
 use mpi::{
     datatype::PartitionMut,
     ffi::*,
@@ -12,7 +15,7 @@ use mpi::{
 };
 use serdes::ExpSerde;
 
-use super::MPIEngine;
+use super::{ompi_win_t, CommBackend, MPIEngine};
 
 #[macro_export]
 macro_rules! root_println {
@@ -25,32 +28,38 @@ macro_rules! root_println {
 
 #[derive(Clone)]
 pub struct MPIConfig<'a> {
+    #[cfg(feature = "mpi")]
     pub universe: Option<&'a Universe>,
+    #[cfg(feature = "mpi")]
     pub world: Option<&'a SimpleCommunicator>,
     pub world_size: i32,
     pub world_rank: i32,
+    /// The backend replacing MPI, if any
+    pub backend: Option<&'a dyn CommBackend>,
 }
 
 impl<'a> Default for MPIConfig<'a> {
     fn default() -> Self {
         Self {
+            #[cfg(feature = "mpi")]
             universe: None,
+            #[cfg(feature = "mpi")]
             world: None,
             world_size: 1,
             world_rank: 0,
+            backend: None,
         }
     }
 }
 
 impl<'a> Debug for MPIConfig<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let universe_fmt = if self.universe.is_none() {
-            Option::<usize>::None
-        } else {
-            Some(0usize)
-        };
+        #[cfg(feature = "mpi")]
+        let (universe_fmt, world_fmt) = (self.universe.map(|_| 0usize), self.world.map(|_| 0usize));
+        #[cfg(not(feature = "mpi"))]
+        let (universe_fmt, world_fmt) = (Option::<usize>::None, Option::<usize>::None);
 
-        let world_fmt = if self.world.is_none() {
+        let backend_fmt = if self.backend.is_none() {
             Option::<usize>::None
         } else {
             Some(0usize)
@@ -61,6 +70,7 @@ impl<'a> Debug for MPIConfig<'a> {
             .field("world", &world_fmt)
             .field("world_size", &self.world_size)
             .field("world_rank", &self.world_rank)
+            .field("backend", &backend_fmt)
             .finish()
     }
 }
@@ -78,11 +88,13 @@ impl<'a> MPIConfig<'a> {
 
     /// Initialize the MPI environment.
     /// Safe to call multiple times as `mpi::initialize()` will return None if already initialized.
+    #[cfg(feature = "mpi")]
     pub fn init() -> Option<Universe> {
         mpi::initialize()
     }
 
     /// Create a new MPI engine for the prover
+    #[cfg(feature = "mpi")]
     pub fn prover_new(
         universe: Option<&'a Universe>,
         communicator: Option<&'a SimpleCommunicator>,
@@ -98,6 +110,21 @@ impl<'a> MPIConfig<'a> {
             world,
             world_size,
             world_rank,
+            backend: None,
+        }
+    }
+
+    /// Create a new MPI engine for the prover, communicating through `backend` instead of MPI,
+    /// e.g. a `SocketCommunicator` or a `ThreadCommunicator`
+    pub fn backend_new(backend: &'a dyn CommBackend) -> Self {
+        Self {
+            #[cfg(feature = "mpi")]
+            universe: None,
+            #[cfg(feature = "mpi")]
+            world: None,
+            world_size: backend.world_size() as i32,
+            world_rank: backend.world_rank() as i32,
+            backend: Some(backend),
         }
     }
 
@@ -108,10 +135,13 @@ impl<'a> MPIConfig<'a> {
     #[inline]
     pub fn verifier_new(world_size: i32) -> Self {
         Self {
+            #[cfg(feature = "mpi")]
             universe: None,
+            #[cfg(feature = "mpi")]
             world: None,
             world_size,
             world_rank: 0,
+            backend: None,
         }
     }
 }
@@ -125,59 +155,87 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         unsafe {
             if self.world_size == 1 {
                 *global_vec = local_vec.to_vec()
-            } else {
+            } else if let Some(backend) = self.backend {
                 assert!(!self.is_root() || global_vec.len() == local_vec.len() * self.world_size());
 
-                let local_vec_u8 = transmute_vec_to_u8_bytes(local_vec);
-                let local_n_bytes = local_vec_u8.len();
-                let n_chunks = (local_n_bytes + Self::CHUNK_SIZE - 1) / Self::CHUNK_SIZE;
-                if n_chunks == 1 {
-                    if self.world_rank == Self::ROOT_RANK {
-                        let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
-                        self.root_process()
-                            .gather_into_root(&local_vec_u8, &mut global_vec_u8);
-                        global_vec_u8.leak(); // discard control of the memory
+                let local_u8s = slice::from_raw_parts(
+                    local_vec.as_ptr() as *const u8,
+                    std::mem::size_of_val(local_vec),
+                );
+                let all_u8s = backend.gather(local_u8s).unwrap_or_else(backend_failed);
+                if self.is_root() {
+                    let global_u8s = slice::from_raw_parts_mut(
+                        global_vec.as_mut_ptr() as *mut u8,
+                        std::mem::size_of_val(global_vec.as_slice()),
+                    );
+                    izip!(global_u8s.chunks_mut(local_u8s.len().max(1)), all_u8s)
+                        .for_each(|(global_c, local_c)| global_c.copy_from_slice(&local_c));
+                }
+            } else {
+                #[cfg(not(feature = "mpi"))]
+                mpi_disabled();
+
+                #[cfg(feature = "mpi")]
+                {
+                    assert!(
+                        !self.is_root() || global_vec.len() == local_vec.len() * self.world_size()
+                    );
+
+                    let local_vec_u8 = transmute_vec_to_u8_bytes(local_vec);
+                    let local_n_bytes = local_vec_u8.len();
+                    let n_chunks = (local_n_bytes + Self::CHUNK_SIZE - 1) / Self::CHUNK_SIZE;
+                    if n_chunks == 1 {
+                        if self.world_rank == Self::ROOT_RANK {
+                            let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
+                            self.root_process()
+                                .gather_into_root(&local_vec_u8, &mut global_vec_u8);
+                            global_vec_u8.leak(); // discard control of the memory
+                        } else {
+                            self.root_process().gather_into(&local_vec_u8);
+                        }
                     } else {
-                        self.root_process().gather_into(&local_vec_u8);
-                    }
-                } else {
-                    if self.world_rank == Self::ROOT_RANK {
-                        let mut chunk_buffer_u8 = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
-                        let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
-                        for i in 0..n_chunks {
-                            let local_start = i * Self::CHUNK_SIZE;
-                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
-                            let actual_chunk_size = local_end - local_start;
-                            if actual_chunk_size < Self::CHUNK_SIZE {
-                                chunk_buffer_u8.resize(actual_chunk_size * self.world_size(), 0u8);
-                            }
-
-                            self.root_process().gather_into_root(
-                                &local_vec_u8[local_start..local_end],
-                                &mut chunk_buffer_u8,
-                            );
-
-                            // distribute the data to where they belong to in global vec
-                            for j in 0..self.world_size() {
-                                let global_start = j * local_n_bytes + local_start;
-                                let global_end = global_start + actual_chunk_size;
-                                global_vec_u8[global_start..global_end].copy_from_slice(
-                                    &chunk_buffer_u8
-                                        [j * actual_chunk_size..(j + 1) * actual_chunk_size],
+                        if self.world_rank == Self::ROOT_RANK {
+                            let mut chunk_buffer_u8 =
+                                vec![0u8; Self::CHUNK_SIZE * self.world_size()];
+                            let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
+                            for i in 0..n_chunks {
+                                let local_start = i * Self::CHUNK_SIZE;
+                                let local_end =
+                                    cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
+                                let actual_chunk_size = local_end - local_start;
+                                if actual_chunk_size < Self::CHUNK_SIZE {
+                                    chunk_buffer_u8
+                                        .resize(actual_chunk_size * self.world_size(), 0u8);
+                                }
+
+                                self.root_process().gather_into_root(
+                                    &local_vec_u8[local_start..local_end],
+                                    &mut chunk_buffer_u8,
                                 );
+
+                                // distribute the data to where they belong to in global vec
+                                for j in 0..self.world_size() {
+                                    let global_start = j * local_n_bytes + local_start;
+                                    let global_end = global_start + actual_chunk_size;
+                                    global_vec_u8[global_start..global_end].copy_from_slice(
+                                        &chunk_buffer_u8
+                                            [j * actual_chunk_size..(j + 1) * actual_chunk_size],
+                                    );
+                                }
+                            }
+                            global_vec_u8.leak(); // discard control of the memory
+                        } else {
+                            for i in 0..n_chunks {
+                                let local_start = i * Self::CHUNK_SIZE;
+                                let local_end =
+                                    cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
+                                self.root_process()
+                                    .gather_into(&local_vec_u8[local_start..local_end]);
                             }
-                        }
-                        global_vec_u8.leak(); // discard control of the memory
-                    } else {
-                        for i in 0..n_chunks {
-                            let local_start = i * Self::CHUNK_SIZE;
-                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
-                            self.root_process()
-                                .gather_into(&local_vec_u8[local_start..local_end]);
                         }
                     }
+                    local_vec_u8.leak(); // discard control of the memory
                 }
-                local_vec_u8.leak(); // discard control of the memory
             }
         }
     }
@@ -197,49 +255,62 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         let recv_u8s: &mut [u8] =
             unsafe { slice::from_raw_parts_mut(recv_vec.as_mut_ptr() as *mut u8, recv_buf_u8_len) };
 
-        let n_chunks = recv_buf_u8_len.div_ceil(Self::CHUNK_SIZE);
-
-        if n_chunks == 1 {
-            if self.is_root() {
-                self.root_process().scatter_into_root(send_u8s, recv_u8s);
-            } else {
-                self.root_process().scatter_into(recv_u8s);
-            }
-
+        if let Some(backend) = self.backend {
+            backend
+                .scatter(send_u8s, recv_u8s)
+                .unwrap_or_else(backend_failed);
             return;
         }
 
-        if !self.is_root() {
-            recv_u8s.chunks_mut(Self::CHUNK_SIZE).for_each(|c| {
-                self.root_process().scatter_into(c);
-            });
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
 
-            return;
-        }
+        #[cfg(feature = "mpi")]
+        {
+            let n_chunks = recv_buf_u8_len.div_ceil(Self::CHUNK_SIZE);
 
-        let mut send_buf = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
+            if n_chunks == 1 {
+                if self.is_root() {
+                    self.root_process().scatter_into_root(send_u8s, recv_u8s);
+                } else {
+                    self.root_process().scatter_into(recv_u8s);
+                }
+
+                return;
+            }
 
-        izip!(0..n_chunks, recv_u8s.chunks_mut(Self::CHUNK_SIZE)).for_each(|(i, recv_c)| {
-            let copy_srt = i * Self::CHUNK_SIZE;
-            let copy_end = copy_srt + recv_c.len();
+            if !self.is_root() {
+                recv_u8s.chunks_mut(Self::CHUNK_SIZE).for_each(|c| {
+                    self.root_process().scatter_into(c);
+                });
 
-            if recv_c.len() < Self::CHUNK_SIZE {
-                send_buf.resize(recv_c.len() * self.world_size(), 0u8);
+                return;
             }
 
-            izip!(0..self.world_size(), send_buf.chunks_mut(recv_c.len())).for_each(
-                |(world_i, send_c)| {
-                    let world_starts = recv_buf_u8_len * world_i;
+            let mut send_buf = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
 
-                    let local_srt = world_starts + copy_srt;
-                    let local_end = world_starts + copy_end;
+            izip!(0..n_chunks, recv_u8s.chunks_mut(Self::CHUNK_SIZE)).for_each(|(i, recv_c)| {
+                let copy_srt = i * Self::CHUNK_SIZE;
+                let copy_end = copy_srt + recv_c.len();
 
-                    send_c.copy_from_slice(&send_u8s[local_srt..local_end]);
-                },
-            );
+                if recv_c.len() < Self::CHUNK_SIZE {
+                    send_buf.resize(recv_c.len() * self.world_size(), 0u8);
+                }
 
-            self.root_process().scatter_into_root(&send_buf, recv_c);
-        })
+                izip!(0..self.world_size(), send_buf.chunks_mut(recv_c.len())).for_each(
+                    |(world_i, send_c)| {
+                        let world_starts = recv_buf_u8_len * world_i;
+
+                        let local_srt = world_starts + copy_srt;
+                        let local_end = world_starts + copy_end;
+
+                        send_c.copy_from_slice(&send_u8s[local_srt..local_end]);
+                    },
+                );
+
+                self.root_process().scatter_into_root(&send_buf, recv_c);
+            })
+        }
     }
 
     /// Root process broadcast a value f into all the processes
@@ -247,10 +318,23 @@ impl<'a> MPIEngine for MPIConfig<'a> {
     fn root_broadcast_f<F: Copy>(&self, f: &mut F) {
         unsafe {
             if self.world_size == 1 {
+            } else if let Some(backend) = self.backend {
+                backend
+                    .broadcast(slice::from_raw_parts_mut(
+                        f as *mut F as *mut u8,
+                        std::mem::size_of::<F>(),
+                    ))
+                    .unwrap_or_else(backend_failed);
             } else {
-                let mut vec_u8 = transmute_elem_to_u8_bytes(f, std::mem::size_of::<F>());
-                self.root_process().broadcast_into(&mut vec_u8);
-                vec_u8.leak();
+                #[cfg(not(feature = "mpi"))]
+                mpi_disabled();
+
+                #[cfg(feature = "mpi")]
+                {
+                    let mut vec_u8 = transmute_elem_to_u8_bytes(f, std::mem::size_of::<F>());
+                    self.root_process().broadcast_into(&mut vec_u8);
+                    vec_u8.leak();
+                }
             }
         }
     }
@@ -260,7 +344,17 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         if self.world_size == 1 {
             return;
         }
-        self.root_process().broadcast_into(bytes);
+        if let Some(backend) = self.backend {
+            backend.broadcast(bytes).unwrap_or_else(backend_failed);
+            return;
+        }
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
+
+        #[cfg(feature = "mpi")]
+        {
+            self.root_process().broadcast_into(bytes);
+        }
     }
 
     /// sum up all local values
@@ -313,52 +407,64 @@ impl<'a> MPIEngine for MPIConfig<'a> {
     fn all_to_all_transpose<F: Sized>(&self, row: &mut [F]) {
         assert_eq!(row.len() % self.world_size(), 0);
 
-        // NOTE(HS) MPI has some upper limit for send buffer size, pre declare here and use later
-        const SEND_BUFFER_MAX: usize = 1 << 22;
-
         let row_as_u8_len = size_of_val(row);
         let row_u8s: &mut [u8] =
             unsafe { slice::from_raw_parts_mut(row.as_mut_ptr() as *mut u8, row_as_u8_len) };
 
-        let num_of_bytes_per_world = row_as_u8_len / self.world_size();
-        let num_of_transposes = row_as_u8_len.div_ceil(SEND_BUFFER_MAX);
+        if let Some(backend) = self.backend {
+            backend.all_to_all(row_u8s).unwrap_or_else(backend_failed);
+            return;
+        }
 
-        let mut send = vec![0u8; SEND_BUFFER_MAX];
-        let mut recv = vec![0u8; SEND_BUFFER_MAX];
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
 
-        let mut send_buffer_size = SEND_BUFFER_MAX;
-        let mut copy_starts = 0;
+        #[cfg(feature = "mpi")]
+        {
+            // NOTE(HS) MPI has some upper limit for send buffer size, pre declare here and use
+            // later
+            const SEND_BUFFER_MAX: usize = 1 << 22;
 
-        (0..num_of_transposes).for_each(|ith_transpose| {
-            if ith_transpose == num_of_transposes - 1 {
-                send_buffer_size = (num_of_bytes_per_world - copy_starts) * self.world_size();
-                send.resize(send_buffer_size, 0u8);
-                recv.resize(send_buffer_size, 0u8);
-            }
+            let num_of_bytes_per_world = row_as_u8_len / self.world_size();
+            let num_of_transposes = row_as_u8_len.div_ceil(SEND_BUFFER_MAX);
 
-            let send_buffer_size_per_world = send_buffer_size / self.world_size();
-            let copy_ends = copy_starts + send_buffer_size_per_world;
+            let mut send = vec![0u8; SEND_BUFFER_MAX];
+            let mut recv = vec![0u8; SEND_BUFFER_MAX];
 
-            izip!(
-                row_u8s.chunks(num_of_bytes_per_world),
-                send.chunks_mut(send_buffer_size_per_world)
-            )
-            .for_each(|(row_chunk, send_chunk)| {
-                send_chunk.copy_from_slice(&row_chunk[copy_starts..copy_ends]);
-            });
+            let mut send_buffer_size = SEND_BUFFER_MAX;
+            let mut copy_starts = 0;
+
+            (0..num_of_transposes).for_each(|ith_transpose| {
+                if ith_transpose == num_of_transposes - 1 {
+                    send_buffer_size = (num_of_bytes_per_world - copy_starts) * self.world_size();
+                    send.resize(send_buffer_size, 0u8);
+                    recv.resize(send_buffer_size, 0u8);
+                }
 
-            self.world.unwrap().all_to_all_into(&send, &mut recv);
+                let send_buffer_size_per_world = send_buffer_size / self.world_size();
+                let copy_ends = copy_starts + send_buffer_size_per_world;
 
-            izip!(
-                row_u8s.chunks_mut(num_of_bytes_per_world),
-                recv.chunks(send_buffer_size_per_world)
-            )
-            .for_each(|(row_chunk, recv_chunk)| {
-                row_chunk[copy_starts..copy_ends].copy_from_slice(recv_chunk);
-            });
+                izip!(
+                    row_u8s.chunks(num_of_bytes_per_world),
+                    send.chunks_mut(send_buffer_size_per_world)
+                )
+                .for_each(|(row_chunk, send_chunk)| {
+                    send_chunk.copy_from_slice(&row_chunk[copy_starts..copy_ends]);
+                });
+
+                self.world.unwrap().all_to_all_into(&send, &mut recv);
 
-            copy_starts += send_buffer_size_per_world;
-        });
+                izip!(
+                    row_u8s.chunks_mut(num_of_bytes_per_world),
+                    recv.chunks(send_buffer_size_per_world)
+                )
+                .for_each(|(row_chunk, recv_chunk)| {
+                    row_chunk[copy_starts..copy_ends].copy_from_slice(recv_chunk);
+                });
+
+                copy_starts += send_buffer_size_per_world;
+            });
+        }
     }
 
     #[inline(always)]
@@ -366,33 +472,51 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         let mut elems_bytes: Vec<u8> = Vec::new();
         elems.serialize_into(&mut elems_bytes).unwrap();
 
-        let mut byte_lengths = vec![0i32; self.world_size()];
-        self.gather_vec(&[elems_bytes.len() as i32], &mut byte_lengths);
+        if let Some(backend) = self.backend {
+            let all_elems_bytes = backend.gather(&elems_bytes).unwrap_or_else(backend_failed);
+            if self.is_root() {
+                *global_elems = all_elems_bytes
+                    .iter()
+                    .map(|bytes| Vec::deserialize_from(bytes.as_slice()).unwrap())
+                    .collect();
+            }
+            return;
+        }
 
-        let all_elems_bytes_len = byte_lengths.iter().sum::<i32>() as usize;
-        let mut all_elems_bytes: Vec<u8> = vec![0u8; all_elems_bytes_len];
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
 
-        if !self.is_root() {
-            self.root_process().gather_varcount_into(&elems_bytes);
-        } else {
-            let displs = byte_lengths
-                .iter()
-                .scan(0, |s, i| {
-                    let srt = *s;
-                    *s += i;
-                    Some(srt)
-                })
-                .collect::<Vec<_>>();
-
-            let mut partition = PartitionMut::new(&mut all_elems_bytes, byte_lengths, &displs[..]);
-
-            self.root_process()
-                .gather_varcount_into_root(&elems_bytes, &mut partition);
-
-            *global_elems = displs
-                .iter()
-                .map(|&srt| Vec::deserialize_from(&all_elems_bytes[srt as usize..]).unwrap())
-                .collect();
+        #[cfg(feature = "mpi")]
+        {
+            let mut byte_lengths = vec![0i32; self.world_size()];
+            self.gather_vec(&[elems_bytes.len() as i32], &mut byte_lengths);
+
+            let all_elems_bytes_len = byte_lengths.iter().sum::<i32>() as usize;
+            let mut all_elems_bytes: Vec<u8> = vec![0u8; all_elems_bytes_len];
+
+            if !self.is_root() {
+                self.root_process().gather_varcount_into(&elems_bytes);
+            } else {
+                let displs = byte_lengths
+                    .iter()
+                    .scan(0, |s, i| {
+                        let srt = *s;
+                        *s += i;
+                        Some(srt)
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut partition =
+                    PartitionMut::new(&mut all_elems_bytes, byte_lengths, &displs[..]);
+
+                self.root_process()
+                    .gather_varcount_into_root(&elems_bytes, &mut partition);
+
+                *global_elems = displs
+                    .iter()
+                    .map(|&srt| Vec::deserialize_from(&all_elems_bytes[srt as usize..]).unwrap())
+                    .collect();
+            }
         }
     }
 
@@ -411,6 +535,7 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         self.world_rank as usize
     }
 
+    #[cfg(feature = "mpi")]
     #[inline(always)]
     fn root_process(&self) -> Process {
         self.world.unwrap().process_at_rank(Self::ROOT_RANK)
@@ -421,52 +546,107 @@ impl<'a> MPIEngine for MPIConfig<'a> {
     #[inline(always)]
     fn barrier(&self) {
         if self.world_size > 1 {
-            self.world.unwrap().barrier();
+            match self.backend {
+                Some(backend) => backend.barrier().unwrap_or_else(backend_failed),
+                #[cfg(feature = "mpi")]
+                None => self.world.unwrap().barrier(),
+                #[cfg(not(feature = "mpi"))]
+                None => mpi_disabled(),
+            }
         }
     }
 
     #[inline]
     fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, *mut ompi_win_t) {
-        let window_size = if self.is_root() { n_bytes } else { 0 };
-        let mut baseptr: *mut c_void = std::ptr::null_mut();
-        let mut window = std::ptr::null_mut();
-        unsafe {
-            MPI_Win_allocate_shared(
-                window_size as isize,
-                1,
-                RSMPI_INFO_NULL,
-                self.world.unwrap().as_raw(),
-                &mut baseptr as *mut *mut c_void as *mut c_void,
-                &mut window,
-            );
-            self.barrier();
+        if let Some(backend) = self.backend {
+            // the window of a backend is the address of the shared memory
+            let ptr = backend
+                .create_shared_mem(n_bytes)
+                .unwrap_or_else(backend_failed);
+            return (ptr, ptr as *mut ompi_win_t);
+        }
 
-            if !self.is_root() {
-                let mut size = 0;
-                let mut disp_unit = 0;
-                let mut query_baseptr: *mut c_void = std::ptr::null_mut();
-                MPI_Win_shared_query(
-                    window,
-                    0,
-                    &mut size,
-                    &mut disp_unit,
-                    &mut query_baseptr as *mut *mut c_void as *mut c_void,
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
+
+        #[cfg(feature = "mpi")]
+        {
+            let window_size = if self.is_root() { n_bytes } else { 0 };
+            let mut baseptr: *mut c_void = std::ptr::null_mut();
+            let mut window = std::ptr::null_mut();
+            unsafe {
+                MPI_Win_allocate_shared(
+                    window_size as isize,
+                    1,
+                    RSMPI_INFO_NULL,
+                    self.world.unwrap().as_raw(),
+                    &mut baseptr as *mut *mut c_void as *mut c_void,
+                    &mut window,
                 );
-                baseptr = query_baseptr;
+                self.barrier();
+
+                if !self.is_root() {
+                    let mut size = 0;
+                    let mut disp_unit = 0;
+                    let mut query_baseptr: *mut c_void = std::ptr::null_mut();
+                    MPI_Win_shared_query(
+                        window,
+                        0,
+                        &mut size,
+                        &mut disp_unit,
+                        &mut query_baseptr as *mut *mut c_void as *mut c_void,
+                    );
+                    baseptr = query_baseptr;
+                }
             }
+
+            (baseptr as *mut u8, window)
         }
+    }
+
+    #[inline]
+    fn free_shared_mem(&self, window: &mut *mut ompi_win_t) {
+        if let Some(backend) = self.backend {
+            backend
+                .free_shared_mem(*window as *mut u8)
+                .unwrap_or_else(backend_failed);
+            return;
+        }
+        #[cfg(not(feature = "mpi"))]
+        mpi_disabled();
 
-        (baseptr as *mut u8, window)
+        #[cfg(feature = "mpi")]
+        {
+            unsafe {
+                MPI_Win_free(window as *mut *mut ompi_win_t);
+            }
+        }
     }
 }
 
+/// The collectives cannot fail, so a failure of the backend aborts the process as the default
+/// error handler of MPI does
+#[cold]
+fn backend_failed(e: io::Error) -> ! {
+    panic!("unable to communicate with the other processes: {e}")
+}
+
+/// Without MPI, a world of several processes communicates through a `CommBackend` only
+#[cfg(not(feature = "mpi"))]
+#[cold]
+fn mpi_disabled() -> ! {
+    panic!("MPI is disabled, a world of several processes needs a CommBackend")
+}
+
 /// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
+#[cfg(feature = "mpi")]
 #[inline]
 unsafe fn transmute_elem_to_u8_bytes<V: Sized>(elem: &V, byte_size: usize) -> Vec<u8> {
     Vec::<u8>::from_raw_parts((elem as *const V) as *mut u8, byte_size, byte_size)
 }
 
 /// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
+#[cfg(feature = "mpi")]
 #[inline]
 unsafe fn transmute_vec_to_u8_bytes<F: Sized>(vec: &[F]) -> Vec<u8> {
     Vec::<u8>::from_raw_parts(
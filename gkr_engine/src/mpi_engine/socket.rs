@@ -0,0 +1,341 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, DirBuilder, OpenOptions},
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    os::{
+        fd::AsRawFd,
+        unix::fs::{DirBuilderExt, OpenOptionsExt},
+    },
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::OsRng, RngCore};
+
+use super::CommBackend;
+
+/// Address the root process listens on, e.g. `127.0.0.1:9000`
+pub const RENDEZVOUS_ENV: &str = "EXPANDER_RENDEZVOUS";
+/// Number of processes
+pub const WORLD_SIZE_ENV: &str = "EXPANDER_WORLD_SIZE";
+/// Rank of the current process, the root being 0
+pub const WORLD_RANK_ENV: &str = "EXPANDER_WORLD_RANK";
+/// Directory of the files backing the shared memory, defaults to the temporary directory
+pub const SHM_DIR_ENV: &str = "EXPANDER_SHM_DIR";
+/// Secret shared by the processes, which the root checks before accepting a process, e.g. the
+/// output of `openssl rand -hex 32`
+pub const TOKEN_ENV: &str = "EXPANDER_SOCKET_TOKEN";
+
+/// The longest accepted token, so that the rendezvous frames are bounded
+pub const MAX_TOKEN_LEN: usize = 256;
+
+/// How long a non-root process retries connecting to the root
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A communication backend over TCP sockets, for running on local processes without MPI.
+///
+/// The processes form a star around the root, the root being connected to all the other
+/// processes. They are run by the same user: the shared memory files are only accessible to
+/// their owner.
+pub struct SocketCommunicator {
+    world_size: usize,
+    world_rank: usize,
+    /// `peers[rank]` is the connection to process `rank`: the root is connected to all the other
+    /// processes, the others to the root only
+    peers: Vec<Option<TcpStream>>,
+    /// Private directory of the shared memory files of this run, created by the root and named
+    /// after a random session id
+    session_dir: PathBuf,
+    n_shared_mems: AtomicUsize,
+    /// Mapped shared memory segments, from their address to their length and backing file
+    shared_mems: Mutex<HashMap<usize, (usize, PathBuf)>>,
+}
+
+impl SocketCommunicator {
+    /// Connect all the processes through the root listening on `addr`.
+    ///
+    /// Every process of the world calls this with the same `addr`, `world_size` and `token`, the
+    /// root returns once all the other processes are connected. The root rejects the processes
+    /// presenting another token.
+    pub fn connect(
+        addr: &str,
+        world_size: usize,
+        world_rank: usize,
+        token: &[u8],
+    ) -> io::Result<Self> {
+        if world_size == 0 || world_rank >= world_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid rank {world_rank} for world size {world_size}"),
+            ));
+        }
+        if token.is_empty() || token.len() > MAX_TOKEN_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the token must be of 1 to {MAX_TOKEN_LEN} bytes"),
+            ));
+        }
+
+        // rank, world size and token
+        let hello_len = 16 + token.len();
+        let mut peers: Vec<Option<TcpStream>> = (0..world_size).map(|_| None).collect();
+        let session_id = if world_rank == 0 {
+            let mut session_id = [0u8; 8];
+            OsRng
+                .try_fill_bytes(&mut session_id)
+                .map_err(io::Error::other)?;
+            let session_id = u64::from_le_bytes(session_id);
+
+            let listener = TcpListener::bind(addr)?;
+            for _ in 1..world_size {
+                let (stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                let hello = recv_frame(&stream, hello_len)?;
+                if hello.len() != hello_len || !tokens_match(&hello[16..], token) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "a process presented a wrong token",
+                    ));
+                }
+                let rank = u64::from_le_bytes(hello[..8].try_into().unwrap()) as usize;
+                let size = u64::from_le_bytes(hello[8..16].try_into().unwrap()) as usize;
+                if size != world_size || rank == 0 || rank >= world_size || peers[rank].is_some() {
+                    return Err(invalid_data(format!(
+                        "unexpected process of rank {rank} in world of size {size}"
+                    )));
+                }
+                send_frame(&stream, &session_id.to_le_bytes())?;
+                peers[rank] = Some(stream);
+            }
+            session_id
+        } else {
+            let deadline = Instant::now() + CONNECT_TIMEOUT;
+            let stream = loop {
+                match TcpStream::connect(addr) {
+                    Ok(stream) => break stream,
+                    Err(e) if Instant::now() >= deadline => return Err(e),
+                    Err(_) => thread::sleep(CONNECT_RETRY_INTERVAL),
+                }
+            };
+            stream.set_nodelay(true)?;
+            let mut hello = (world_rank as u64).to_le_bytes().to_vec();
+            hello.extend_from_slice(&(world_size as u64).to_le_bytes());
+            hello.extend_from_slice(token);
+            send_frame(&stream, &hello)?;
+            let session_id = recv_frame(&stream, 8)?
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| invalid_data("malformed rendezvous message"))?;
+            peers[0] = Some(stream);
+            session_id
+        };
+
+        let session_dir = env::var_os(SHM_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(env::temp_dir)
+            .join(format!("expander-{session_id:016x}"));
+        if world_rank == 0 {
+            // fails if the directory exists, so that its content is ours only
+            DirBuilder::new().mode(0o700).create(&session_dir)?;
+        }
+
+        Ok(Self {
+            world_size,
+            world_rank,
+            peers,
+            session_dir,
+            n_shared_mems: AtomicUsize::new(0),
+            shared_mems: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Connect with the rendezvous address, world size, rank and token given by
+    /// `EXPANDER_RENDEZVOUS`, `EXPANDER_WORLD_SIZE`, `EXPANDER_WORLD_RANK` and
+    /// `EXPANDER_SOCKET_TOKEN`, or None if `EXPANDER_RENDEZVOUS` is not set.
+    pub fn from_env() -> Option<io::Result<Self>> {
+        let addr = env::var(RENDEZVOUS_ENV).ok()?;
+        let token = match env::var(TOKEN_ENV) {
+            Ok(token) => token,
+            Err(_) => {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{TOKEN_ENV} must be set along with {RENDEZVOUS_ENV}"),
+                )))
+            }
+        };
+        let parse_var = |name: &str| -> io::Result<usize> {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("{name} must be set to an integer along with {RENDEZVOUS_ENV}"),
+                    )
+                })
+        };
+
+        Some(parse_var(WORLD_SIZE_ENV).and_then(|world_size| {
+            parse_var(WORLD_RANK_ENV).and_then(|world_rank| {
+                Self::connect(&addr, world_size, world_rank, token.as_bytes())
+            })
+        }))
+    }
+
+    #[inline(always)]
+    fn peer(&self, rank: usize) -> io::Result<&TcpStream> {
+        self.peers[rank].as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("not connected to process {rank}"),
+            )
+        })
+    }
+}
+
+impl CommBackend for SocketCommunicator {
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    #[inline]
+    fn send(&self, rank: usize, bytes: &[u8]) -> io::Result<()> {
+        send_frame(self.peer(rank)?, bytes)
+    }
+
+    /// The messages are not bounded, the peers being authenticated at the rendezvous
+    #[inline]
+    fn recv(&self, rank: usize) -> io::Result<Vec<u8>> {
+        recv_frame(self.peer(rank)?, usize::MAX)
+    }
+
+    /// The shared memory is a file created by the root and mapped by all processes
+    fn create_shared_mem(&self, n_bytes: usize) -> io::Result<*mut u8> {
+        let id = self.n_shared_mems.fetch_add(1, Ordering::SeqCst);
+        let path = self.session_dir.join(format!("shm-{id}"));
+
+        let file = if self.is_root() {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)?;
+            // mmap rejects empty mappings
+            file.set_len(n_bytes.max(1) as u64)?;
+            self.barrier()?;
+            file
+        } else {
+            self.barrier()?; // wait for root to create the file
+            OpenOptions::new().read(true).write(true).open(&path)?
+        };
+
+        let len = file.metadata()?.len() as usize;
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        self.shared_mems
+            .lock()
+            .unwrap()
+            .insert(ptr as usize, (len, path));
+        Ok(ptr as *mut u8)
+    }
+
+    /// Unmap the shared memory at `ptr`, the root also removes the backing file
+    fn free_shared_mem(&self, ptr: *mut u8) -> io::Result<()> {
+        let (len, path) = self
+            .shared_mems
+            .lock()
+            .unwrap()
+            .remove(&(ptr as usize))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "not a shared memory of this communicator",
+                )
+            })?;
+        if unsafe { libc::munmap(ptr as *mut libc::c_void, len) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if self.is_root() {
+            // the other processes keep their mapping alive after the removal
+            let _ = fs::remove_file(path);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SocketCommunicator {
+    fn drop(&mut self) {
+        if self.is_root() {
+            for (_, (_, path)) in self.shared_mems.get_mut().unwrap().drain() {
+                let _ = fs::remove_file(path);
+            }
+            let _ = fs::remove_dir(&self.session_dir);
+        }
+    }
+}
+
+#[inline]
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Send a message prefixed with its length
+#[inline]
+fn send_frame(mut stream: &TcpStream, bytes: &[u8]) -> io::Result<()> {
+    stream.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    stream.write_all(bytes)
+}
+
+#[inline]
+fn recv_len(mut stream: &TcpStream) -> io::Result<usize> {
+    let mut len = [0u8; 8];
+    stream.read_exact(&mut len)?;
+    Ok(u64::from_le_bytes(len) as usize)
+}
+
+/// Receive a message of at most `max_len` bytes, before allocating it
+#[inline]
+fn recv_frame(mut stream: &TcpStream, max_len: usize) -> io::Result<Vec<u8>> {
+    let len = recv_len(stream)?;
+    if len > max_len {
+        return Err(invalid_data(format!(
+            "message of {len} bytes, expected at most {max_len}"
+        )));
+    }
+    let mut bytes = vec![0u8; len];
+    stream.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Compare the tokens in a time independent of their content
+#[inline]
+fn tokens_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,10 +1,20 @@
+#[cfg(feature = "socket")]
+use std::{
+    io::{self, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
 use arith::Field;
 use ark_std::test_rng;
 use gf2::{GF2x128, GF2x64, GF2x8};
 use itertools::izip;
 use mersenne31::{M31Ext3, M31x16, M31};
 
-use crate::{MPIConfig, MPIEngine};
+#[cfg(feature = "socket")]
+use crate::SocketCommunicator;
+use crate::{MPIConfig, MPIEngine, SharedMemory, ThreadCommunicator};
 
 fn test_gather_vec_helper(mpi_config: &MPIConfig) {
     const TEST_SIZE: usize = (1 << 10) + 1;
@@ -108,6 +118,31 @@ fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
     assert!(expected);
 }
 
+fn test_sum_vec_helper(mpi_config: &MPIConfig) {
+    let local_vec = vec![M31::from(mpi_config.world_rank() as u32 + 1); 3];
+    let sum = mpi_config.sum_vec(&local_vec);
+    if mpi_config.is_root() {
+        let world_size = mpi_config.world_size() as u32;
+        assert_eq!(sum, vec![M31::from(world_size * (world_size + 1) / 2); 3]);
+    }
+}
+
+fn test_shared_mem_helper(mpi_config: &MPIConfig) {
+    let obj = if mpi_config.is_root() {
+        Some((0..1025usize).collect::<Vec<_>>())
+    } else {
+        None
+    };
+
+    let (shared, mut window) = mpi_config.consume_obj_and_create_shared(obj);
+    assert!(shared.iter().enumerate().all(|(i, v)| i == *v));
+
+    shared.discard_control_of_shared_mem();
+    mpi_config.barrier();
+    mpi_config.free_shared_mem(&mut window);
+}
+
+#[cfg(feature = "mpi")]
 #[test]
 fn test_mpi_engine() {
     let universe = MPIConfig::init().unwrap();
@@ -128,3 +163,115 @@ fn test_mpi_engine() {
 
     test_scatter_vec_helper(&mpi_config);
 }
+
+#[cfg(feature = "socket")]
+const TEST_TOKEN: &[u8] = b"0123456789abcdef";
+
+/// A free port for the rendezvous
+#[cfg(feature = "socket")]
+fn free_local_addr() -> String {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string()
+}
+
+/// Connect to the root as soon as it listens
+#[cfg(feature = "socket")]
+fn connect_to_root(addr: &str) -> TcpStream {
+    loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => return stream,
+            Err(_) => thread::sleep(Duration::from_millis(10)),
+        }
+    }
+}
+
+#[cfg(feature = "socket")]
+#[test]
+fn test_socket_engine() {
+    const WORLD_SIZE: usize = 4;
+
+    let addr = free_local_addr();
+
+    thread::scope(|s| {
+        for world_rank in 0..WORLD_SIZE {
+            let addr = &addr;
+            s.spawn(move || {
+                let socket =
+                    SocketCommunicator::connect(addr, WORLD_SIZE, world_rank, TEST_TOKEN).unwrap();
+                let mpi_config = MPIConfig::backend_new(&socket);
+
+                test_gather_vec_helper(&mpi_config);
+
+                test_all_to_all_transpose_helper::<GF2x8>(&mpi_config);
+                test_all_to_all_transpose_helper::<M31>(&mpi_config);
+
+                test_varlen_gather_vec_helper(&mpi_config);
+
+                test_scatter_vec_helper(&mpi_config);
+
+                test_sum_vec_helper(&mpi_config);
+
+                test_shared_mem_helper(&mpi_config);
+            });
+        }
+    });
+}
+
+#[cfg(feature = "socket")]
+#[test]
+fn test_socket_rendezvous_rejects_wrong_token() {
+    let addr = free_local_addr();
+
+    thread::scope(|s| {
+        let root = s.spawn(|| SocketCommunicator::connect(&addr, 2, 0, TEST_TOKEN).err());
+        let other = SocketCommunicator::connect(&addr, 2, 1, b"fedcba9876543210");
+
+        assert!(other.is_err());
+        assert_eq!(
+            root.join().unwrap().map(|e| e.kind()),
+            Some(io::ErrorKind::PermissionDenied)
+        );
+    });
+}
+
+#[cfg(feature = "socket")]
+#[test]
+fn test_socket_rendezvous_rejects_long_frame() {
+    let addr = free_local_addr();
+
+    thread::scope(|s| {
+        let root = s.spawn(|| SocketCommunicator::connect(&addr, 2, 0, TEST_TOKEN).err());
+
+        // a frame claiming more bytes than a rendezvous message, which are never sent
+        let mut stream = connect_to_root(&addr);
+        stream.write_all(&u64::MAX.to_le_bytes()).unwrap();
+
+        assert_eq!(
+            root.join().unwrap().map(|e| e.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+    });
+}
+
+#[test]
+fn test_thread_engine() {
+    for world_size in [2, 4, 8] {
+        ThreadCommunicator::run_world(world_size, |mpi_config| {
+            test_gather_vec_helper(&mpi_config);
+
+            test_all_to_all_transpose_helper::<GF2x8>(&mpi_config);
+            test_all_to_all_transpose_helper::<M31>(&mpi_config);
+
+            test_varlen_gather_vec_helper(&mpi_config);
+
+            test_scatter_vec_helper(&mpi_config);
+
+            test_sum_vec_helper(&mpi_config);
+
+            test_shared_mem_helper(&mpi_config);
+        });
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    io,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use super::{CommBackend, MPIConfig};
+
+/// A communication backend running the processes as threads of a single process, to exercise
+/// the distributed code paths in tests without `mpiexec`.
+///
+/// The threads are connected to the root with channels, and the shared memory is a buffer
+/// allocated by the root, visible to all threads of the process.
+pub struct ThreadCommunicator {
+    world_size: usize,
+    world_rank: usize,
+    /// `senders[rank]` sends to thread `rank`: the root is connected to all the other threads,
+    /// the others to the root only
+    senders: Vec<Option<Sender<Vec<u8>>>>,
+    /// `receivers[rank]` receives from thread `rank`
+    receivers: Vec<Option<Mutex<Receiver<Vec<u8>>>>>,
+    /// Shared memory allocated by the root, from their address to their number of words
+    shared_mems: Mutex<HashMap<usize, usize>>,
+}
+
+impl ThreadCommunicator {
+    /// Create the communicators of a world of `world_size` threads, indexed by rank. Each one is
+    /// to be moved into its own thread.
+    pub fn new_world(world_size: usize) -> Vec<Self> {
+        assert!(world_size > 0);
+
+        let mut world = (0..world_size)
+            .map(|world_rank| Self {
+                world_size,
+                world_rank,
+                senders: (0..world_size).map(|_| None).collect(),
+                receivers: (0..world_size).map(|_| None).collect(),
+                shared_mems: Mutex::new(HashMap::new()),
+            })
+            .collect::<Vec<_>>();
+
+        for rank in 1..world_size {
+            let (to_rank, from_root) = channel();
+            let (to_root, from_rank) = channel();
+            world[0].senders[rank] = Some(to_rank);
+            world[0].receivers[rank] = Some(Mutex::new(from_rank));
+            world[rank].senders[0] = Some(to_root);
+            world[rank].receivers[0] = Some(Mutex::new(from_root));
+        }
+
+        world
+    }
+
+    /// Run `f` in each thread of a world of `world_size` threads, with an `MPIConfig` over this
+    /// backend, as `mpiexec -n <world_size>` would.
+    pub fn run_world<F: Fn(MPIConfig) + Sync>(world_size: usize, f: F) {
+        thread::scope(|s| {
+            for communicator in Self::new_world(world_size) {
+                let f = &f;
+                s.spawn(move || f(MPIConfig::backend_new(&communicator)));
+            }
+        });
+    }
+}
+
+impl CommBackend for ThreadCommunicator {
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    #[inline]
+    fn send(&self, rank: usize, bytes: &[u8]) -> io::Result<()> {
+        self.senders[rank]
+            .as_ref()
+            .unwrap()
+            .send(bytes.to_vec())
+            .map_err(|_| thread_exited())
+    }
+
+    #[inline]
+    fn recv(&self, rank: usize) -> io::Result<Vec<u8>> {
+        self.receivers[rank]
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .recv()
+            .map_err(|_| thread_exited())
+    }
+
+    /// The root allocates the memory and sends its address to the other threads
+    fn create_shared_mem(&self, n_bytes: usize) -> io::Result<*mut u8> {
+        let mut addr = [0u8; std::mem::size_of::<usize>()];
+        if self.is_root() {
+            // u64 words keep the memory aligned for the objects written into it
+            let n_words = n_bytes.div_ceil(8).max(1);
+            let ptr = Box::into_raw(vec![0u64; n_words].into_boxed_slice()) as *mut u64 as usize;
+            self.shared_mems.lock().unwrap().insert(ptr, n_words);
+            addr = ptr.to_le_bytes();
+        }
+        self.broadcast(&mut addr)?;
+
+        Ok(usize::from_le_bytes(addr) as *mut u8)
+    }
+
+    /// The root frees the memory once all threads are done with it
+    fn free_shared_mem(&self, ptr: *mut u8) -> io::Result<()> {
+        self.barrier()?;
+        if self.is_root() {
+            let n_words = self
+                .shared_mems
+                .lock()
+                .unwrap()
+                .remove(&(ptr as usize))
+                .expect("not a shared memory of this communicator");
+            free_words(ptr as usize, n_words);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ThreadCommunicator {
+    fn drop(&mut self) {
+        for (ptr, n_words) in self.shared_mems.get_mut().unwrap().drain() {
+            free_words(ptr, n_words);
+        }
+    }
+}
+
+#[inline]
+fn thread_exited() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "the other thread has exited")
+}
+
+#[inline]
+fn free_words(ptr: usize, n_words: usize) {
+    unsafe {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            ptr as *mut u64,
+            n_words,
+        )));
+    }
+}
@@ -0,0 +1,155 @@
+//! Header of the PCS setup files, see `expander-exec setup`.
+//!
+//! Each setup file starts with a [`PCSSetupHeader`] that records the PCS, the field and the size
+//! the setup was generated for, so that a setup that does not match the circuit and MPI size a
+//! prover or verifier is running with is rejected.
+
+use std::io::Cursor;
+
+use serdes::ExpSerde;
+
+use crate::{
+    ExpErrors, ExpanderPCS, FieldEngine, FieldType, GKREngine, PolynomialCommitmentType,
+    StructuredReferenceString,
+};
+
+/// b"EXPPCSSU" in little endian
+pub const PCS_SETUP_MAGIC: u64 = 0x5553_5343_5050_5845;
+
+pub const PCS_SETUP_VERSION: u32 = 1;
+
+pub type PCSOf<Cfg> = <Cfg as GKREngine>::PCSConfig;
+pub type PCSParamsOf<Cfg> = <PCSOf<Cfg> as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::Params;
+pub type PCSScratchPadOf<Cfg> = <PCSOf<Cfg> as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::ScratchPad;
+pub type PCSProvingKeyOf<Cfg> = <<PCSOf<Cfg> as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::SRS as StructuredReferenceString>::PKey;
+pub type PCSVerifyingKeyOf<Cfg> = <<PCSOf<Cfg> as ExpanderPCS<
+    <Cfg as GKREngine>::FieldConfig,
+    <Cfg as GKREngine>::PCSField,
+>>::SRS as StructuredReferenceString>::VKey;
+
+#[derive(Debug, Clone, Copy, PartialEq, ExpSerde)]
+pub enum PCSSetupFileKind {
+    Params,
+    ProvingKey,
+    VerifyingKey,
+}
+
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct PCSSetupHeader {
+    pub magic: u64,
+    pub version: u32,
+    pub kind: PCSSetupFileKind,
+    pub pcs_type: PolynomialCommitmentType,
+    pub field_type: FieldType,
+    /// Number of input variables of the circuit, excluding the SIMD and MPI variables
+    pub n_input_vars: usize,
+    pub world_size: usize,
+    /// Rank owning the file; proving keys are rank local, other files are written by the root
+    pub world_rank: usize,
+}
+
+impl PCSSetupHeader {
+    pub fn new<Cfg: GKREngine>(
+        kind: PCSSetupFileKind,
+        n_input_vars: usize,
+        world_size: usize,
+        world_rank: usize,
+    ) -> Self {
+        Self {
+            magic: PCS_SETUP_MAGIC,
+            version: PCS_SETUP_VERSION,
+            kind,
+            pcs_type: <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::PCS_TYPE,
+            field_type: Cfg::FieldConfig::FIELD_TYPE,
+            n_input_vars,
+            world_size,
+            world_rank,
+        }
+    }
+
+    /// Check a header read from a file against the one expected by the caller.
+    pub fn check(&self, expected: &Self) -> Result<(), ExpErrors> {
+        if self.magic != expected.magic {
+            return Err(ExpErrors::PCSSetupError("not a PCS setup file".to_string()));
+        }
+        if self.version != expected.version {
+            return Err(ExpErrors::PCSSetupError(format!(
+                "unsupported version {}, expected {}",
+                self.version, expected.version
+            )));
+        }
+
+        let mismatch = |what: &str, found: String, expected: String| {
+            Err(ExpErrors::PCSSetupError(format!(
+                "{what} is {found}, expected {expected}"
+            )))
+        };
+        if self.kind != expected.kind {
+            return mismatch(
+                "file kind",
+                format!("{:?}", self.kind),
+                format!("{:?}", expected.kind),
+            );
+        }
+        if self.pcs_type != expected.pcs_type {
+            return mismatch(
+                "PCS",
+                format!("{:?}", self.pcs_type),
+                format!("{:?}", expected.pcs_type),
+            );
+        }
+        if self.field_type != expected.field_type {
+            return mismatch(
+                "field",
+                format!("{:?}", self.field_type),
+                format!("{:?}", expected.field_type),
+            );
+        }
+        if self.n_input_vars != expected.n_input_vars {
+            return mismatch(
+                "number of input variables",
+                self.n_input_vars.to_string(),
+                expected.n_input_vars.to_string(),
+            );
+        }
+        if self.world_size != expected.world_size {
+            return mismatch(
+                "MPI world size",
+                self.world_size.to_string(),
+                expected.world_size.to_string(),
+            );
+        }
+        if self.world_rank != expected.world_rank {
+            return mismatch(
+                "MPI world rank",
+                self.world_rank.to_string(),
+                expected.world_rank.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the payload of a setup file from its bytes, checking its header against `expected`.
+pub fn load_pcs_setup_bytes<T: ExpSerde>(
+    bytes: &[u8],
+    expected: &PCSSetupHeader,
+) -> Result<T, ExpErrors> {
+    let mut cursor = Cursor::new(bytes);
+
+    PCSSetupHeader::deserialize_from(&mut cursor)
+        .map_err(|_| ExpErrors::PCSSetupError("no valid header".to_string()))?
+        .check(expected)?;
+
+    Ok(T::deserialize_from(&mut cursor)?)
+}
@@ -38,6 +38,10 @@ pub trait ExpanderPCS<F: FieldEngine, PolyField: Field> {
     type Commitment: Clone + Debug + Default + ExpSerde + Send + Sync;
     type Opening: Clone + Debug + Default + ExpSerde + Send + Sync;
 
+    /// Part of the opening verification that is deferred by `verify_deferred`, so that it can be
+    /// checked for many openings at once by `batch_check`, e.g., the final pairing check of KZG.
+    type DeferredCheck: Clone + Debug + Default + Send + Sync = ();
+
     /// This function returns the SRS for the PCS.
     ///
     /// If `path` is provided, it will attempt to load the SRS from the specified path.
@@ -155,6 +159,34 @@ pub trait ExpanderPCS<F: FieldEngine, PolyField: Field> {
         transcript: &mut impl Transcript,
         opening: &Self::Opening,
     ) -> bool;
+
+    /// Same as `verify`, except that the check returned is left to `batch_check`.
+    /// Returns `None` if the opening is already rejected before that.
+    ///
+    /// The default implementation runs `verify` in full, and defers nothing.
+    fn verify_deferred(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<F>,
+        v: F::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> Option<Self::DeferredCheck> {
+        Self::verify(params, verifying_key, commitment, x, v, transcript, opening)
+            .then(Self::DeferredCheck::default)
+    }
+
+    /// Run the checks deferred by `verify_deferred` for a batch of openings at once.
+    /// `rng` is the verifier's randomness, used to combine the checks.
+    fn batch_check(
+        _params: &Self::Params,
+        _verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        _checks: &[Self::DeferredCheck],
+        _rng: impl RngCore,
+    ) -> bool {
+        true
+    }
 }
 
 impl StructuredReferenceString for () {
@@ -166,7 +198,7 @@ impl StructuredReferenceString for () {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, ExpSerde)]
 pub enum PolynomialCommitmentType {
     #[default]
     Raw,
@@ -174,6 +206,7 @@ pub enum PolynomialCommitmentType {
     Hyrax,
     Orion,
     FRI,
+    HidingHyrax,
 }
 
 impl FromStr for PolynomialCommitmentType {
@@ -186,6 +219,7 @@ impl FromStr for PolynomialCommitmentType {
             "Hyrax" => Ok(PolynomialCommitmentType::Hyrax),
             "Orion" => Ok(PolynomialCommitmentType::Orion),
             "FRI" => Ok(PolynomialCommitmentType::FRI),
+            "HidingHyrax" => Ok(PolynomialCommitmentType::HidingHyrax),
             _ => Err(ExpErrors::PCSTypeError(s.to_string())),
         }
     }
@@ -0,0 +1,124 @@
+use arith::Field;
+use serdes::ExpSerde;
+
+use crate::{
+    ExpErrors, ExpanderPCS, FiatShamirHashType, FieldEngine, FieldType, GKREngine, GKRScheme,
+    PolynomialCommitmentType, Proof, StatementBinding,
+};
+
+/// b"EXPPROOF" in little endian
+pub const PROOF_ENVELOPE_MAGIC: u64 = 0x464f_4f52_5050_5845;
+
+/// Bumped whenever the proof layout changes in a way old verifiers cannot read
+pub const PROOF_ENVELOPE_VERSION: u32 = 2;
+
+/// Last version whose transcript is bound to the PCS commitment only, see `StatementBinding`
+pub const PROOF_ENVELOPE_LEGACY_VERSION: u32 = 1;
+
+/// Header of a serialized proof, recording everything a verifier needs to pick the right config.
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct ProofHeader {
+    pub magic: u64,
+    pub version: u32,
+    pub field_type: FieldType,
+    pub fiat_shamir_hash_type: FiatShamirHashType,
+    pub pcs_type: PolynomialCommitmentType,
+    pub gkr_scheme: GKRScheme,
+    /// Number of MPI processes the proof was generated with
+    pub proving_time_mpi_size: usize,
+    /// Hash of the circuit the proof is for
+    pub circuit_hash: [u8; 32],
+}
+
+impl ProofHeader {
+    pub fn new<Cfg: GKREngine>(proving_time_mpi_size: usize, circuit_hash: [u8; 32]) -> Self {
+        Self {
+            magic: PROOF_ENVELOPE_MAGIC,
+            version: PROOF_ENVELOPE_VERSION,
+            field_type: Cfg::FieldConfig::FIELD_TYPE,
+            fiat_shamir_hash_type: Cfg::FIAT_SHAMIR_HASH,
+            pcs_type: <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig, Cfg::PCSField>>::PCS_TYPE,
+            gkr_scheme: Cfg::SCHEME,
+            proving_time_mpi_size,
+            circuit_hash,
+        }
+    }
+
+    /// Check that the proof was generated with config `Cfg` for the circuit hashed to
+    /// `circuit_hash`, by `proving_time_mpi_size` processes.
+    pub fn check<Cfg: GKREngine>(
+        &self,
+        proving_time_mpi_size: usize,
+        circuit_hash: &[u8; 32],
+    ) -> Result<(), ExpErrors> {
+        let expected = Self::new::<Cfg>(proving_time_mpi_size, *circuit_hash);
+
+        if self.magic != expected.magic {
+            return Err(ExpErrors::ProofMismatchError(
+                "not an expander proof".to_string(),
+            ));
+        }
+        if self.version != expected.version && self.version != PROOF_ENVELOPE_LEGACY_VERSION {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "proof format version is {}, expected {}",
+                self.version, expected.version
+            )));
+        }
+        if self.field_type != expected.field_type {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "field is {:?}, expected {:?}",
+                self.field_type, expected.field_type
+            )));
+        }
+        if self.fiat_shamir_hash_type != expected.fiat_shamir_hash_type {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "Fiat-Shamir hash is {:?}, expected {:?}",
+                self.fiat_shamir_hash_type, expected.fiat_shamir_hash_type
+            )));
+        }
+        if self.pcs_type != expected.pcs_type {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "PCS is {:?}, expected {:?}",
+                self.pcs_type, expected.pcs_type
+            )));
+        }
+        if self.gkr_scheme != expected.gkr_scheme {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "GKR scheme is {:?}, expected {:?}",
+                self.gkr_scheme, expected.gkr_scheme
+            )));
+        }
+        if self.proving_time_mpi_size != expected.proving_time_mpi_size {
+            return Err(ExpErrors::ProofMismatchError(format!(
+                "proving time MPI size is {}, expected {}",
+                self.proving_time_mpi_size, expected.proving_time_mpi_size
+            )));
+        }
+        if self.circuit_hash != expected.circuit_hash {
+            return Err(ExpErrors::ProofMismatchError(
+                "proof is for a different circuit".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// How the transcript of the proof is bound to the statement, depending on its version.
+    pub fn statement_binding(&self) -> StatementBinding {
+        match self.version {
+            PROOF_ENVELOPE_LEGACY_VERSION => StatementBinding::Legacy,
+            _ => StatementBinding::Full,
+        }
+    }
+}
+
+/// Versioned, self-describing container of a proof and the claimed output of the circuit.
+///
+/// The header is serialized first, so that it can be read with `ProofHeader::deserialize_from`
+/// before the config, and hence the type of the claimed value, is known.
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct ProofEnvelope<F: Field> {
+    pub header: ProofHeader,
+    pub proof: Proof,
+    pub claimed_v: F,
+}
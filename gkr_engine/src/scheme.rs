@@ -1,6 +1,29 @@
-#[derive(Debug, Clone, PartialEq, Default)]
+use serdes::ExpSerde;
+
+#[derive(Debug, Clone, PartialEq, Default, ExpSerde)]
 pub enum GKRScheme {
     #[default]
     Vanilla,
     GkrSquare,
+    /// Vanilla GKR in zero-knowledge: the sumcheck messages and the layer claims are sent as
+    /// Pedersen commitments, and the input claims are opened against a hiding PCS.
+    /// Proven and verified by `Prover::prove_zk` and `Verifier::verify_zk`.
+    ZkVanilla,
+    /// GKR over circuits whose gates may read from any earlier layer, see the
+    /// `crosslayer_prototype` crate. Proven and verified by its `CrossLayerProver` and
+    /// `CrossLayerVerifier`.
+    CrossLayer,
+}
+
+/// What the Fiat-Shamir transcript is bound to, besides the PCS commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatementBinding {
+    /// Only the PCS commitment, as in the proofs of envelope version 1.
+    /// The challenges are then independent of the public inputs and the claimed output, so this
+    /// is only meant to verify old proofs.
+    Legacy,
+    /// The public inputs are absorbed after the commitment, and the claimed output after the
+    /// output challenge is sampled.
+    #[default]
+    Full,
 }
@@ -0,0 +1,325 @@
+//! Runtime dispatch of the prover hot loops to the AVX-512 implementations of the SIMD fields.
+//!
+//! A hot loop is written once as a kernel, generic over the fields it runs on. On x86_64 builds
+//! where the SIMD fields are the AVX2 implementations, see [`SimdBackend::AVX512_DISPATCH`], the
+//! kernel runs on their AVX-512 twins if [`SimdBackend::runtime`] is AVX-512, and on the given
+//! fields otherwise. A twin shares the memory layout of the AVX2 type, so the kernel reinterprets
+//! its inputs and outputs in place with [`twin_slice`], [`twin_slice_mut`] and [`twin_value`].
+//!
+//! The kernel is entered through a function with `#[target_feature(enable = "avx512f")]`, into
+//! which the `#[inline(always)]` methods of the fields are inlined and compiled for AVX-512. The
+//! twins themselves are compiled without the attribute, as trait methods cannot have it: their
+//! methods that are not inlined into the kernel, e.g. those marked `#[inline]` only, call the
+//! AVX-512 intrinsics out of line, which gives the same results at a lower speed.
+//!
+//! A kernel over fields without an AVX-512 twin, e.g. the challenge fields of the SIMD gates,
+//! runs on the given fields, entered the same way if [`SimdBackend::runtime`] is AVX-512.
+
+use std::any::TypeId;
+use std::ops::{Add, Mul};
+
+use arith::{Field, SimdBackend};
+use babybear::{AVX512BabyBearExt3x16, AVX512BabyBearx16, BabyBearExt3x16, BabyBearx16};
+use gf2::GF2x8;
+use gf2_128::{AVX512GF2_128x8, GF2_128x8};
+use goldilocks::{AVX512GoldilocksExt2x8, AVX512Goldilocksx8, GoldilocksExt2x8, Goldilocksx8};
+use mersenne31::{AVX512M31Ext3x16, AVX512M31x16, M31Ext3x16, M31x16};
+
+/// A hot loop over a SIMD field
+pub trait SimdKernel {
+    type Output;
+
+    /// Runs the loop over `G`, being the field the kernel is dispatched on or its AVX-512 twin
+    fn run<G: Field>(self) -> Self::Output;
+}
+
+/// A hot loop over a SIMD field `V` and its extension `E`, e.g. the SIMD circuit field and the
+/// main field of a field engine
+pub trait SimdPairKernel {
+    type Output;
+
+    /// Runs the loop over `V` and `E`, being the fields the kernel is dispatched on or their
+    /// AVX-512 twins
+    fn run<V, E>(self) -> Self::Output
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>;
+}
+
+/// Runs the kernel on `F`, or on its AVX-512 twin if the CPU supports AVX-512. Without a twin,
+/// the kernel runs on `F`, entered through an AVX-512 function.
+#[inline]
+pub fn run_simd_kernel<F: Field, K: SimdKernel>(kernel: K) -> K::Output {
+    #[cfg(target_arch = "x86_64")]
+    if SimdBackend::AVX512_DISPATCH && SimdBackend::runtime() == SimdBackend::Avx512 {
+        // SAFETY: the CPU supports AVX-512
+        return unsafe {
+            if is::<F, M31x16>() {
+                avx512::run::<AVX512M31x16, K>(kernel)
+            } else if is::<F, M31Ext3x16>() {
+                avx512::run::<AVX512M31Ext3x16, K>(kernel)
+            } else if is::<F, BabyBearx16>() {
+                avx512::run::<AVX512BabyBearx16, K>(kernel)
+            } else if is::<F, BabyBearExt3x16>() {
+                avx512::run::<AVX512BabyBearExt3x16, K>(kernel)
+            } else if is::<F, Goldilocksx8>() {
+                avx512::run::<AVX512Goldilocksx8, K>(kernel)
+            } else if is::<F, GoldilocksExt2x8>() {
+                avx512::run::<AVX512GoldilocksExt2x8, K>(kernel)
+            } else if is::<F, GF2_128x8>() && avx512::has_vpclmulqdq() {
+                avx512::run_clmul::<AVX512GF2_128x8, K>(kernel)
+            } else {
+                avx512::run::<F, K>(kernel)
+            }
+        };
+    }
+
+    kernel.run::<F>()
+}
+
+/// Runs the kernel on `V` and `E`, or on their AVX-512 twins if the CPU supports AVX-512.
+/// Without twins, the kernel runs on `V` and `E`, entered through an AVX-512 function.
+#[inline]
+pub fn run_simd_pair_kernel<V, E, K>(kernel: K) -> K::Output
+where
+    V: Field,
+    E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    K: SimdPairKernel,
+{
+    #[cfg(target_arch = "x86_64")]
+    if SimdBackend::AVX512_DISPATCH && SimdBackend::runtime() == SimdBackend::Avx512 {
+        // SAFETY: the CPU supports AVX-512
+        return unsafe {
+            if is::<V, M31x16>() && is::<E, M31Ext3x16>() {
+                avx512::run_pair::<AVX512M31x16, AVX512M31Ext3x16, K>(kernel)
+            } else if is::<V, BabyBearx16>() && is::<E, BabyBearExt3x16>() {
+                avx512::run_pair::<AVX512BabyBearx16, AVX512BabyBearExt3x16, K>(kernel)
+            } else if is::<V, Goldilocksx8>() && is::<E, GoldilocksExt2x8>() {
+                avx512::run_pair::<AVX512Goldilocksx8, AVX512GoldilocksExt2x8, K>(kernel)
+            } else if is::<V, GF2x8>() && is::<E, GF2_128x8>() && avx512::has_vpclmulqdq() {
+                avx512::run_pair_clmul::<GF2x8, AVX512GF2_128x8, K>(kernel)
+            } else {
+                avx512::run_pair::<V, E, K>(kernel)
+            }
+        };
+    }
+
+    kernel.run::<V, E>()
+}
+
+/// Reinterprets a slice of `F` as a slice of `G`, `G` being `F` or its AVX-512 twin
+#[inline(always)]
+pub fn twin_slice<F: Field, G: Field>(s: &[F]) -> &[G] {
+    assert_twins::<F, G>();
+    // SAFETY: F and G are the same field with the same memory layout
+    unsafe { std::slice::from_raw_parts(s.as_ptr() as *const G, s.len()) }
+}
+
+/// Reinterprets a mutable slice of `F` as a mutable slice of `G`, `G` being `F` or its AVX-512
+/// twin
+#[inline(always)]
+pub fn twin_slice_mut<F: Field, G: Field>(s: &mut [F]) -> &mut [G] {
+    assert_twins::<F, G>();
+    // SAFETY: F and G are the same field with the same memory layout
+    unsafe { std::slice::from_raw_parts_mut(s.as_mut_ptr() as *mut G, s.len()) }
+}
+
+/// Reinterprets an element of `F` as an element of `G`, `G` being `F` or its AVX-512 twin
+#[inline(always)]
+pub fn twin_value<F: Field, G: Field>(x: F) -> G {
+    assert_twins::<F, G>();
+    // SAFETY: F and G are the same field with the same memory layout
+    unsafe { std::mem::transmute_copy(&x) }
+}
+
+#[inline(always)]
+fn is<A: Field, B: Field>() -> bool {
+    TypeId::of::<A>() == TypeId::of::<B>()
+}
+
+/// Whether `G` is `F` or its AVX-512 twin
+#[inline(always)]
+fn is_twin<F: Field, G: Field>() -> bool {
+    is::<F, G>()
+        || (is::<F, M31x16>() && is::<G, AVX512M31x16>())
+        || (is::<F, M31Ext3x16>() && is::<G, AVX512M31Ext3x16>())
+        || (is::<F, BabyBearx16>() && is::<G, AVX512BabyBearx16>())
+        || (is::<F, BabyBearExt3x16>() && is::<G, AVX512BabyBearExt3x16>())
+        || (is::<F, Goldilocksx8>() && is::<G, AVX512Goldilocksx8>())
+        || (is::<F, GoldilocksExt2x8>() && is::<G, AVX512GoldilocksExt2x8>())
+        || (is::<F, GF2_128x8>() && is::<G, AVX512GF2_128x8>())
+}
+
+#[inline(always)]
+fn assert_twins<F: Field, G: Field>() {
+    assert!(
+        is_twin::<F, G>(),
+        "{} is not a twin of {}",
+        G::NAME,
+        F::NAME
+    );
+    assert_eq!(std::mem::size_of::<F>(), std::mem::size_of::<G>());
+    assert_eq!(std::mem::align_of::<F>(), std::mem::align_of::<G>());
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx512 {
+    use std::ops::{Add, Mul};
+
+    use arith::Field;
+
+    use super::{SimdKernel, SimdPairKernel};
+
+    /// The AVX-512 GF2_128x8 multiplies with the 512-bit carry-less multiplication
+    #[inline(always)]
+    pub(super) fn has_vpclmulqdq() -> bool {
+        std::arch::is_x86_feature_detected!("vpclmulqdq")
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn run<G: Field, K: SimdKernel>(kernel: K) -> K::Output {
+        kernel.run::<G>()
+    }
+
+    #[target_feature(enable = "avx512f,vpclmulqdq")]
+    pub(super) unsafe fn run_clmul<G: Field, K: SimdKernel>(kernel: K) -> K::Output {
+        kernel.run::<G>()
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn run_pair<V, E, K>(kernel: K) -> K::Output
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+        K: SimdPairKernel,
+    {
+        kernel.run::<V, E>()
+    }
+
+    #[target_feature(enable = "avx512f,vpclmulqdq")]
+    pub(super) unsafe fn run_pair_clmul<V, E, K>(kernel: K) -> K::Output
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+        K: SimdPairKernel,
+    {
+        kernel.run::<V, E>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::{Add, Mul};
+
+    use arith::{Field, SimdBackend};
+    use ark_std::test_rng;
+    use babybear::{BabyBearExt3x16, BabyBearx16};
+    use gf2::GF2x8;
+    use gf2_128::GF2_128x8;
+    use goldilocks::{GoldilocksExt2x8, Goldilocksx8};
+    use mersenne31::{M31Ext3, M31Ext3x16, M31x16};
+
+    use super::*;
+
+    struct InnerProduct<'a, F> {
+        a: &'a [F],
+        b: &'a [F],
+    }
+
+    impl<F: Field> SimdKernel for InnerProduct<'_, F> {
+        type Output = F;
+
+        fn run<G: Field>(self) -> F {
+            let a = twin_slice::<F, G>(self.a);
+            let b = twin_slice::<F, G>(self.b);
+            let res = a
+                .iter()
+                .zip(b)
+                .fold(G::ZERO, |acc, (x, y)| acc + *x * y + x);
+            twin_value::<G, F>(res)
+        }
+    }
+
+    struct PairInnerProduct<'a, V, E> {
+        v: &'a [V],
+        e: &'a [E],
+    }
+
+    impl<V: Field, E: Field> SimdPairKernel for PairInnerProduct<'_, V, E> {
+        type Output = E;
+
+        fn run<V2, E2>(self) -> E
+        where
+            V2: Field,
+            E2: Field + Mul<V2, Output = E2> + Add<V2, Output = E2>,
+        {
+            let v = twin_slice::<V, V2>(self.v);
+            let e = twin_slice::<E, E2>(self.e);
+            let res = v
+                .iter()
+                .zip(e)
+                .fold(E2::ZERO, |acc, (x, y)| acc + (*y * *x + *x));
+            twin_value::<E2, E>(res)
+        }
+    }
+
+    fn test_kernel_helper<F: Field>() {
+        let mut rng = test_rng();
+        let a: Vec<F> = (0..64).map(|_| F::random_unsafe(&mut rng)).collect();
+        let b: Vec<F> = (0..64).map(|_| F::random_unsafe(&mut rng)).collect();
+
+        let dispatched = run_simd_kernel::<F, _>(InnerProduct { a: &a, b: &b });
+        let forced = InnerProduct { a: &a, b: &b }.run::<F>();
+        assert_eq!(dispatched, forced, "{}", F::NAME);
+    }
+
+    fn test_pair_kernel_helper<V, E>()
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    {
+        let mut rng = test_rng();
+        let v: Vec<V> = (0..64).map(|_| V::random_unsafe(&mut rng)).collect();
+        let e: Vec<E> = (0..64).map(|_| E::random_unsafe(&mut rng)).collect();
+
+        let dispatched = run_simd_pair_kernel::<V, E, _>(PairInnerProduct { v: &v, e: &e });
+        let forced = PairInnerProduct { v: &v, e: &e }.run::<V, E>();
+        assert_eq!(dispatched, forced, "{}", E::NAME);
+    }
+
+    #[test]
+    fn test_runtime_backend() {
+        let backend = SimdBackend::runtime();
+        if SimdBackend::AVX512_DISPATCH {
+            assert!(backend == SimdBackend::COMPILED || backend == SimdBackend::Avx512);
+        } else {
+            assert_eq!(backend, SimdBackend::COMPILED);
+        }
+        assert_eq!(SimdBackend::from_name(backend.name()), Some(backend));
+    }
+
+    #[test]
+    fn test_dispatched_kernels() {
+        test_kernel_helper::<M31x16>();
+        test_kernel_helper::<M31Ext3x16>();
+        test_kernel_helper::<BabyBearx16>();
+        test_kernel_helper::<BabyBearExt3x16>();
+        test_kernel_helper::<Goldilocksx8>();
+        test_kernel_helper::<GoldilocksExt2x8>();
+        test_kernel_helper::<GF2_128x8>();
+        // no twin, the kernel runs on the field itself
+        test_kernel_helper::<M31Ext3>();
+
+        test_pair_kernel_helper::<M31x16, M31Ext3x16>();
+        test_pair_kernel_helper::<BabyBearx16, BabyBearExt3x16>();
+        test_pair_kernel_helper::<Goldilocksx8, GoldilocksExt2x8>();
+        test_pair_kernel_helper::<GF2x8, GF2_128x8>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_twin_slice_mismatch() {
+        let a = vec![M31x16::ZERO; 4];
+        let _ = twin_slice::<M31x16, BabyBearx16>(&a);
+    }
+}
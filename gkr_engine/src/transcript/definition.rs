@@ -1,6 +1,7 @@
 use std::{fmt::Debug, str::FromStr};
 
 use arith::Field;
+use serdes::ExpSerde;
 
 use crate::ExpErrors;
 
@@ -95,7 +96,7 @@ pub trait Transcript: Clone + Debug {
     fn refresh_digest(&mut self);
 }
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, ExpSerde)]
 pub enum FiatShamirHashType {
     #[default]
     SHA256,
@@ -1,16 +1,19 @@
 // traits definitions
 
 mod traits;
-pub use traits::{FiatShamirHasher, PoseidonStateTrait};
+pub use traits::{FiatShamirHasher, Poseidon2Params, PoseidonStateTrait};
 
 // field hashers
 
 mod mimc;
-pub use mimc::MiMC5FiatShamirHasher;
+pub use mimc::{generate_mimc_constants, MiMC5FiatShamirHasher};
 
 pub mod poseidon;
 pub use poseidon::PoseidonFiatShamirHasher;
 
+pub mod poseidon2;
+pub use poseidon2::Poseidon2FiatShamirHasher;
+
 // byte hashers
 
 pub mod sha2_256;
@@ -0,0 +1,277 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, OnceLock},
+};
+
+use arith::Field;
+use ethnum::U256;
+
+use crate::{FiatShamirHasher, Poseidon2Params};
+
+/// The Poseidon2 permutation of https://eprint.iacr.org/2023/323, with the round constants of
+/// the reference implementation, generated by the Grain LFSR from the parameters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Poseidon2Permutation<Params: Poseidon2Params> {
+    /// STATE_WIDTH round constants for each full round
+    pub external_round_constants: Vec<Vec<Params::ElemT>>,
+    /// A round constant for each partial round, added to the first element
+    pub internal_round_constants: Vec<Params::ElemT>,
+    pub internal_diag_m_1: Vec<Params::ElemT>,
+}
+
+/// The Grain LFSR generating the round constants in the Poseidon and Poseidon2 reference
+/// implementations, in self-shrinking mode.
+struct GrainLFSR {
+    bits: VecDeque<bool>,
+}
+
+impl GrainLFSR {
+    const STATE_BITS: usize = 80;
+
+    const WARMUP_ROUNDS: usize = 160;
+
+    fn new(field_size: usize, width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut bits = VecDeque::with_capacity(Self::STATE_BITS);
+        let mut push_bits = |value: usize, n_bits: usize| {
+            (0..n_bits)
+                .rev()
+                .for_each(|i| bits.push_back((value >> i) & 1 == 1))
+        };
+        // prime field, sbox x^alpha
+        push_bits(1, 2);
+        push_bits(0, 4);
+        push_bits(field_size, 12);
+        push_bits(width, 12);
+        push_bits(full_rounds, 10);
+        push_bits(partial_rounds, 10);
+        push_bits((1 << 30) - 1, 30);
+
+        let mut lfsr = Self { bits };
+        (0..Self::WARMUP_ROUNDS).for_each(|_| {
+            lfsr.next_raw_bit();
+        });
+        lfsr
+    }
+
+    #[inline(always)]
+    fn next_raw_bit(&mut self) -> bool {
+        let b = &self.bits;
+        let new_bit = b[62] ^ b[51] ^ b[38] ^ b[23] ^ b[13] ^ b[0];
+        self.bits.pop_front();
+        self.bits.push_back(new_bit);
+        new_bit
+    }
+
+    /// A pair of raw bits (1, b) outputs b, a pair (0, b) is discarded
+    #[inline(always)]
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_raw_bit();
+            let bit = self.next_raw_bit();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Sample a field element from field_size bits, most significant first, by rejection
+    fn next_field_elem<F: Field>(&mut self, field_size: usize) -> F {
+        loop {
+            let value = (0..field_size).fold(U256::ZERO, |acc, _| {
+                (acc << 1) | U256::from(self.next_bit() as u64)
+            });
+            if value < F::MODULUS {
+                return F::from_u256(value);
+            }
+        }
+    }
+}
+
+impl<Params: Poseidon2Params> Poseidon2Permutation<Params> {
+    /// The permutation of `Params`, whose round constants are generated once per process and
+    /// cloned afterwards.
+    fn new() -> Self {
+        // a static is shared by all the instances of a generic function, hence the map by type
+        type Permutations = HashMap<TypeId, &'static (dyn Any + Send + Sync)>;
+        static PERMUTATIONS: OnceLock<Mutex<Permutations>> = OnceLock::new();
+
+        let mut permutations = PERMUTATIONS.get_or_init(Default::default).lock().unwrap();
+        let permutation = *permutations
+            .entry(TypeId::of::<Params>())
+            .or_insert_with(|| Box::leak(Box::new(Self::generate())));
+        permutation.downcast_ref::<Self>().unwrap().clone()
+    }
+
+    fn generate() -> Self {
+        assert_eq!(Params::STATE_WIDTH % 4, 0);
+        assert_eq!(Params::FULL_ROUNDS % 2, 0);
+
+        let field_size = 256 - Params::ElemT::MODULUS.leading_zeros() as usize;
+        let mut grain = GrainLFSR::new(
+            field_size,
+            Params::STATE_WIDTH,
+            Params::FULL_ROUNDS,
+            Params::PARTIAL_ROUNDS,
+        );
+
+        // a row of constants is drawn for every round, the partial rounds keeping the first one
+        let half_full_rounds = Params::FULL_ROUNDS / 2;
+        let mut external_round_constants = vec![];
+        let mut internal_round_constants = vec![];
+        for i in 0..Params::FULL_ROUNDS + Params::PARTIAL_ROUNDS {
+            let round_constants: Vec<Params::ElemT> = (0..Params::STATE_WIDTH)
+                .map(|_| grain.next_field_elem(field_size))
+                .collect();
+
+            if i < half_full_rounds || i >= half_full_rounds + Params::PARTIAL_ROUNDS {
+                external_round_constants.push(round_constants);
+            } else {
+                internal_round_constants.push(round_constants[0]);
+            }
+        }
+
+        let internal_diag_m_1 = Params::internal_diag_m_1();
+        assert_eq!(internal_diag_m_1.len(), Params::STATE_WIDTH);
+
+        Self {
+            external_round_constants,
+            internal_round_constants,
+            internal_diag_m_1,
+        }
+    }
+
+    /// Multiply a chunk of 4 elements by the circulant-like matrix
+    /// [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]
+    #[inline(always)]
+    fn apply_m4(x: &mut [Params::ElemT]) {
+        let t0 = x[0] + x[1];
+        let t1 = x[2] + x[3];
+        let t2 = x[1].double() + t1;
+        let t3 = x[3].double() + t0;
+        let t4 = t1.double().double() + t3;
+        let t5 = t0.double().double() + t2;
+        let t6 = t3 + t5;
+        let t7 = t2 + t4;
+
+        x[0] = t6;
+        x[1] = t5;
+        x[2] = t7;
+        x[3] = t4;
+    }
+
+    /// The external matrix is circ(2 M4, M4, ..., M4), applied as M4 on each chunk of 4, then
+    /// adding to each element the sum of the elements at the same position in the chunks
+    #[inline(always)]
+    fn apply_external_matrix(state: &mut [Params::ElemT]) {
+        state.chunks_exact_mut(4).for_each(Self::apply_m4);
+
+        let mut sums = [Params::ElemT::ZERO; 4];
+        state.chunks_exact(4).for_each(|chunk| {
+            sums.iter_mut().zip(chunk).for_each(|(s, c)| *s += c);
+        });
+        state
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, s)| *s += sums[i % 4]);
+    }
+
+    #[inline(always)]
+    fn apply_internal_matrix(&self, state: &mut [Params::ElemT]) {
+        let sum: Params::ElemT = state.iter().sum();
+        state
+            .iter_mut()
+            .zip(&self.internal_diag_m_1)
+            .for_each(|(s, d)| *s = *s * d + sum);
+    }
+
+    #[inline(always)]
+    fn external_round(&self, state: &mut [Params::ElemT], round_constants: &[Params::ElemT]) {
+        state
+            .iter_mut()
+            .zip(round_constants)
+            .for_each(|(s, rc)| *s = (*s + rc).exp(Params::SBOX_POW as u128));
+        Self::apply_external_matrix(state);
+    }
+
+    pub fn permute(&self, state: &mut [Params::ElemT]) {
+        assert_eq!(state.len(), Params::STATE_WIDTH);
+        let half_full_rounds = Params::FULL_ROUNDS / 2;
+
+        Self::apply_external_matrix(state);
+
+        self.external_round_constants[..half_full_rounds]
+            .iter()
+            .for_each(|rc| self.external_round(state, rc));
+
+        self.internal_round_constants.iter().for_each(|rc| {
+            state[0] = (state[0] + rc).exp(Params::SBOX_POW as u128);
+            self.apply_internal_matrix(state);
+        });
+
+        self.external_round_constants[half_full_rounds..]
+            .iter()
+            .for_each(|rc| self.external_round(state, rc));
+    }
+}
+
+impl<Params: Poseidon2Params> Poseidon2Permutation<Params> {
+    fn hash_u8_to_state(&self, input: &[u8]) -> Vec<Params::ElemT> {
+        let u8_chunk_size = Params::RATE * Params::ElemT::SIZE;
+        let mut res = vec![Params::ElemT::ZERO; Params::STATE_WIDTH];
+        let chunks = input.chunks_exact(u8_chunk_size);
+        let mut remainder = chunks.remainder().to_vec();
+
+        for chunk in chunks {
+            for (elem, elts) in chunk
+                .chunks(Params::ElemT::SIZE)
+                .zip(res[Params::CAPACITY..].iter_mut())
+            {
+                *elts += Params::ElemT::from_uniform_bytes(elem);
+            }
+            self.permute(&mut res);
+        }
+
+        if !remainder.is_empty() {
+            remainder.resize(u8_chunk_size, 0);
+
+            for (elem, elts) in remainder
+                .chunks(Params::ElemT::SIZE)
+                .zip(res[Params::CAPACITY..].iter_mut())
+            {
+                *elts += Params::ElemT::from_uniform_bytes(elem);
+            }
+            self.permute(&mut res);
+        }
+
+        res
+    }
+}
+
+impl<Params: Poseidon2Params> FiatShamirHasher for Poseidon2Permutation<Params> {
+    const NAME: &'static str = "Poseidon2 Field Hasher";
+
+    const DIGEST_SIZE: usize = Params::STATE_WIDTH * Params::ElemT::SIZE;
+
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        assert!(output.len() == Self::DIGEST_SIZE);
+        let res = self.hash_u8_to_state(input);
+        res.iter()
+            .zip(output.chunks_mut(Params::ElemT::SIZE))
+            .for_each(|(e, o)| e.to_bytes(o));
+    }
+
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        assert!(buffer.len() == Self::DIGEST_SIZE);
+        let res = self.hash_u8_to_state(buffer);
+        res.iter()
+            .zip(buffer.chunks_mut(Params::ElemT::SIZE))
+            .for_each(|(e, o)| e.to_bytes(o));
+    }
+}
+
+pub type Poseidon2FiatShamirHasher<Params> = Poseidon2Permutation<Params>;
@@ -98,3 +98,34 @@ pub trait PoseidonStateTrait:
 
     fn to_u8_slices(&self, output: &mut [u8]);
 }
+
+pub trait Poseidon2Params: Sized + Default + Clone + Debug + 'static {
+    /// ElemT is the element (prime field) type of the poseidon2 state.
+    type ElemT: Field;
+
+    /// SBOX_POW is a pow \alpha for poseidon2 sbox, that \alpha >= 3 and gcd(\alpha, p - 1) = 1,
+    /// where p is the modulus of the prime field ElemT.
+    const SBOX_POW: usize;
+
+    /// FULL_ROUNDS in a poseidon2 permutation, split evenly before and after the partial rounds.
+    const FULL_ROUNDS: usize;
+
+    /// PARTIAL_ROUNDS in a poseidon2 permutation.
+    const PARTIAL_ROUNDS: usize;
+
+    /// STATE_WIDTH stands for the number of field elements in a poseidon2 state,
+    /// a multiple of 4 for the external matrix.
+    const STATE_WIDTH: usize;
+
+    /// CAPACITY is the number of field elements of the state not absorbing the input,
+    /// chosen for (roughly) 128 bits of collision resilience as in PoseidonStateTrait.
+    const CAPACITY: usize = 128 / <Self::ElemT as Field>::FIELD_SIZE * 2;
+
+    /// RATE is the number of input elements in a round of sponge absorbing.
+    /// The invariant here is RATE + CAPACITY = STATE_WIDTH
+    const RATE: usize = Self::STATE_WIDTH - Self::CAPACITY;
+
+    /// internal_diag_m_1 is the diagonal of the internal matrix minus the identity, i.e.,
+    /// the internal matrix is the all-one matrix plus diag(internal_diag_m_1).
+    fn internal_diag_m_1() -> Vec<Self::ElemT>;
+}
@@ -0,0 +1,14 @@
+mod utils;
+pub use utils::{
+    basefold_query_complexity, BasefoldCommitment, BasefoldPCSError, BasefoldProof, BasefoldResult,
+    BasefoldScratchPad, BASEFOLD_LOG_INV_RATE,
+};
+
+mod simd_field_impl;
+pub use simd_field_impl::{basefold_commit_simd_field, basefold_open_simd_field};
+
+mod verify;
+pub use verify::basefold_verify;
+
+mod expander_api;
+pub use expander_api::BasefoldPCSForGKR;
@@ -0,0 +1,148 @@
+use arith::{FFTField, SimdField};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    StructuredReferenceString, Transcript,
+};
+use polynomials::MultilinearExtension;
+
+use crate::{
+    basefold::{
+        simd_field_impl::{basefold_commit_simd_field, basefold_open_simd_field},
+        verify::basefold_verify,
+        BasefoldCommitment, BasefoldProof, BasefoldScratchPad,
+    },
+    utils::{
+        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
+        lift_poly_to_n_vars,
+    },
+};
+
+/// Basefold over Reed-Solomon codes, a transparent hash based PCS for the SIMD circuit field.
+pub struct BasefoldPCSForGKR<C: FieldEngine> {
+    _phantom: std::marker::PhantomData<C>,
+}
+
+impl<C> ExpanderPCS<C, C::SimdCircuitField> for BasefoldPCSForGKR<C>
+where
+    C: FieldEngine,
+    C::CircuitField: FFTField,
+{
+    const NAME: &'static str = "BasefoldPCSForExpanderGKR";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::FRI;
+
+    type Params = usize;
+    type ScratchPad = BasefoldScratchPad<C::CircuitField>;
+
+    type Commitment = BasefoldCommitment;
+    type Opening = BasefoldProof<C::ChallengeField>;
+    type SRS = ();
+
+    /// NOTE(HS): this is the number of variables for local polynomial w.r.t. SIMD field elements,
+    /// s.t. the local polynomial has at least one variable over the base field to fold.
+    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+        match C::SimdCircuitField::PACK_SIZE {
+            1 => n_input_vars.max(1),
+            _ => n_input_vars,
+        }
+    }
+
+    fn gen_srs(
+        _params: &Self::Params,
+        _mpi_engine: &impl MPIEngine,
+        _rng: impl rand::RngCore,
+    ) -> Self::SRS {
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
+        Self::ScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> Option<Self::Commitment> {
+        if poly.num_vars() < *params {
+            let poly = lift_poly_to_n_vars(poly, *params);
+            return <Self as ExpanderPCS<C, C::SimdCircuitField>>::commit(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                scratch_pad,
+            );
+        }
+
+        basefold_commit_simd_field(mpi_engine, poly, scratch_pad).ok()
+    }
+
+    fn open(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> Option<Self::Opening> {
+        if poly.num_vars() < *params {
+            let (poly, eval_point) =
+                lift_poly_and_expander_challenge_to_n_vars(poly, eval_point, *params);
+            return <Self as ExpanderPCS<C, C::SimdCircuitField>>::open(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                &eval_point,
+                transcript,
+                scratch_pad,
+            );
+        }
+
+        basefold_open_simd_field(
+            mpi_engine,
+            poly,
+            &eval_point.local_xs(),
+            &eval_point.r_mpi,
+            transcript,
+            scratch_pad,
+        )
+        .ok()
+        .flatten()
+    }
+
+    fn verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        eval: C::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        if eval_point.rz.len() < *params {
+            let eval_point = lift_expander_challenge_to_n_vars(eval_point, *params);
+            return <Self as ExpanderPCS<C, C::SimdCircuitField>>::verify(
+                params,
+                verifying_key,
+                commitment,
+                &eval_point,
+                eval,
+                transcript,
+                opening,
+            );
+        }
+
+        basefold_verify(
+            commitment,
+            &eval_point.local_xs(),
+            &eval_point.r_mpi,
+            eval,
+            transcript,
+            opening,
+        )
+    }
+}
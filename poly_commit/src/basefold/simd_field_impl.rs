@@ -0,0 +1,173 @@
+use std::iter;
+
+use arith::{ExtensionField, FFTField, Field, SimdField};
+use gkr_engine::{MPIEngine, Transcript};
+use polynomials::{EqPolynomial, MultilinearExtension};
+use tree::{Path, Tree};
+
+use crate::basefold::{
+    utils::{
+        basefold_commit_codeword, basefold_encode, basefold_fold_codeword,
+        basefold_query_complexity, merkle_cap_root, mpi_all_gather, mpi_all_sum,
+    },
+    BasefoldCommitment, BasefoldProof, BasefoldResult, BasefoldScratchPad,
+};
+
+/// Commit to the local polynomial of each MPI process, the commitment being the root of the tree
+/// over the codeword commitments of all the processes.
+#[inline(always)]
+pub fn basefold_commit_simd_field<F, SimdF>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<SimdF>,
+    scratch_pad: &mut BasefoldScratchPad<F>,
+) -> BasefoldResult<BasefoldCommitment>
+where
+    F: FFTField,
+    SimdF: SimdField<Scalar = F>,
+{
+    let msg: Vec<F> = poly
+        .hypercube_basis_ref()
+        .iter()
+        .flat_map(|v| v.unpack())
+        .collect();
+
+    scratch_pad.codeword = basefold_encode(&msg);
+    scratch_pad.codeword_commitment = basefold_commit_codeword(&scratch_pad.codeword)?;
+    scratch_pad.merkle_cap = mpi_all_gather(mpi_engine, scratch_pad.codeword_commitment.root())?;
+
+    Ok(merkle_cap_root(&scratch_pad.merkle_cap))
+}
+
+/// Open the global polynomial at `point` over the local variables and `mpi_point` over the MPI
+/// variables.  All MPI processes run the sumcheck over the local variables together, each folding
+/// its own codeword, and only the root process returns the proof.
+#[inline(always)]
+pub fn basefold_open_simd_field<F, SimdF, EvalF>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<SimdF>,
+    point: &[EvalF],
+    mpi_point: &[EvalF],
+    transcript: &mut impl Transcript,
+    scratch_pad: &BasefoldScratchPad<F>,
+) -> BasefoldResult<Option<BasefoldProof<EvalF>>>
+where
+    F: FFTField,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let num_vars = point.len();
+    assert_eq!(
+        num_vars,
+        poly.num_vars() + SimdF::PACK_SIZE.ilog2() as usize
+    );
+
+    let mut msg: Vec<EvalF> = poly
+        .hypercube_basis_ref()
+        .iter()
+        .flat_map(|v| v.unpack())
+        .map(EvalF::from)
+        .collect();
+
+    // NOTE: the eq polynomial is weighted by the MPI part of the point, s.t., the sum of the local
+    // sumcheck messages over all MPI processes is the one of the global polynomial
+    let mut eq_evals = {
+        let mut evals = EqPolynomial::build_eq_x_r(point);
+        let mpi_weight = EqPolynomial::ith_eq_vec_elem(mpi_point, mpi_engine.world_rank());
+        evals.iter_mut().for_each(|e| *e *= mpi_weight);
+        evals
+    };
+
+    // NOTE: sumcheck over the local variables, folding the codeword along with the polynomial
+    let mut sumcheck_messages = Vec::with_capacity(num_vars);
+    let mut folded_merkle_caps = Vec::with_capacity(num_vars.saturating_sub(1));
+    let mut folded_commitments: Vec<Tree> = Vec::with_capacity(num_vars.saturating_sub(1));
+    let mut folded_codeword: Vec<EvalF> = Vec::new();
+
+    for i in 0..num_vars {
+        let message = mpi_all_sum(mpi_engine, &sumcheck_round_evals(&msg, &eq_evals));
+        message
+            .iter()
+            .for_each(|m| transcript.append_field_element(m));
+        let r = transcript.generate_field_element::<EvalF>();
+        sumcheck_messages.push(message);
+
+        fold_evals_in_place(&mut msg, r);
+        fold_evals_in_place(&mut eq_evals, r);
+        folded_codeword = match i {
+            0 => basefold_fold_codeword(&scratch_pad.codeword, r),
+            _ => basefold_fold_codeword(&folded_codeword, r),
+        };
+
+        // NOTE: the last folded codeword is a constant one, sent in clear as the final evaluation
+        if i + 1 < num_vars {
+            let commitment = basefold_commit_codeword(&folded_codeword)?;
+            let merkle_cap = mpi_all_gather(mpi_engine, commitment.root())?;
+            merkle_cap
+                .iter()
+                .for_each(|node| transcript.append_u8_slice(node.as_bytes()));
+
+            folded_merkle_caps.push(merkle_cap);
+            folded_commitments.push(commitment);
+        }
+    }
+
+    let final_evals = mpi_all_gather(mpi_engine, msg[0])?;
+    final_evals
+        .iter()
+        .for_each(|e| transcript.append_field_element(e));
+
+    // NOTE: MT opening for point queries, on each of the codewords
+    let num_leaves = scratch_pad.codeword.len() / 2;
+    let query_indices = transcript.generate_usize_vector(basefold_query_complexity());
+    let local_paths: Vec<Path> = query_indices
+        .iter()
+        .flat_map(|&q| {
+            iter::once(&scratch_pad.codeword_commitment)
+                .chain(&folded_commitments)
+                .enumerate()
+                .map(move |(i, commitment)| commitment.index_query(q % (num_leaves >> i)))
+        })
+        .collect();
+
+    let query_openings = if mpi_engine.is_single_process() {
+        vec![local_paths]
+    } else {
+        let mut global_paths: Vec<Vec<Path>> = Vec::new();
+        mpi_engine.gather_varlen_vec(&local_paths, &mut global_paths);
+        global_paths
+    };
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    Ok(Some(BasefoldProof {
+        merkle_cap: scratch_pad.merkle_cap.clone(),
+        sumcheck_messages,
+        folded_merkle_caps,
+        final_evals,
+        query_openings,
+    }))
+}
+
+/// Evaluations at 0, 1 and 2 of the sumcheck round polynomial, over the lowest variable.
+#[inline(always)]
+fn sumcheck_round_evals<F: Field>(evals: &[F], eq_evals: &[F]) -> Vec<F> {
+    let mut round_evals = vec![F::ZERO; 3];
+    evals.chunks(2).zip(eq_evals.chunks(2)).for_each(|(v, e)| {
+        round_evals[0] += v[0] * e[0];
+        round_evals[1] += v[1] * e[1];
+        round_evals[2] += (v[1].double() - v[0]) * (e[1].double() - e[0]);
+    });
+    round_evals
+}
+
+/// Fix the lowest variable of a multilinear polynomial to `r`.
+#[inline(always)]
+fn fold_evals_in_place<F: Field>(evals: &mut Vec<F>, r: F) {
+    let half = evals.len() / 2;
+    for i in 0..half {
+        evals[i] = evals[2 * i] + r * (evals[2 * i + 1] - evals[2 * i]);
+    }
+    evals.truncate(half);
+}
@@ -0,0 +1,209 @@
+use arith::{ExtensionField, FFTField, Field};
+use gkr_engine::MPIEngine;
+use serdes::{ExpSerde, SerdeError};
+use thiserror::Error;
+use tree::{Leaf, Node, Path, Tree, LEAF_BYTES};
+
+use crate::PCS_SOUNDNESS_BITS;
+
+/*
+ * PCS ERROR AND RESULT SETUP
+ */
+
+#[derive(Debug, Error)]
+pub enum BasefoldPCSError {
+    #[error("field serde error")]
+    SerializationError(#[from] SerdeError),
+}
+
+pub type BasefoldResult<T> = std::result::Result<T, BasefoldPCSError>;
+
+/*
+ * CODE PARAMETERS
+ */
+
+/// The Reed-Solomon codewords are `2^BASEFOLD_LOG_INV_RATE` times longer than the messages.
+pub const BASEFOLD_LOG_INV_RATE: usize = 2;
+
+/// Number of codeword positions queried in the proximity test.
+///
+/// Each query is worth `-log2(1 - delta)` bits of soundness for a proximity parameter
+/// `delta` up to the Johnson bound `1 - sqrt(rate)`, i.e., half of `BASEFOLD_LOG_INV_RATE` bits.
+pub const fn basefold_query_complexity() -> usize {
+    (2 * PCS_SOUNDNESS_BITS).div_ceil(BASEFOLD_LOG_INV_RATE)
+}
+
+/*
+ * RELEVANT TYPES SETUP
+ */
+
+pub type BasefoldCommitment = Node;
+
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct BasefoldScratchPad<F: Field> {
+    /// Reed-Solomon codeword of the local polynomial over the base field
+    pub codeword: Vec<F>,
+    pub codeword_commitment: Tree,
+    /// Roots of the codeword commitments of all the MPI processes
+    pub merkle_cap: Vec<Node>,
+}
+
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct BasefoldProof<EvalF: Field> {
+    /// Roots of the codeword commitments of all the MPI processes
+    pub merkle_cap: Vec<Node>,
+    /// Evaluations at 0, 1 and 2 of the sumcheck round polynomials
+    pub sumcheck_messages: Vec<Vec<EvalF>>,
+    /// Roots of the folded codeword commitments, for each round but the last one,
+    /// for each MPI process
+    pub folded_merkle_caps: Vec<Vec<Node>>,
+    /// The local polynomials of all the MPI processes evaluated at the sumcheck challenges
+    pub final_evals: Vec<EvalF>,
+    /// For each MPI process, the paths to the queried leaves of each codeword, query by query
+    pub query_openings: Vec<Vec<Path>>,
+}
+
+/*
+ * CODEWORDS
+ */
+
+/// Encode the evaluations of a multilinear polynomial, as the coefficients of a univariate
+/// polynomial evaluated over the multiplicative subgroup of order `2^BASEFOLD_LOG_INV_RATE` times
+/// the message length.
+#[inline(always)]
+pub(crate) fn basefold_encode<F: FFTField>(msg: &[F]) -> Vec<F> {
+    let mut codeword = msg.to_vec();
+    codeword.resize(msg.len() << BASEFOLD_LOG_INV_RATE, F::ZERO);
+    F::fft_in_place(&mut codeword);
+    codeword
+}
+
+/// Fold the codeword values `a = g(x)` and `b = g(-x)` into the value at `x^2` of the codeword of
+/// the message folded with `r`, namely `(1 - r) * g_even(x^2) + r * g_odd(x^2)`.
+#[inline(always)]
+pub(crate) fn basefold_fold_pair<F, EvalF>(
+    a: EvalF,
+    b: EvalF,
+    r: EvalF,
+    x_inv: F,
+    two_inv: F,
+) -> EvalF
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let even = a + b;
+    let odd = (a - b).mul_by_base_field(&x_inv);
+    (even + r * (odd - even)).mul_by_base_field(&two_inv)
+}
+
+/// Fold a codeword over the subgroup of order `codeword.len()`, with the folding challenge `r`.
+#[inline(always)]
+pub(crate) fn basefold_fold_codeword<F, EvalF, CodeF>(codeword: &[CodeF], r: EvalF) -> Vec<EvalF>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F> + From<CodeF>,
+    CodeF: Field,
+{
+    let half = codeword.len() / 2;
+    let omega_inv = F::two_adic_generator(codeword.len().ilog2() as usize)
+        .inv()
+        .unwrap();
+    let two_inv = F::ONE.double().inv().unwrap();
+
+    let mut x_inv = F::ONE;
+    (0..half)
+        .map(|i| {
+            let folded = basefold_fold_pair(
+                EvalF::from(codeword[i]),
+                EvalF::from(codeword[i + half]),
+                r,
+                x_inv,
+                two_inv,
+            );
+            x_inv *= omega_inv;
+            folded
+        })
+        .collect()
+}
+
+/// Commit to a codeword, with the pair of values at `x` and `-x` in each leaf.
+#[inline(always)]
+pub(crate) fn basefold_commit_codeword<CodeF: Field>(codeword: &[CodeF]) -> BasefoldResult<Tree> {
+    let half = codeword.len() / 2;
+    let leaves = (0..half)
+        .map(|i| {
+            let mut bytes = Vec::with_capacity(LEAF_BYTES);
+            codeword[i].serialize_into(&mut bytes)?;
+            codeword[i + half].serialize_into(&mut bytes)?;
+            assert!(
+                bytes.len() <= LEAF_BYTES,
+                "field too large for a Basefold leaf"
+            );
+
+            let mut data = [0u8; LEAF_BYTES];
+            data[..bytes.len()].copy_from_slice(&bytes);
+            Ok(Leaf::new(data))
+        })
+        .collect::<BasefoldResult<Vec<_>>>()?;
+
+    Ok(Tree::new_with_leaves(leaves))
+}
+
+/// Read back the pair of codeword values in a leaf.
+#[inline(always)]
+pub(crate) fn basefold_leaf_values<CodeF: Field>(leaf: &Leaf) -> BasefoldResult<(CodeF, CodeF)> {
+    let mut reader = &leaf.data[..];
+    let a = CodeF::deserialize_from(&mut reader)?;
+    let b = CodeF::deserialize_from(&mut reader)?;
+    Ok((a, b))
+}
+
+/// Root of the tree over the roots of all the MPI processes.
+#[inline(always)]
+pub(crate) fn merkle_cap_root(merkle_cap: &[Node]) -> Node {
+    if merkle_cap.len() == 1 {
+        return merkle_cap[0];
+    }
+
+    let height = 1 + merkle_cap.len().ilog2();
+    let internal = Tree::new_with_leaf_nodes(merkle_cap, height);
+    internal[0]
+}
+
+/*
+ * MPI UTILS
+ */
+
+/// Every MPI process gets the local values of all the processes, in rank order.
+#[inline(always)]
+pub(crate) fn mpi_all_gather<T>(mpi_engine: &impl MPIEngine, local: T) -> BasefoldResult<Vec<T>>
+where
+    T: Copy + Default + ExpSerde,
+{
+    if mpi_engine.is_single_process() {
+        return Ok(vec![local]);
+    }
+
+    let mut global = vec![T::default(); mpi_engine.world_size()];
+    mpi_engine.gather_vec(&[local], &mut global);
+
+    let mut bytes: Vec<u8> = Vec::new();
+    global.serialize_into(&mut bytes)?;
+    mpi_engine.root_broadcast_bytes(&mut bytes);
+
+    if !mpi_engine.is_root() {
+        global = Vec::deserialize_from(bytes.as_slice())?;
+    }
+
+    Ok(global)
+}
+
+/// Every MPI process gets the sum of the local vectors of all the processes.
+#[inline(always)]
+pub(crate) fn mpi_all_sum<F: Field>(mpi_engine: &impl MPIEngine, local: &[F]) -> Vec<F> {
+    let mut sum = mpi_engine.sum_vec(local);
+    sum.resize(local.len(), F::ZERO);
+    sum.iter_mut().for_each(|s| mpi_engine.root_broadcast_f(s));
+    sum
+}
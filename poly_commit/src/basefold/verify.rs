@@ -0,0 +1,187 @@
+use arith::{ExtensionField, FFTField, Field};
+use gkr_engine::Transcript;
+use polynomials::EqPolynomial;
+use tree::{Node, Path};
+
+use crate::basefold::{
+    utils::{
+        basefold_fold_pair, basefold_leaf_values, basefold_query_complexity, merkle_cap_root,
+        BASEFOLD_LOG_INV_RATE,
+    },
+    BasefoldCommitment, BasefoldProof,
+};
+
+#[inline(always)]
+pub fn basefold_verify<F, EvalF>(
+    commitment: &BasefoldCommitment,
+    point: &[EvalF],
+    mpi_point: &[EvalF],
+    evaluation: EvalF,
+    transcript: &mut impl Transcript,
+    proof: &BasefoldProof<EvalF>,
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let world_size = 1 << mpi_point.len();
+    let num_vars = point.len();
+    let query_num = basefold_query_complexity();
+
+    // NOTE: check the shape of the proof, and the codeword commitments against the commitment
+    if num_vars == 0
+        || proof.merkle_cap.len() != world_size
+        || proof.sumcheck_messages.len() != num_vars
+        || proof.sumcheck_messages.iter().any(|m| m.len() != 3)
+        || proof.folded_merkle_caps.len() != num_vars - 1
+        || proof
+            .folded_merkle_caps
+            .iter()
+            .any(|c| c.len() != world_size)
+        || proof.final_evals.len() != world_size
+        || proof.query_openings.len() != world_size
+        || proof
+            .query_openings
+            .iter()
+            .any(|o| o.len() != query_num * num_vars)
+    {
+        return false;
+    }
+
+    if merkle_cap_root(&proof.merkle_cap) != *commitment {
+        return false;
+    }
+
+    // NOTE: replay the sumcheck over the local variables
+    let mut claim = evaluation;
+    let mut challenges = Vec::with_capacity(num_vars);
+    for (i, message) in proof.sumcheck_messages.iter().enumerate() {
+        if message[0] + message[1] != claim {
+            return false;
+        }
+
+        message
+            .iter()
+            .for_each(|m| transcript.append_field_element(m));
+        let r = transcript.generate_field_element::<EvalF>();
+        claim = degree_two_eval(message, r);
+        challenges.push(r);
+
+        if let Some(merkle_cap) = proof.folded_merkle_caps.get(i) {
+            merkle_cap
+                .iter()
+                .for_each(|node| transcript.append_u8_slice(node.as_bytes()));
+        }
+    }
+
+    proof
+        .final_evals
+        .iter()
+        .for_each(|e| transcript.append_field_element(e));
+
+    let final_eval = proof
+        .final_evals
+        .iter()
+        .enumerate()
+        .map(|(rank, e)| *e * EqPolynomial::ith_eq_vec_elem(mpi_point, rank))
+        .sum::<EvalF>();
+    if claim != final_eval * EqPolynomial::eq_vec(&challenges, point) {
+        return false;
+    }
+
+    // NOTE: check the folding of the codewords of each MPI process at the queried positions
+    let num_leaves = 1 << (num_vars + BASEFOLD_LOG_INV_RATE - 1);
+    let query_indices = transcript.generate_usize_vector(query_num);
+
+    (0..world_size).all(|rank| {
+        let roots: Vec<Node> = std::iter::once(proof.merkle_cap[rank])
+            .chain(proof.folded_merkle_caps.iter().map(|c| c[rank]))
+            .collect();
+
+        query_indices
+            .iter()
+            .zip(proof.query_openings[rank].chunks(num_vars))
+            .all(|(&q, paths)| {
+                basefold_query_verify(
+                    q % num_leaves,
+                    num_leaves,
+                    &roots,
+                    paths,
+                    &challenges,
+                    proof.final_evals[rank],
+                )
+            })
+    })
+}
+
+/// Evaluate at `r` the degree two polynomial with evaluations `evals` at 0, 1 and 2.
+#[inline(always)]
+fn degree_two_eval<F, EvalF>(evals: &[EvalF], r: EvalF) -> EvalF
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let two_inv = F::ONE.double().inv().unwrap();
+    let r_minus_one = r - EvalF::ONE;
+    let r_minus_two = r_minus_one - EvalF::ONE;
+
+    (evals[0] * r_minus_one * r_minus_two).mul_by_base_field(&two_inv) - evals[1] * r * r_minus_two
+        + (evals[2] * r * r_minus_one).mul_by_base_field(&two_inv)
+}
+
+/// Check the paths of a query on all the codewords of a process, and that each codeword is the
+/// folding of the previous one at the queried position, down to the final evaluation.
+/// `num_leaves` is the number of leaves of the first codeword.
+#[inline(always)]
+fn basefold_query_verify<F, EvalF>(
+    index: usize,
+    num_leaves: usize,
+    roots: &[Node],
+    paths: &[Path],
+    challenges: &[EvalF],
+    final_eval: EvalF,
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let two_inv = F::ONE.double().inv().unwrap();
+
+    let mut position = index;
+    let mut folded: Option<EvalF> = None;
+    for (i, (root, path)) in roots.iter().zip(paths).enumerate() {
+        let num_layer_leaves = num_leaves >> i;
+        let leaf_index = position % num_layer_leaves;
+        if path.index != leaf_index || !path.verify(root) {
+            return false;
+        }
+
+        let values = match i {
+            0 => {
+                basefold_leaf_values::<F>(&path.leaf).map(|(a, b)| (EvalF::from(a), EvalF::from(b)))
+            }
+            _ => basefold_leaf_values::<EvalF>(&path.leaf),
+        };
+        let (a, b) = match values {
+            Ok(values) => values,
+            Err(_) => return false,
+        };
+
+        // NOTE: the value folded from the previous codeword sits at the same position
+        if let Some(v) = folded {
+            let expected = if position < num_layer_leaves { a } else { b };
+            if v != expected {
+                return false;
+            }
+        }
+
+        let x_inv = F::two_adic_generator((2 * num_layer_leaves).ilog2() as usize)
+            .exp(leaf_index as u128)
+            .inv()
+            .unwrap();
+        folded = Some(basefold_fold_pair(a, b, challenges[i], x_inv, two_inv));
+        position = leaf_index;
+    }
+
+    folded == Some(final_eval)
+}
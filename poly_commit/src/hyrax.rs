@@ -2,9 +2,21 @@ mod pedersen;
 pub use pedersen::PedersenParams;
 
 mod hyrax_impl;
-pub use hyrax_impl::{HyraxCommitment, HyraxOpening};
+pub use hyrax_impl::{HyraxCommitment, HyraxOpening, HyraxPedersenCheck};
 
 mod pcs_trait_impl;
 pub use pcs_trait_impl::HyraxPCS;
 
 mod expander_api;
+
+mod sigma;
+pub use sigma::{DotProductProof, ProductProof, ZeroProof};
+
+mod hiding;
+pub use hiding::{
+    hiding_hyrax_prove_eval, hiding_hyrax_verify_eval, HidingHyraxEvalProof, HidingHyraxOpening,
+    HidingHyraxScratchPad, HidingPedersenParams,
+};
+
+mod hiding_expander_api;
+pub use hiding_expander_api::HidingHyraxPCS;
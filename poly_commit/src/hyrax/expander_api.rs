@@ -12,10 +12,10 @@ use serdes::ExpSerde;
 
 use crate::{
     hyrax::{
-        hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
+        hyrax_impl::{hyrax_batch_verify, hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
         pedersen::pedersen_commit,
     },
-    HyraxCommitment, HyraxOpening, HyraxPCS, PedersenParams,
+    HyraxCommitment, HyraxOpening, HyraxPCS, HyraxPedersenCheck, PedersenParams,
 };
 
 impl<G, C> ExpanderPCS<G, C::Scalar> for HyraxPCS<C>
@@ -36,6 +36,7 @@ where
     type Commitment = HyraxCommitment<C>;
     type Opening = HyraxOpening<C>;
     type SRS = PedersenParams<C>;
+    type DeferredCheck = HyraxPedersenCheck<C>;
 
     fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
         n_input_vars
@@ -129,10 +130,17 @@ where
         let pedersen_vars = pedersen_len.ilog2() as usize;
 
         let local_vars = x.local_xs();
+        if local_vars.len() < pedersen_vars || opening.0.len() != pedersen_len {
+            return false;
+        }
+
         let mut non_row_vars = local_vars[pedersen_vars..].to_vec();
         non_row_vars.extend_from_slice(&x.r_mpi);
 
         let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&non_row_vars);
+        if eq_combination.len() != commitment.0.len() {
+            return false;
+        }
         let row_comm = msm::best_multiexp(&eq_combination, &commitment.0);
 
         if pedersen_commit(verifying_key, &opening.0) != row_comm.into() {
@@ -143,4 +151,47 @@ where
         v == RefMultiLinearPoly::from_ref(&opening.0)
             .evaluate_with_buffer(&local_vars[..pedersen_vars], &mut scratch)
     }
+
+    fn verify_deferred(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        _transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> Option<Self::DeferredCheck> {
+        let pedersen_len = verifying_key.msm_len();
+        let pedersen_vars = pedersen_len.ilog2() as usize;
+
+        let local_vars = x.local_xs();
+        if local_vars.len() < pedersen_vars || opening.0.len() != pedersen_len {
+            return None;
+        }
+
+        let mut scratch = vec![C::Scalar::default(); opening.0.len()];
+        if v != RefMultiLinearPoly::from_ref(&opening.0)
+            .evaluate_with_buffer(&local_vars[..pedersen_vars], &mut scratch)
+        {
+            return None;
+        }
+
+        let mut non_row_vars = local_vars[pedersen_vars..].to_vec();
+        non_row_vars.extend_from_slice(&x.r_mpi);
+
+        Some(HyraxPedersenCheck {
+            row_combination: EqPolynomial::build_eq_x_r(&non_row_vars),
+            commitment: commitment.0.clone(),
+            opening: opening.0.clone(),
+        })
+    }
+
+    fn batch_check(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        checks: &[Self::DeferredCheck],
+        rng: impl rand::RngCore,
+    ) -> bool {
+        hyrax_batch_verify(verifying_key, checks, rng)
+    }
 }
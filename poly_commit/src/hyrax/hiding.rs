@@ -0,0 +1,316 @@
+use arith::ExtensionField;
+use derivative::Derivative;
+use gkr_engine::{StructuredReferenceString, Transcript};
+use halo2curves::{
+    ff::{Field, PrimeField},
+    group::{Curve, UncompressedEncoding},
+    msm, CurveAffine,
+};
+use polynomials::{
+    EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
+    RefMultiLinearPoly,
+};
+use serdes::ExpSerde;
+
+use crate::{
+    hyrax::{hyrax_impl::hyrax_setup, pedersen::pedersen_commit},
+    DotProductProof, HyraxCommitment, PedersenParams,
+};
+
+/// Pedersen parameters of the hiding Hyrax PCS: the Hyrax row bases, together with the bases
+/// `g` and `h` of the scalar commitments `v * g + r * h`, where `h` also blinds the rows.
+#[derive(Clone, Debug, Default)]
+pub struct HidingPedersenParams<C>
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    pub row_params: PedersenParams<C>,
+    pub value_base: C,
+    pub blinding_base: C,
+}
+
+impl<C> ExpSerde for HidingPedersenParams<C>
+where
+    C: CurveAffine + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> serdes::SerdeResult<()> {
+        self.row_params.serialize_into(&mut writer)?;
+        self.value_base.serialize_into(&mut writer)?;
+        self.blinding_base.serialize_into(&mut writer)
+    }
+
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> serdes::SerdeResult<Self> {
+        Ok(Self {
+            row_params: PedersenParams::deserialize_from(&mut reader)?,
+            value_base: C::deserialize_from(&mut reader)?,
+            blinding_base: C::deserialize_from(&mut reader)?,
+        })
+    }
+}
+
+impl<C> StructuredReferenceString for HidingPedersenParams<C>
+where
+    C: CurveAffine + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    type PKey = Self;
+    type VKey = Self;
+
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        (self.clone(), self)
+    }
+}
+
+impl<C> HidingPedersenParams<C>
+where
+    C: CurveAffine,
+    C::Scalar: PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    /// Commitment `v * g + blind * h` to a scalar.
+    #[inline(always)]
+    pub fn commit_scalar(&self, v: C::Scalar, blind: C::Scalar) -> C::Curve {
+        self.value_base * v + self.blinding_base * blind
+    }
+
+    /// Commitment to a vector of at most `row_params.msm_len()` scalars under the row bases,
+    /// blinded by `blind * h`.
+    #[inline(always)]
+    pub fn commit_vector(&self, coeffs: &[C::Scalar], blind: C::Scalar) -> C::Curve {
+        pedersen_commit(&self.row_params, coeffs).to_curve() + self.blinding_base * blind
+    }
+}
+
+/// The blinding factors of the rows of the last polynomial committed by the hiding Hyrax PCS,
+/// needed to open it.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct HidingHyraxScratchPad<C>
+where
+    C: CurveAffine,
+    C::Scalar: ExpSerde,
+{
+    pub row_blinds: Vec<C::Scalar>,
+}
+
+/// Opening of the hiding Hyrax PCS at a public evaluation, i.e., the combination of the rows
+/// together with the combination of their blinding factors.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct HidingHyraxOpening<C>
+where
+    C: CurveAffine,
+    C::Scalar: ExpSerde,
+{
+    pub row_combination: Vec<C::Scalar>,
+    pub blind: C::Scalar,
+}
+
+/// Proof that a hiding Hyrax commitment evaluates at a point to the opening of a scalar
+/// commitment, revealing nothing about the polynomial, nor about the evaluation.
+pub type HidingHyraxEvalProof<C> = DotProductProof<C>;
+
+pub(crate) fn hiding_hyrax_setup<C>(
+    local_vars: usize,
+    mpi_vars: usize,
+    mut rng: impl rand::RngCore,
+) -> HidingPedersenParams<C>
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let row_params = hyrax_setup(local_vars, mpi_vars, &mut rng);
+    let value_base = (C::generator() * C::Scalar::random(&mut rng)).to_affine();
+    let blinding_base = (C::generator() * C::Scalar::random(&mut rng)).to_affine();
+
+    HidingPedersenParams {
+        row_params,
+        value_base,
+        blinding_base,
+    }
+}
+
+pub(crate) fn hiding_hyrax_commit<C>(
+    params: &HidingPedersenParams<C>,
+    mle_poly: &impl MultilinearExtension<C::Scalar>,
+    mut rng: impl rand::RngCore,
+) -> (HyraxCommitment<C>, Vec<C::Scalar>)
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let (commitments, row_blinds) = mle_poly
+        .hypercube_basis_ref()
+        .chunks(params.row_params.msm_len())
+        .map(|row| {
+            let blind = C::Scalar::random(&mut rng);
+            (params.commit_vector(row, blind).to_affine(), blind)
+        })
+        .unzip();
+
+    (HyraxCommitment(commitments), row_blinds)
+}
+
+/// The rows of the polynomial, and their blinding factors, combined by the non-row variables of
+/// the evaluation point.
+fn combine_rows<C>(
+    params: &HidingPedersenParams<C>,
+    mle_poly: &impl MultilinearExtension<C::Scalar>,
+    row_blinds: &[C::Scalar],
+    eval_point: &[C::Scalar],
+) -> (Vec<C::Scalar>, C::Scalar)
+where
+    C: CurveAffine,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_vars = params.row_params.msm_len().ilog2() as usize;
+
+    let mut local_basis = mle_poly.hypercube_basis();
+    let mut local_mle = MutRefMultiLinearPoly::from_ref(&mut local_basis);
+    local_mle.fix_variables(&eval_point[pedersen_vars..]);
+
+    let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[pedersen_vars..]);
+    let blind = eq_combination
+        .iter()
+        .zip(row_blinds.iter())
+        .map(|(e, b)| *e * b)
+        .sum();
+
+    (local_basis, blind)
+}
+
+pub(crate) fn hiding_hyrax_open<C>(
+    params: &HidingPedersenParams<C>,
+    mle_poly: &impl MultilinearExtension<C::Scalar>,
+    row_blinds: &[C::Scalar],
+    eval_point: &[C::Scalar],
+) -> HidingHyraxOpening<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let (row_combination, blind) = combine_rows(params, mle_poly, row_blinds, eval_point);
+
+    HidingHyraxOpening {
+        row_combination,
+        blind,
+    }
+}
+
+/// The commitment to the combination of the rows, by the non-row variables of the evaluation
+/// point.
+fn combine_row_commitments<C>(
+    params: &HidingPedersenParams<C>,
+    comm: &HyraxCommitment<C>,
+    eval_point: &[C::Scalar],
+) -> Option<C::Curve>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_vars = params.row_params.msm_len().ilog2() as usize;
+    if eval_point.len() < pedersen_vars {
+        return None;
+    }
+
+    let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[pedersen_vars..]);
+    if eq_combination.len() != comm.0.len() {
+        return None;
+    }
+
+    Some(msm::best_multiexp(&eq_combination, &comm.0))
+}
+
+pub(crate) fn hiding_hyrax_verify<C>(
+    params: &HidingPedersenParams<C>,
+    comm: &HyraxCommitment<C>,
+    eval_point: &[C::Scalar],
+    eval: C::Scalar,
+    opening: &HidingHyraxOpening<C>,
+) -> bool
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_len = params.row_params.msm_len();
+    let pedersen_vars = pedersen_len.ilog2() as usize;
+
+    let row_comm = match combine_row_commitments(params, comm, eval_point) {
+        Some(row_comm) => row_comm,
+        None => return false,
+    };
+    if opening.row_combination.len() != pedersen_len
+        || params.commit_vector(&opening.row_combination, opening.blind) != row_comm
+    {
+        return false;
+    }
+
+    let mut scratch = vec![C::Scalar::default(); pedersen_len];
+    eval == RefMultiLinearPoly::from_ref(&opening.row_combination)
+        .evaluate_with_buffer(&eval_point[..pedersen_vars], &mut scratch)
+}
+
+/// Prove that the committed polynomial evaluates at `eval_point` to the value committed to by
+/// `params.commit_scalar(eval, eval_blind)`.
+pub fn hiding_hyrax_prove_eval<C>(
+    params: &HidingPedersenParams<C>,
+    mle_poly: &impl MultilinearExtension<C::Scalar>,
+    row_blinds: &[C::Scalar],
+    eval_point: &[C::Scalar],
+    eval_blind: C::Scalar,
+    transcript: &mut impl Transcript,
+    rng: impl rand::RngCore,
+) -> HidingHyraxEvalProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_vars = params.row_params.msm_len().ilog2() as usize;
+
+    let (row_combination, row_blind) = combine_rows(params, mle_poly, row_blinds, eval_point);
+    let eq_row: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[..pedersen_vars]);
+
+    DotProductProof::prove(
+        params,
+        (&row_combination, row_blind),
+        eval_blind,
+        &eq_row,
+        transcript,
+        rng,
+    )
+}
+
+/// Verify that the committed polynomial evaluates at `eval_point` to the value committed to by
+/// `eval_comm`.
+pub fn hiding_hyrax_verify_eval<C>(
+    params: &HidingPedersenParams<C>,
+    comm: &HyraxCommitment<C>,
+    eval_point: &[C::Scalar],
+    eval_comm: &C::Curve,
+    transcript: &mut impl Transcript,
+    proof: &HidingHyraxEvalProof<C>,
+) -> bool
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_vars = params.row_params.msm_len().ilog2() as usize;
+
+    let row_comm = match combine_row_commitments(params, comm, eval_point) {
+        Some(row_comm) => row_comm,
+        None => return false,
+    };
+    let eq_row: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[..pedersen_vars]);
+
+    proof.verify(params, &row_comm, eval_comm, &eq_row, transcript)
+}
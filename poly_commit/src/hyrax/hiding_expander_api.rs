@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use arith::ExtensionField;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    StructuredReferenceString, Transcript,
+};
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
+use polynomials::MultilinearExtension;
+use serdes::ExpSerde;
+
+use crate::{
+    hyrax::hiding::{
+        hiding_hyrax_commit, hiding_hyrax_open, hiding_hyrax_setup, hiding_hyrax_verify,
+    },
+    HidingHyraxOpening, HidingHyraxScratchPad, HidingPedersenParams, HyraxCommitment,
+};
+
+/// Hyrax PCS with blinded row commitments, s.t., the commitment reveals nothing about the
+/// polynomial.
+///
+/// The openings of `ExpanderPCS` are against public evaluations, the zero-knowledge GKR opens the
+/// commitment against committed evaluations instead, see `hiding_hyrax_prove_eval`.
+pub struct HidingHyraxPCS<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField,
+    C::ScalarExt: ExtensionField,
+{
+    _phantom_c: PhantomData<C>,
+}
+
+impl<G, C> ExpanderPCS<G, C::Scalar> for HidingHyraxPCS<C>
+where
+    G: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::ScalarExt: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    const NAME: &'static str = "HidingHyraxPCSForExpanderGKR";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::HidingHyrax;
+
+    type Params = usize;
+    type ScratchPad = HidingHyraxScratchPad<C>;
+
+    type Commitment = HyraxCommitment<C>;
+    type Opening = HidingHyraxOpening<C>;
+    type SRS = HidingPedersenParams<C>;
+
+    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+        n_input_vars
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
+        HidingHyraxScratchPad::default()
+    }
+
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let mpi_vars = mpi_engine.world_size().ilog2() as usize;
+
+        hiding_hyrax_setup(*params, mpi_vars, rng)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::Scalar>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> Option<Self::Commitment> {
+        let (local_commit, row_blinds) = hiding_hyrax_commit(proving_key, poly, rand::thread_rng());
+        scratch_pad.row_blinds = row_blinds;
+
+        if mpi_engine.is_single_process() {
+            return local_commit.into();
+        }
+
+        let mut global_commit: Vec<C> = if mpi_engine.is_root() {
+            vec![C::default(); mpi_engine.world_size() * local_commit.0.len()]
+        } else {
+            vec![]
+        };
+
+        mpi_engine.gather_vec(&local_commit.0, &mut global_commit);
+        if !mpi_engine.is_root() {
+            return None;
+        }
+
+        HyraxCommitment(global_commit).into()
+    }
+
+    fn open(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::Scalar>,
+        x: &ExpanderSingleVarChallenge<G>,
+        _transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> Option<Self::Opening> {
+        let local_opening =
+            hiding_hyrax_open(proving_key, poly, &scratch_pad.row_blinds, &x.local_xs());
+
+        if mpi_engine.is_single_process() {
+            return local_opening.into();
+        }
+
+        let eq_mpi_vars = polynomials::EqPolynomial::build_eq_x_r(&x.r_mpi);
+        let row_combination =
+            mpi_engine.coef_combine_vec(&local_opening.row_combination, &eq_mpi_vars);
+        let blind = mpi_engine.coef_combine_vec(&[local_opening.blind], &eq_mpi_vars);
+
+        if !mpi_engine.is_root() {
+            return None;
+        }
+
+        HidingHyraxOpening {
+            row_combination,
+            blind: blind[0],
+        }
+        .into()
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        _transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        // the rows of the MPI processes are concatenated in rank order, hence the MPI variables
+        // are the most significant ones
+        let eval_point = [x.local_xs(), x.r_mpi.clone()].concat();
+
+        hiding_hyrax_verify(verifying_key, commitment, &eval_point, v, opening)
+    }
+}
@@ -1,5 +1,9 @@
 use arith::ExtensionField;
-use halo2curves::{ff::PrimeField, group::UncompressedEncoding, msm, CurveAffine};
+use halo2curves::{
+    ff::{Field, PrimeField},
+    group::{Group, UncompressedEncoding},
+    msm, CurveAffine,
+};
 use polynomials::{
     EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
     RefMultiLinearPoly,
@@ -46,6 +50,18 @@ pub struct HyraxOpening<C>(pub Vec<C::Scalar>)
 where
     C: CurveAffine + ExpSerde + UncompressedEncoding;
 
+/// Commitment check `pedersen_commit(opening) == msm(row_combination, commitment)` of a Hyrax
+/// opening, deferred so that a batch of them can be checked with a single MSM.
+#[derive(Clone, Debug, Default)]
+pub struct HyraxPedersenCheck<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+{
+    pub row_combination: Vec<C::Scalar>,
+    pub commitment: Vec<C>,
+    pub opening: Vec<C::Scalar>,
+}
+
 impl<C> ExpSerde for HyraxCommitment<C>
 where
     C: CurveAffine + ExpSerde + UncompressedEncoding,
@@ -151,7 +167,16 @@ where
     let pedersen_len = params.msm_len();
     let pedersen_vars = pedersen_len.ilog2() as usize;
 
+    // the commitment and the opening come from the proof, reject them rather than panicking
+    // if they have the wrong number of rows or columns
+    if eval_point.len() < pedersen_vars || proof.0.len() != pedersen_len {
+        return false;
+    }
+
     let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[pedersen_vars..]);
+    if eq_combination.len() != comm.0.len() {
+        return false;
+    }
     let row_comm = msm::best_multiexp(&eq_combination, &comm.0);
 
     if pedersen_commit(params, &proof.0) != row_comm.into() {
@@ -162,3 +187,40 @@ where
     eval == RefMultiLinearPoly::from_ref(&proof.0)
         .evaluate_with_buffer(&eval_point[..pedersen_vars], &mut scratch)
 }
+
+/// Check a batch of Pedersen commitment checks with a single MSM over a random linear combination
+/// of them. The checks are expected to have openings of length `params.msm_len()`.
+pub(crate) fn hyrax_batch_verify<C>(
+    params: &PedersenParams<C>,
+    checks: &[HyraxPedersenCheck<C>],
+    mut rng: impl rand::RngCore,
+) -> bool
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let pedersen_len = params.msm_len();
+
+    // sum_i rho_i * (pedersen_commit(opening_i) - msm(row_combination_i, commitment_i)) == 0,
+    // where the Pedersen parts share the bases, hence their scalars add up
+    let mut scalars = vec![C::Scalar::default(); pedersen_len];
+    let mut bases = params.bases.clone();
+    for check in checks {
+        if check.opening.len() != pedersen_len
+            || check.row_combination.len() != check.commitment.len()
+        {
+            return false;
+        }
+
+        let rho = C::Scalar::random(&mut rng);
+        scalars
+            .iter_mut()
+            .zip(check.opening.iter())
+            .for_each(|(s, o)| *s += rho * o);
+        scalars.extend(check.row_combination.iter().map(|e| -(rho * e)));
+        bases.extend_from_slice(&check.commitment);
+    }
+
+    msm::best_multiexp(&scalars, &bases).is_identity().into()
+}
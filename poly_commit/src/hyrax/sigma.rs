@@ -0,0 +1,234 @@
+//! Sigma protocols over the scalar Pedersen commitments `v * g + r * h` of
+//! `HidingPedersenParams`, made non-interactive with the Fiat-Shamir transcript.
+//!
+//! The first messages are appended to the transcript to derive the challenge, the caller is
+//! expected to lock the proof around `prove`/`verify`, and write the serialized proof afterwards,
+//! as for PCS openings.
+
+use arith::ExtensionField;
+use derivative::Derivative;
+use gkr_engine::Transcript;
+use halo2curves::{
+    ff::{Field, PrimeField},
+    group::{Curve, UncompressedEncoding},
+    CurveAffine,
+};
+use serdes::ExpSerde;
+
+use crate::HidingPedersenParams;
+
+#[inline(always)]
+pub(crate) fn append_point<C>(transcript: &mut impl Transcript, point: &C)
+where
+    C: CurveAffine + ExpSerde,
+{
+    let mut buffer = vec![];
+    point.serialize_into(&mut buffer).unwrap(); // TODO: error propagation
+    transcript.append_u8_slice(&buffer);
+}
+
+/// Proof that a commitment `z_comm` opens to the product of the openings of `x_comm` and
+/// `y_comm`, the proof of product of Hyrax.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct ProductProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExpSerde,
+{
+    pub alpha: C,
+    pub beta: C,
+    pub delta: C,
+    pub z1: C::Scalar,
+    pub z2: C::Scalar,
+    pub z3: C::Scalar,
+    pub z4: C::Scalar,
+    pub z5: C::Scalar,
+}
+
+impl<C> ProductProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    /// Prove that `z_blind` blinds the commitment to `x * y`, given the openings `(x, x_blind)`
+    /// and `(y, y_blind)`.
+    pub fn prove(
+        params: &HidingPedersenParams<C>,
+        (x, x_blind): (C::Scalar, C::Scalar),
+        (y, y_blind): (C::Scalar, C::Scalar),
+        z_blind: C::Scalar,
+        transcript: &mut impl Transcript,
+        mut rng: impl rand::RngCore,
+    ) -> Self {
+        let b: [C::Scalar; 5] = std::array::from_fn(|_| C::Scalar::random(&mut rng));
+        let x_comm = params.commit_scalar(x, x_blind);
+
+        let alpha = params.commit_scalar(b[0], b[1]).to_affine();
+        let beta = params.commit_scalar(b[2], b[3]).to_affine();
+        let delta = (x_comm * b[2] + params.blinding_base * b[4]).to_affine();
+        [alpha, beta, delta]
+            .iter()
+            .for_each(|p| append_point(transcript, p));
+
+        let c = transcript.generate_field_element::<C::Scalar>();
+        Self {
+            alpha,
+            beta,
+            delta,
+            z1: b[0] + c * x,
+            z2: b[1] + c * x_blind,
+            z3: b[2] + c * y,
+            z4: b[3] + c * y_blind,
+            z5: b[4] + c * (z_blind - x_blind * y),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        params: &HidingPedersenParams<C>,
+        x_comm: &C::Curve,
+        y_comm: &C::Curve,
+        z_comm: &C::Curve,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        [self.alpha, self.beta, self.delta]
+            .iter()
+            .for_each(|p| append_point(transcript, p));
+        let c = transcript.generate_field_element::<C::Scalar>();
+
+        self.alpha.to_curve() + *x_comm * c == params.commit_scalar(self.z1, self.z2)
+            && self.beta.to_curve() + *y_comm * c == params.commit_scalar(self.z3, self.z4)
+            && self.delta.to_curve() + *z_comm * c
+                == *x_comm * self.z3 + params.blinding_base * self.z5
+    }
+}
+
+/// Proof that a commitment opens to zero, i.e., a Schnorr proof of knowledge of its discrete
+/// logarithm w.r.t. the blinding base `h`.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct ZeroProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExpSerde,
+{
+    pub k: C,
+    pub z: C::Scalar,
+}
+
+impl<C> ZeroProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    /// Prove that `blind * h` is a commitment to zero.
+    pub fn prove(
+        params: &HidingPedersenParams<C>,
+        blind: C::Scalar,
+        transcript: &mut impl Transcript,
+        mut rng: impl rand::RngCore,
+    ) -> Self {
+        let k_blind = C::Scalar::random(&mut rng);
+        let k = (params.blinding_base * k_blind).to_affine();
+        append_point(transcript, &k);
+
+        let c = transcript.generate_field_element::<C::Scalar>();
+        Self {
+            k,
+            z: k_blind + c * blind,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        params: &HidingPedersenParams<C>,
+        comm: &C::Curve,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        append_point(transcript, &self.k);
+        let c = transcript.generate_field_element::<C::Scalar>();
+
+        params.blinding_base * self.z == self.k.to_curve() + *comm * c
+    }
+}
+
+/// Proof that a commitment `y_comm` opens to `<x, a>` for a public vector `a`, where `x` is the
+/// opening of a vector commitment `x_comm` under the Hyrax row bases, the proof of dot product of
+/// Hyrax.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct DotProductProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExpSerde,
+{
+    pub delta: C,
+    pub beta: C,
+    pub z: Vec<C::Scalar>,
+    pub z_delta: C::Scalar,
+    pub z_beta: C::Scalar,
+}
+
+impl<C> DotProductProof<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField + ExpSerde,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    pub fn prove(
+        params: &HidingPedersenParams<C>,
+        (x, x_blind): (&[C::Scalar], C::Scalar),
+        y_blind: C::Scalar,
+        a: &[C::Scalar],
+        transcript: &mut impl Transcript,
+        mut rng: impl rand::RngCore,
+    ) -> Self {
+        let d: Vec<C::Scalar> = (0..x.len()).map(|_| C::Scalar::random(&mut rng)).collect();
+        let (delta_blind, beta_blind) = (C::Scalar::random(&mut rng), C::Scalar::random(&mut rng));
+
+        let delta = params.commit_vector(&d, delta_blind).to_affine();
+        let beta = params
+            .commit_scalar(inner_product(&d, a), beta_blind)
+            .to_affine();
+        append_point(transcript, &delta);
+        append_point(transcript, &beta);
+
+        let c = transcript.generate_field_element::<C::Scalar>();
+        Self {
+            delta,
+            beta,
+            z: x.iter().zip(d.iter()).map(|(x, d)| c * x + d).collect(),
+            z_delta: c * x_blind + delta_blind,
+            z_beta: c * y_blind + beta_blind,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        params: &HidingPedersenParams<C>,
+        x_comm: &C::Curve,
+        y_comm: &C::Curve,
+        a: &[C::Scalar],
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        if self.z.len() != a.len() || self.z.len() > params.row_params.msm_len() {
+            return false;
+        }
+
+        append_point(transcript, &self.delta);
+        append_point(transcript, &self.beta);
+        let c = transcript.generate_field_element::<C::Scalar>();
+
+        *x_comm * c + self.delta.to_curve() == params.commit_vector(&self.z, self.z_delta)
+            && *y_comm * c + self.beta.to_curve()
+                == params.commit_scalar(inner_product(&self.z, a), self.z_beta)
+    }
+}
+
+#[inline(always)]
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b.iter()).map(|(a, b)| *a * b).sum()
+}
@@ -133,6 +133,45 @@ where
     gt_result.final_exponentiation().is_identity().into()
 }
 
+/// Check a batch of pairing checks against the same verifying key, with a single pairing check on
+/// a random linear combination of them.
+pub fn coeff_form_bi_kzg_batch_verify<E: MultiMillerLoop>(
+    vk: &BiKZGVerifierParam<E>,
+    checks: &[KZGPairingCheck<E>],
+    mut rng: impl rand::RngCore,
+) -> bool
+where
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+{
+    // NOTE: e(q, [tau - alpha]_2) = e(q, [tau]_2) * e(-alpha * q, [1]_2), so that all the
+    // pairings against the G2 generator merge into a single one
+    let mut quotient_x_acc = E::G1::identity();
+    let mut quotient_y_acc = E::G1::identity();
+    let mut generator_acc = E::G1::identity();
+
+    checks.iter().for_each(|check| {
+        let rho = E::Fr::random(&mut rng);
+        let quotient_x = check.quotient_x * rho;
+        let quotient_y = check.quotient_y * rho;
+
+        quotient_x_acc += quotient_x;
+        quotient_y_acc += quotient_y;
+        generator_acc += (E::G1Affine::generator() * check.eval - check.commitment.to_curve())
+            * rho
+            - quotient_x * check.alpha
+            - quotient_y * check.beta;
+    });
+
+    let gt_result = E::multi_miller_loop(&[
+        (&quotient_x_acc.to_affine(), &vk.tau_x_g2.into()),
+        (&quotient_y_acc.to_affine(), &vk.tau_y_g2.into()),
+        (&generator_acc.to_affine(), &E::G2Affine::generator().into()),
+    ]);
+
+    gt_result.final_exponentiation().is_identity().into()
+}
+
 #[cfg(test)]
 mod tests {
     use ark_std::test_rng;
@@ -33,6 +33,7 @@ where
 
     type Commitment = KZGCommitment<E>;
     type Opening = HyperBiKZGOpening<E>;
+    type DeferredCheck = KZGPairingCheck<E>;
     type Params = usize;
     type SRS = CoefFormBiKZGLocalSRS<E>;
     type ScratchPad = ();
@@ -165,4 +166,45 @@ where
             transcript,
         )
     }
+
+    fn verify_deferred(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> Option<Self::DeferredCheck> {
+        if x.rz.len() < Self::MINIMUM_SUPPORTED_NUM_VARS {
+            let x = lift_expander_challenge_to_n_vars(x, Self::MINIMUM_SUPPORTED_NUM_VARS);
+            return <Self as ExpanderPCS<G, E::Fr>>::verify_deferred(
+                _params,
+                verifying_key,
+                commitment,
+                &x,
+                v,
+                transcript,
+                opening,
+            );
+        };
+
+        coeff_form_hyper_bikzg_verify_deferred(
+            &x.local_xs(),
+            &x.r_mpi,
+            v,
+            commitment.0,
+            opening,
+            transcript,
+        )
+    }
+
+    fn batch_check(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        checks: &[Self::DeferredCheck],
+        rng: impl rand::RngCore,
+    ) -> bool {
+        coeff_form_bi_kzg_batch_verify(verifying_key, checks, rng)
+    }
 }
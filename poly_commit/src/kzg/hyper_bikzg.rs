@@ -452,6 +452,58 @@ pub fn coeff_form_hyper_bikzg_verify<E, T>(
     opening: &HyperBiKZGOpening<E>,
     fs_transcript: &mut T,
 ) -> bool
+where
+    E: MultiMillerLoop,
+    T: Transcript,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+    E::Fr: ExtensionField,
+{
+    let check = match coeff_form_hyper_bikzg_verify_deferred(
+        local_alphas,
+        mpi_alphas,
+        eval,
+        commitment,
+        opening,
+        fs_transcript,
+    ) {
+        Some(check) => check,
+        None => return false,
+    };
+
+    // NOTE(HS) deteriorate to vanilla HyperKZG verify if mpi_alphas is empty
+    if mpi_alphas.is_empty() {
+        return coeff_form_uni_kzg_verify(
+            vk.into(),
+            check.commitment,
+            check.alpha,
+            check.eval,
+            check.quotient_x,
+        );
+    }
+
+    coeff_form_bi_kzg_verify(
+        vk.clone(),
+        check.commitment,
+        check.alpha,
+        check.beta,
+        check.eval,
+        BiKZGProof {
+            quotient_x: check.quotient_x,
+            quotient_y: check.quotient_y,
+        },
+    )
+}
+
+/// HyperBiKZG verification up to the final pairing check, which is returned to the caller.
+pub fn coeff_form_hyper_bikzg_verify_deferred<E, T>(
+    local_alphas: &[E::Fr],
+    mpi_alphas: &[E::Fr],
+    eval: E::Fr,
+    commitment: E::G1Affine,
+    opening: &HyperBiKZGOpening<E>,
+    fs_transcript: &mut T,
+) -> Option<KZGPairingCheck<E>>
 where
     E: MultiMillerLoop,
     T: Transcript,
@@ -464,16 +516,23 @@ where
         let hyper_bikzg_opening = opening.clone();
         let hyper_kzg_opening: HyperKZGOpening<E> = hyper_bikzg_opening.into();
 
-        let what = coeff_form_uni_hyperkzg_verify(
-            vk.into(),
+        return coeff_form_uni_hyperkzg_verify_deferred(
             commitment,
             local_alphas,
             eval,
             &hyper_kzg_opening,
             fs_transcript,
         );
+    }
 
-        return what;
+    // NOTE: the opening is read from the proof, check its shape before indexing into it:
+    // the folded oracles over x, the oracle of the final evals at x and the folded oracles over y
+    if local_alphas.is_empty()
+        || opening.folded_oracle_commitments.len() != local_alphas.len() + mpi_alphas.len() - 1
+        || !opening.aggregated_evals.has_num_vars(local_alphas.len())
+        || !opening.leader_evals.has_num_vars(mpi_alphas.len())
+    {
+        return None;
     }
 
     let mpi_world_size = 1 << mpi_alphas.len();
@@ -528,19 +587,19 @@ where
     // );
 
     if beta_y2_final_eval != opening.leader_evals.beta_x2_eval {
-        return false;
+        return None;
     }
     if pos_beta_y_final_eval != opening.leader_evals.pos_beta_x_evals[0] {
-        return false;
+        return None;
     }
     if neg_beta_y_final_eval != opening.leader_evals.neg_beta_x_evals[0] {
-        return false;
+        return None;
     }
 
     let local_final_eval =
         HyperKZGLocalEvals::new_from_exported_evals(&opening.leader_evals, mpi_alphas, beta_y);
     if eval != local_final_eval.multilinear_final_eval() {
-        return false;
+        return None;
     }
 
     opening.aggregated_evals.append_to_transcript(fs_transcript);
@@ -634,17 +693,12 @@ where
 
     // dbg!(com_r);
 
-    let final_opening = BiKZGProof {
+    Some(KZGPairingCheck {
+        commitment: com_r.to_affine(),
+        alpha: delta_x,
+        beta: delta_y,
+        eval: degree_2_final_eval,
         quotient_x: opening.quotient_delta_x_commitment,
         quotient_y: opening.quotient_delta_y_commitment,
-    };
-
-    coeff_form_bi_kzg_verify(
-        vk.clone(),
-        com_r.to_affine(),
-        delta_x,
-        delta_y,
-        degree_2_final_eval,
-        final_opening,
-    )
+    })
 }
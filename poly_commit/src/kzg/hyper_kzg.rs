@@ -187,6 +187,43 @@ where
     E::Fr: ExtensionField + ExpSerde,
     T: Transcript,
 {
+    coeff_form_uni_hyperkzg_verify_deferred(comm, alphas, eval, opening, fs_transcript).is_some_and(
+        |check| {
+            coeff_form_uni_kzg_verify(
+                vk,
+                check.commitment,
+                check.alpha,
+                check.eval,
+                check.quotient_x,
+            )
+        },
+    )
+}
+
+/// HyperKZG verification up to the final pairing check, which is returned to the caller.
+pub fn coeff_form_uni_hyperkzg_verify_deferred<E, T>(
+    comm: E::G1Affine,
+    alphas: &[E::Fr],
+    eval: E::Fr,
+    opening: &HyperKZGOpening<E>,
+    fs_transcript: &mut T,
+) -> Option<KZGPairingCheck<E>>
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    // NOTE: the opening is read from the proof, and its evals are zipped with the alphas,
+    // hence check that there is one folded oracle per alpha but the last one and one eval per alpha
+    if alphas.is_empty()
+        || opening.folded_oracle_commitments.len() != alphas.len() - 1
+        || !opening.evals_at_x.has_num_vars(alphas.len())
+    {
+        return None;
+    }
+
     opening
         .folded_oracle_commitments
         .iter()
@@ -201,7 +238,7 @@ where
     opening.evals_at_x.append_to_transcript(fs_transcript);
 
     if local_evals.multilinear_final_eval() != eval {
-        return false;
+        return None;
     }
 
     let gamma = fs_transcript.generate_field_element::<E::Fr>();
@@ -227,11 +264,12 @@ where
     let lagrange_eval =
         lagrange_degree2[0] + lagrange_degree2[1] * tau + lagrange_degree2[2] * tau * tau;
 
-    coeff_form_uni_kzg_verify(
-        vk,
-        (commitment_agg_g1 - opening.beta_x_commitment.to_curve() * q_weight).into(),
-        tau,
-        lagrange_eval,
-        opening.quotient_delta_x_commitment,
-    )
+    Some(KZGPairingCheck {
+        commitment: (commitment_agg_g1 - opening.beta_x_commitment.to_curve() * q_weight).into(),
+        alpha: tau,
+        beta: E::Fr::ZERO,
+        eval: lagrange_eval,
+        quotient_x: opening.quotient_delta_x_commitment,
+        quotient_y: E::G1Affine::identity(),
+    })
 }
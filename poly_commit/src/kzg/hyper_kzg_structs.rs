@@ -36,6 +36,11 @@ where
         self.pos_beta_x_evals.len() + self.neg_beta_x_evals.len() + 1
     }
 
+    /// Whether these are the evals of a polynomial folded over `num_vars` variables.
+    pub(crate) fn has_num_vars(&self, num_vars: usize) -> bool {
+        self.pos_beta_x_evals.len() == num_vars && self.neg_beta_x_evals.len() == num_vars
+    }
+
     pub(crate) fn append_to_transcript<T>(&self, fs_transcript: &mut T)
     where
         T: Transcript,
@@ -271,6 +276,13 @@ where
         aggregated
     }
 
+    /// Whether these are the aggregated evals of polynomials folded over `num_vars` variables.
+    pub(crate) fn has_num_vars(&self, num_vars: usize) -> bool {
+        self.beta_y2_evals.has_num_vars(num_vars)
+            && self.pos_beta_y_evals.has_num_vars(num_vars)
+            && self.neg_beta_y_evals.has_num_vars(num_vars)
+    }
+
     pub(crate) fn append_to_transcript<T>(&self, fs_transcript: &mut T)
     where
         T: Transcript,
@@ -130,3 +130,18 @@ pub struct BiKZGProof<E: Engine> {
     pub quotient_x: E::G1Affine,
     pub quotient_y: E::G1Affine,
 }
+
+/// Pairing check of a Bi-KZG opening of `commitment` to `eval` at `(alpha, beta)`, i.e.,
+/// `e(quotient_x, [tau_x - alpha]_2) * e(quotient_y, [tau_y - beta]_2)
+///   = e(commitment - [eval]_1, [1]_2)`.
+/// A univariate KZG opening has `quotient_y` set to the identity.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGPairingCheck<E: Engine> {
+    pub commitment: E::G1Affine,
+    pub alpha: E::Fr,
+    pub beta: E::Fr,
+    pub eval: E::Fr,
+    pub quotient_x: E::G1Affine,
+    pub quotient_y: E::G1Affine,
+}
@@ -19,3 +19,6 @@ pub use hyrax::*;
 
 pub mod kzg;
 pub use kzg::*;
+
+pub mod basefold;
+pub use basefold::*;
@@ -2,13 +2,12 @@ use std::collections::VecDeque;
 
 use arith::SimdField;
 use gkr_engine::{MPIEngine, Transcript};
-use itertools::izip;
 use serdes::ExpSerde;
 use transpose::transpose_inplace;
 use tree::{RangePath, Tree};
 
 use crate::{
-    orion::{OrionCommitment, OrionResult, OrionSRS, OrionScratchPad},
+    orion::{utils::encode_packed_rows, OrionCommitment, OrionResult, OrionSRS, OrionScratchPad},
     traits::TensorCodeIOPPCS,
     PCS_SOUNDNESS_BITS,
 };
@@ -117,11 +116,7 @@ where
 
     // NOTE: packed codeword buffer and encode over packed field
     let mut codewords = vec![PackF::ZERO; packed_rows * pk.codeword_len()];
-    izip!(
-        packed_evals.chunks(pk.message_len()),
-        codewords.chunks_mut(pk.codeword_len())
-    )
-    .try_for_each(|(evals, codeword)| pk.code_instance.encode_in_place(evals, codeword))?;
+    encode_packed_rows(pk, packed_evals, &mut codewords)?;
 
     // NOTE: transpose codeword s.t., the matrix has codewords being columns
     if packed_rows > 1 {
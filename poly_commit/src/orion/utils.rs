@@ -1,5 +1,5 @@
 use arith::{ExtensionField, Field, SimdField};
-use gkr_engine::Transcript;
+use gkr_engine::{run_simd_kernel, twin_slice, twin_slice_mut, SimdKernel, Transcript};
 use itertools::izip;
 use serdes::{ExpSerde, SerdeError};
 use thiserror::Error;
@@ -206,6 +206,42 @@ pub struct OrionProof<EvalF: Field> {
     pub merkle_cap: Vec<Node>,
 }
 
+/// Encodes the rows of the packed evaluations into the codewords, on the AVX-512 twin of the
+/// packed field if the CPU supports it, see `gkr_engine::run_simd_kernel`
+#[inline(always)]
+pub(crate) fn encode_packed_rows<PackF: SimdField>(
+    pk: &OrionSRS,
+    packed_evals: &[PackF],
+    codewords: &mut [PackF],
+) -> OrionResult<()> {
+    run_simd_kernel::<PackF, _>(OrionEncoding {
+        pk,
+        packed_evals,
+        codewords,
+    })
+}
+
+struct OrionEncoding<'a, PackF> {
+    pk: &'a OrionSRS,
+    packed_evals: &'a [PackF],
+    codewords: &'a mut [PackF],
+}
+
+impl<PackF: Field> SimdKernel for OrionEncoding<'_, PackF> {
+    type Output = OrionResult<()>;
+
+    #[inline(always)]
+    fn run<G: Field>(self) -> Self::Output {
+        let packed_evals = twin_slice::<PackF, G>(self.packed_evals);
+        let codewords = twin_slice_mut::<PackF, G>(self.codewords);
+        izip!(
+            packed_evals.chunks(self.pk.message_len()),
+            codewords.chunks_mut(self.pk.codeword_len())
+        )
+        .try_for_each(|(evals, codeword)| self.pk.code_instance.encode_in_place(evals, codeword))
+    }
+}
+
 #[inline(always)]
 pub(crate) fn commit_encoded<PackF>(
     pk: &OrionSRS,
@@ -219,11 +255,7 @@ where
 
     // NOTE: packed codeword buffer and encode over packed field
     let mut codewords = vec![PackF::ZERO; packed_rows * pk.codeword_len()];
-    izip!(
-        packed_evals.chunks(pk.message_len()),
-        codewords.chunks_mut(pk.codeword_len())
-    )
-    .try_for_each(|(evals, codeword)| pk.code_instance.encode_in_place(evals, codeword))?;
+    encode_packed_rows(pk, packed_evals, &mut codewords)?;
 
     // NOTE: transpose codeword s.t., the matrix has codewords being columns
     let mut scratch = vec![PackF::ZERO; std::cmp::max(packed_rows, pk.codeword_len())];
@@ -283,14 +315,22 @@ pub(crate) fn orion_mt_verify(
 ) -> bool {
     let world_size = merkle_cap.len();
     let indices_per_merkle_cap = vk.codeword_len().next_power_of_two() / world_size;
+    let leaves_per_query = vk.num_leaves_per_mt_query();
+
+    if query_indices.len() != range_openings.len() {
+        return false;
+    }
 
     izip!(query_indices, range_openings).all(|(&qi, range_path)| {
         let index = qi % vk.codeword_len();
         let merkle_cap_index = index / indices_per_merkle_cap;
         let in_sub_tree_index = index % indices_per_merkle_cap;
 
-        range_path.verify(&merkle_cap[merkle_cap_index])
-            && in_sub_tree_index == range_path.left / range_path.leaves.len()
+        // check the range before hashing, the range and the leaves are read from the proof
+        range_path.leaves.len() == leaves_per_query
+            && range_path.left == in_sub_tree_index * leaves_per_query
+            && range_path.right == range_path.left + leaves_per_query - 1
+            && range_path.verify(&merkle_cap[merkle_cap_index])
     })
 }
 
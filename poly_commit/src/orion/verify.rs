@@ -38,6 +38,16 @@ where
     let num_vars_in_com_simd = ComPackF::PACK_SIZE.ilog2() as usize;
     let num_vars_in_msg = msg_size.ilog2() as usize;
 
+    // NOTE: the opening is read from the proof, reject it if its rows are not of the shape of
+    // the message, or if it does not have as many proximity rows as repetitions
+    let proximity_reps = vk.proximity_repetitions::<EvalF>(PCS_SOUNDNESS_BITS);
+    if proof.eval_row.len() != msg_size
+        || proof.proximity_rows.len() != proximity_reps
+        || proof.proximity_rows.iter().any(|row| row.len() != msg_size)
+    {
+        return false;
+    }
+
     // NOTE: working on evaluation response, evaluate the rest of the response
     let mut scratch = vec![EvalF::ZERO; msg_size];
     let final_eval = RefMultiLinearPoly::from_ref(&proof.eval_row).evaluate_with_buffer(
@@ -51,7 +61,6 @@ where
 
     // NOTE: working on proximity responses, draw random linear combinations
     // then draw query points from fiat shamir transcripts
-    let proximity_reps = vk.proximity_repetitions::<EvalF>(PCS_SOUNDNESS_BITS);
     let random_linear_combinations: Vec<_> = (0..proximity_reps)
         .map(|_| {
             let num_vars = point.len() - num_vars_in_msg + mpi_point.len();
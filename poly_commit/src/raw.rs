@@ -201,6 +201,11 @@ impl<C: FieldEngine> ExpanderPCS<C, C::SimdCircuitField> for RawExpanderGKR<C> {
         _transcript: &mut impl Transcript,
         _opening: &Self::Opening,
     ) -> bool {
+        // the commitment comes from the proof, it must hold all the evaluations of the hypercube
+        if commitment.evals.len() != 1 << (challenge.rz.len() + challenge.r_mpi.len()) {
+            return false;
+        }
+
         let v_target =
             C::single_core_eval_circuit_vals_at_expander_challenge(&commitment.evals, challenge);
         v == v_target
@@ -74,8 +74,10 @@ pub fn test_pcs_for_expander_gkr<
     };
     mpi_config.gather_vec(poly.hypercube_basis_ref(), &mut coeffs_gathered);
 
+    let mut deferred_checks = vec![];
     for xx in xs {
         let mut transcript_cloned = transcript.clone();
+        let mut transcript_deferred = transcript.clone();
 
         transcript.lock_proof();
         let opening = P::open(
@@ -101,9 +103,30 @@ pub fn test_pcs_for_expander_gkr<
                 xx,
                 v,
                 &mut transcript_cloned,
-                &opening.unwrap()
+                opening.as_ref().unwrap()
             ));
+            deferred_checks.push(
+                P::verify_deferred(
+                    params,
+                    &verification_key,
+                    &commitment.clone().unwrap(),
+                    xx,
+                    v,
+                    &mut transcript_deferred,
+                    &opening.unwrap(),
+                )
+                .unwrap(),
+            );
             transcript.unlock_proof();
         }
     }
+
+    if mpi_config.is_root() {
+        assert!(P::batch_check(
+            params,
+            &verification_key,
+            &deferred_checks,
+            &mut rng
+        ));
+    }
 }
@@ -0,0 +1,141 @@
+#[allow(dead_code)]
+mod common;
+
+use arith::{Field, SimdField};
+use ark_std::test_rng;
+use gkr_engine::{
+    BabyBearx16Config, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, Goldilocksx8Config,
+    MPIConfig, MPIEngine, Transcript,
+};
+use gkr_hashers::{Keccak256hasher, SHA256hasher};
+use poly_commit::*;
+use polynomials::MultiLinearPoly;
+use transcript::BytesHashTranscript;
+
+fn test_basefold_for_expander_gkr_generics<C, T>(mpi_config_ref: &MPIConfig, total_num_vars: usize)
+where
+    C: FieldEngine,
+    T: Transcript,
+    BasefoldPCSForGKR<C>: ExpanderPCS<C, C::SimdCircuitField>,
+{
+    let mut rng = test_rng();
+
+    // NOTE: generate global random polynomial
+    let num_vars_in_simd = C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+    let num_vars_in_mpi = mpi_config_ref.world_size().ilog2() as usize;
+    let num_vars_in_each_poly = total_num_vars - num_vars_in_mpi - num_vars_in_simd;
+    let num_vars_in_global_poly = total_num_vars - num_vars_in_simd;
+
+    let global_poly =
+        MultiLinearPoly::<C::SimdCircuitField>::random(num_vars_in_global_poly, &mut rng);
+
+    // NOTE generate shared challenge point in each party
+    let challenge_points: Vec<_> = (0..2)
+        .map(|_| ExpanderSingleVarChallenge::<C> {
+            r_mpi: (0..num_vars_in_mpi)
+                .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+                .collect(),
+            r_simd: (0..num_vars_in_simd)
+                .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+                .collect(),
+            rz: (0..num_vars_in_each_poly)
+                .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+                .collect(),
+        })
+        .collect();
+
+    let mut transcript = T::new();
+
+    // NOTE separate polynomial into different pieces by mpi rank
+    let poly_vars_stride = (1 << global_poly.get_num_vars()) / mpi_config_ref.world_size();
+    let poly_coeff_starts = mpi_config_ref.world_rank() * poly_vars_stride;
+    let poly_coeff_ends = poly_coeff_starts + poly_vars_stride;
+    let local_poly =
+        MultiLinearPoly::new(global_poly.coeffs[poly_coeff_starts..poly_coeff_ends].to_vec());
+
+    common::test_pcs_for_expander_gkr::<C, T, BasefoldPCSForGKR<C>>(
+        &num_vars_in_each_poly,
+        mpi_config_ref,
+        &mut transcript,
+        &local_poly,
+        &challenge_points,
+        None,
+    );
+}
+
+#[test]
+fn test_basefold_for_expander_gkr() {
+    let universe = MPIConfig::init().unwrap();
+    let world = universe.world();
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+
+    test_basefold_for_expander_gkr_generics::<
+        Goldilocksx8Config,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 16);
+
+    test_basefold_for_expander_gkr_generics::<
+        BabyBearx16Config,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 16);
+}
+
+#[test]
+fn test_basefold_rejects_wrong_evaluation() {
+    type C = Goldilocksx8Config;
+    type SimdF = <C as FieldEngine>::SimdCircuitField;
+    type EvalF = <C as FieldEngine>::ChallengeField;
+    type T = BytesHashTranscript<SHA256hasher>;
+    type P = BasefoldPCSForGKR<C>;
+
+    let mut rng = test_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let num_vars = 6;
+
+    let poly = MultiLinearPoly::<SimdF>::random(num_vars, &mut rng);
+    let point = ExpanderSingleVarChallenge::<C> {
+        r_mpi: vec![],
+        r_simd: (0..3).map(|_| EvalF::random_unsafe(&mut rng)).collect(),
+        rz: (0..num_vars)
+            .map(|_| EvalF::random_unsafe(&mut rng))
+            .collect(),
+    };
+    let eval = C::single_core_eval_circuit_vals_at_expander_challenge(&poly.coeffs, &point);
+
+    let (params, proving_key, verification_key, mut scratch_pad) =
+        expander_pcs_init_testing_only::<C, SimdF, P>(num_vars, &mpi_config);
+    let commitment =
+        P::commit(&params, &mpi_config, &proving_key, &poly, &mut scratch_pad).unwrap();
+    let opening = P::open(
+        &params,
+        &mpi_config,
+        &proving_key,
+        &poly,
+        &point,
+        &mut T::new(),
+        &scratch_pad,
+    )
+    .unwrap();
+
+    let verify = |eval: EvalF, opening: &BasefoldProof<EvalF>| {
+        P::verify(
+            &params,
+            &verification_key,
+            &commitment,
+            &point,
+            eval,
+            &mut T::new(),
+            opening,
+        )
+    };
+    assert!(verify(eval, &opening));
+    assert!(!verify(eval + EvalF::ONE, &opening));
+
+    let mut tampered = opening.clone();
+    tampered.final_evals[0] += EvalF::ONE;
+    assert!(!verify(eval, &tampered));
+
+    let mut tampered = opening.clone();
+    tampered.query_openings[0][0] = tampered.query_openings[0][1].clone();
+    assert!(!verify(eval, &tampered));
+}
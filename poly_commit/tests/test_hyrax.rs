@@ -2,10 +2,13 @@ mod common;
 
 use arith::{Field, Fr};
 use ark_std::test_rng;
-use gkr_engine::{BN254Config, ExpanderSingleVarChallenge, MPIConfig, MPIEngine, Transcript};
+use gkr_engine::{
+    BN254Config, ExpanderPCS, ExpanderSingleVarChallenge, MPIConfig, MPIEngine,
+    StructuredReferenceString, Transcript,
+};
 use gkr_hashers::Keccak256hasher;
 use halo2curves::bn256::G1Affine;
-use poly_commit::HyraxPCS;
+use poly_commit::{hiding_hyrax_prove_eval, hiding_hyrax_verify_eval, HidingHyraxPCS, HyraxPCS};
 use polynomials::MultiLinearPoly;
 use transcript::BytesHashTranscript;
 
@@ -81,3 +84,94 @@ fn test_hyrax_for_expander_gkr() {
     let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
     test_hyrax_for_expander_gkr_generics(&mpi_config, 19);
 }
+
+#[test]
+fn test_hiding_hyrax_for_expander_gkr() {
+    let mut rng = test_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let num_vars = 11;
+
+    let poly = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+    let challenge_point = ExpanderSingleVarChallenge::<BN254Config> {
+        r_mpi: Vec::new(),
+        r_simd: Vec::new(),
+        rz: (0..num_vars).map(|_| Fr::random_unsafe(&mut rng)).collect(),
+    };
+
+    let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+    common::test_pcs_for_expander_gkr::<
+        BN254Config,
+        BytesHashTranscript<Keccak256hasher>,
+        HidingHyraxPCS<G1Affine>,
+    >(
+        &num_vars,
+        &mpi_config,
+        &mut transcript,
+        &poly,
+        &[challenge_point],
+        None,
+    );
+}
+
+#[test]
+fn test_hiding_hyrax_eval_proof() {
+    type HidingPCS = HidingHyraxPCS<G1Affine>;
+
+    let mut rng = test_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    (3..=11).for_each(|num_vars| {
+        let srs =
+            <HidingPCS as ExpanderPCS<BN254Config, Fr>>::gen_srs(&num_vars, &mpi_config, &mut rng);
+        let (proving_key, verification_key) = srs.into_keys();
+        let mut scratch_pad =
+            <HidingPCS as ExpanderPCS<BN254Config, Fr>>::init_scratch_pad(&num_vars, &mpi_config);
+
+        let poly = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        let commitment = <HidingPCS as ExpanderPCS<BN254Config, Fr>>::commit(
+            &num_vars,
+            &mpi_config,
+            &proving_key,
+            &poly,
+            &mut scratch_pad,
+        )
+        .unwrap();
+
+        let eval_point: Vec<Fr> = (0..num_vars).map(|_| Fr::random_unsafe(&mut rng)).collect();
+        let eval = poly.evaluate_jolt(&eval_point);
+        let eval_blind = Fr::random_unsafe(&mut rng);
+        let eval_comm = proving_key.commit_scalar(eval, eval_blind);
+
+        let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        let proof = hiding_hyrax_prove_eval(
+            &proving_key,
+            &poly,
+            &scratch_pad.row_blinds,
+            &eval_point,
+            eval_blind,
+            &mut transcript,
+            &mut rng,
+        );
+
+        let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        assert!(hiding_hyrax_verify_eval(
+            &verification_key,
+            &commitment,
+            &eval_point,
+            &eval_comm,
+            &mut transcript,
+            &proof
+        ));
+
+        let wrong_eval_comm = verification_key.commit_scalar(eval + Fr::ONE, eval_blind);
+        let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        assert!(!hiding_hyrax_verify_eval(
+            &verification_key,
+            &commitment,
+            &eval_point,
+            &wrong_eval_comm,
+            &mut transcript,
+            &proof
+        ));
+    })
+}
@@ -0,0 +1,430 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use arith::{Field, SimdField};
+use circuit::{Circuit, LayerStats};
+use gkr::{
+    dispatch_gkr_config, dispatch_gkr_config_name, load_circuit_bytes, open_proof_envelope,
+    prover_pcs_setup, verifier_pcs_setup, verify_proof_envelope, GKRConfigVisitor, Prover,
+};
+use gkr_engine::{
+    FiatShamirHashType, FieldEngine, FieldType, GKREngine, MPIConfig, PCSSetupFileKind,
+    PCSSetupHeader, PolynomialCommitmentType, ProofEnvelope, ProofHeader,
+};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+
+use crate::ExpanderPyError;
+
+/// Runs `visitor` on a config over `field_type`, for what only depends on the field of the
+/// circuit. Every field has a Keccak256 config with the raw PCS, which adds no relay layer.
+fn dispatch_field<V: GKRConfigVisitor>(
+    field_type: &FieldType,
+    visitor: V,
+) -> Result<V::Output, ExpanderPyError> {
+    dispatch_gkr_config(
+        FiatShamirHashType::Keccak256,
+        PolynomialCommitmentType::Raw,
+        field_type.clone(),
+        visitor,
+    )
+    .ok_or_else(|| ExpanderPyError::UnsupportedConfig(format!("Field: {field_type:?}")))
+}
+
+/// The circuit flattened for each config it was used with, so that a circuit is flattened once per
+/// config rather than per call.
+#[derive(Default)]
+pub(crate) struct ConfigCache {
+    states: Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+struct ConfigState<Cfg: GKREngine> {
+    /// Cloned by each call, as loading a witness writes it into the circuit
+    circuit: Mutex<Circuit<Cfg::FieldConfig>>,
+}
+
+impl ConfigCache {
+    /// The state of config `Cfg`, flattening the serialized circuit on first use.
+    fn state<Cfg: GKREngine + 'static>(
+        &self,
+        circuit: &[u8],
+    ) -> Result<Arc<ConfigState<Cfg>>, ExpanderPyError> {
+        let mut states = self.states.lock().unwrap();
+        if let Some(state) = states.get(&TypeId::of::<Cfg>()) {
+            return Ok(state.clone().downcast().unwrap());
+        }
+
+        let state = Arc::new(ConfigState::<Cfg> {
+            circuit: Mutex::new(load_circuit_bytes::<Cfg>(circuit)?),
+        });
+        states.insert(TypeId::of::<Cfg>(), state.clone());
+        Ok(state)
+    }
+}
+
+impl<Cfg: GKREngine> ConfigState<Cfg> {
+    fn circuit(&self) -> Circuit<Cfg::FieldConfig> {
+        self.circuit.lock().unwrap().clone()
+    }
+}
+
+/// Rejects a config named `config` that is not over the field of the circuit.
+fn check_config_field<Cfg: GKREngine>(
+    config: &str,
+    field_type: &FieldType,
+) -> Result<(), ExpanderPyError> {
+    if Cfg::FieldConfig::FIELD_TYPE != *field_type {
+        return Err(ExpanderPyError::FieldMismatch {
+            config: config.to_string(),
+            config_field: format!("{:?}", Cfg::FieldConfig::FIELD_TYPE),
+            circuit_field: format!("{field_type:?}"),
+        });
+    }
+    Ok(())
+}
+
+/// Layer statistics and number of outputs expected to be zero of a serialized circuit.
+pub(crate) fn inspect(
+    configs: &ConfigCache,
+    circuit: &[u8],
+    field_type: &FieldType,
+) -> Result<(Vec<LayerStats>, usize), ExpanderPyError> {
+    dispatch_field(field_type, Inspect { configs, circuit })?
+}
+
+struct Inspect<'a> {
+    configs: &'a ConfigCache,
+    circuit: &'a [u8],
+}
+
+impl GKRConfigVisitor for Inspect<'_> {
+    type Output = Result<(Vec<LayerStats>, usize), ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        let circuit = state.circuit.lock().unwrap();
+        Ok((circuit.layer_stats(), circuit.expected_num_output_zeros))
+    }
+}
+
+/// Check that a serialized witness fits the circuit.
+pub(crate) fn check_witness(
+    configs: &ConfigCache,
+    circuit: &[u8],
+    field_type: &FieldType,
+    witness: &[u8],
+) -> Result<(), ExpanderPyError> {
+    dispatch_field(
+        field_type,
+        CheckWitness {
+            configs,
+            circuit,
+            witness,
+        },
+    )?
+}
+
+struct CheckWitness<'a> {
+    configs: &'a ConfigCache,
+    circuit: &'a [u8],
+    witness: &'a [u8],
+}
+
+impl GKRConfigVisitor for CheckWitness<'_> {
+    type Output = Result<(), ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        state.circuit.lock().unwrap().with_witness_bytes(
+            self.witness,
+            &MPIConfig::default(),
+            true,
+            false,
+        )?;
+        Ok(())
+    }
+}
+
+/// Outputs of the circuit on each piece of the witness, as little-endian integers, with random
+/// coefficients sampled afresh.
+pub(crate) fn evaluate(
+    configs: &ConfigCache,
+    circuit: &[u8],
+    field_type: &FieldType,
+    witness: &[u8],
+) -> Result<Vec<Vec<[u8; 32]>>, ExpanderPyError> {
+    dispatch_field(
+        field_type,
+        Evaluate {
+            configs,
+            circuit,
+            witness,
+        },
+    )?
+}
+
+struct Evaluate<'a> {
+    configs: &'a ConfigCache,
+    circuit: &'a [u8],
+    witness: &'a [u8],
+}
+
+impl GKRConfigVisitor for Evaluate<'_> {
+    type Output = Result<Vec<Vec<[u8; 32]>>, ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        let circuit = state.circuit.lock().unwrap().with_witness_bytes(
+            self.witness,
+            &MPIConfig::default(),
+            true,
+            false,
+        )?;
+        let mut circuit = circuit.with_fresh_rnd_coefs();
+        circuit.evaluate();
+
+        let outputs = circuit
+            .layers
+            .last()
+            .unwrap()
+            .output_vals
+            .iter()
+            .map(|v| v.unpack())
+            .collect::<Vec<_>>();
+        Ok((0..Cfg::FieldConfig::get_field_pack_size())
+            .map(|i| {
+                outputs
+                    .iter()
+                    .map(|lanes| lanes[i].to_u256().to_le_bytes())
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// The insecure testing PCS setup of the config named `config`, for a single process: the
+/// `pcs.pk` and `pcs.vk` files `expander-exec setup` would write, but derived from a fixed seed.
+pub(crate) fn insecure_testing_only_setup(
+    configs: &ConfigCache,
+    config: &str,
+    circuit: &[u8],
+    field_type: &FieldType,
+) -> Result<(Vec<u8>, Vec<u8>), ExpanderPyError> {
+    dispatch_gkr_config_name(
+        config,
+        InsecureTestingOnlySetup {
+            configs,
+            config,
+            circuit,
+            field_type,
+        },
+    )
+    .ok_or_else(|| ExpanderPyError::UnknownConfig(config.to_string()))?
+}
+
+struct InsecureTestingOnlySetup<'a> {
+    configs: &'a ConfigCache,
+    config: &'a str,
+    circuit: &'a [u8],
+    field_type: &'a FieldType,
+}
+
+impl GKRConfigVisitor for InsecureTestingOnlySetup<'_> {
+    type Output = Result<(Vec<u8>, Vec<u8>), ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        check_config_field::<Cfg>(self.config, self.field_type)?;
+
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        let n_input_vars = state.circuit.lock().unwrap().log_input_size();
+        let (_, pcs_proving_key, pcs_verification_key, _) =
+            expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSField, Cfg::PCSConfig>(
+                n_input_vars,
+                &MPIConfig::default(),
+            );
+
+        let mut pk_bytes = vec![];
+        PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::ProvingKey, n_input_vars, 1, 0)
+            .serialize_into(&mut pk_bytes)
+            .unwrap();
+        pcs_proving_key.serialize_into(&mut pk_bytes).unwrap();
+
+        let mut vk_bytes = vec![];
+        PCSSetupHeader::new::<Cfg>(PCSSetupFileKind::VerifyingKey, n_input_vars, 1, 0)
+            .serialize_into(&mut vk_bytes)
+            .unwrap();
+        pcs_verification_key.serialize_into(&mut vk_bytes).unwrap();
+
+        Ok((pk_bytes, vk_bytes))
+    }
+}
+
+/// Prove the witness with the config named `config`, with `pcs_proving_key`, the `pcs.pk` file
+/// written by `expander-exec setup` for a single process. Returns the proof envelope, in the
+/// format of `expander-exec prove`, and the serialized claimed value.
+pub(crate) fn prove(
+    configs: &ConfigCache,
+    config: &str,
+    circuit: &[u8],
+    field_type: &FieldType,
+    circuit_hash: [u8; 32],
+    witness: &[u8],
+    pcs_proving_key: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), ExpanderPyError> {
+    dispatch_gkr_config_name(
+        config,
+        Prove {
+            configs,
+            config,
+            circuit,
+            field_type,
+            circuit_hash,
+            witness,
+            pcs_proving_key,
+        },
+    )
+    .ok_or_else(|| ExpanderPyError::UnknownConfig(config.to_string()))?
+}
+
+struct Prove<'a> {
+    configs: &'a ConfigCache,
+    config: &'a str,
+    circuit: &'a [u8],
+    field_type: &'a FieldType,
+    circuit_hash: [u8; 32],
+    witness: &'a [u8],
+    pcs_proving_key: &'a [u8],
+}
+
+impl GKRConfigVisitor for Prove<'_> {
+    type Output = Result<(Vec<u8>, Vec<u8>), ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        check_config_field::<Cfg>(self.config, self.field_type)?;
+
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        let mpi_config = MPIConfig::default();
+        let mut circuit = state.circuit.lock().unwrap().with_witness_bytes(
+            self.witness,
+            &mpi_config,
+            true,
+            false,
+        )?;
+
+        let (pcs_params, pcs_proving_key, mut pcs_scratch) =
+            prover_pcs_setup::<Cfg>(circuit.log_input_size(), &mpi_config, self.pcs_proving_key)?;
+        let mut prover = Prover::<Cfg>::new(mpi_config);
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove(
+            &mut circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        );
+
+        let mut claimed_v_bytes = vec![];
+        claimed_v.serialize_into(&mut claimed_v_bytes).unwrap();
+        let envelope = ProofEnvelope {
+            header: ProofHeader::new::<Cfg>(1, self.circuit_hash),
+            proof,
+            claimed_v,
+        };
+        let mut proof_bytes = vec![];
+        envelope.serialize_into(&mut proof_bytes).unwrap();
+        Ok((proof_bytes, claimed_v_bytes))
+    }
+}
+
+/// Verify a proof envelope against the public inputs of a serialized witness, with
+/// `pcs_verifying_key`, the `pcs.vk` file written by `expander-exec setup`, picking the config
+/// from the proof header.
+pub(crate) fn verify(
+    configs: &ConfigCache,
+    circuit: &[u8],
+    circuit_hash: [u8; 32],
+    public_input: &[u8],
+    proof: &[u8],
+    pcs_verifying_key: &[u8],
+) -> Result<bool, ExpanderPyError> {
+    let header = ProofHeader::deserialize_from(proof)
+        .map_err(|e| ExpanderPyError::ProofMismatch(e.to_string()))?;
+
+    dispatch_gkr_config(
+        header.fiat_shamir_hash_type.clone(),
+        header.pcs_type.clone(),
+        header.field_type.clone(),
+        Verify {
+            configs,
+            circuit,
+            circuit_hash,
+            public_input,
+            proof,
+            pcs_verifying_key,
+        },
+    )
+    .unwrap_or_else(|| {
+        Err(ExpanderPyError::UnsupportedConfig(format!(
+            "FS: {:?}, PCS: {:?}, Field: {:?}",
+            header.fiat_shamir_hash_type, header.pcs_type, header.field_type
+        )))
+    })
+}
+
+struct Verify<'a> {
+    configs: &'a ConfigCache,
+    circuit: &'a [u8],
+    circuit_hash: [u8; 32],
+    public_input: &'a [u8],
+    proof: &'a [u8],
+    pcs_verifying_key: &'a [u8],
+}
+
+impl GKRConfigVisitor for Verify<'_> {
+    type Output = Result<bool, ExpanderPyError>;
+
+    fn visit<Cfg>(self) -> Self::Output
+    where
+        Cfg: GKREngine + 'static,
+        Cfg::FieldConfig: FieldEngine<SimdCircuitField = Cfg::PCSField>,
+    {
+        let envelope = open_proof_envelope::<Cfg>(self.proof, &self.circuit_hash)?;
+        let state = self.configs.state::<Cfg>(self.circuit)?;
+        let circuit = state.circuit();
+
+        let (pcs_params, pcs_verification_key) = verifier_pcs_setup::<Cfg>(
+            circuit.log_input_size(),
+            envelope.header.proving_time_mpi_size,
+            self.pcs_verifying_key,
+        )?;
+        Ok(verify_proof_envelope::<Cfg>(
+            &circuit,
+            envelope,
+            self.public_input,
+            &pcs_params,
+            &pcs_verification_key,
+        )?)
+    }
+}
@@ -0,0 +1,71 @@
+use circuit::WitnessLoadError;
+use gkr::ProofInputError;
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    PyErr,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExpanderPyError {
+    #[error("unknown config: {0}")]
+    UnknownConfig(String),
+
+    #[error("unsupported config: {0}")]
+    UnsupportedConfig(String),
+
+    #[error("config {config} is over {config_field}, the circuit is over {circuit_field}")]
+    FieldMismatch {
+        config: String,
+        config_field: String,
+        circuit_field: String,
+    },
+
+    #[error("malformed circuit: {0}")]
+    MalformedCircuit(String),
+
+    #[error("malformed witness: {0}")]
+    MalformedWitness(String),
+
+    #[error("no witness loaded")]
+    NoWitness,
+
+    /// The PCS key does not deserialize or was generated for another config or circuit
+    #[error("PCS setup mismatch: {0}")]
+    PcsSetupMismatch(String),
+
+    #[error("proof mismatch: {0}")]
+    ProofMismatch(String),
+
+    #[error("malformed proof: {0}")]
+    MalformedProof(String),
+}
+
+impl From<ProofInputError> for ExpanderPyError {
+    fn from(e: ProofInputError) -> Self {
+        match e {
+            ProofInputError::UnsupportedConfig(msg) => Self::UnsupportedConfig(msg),
+            ProofInputError::MalformedCircuit(msg) => Self::MalformedCircuit(msg),
+            ProofInputError::InvalidWitness(e) => Self::MalformedWitness(e.to_string()),
+            ProofInputError::PcsSetupMismatch(msg) => Self::PcsSetupMismatch(msg),
+            ProofInputError::ProofMismatch(msg) => Self::ProofMismatch(msg),
+            ProofInputError::MalformedProof(e) => Self::MalformedProof(e.to_string()),
+        }
+    }
+}
+
+impl From<WitnessLoadError> for ExpanderPyError {
+    fn from(e: WitnessLoadError) -> Self {
+        Self::MalformedWitness(e.to_string())
+    }
+}
+
+/// Missing witnesses are a misuse of the API, anything else is a bad argument.
+impl From<ExpanderPyError> for PyErr {
+    fn from(e: ExpanderPyError) -> Self {
+        match e {
+            ExpanderPyError::NoWitness => PyRuntimeError::new_err(e.to_string()),
+            e => PyValueError::new_err(e.to_string()),
+        }
+    }
+}
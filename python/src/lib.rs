@@ -0,0 +1,257 @@
+//! Python bindings of Expander, to load circuits, inspect them, prove and verify from Python
+//! without going through `expander-exec`.
+//!
+//! ```python
+//! import expander
+//!
+//! circuit = expander.Circuit.load("data/circuit_m31.txt")
+//! circuit.load_witness(open("data/witness_m31.txt", "rb").read())
+//! # the setup directory written by `expander-exec -f SHA256 -p Orion setup`
+//! pcs_proving_key = open("pcs_setup/pcs.pk", "rb").read()
+//! pcs_verifying_key = open("pcs_setup/pcs.vk", "rb").read()
+//! proof, claimed_v = circuit.prove("M31x16ConfigSha2OrionVanilla", pcs_proving_key)
+//! assert circuit.verify(proof, pcs_verifying_key)
+//! ```
+//!
+//! Circuits, witnesses and PCS keys are the files `expander-exec` reads, and proofs are in the
+//! format of `expander-exec prove`, proven by a single process. The configs are the vanilla
+//! configs of `expander-exec`, listed in `expander.CONFIG_NAMES`.
+//!
+//! The module is built with [maturin](https://www.maturin.rs), see `pyproject.toml`.
+
+mod configs;
+mod errors;
+
+use std::{fs, path::PathBuf};
+
+use circuit::LayerStats;
+use configs::ConfigCache;
+use gkr::{detect_field_type, GKR_CONFIG_NAMES};
+use gkr_engine::FieldType;
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
+use pyo3::{
+    prelude::*,
+    types::{PyBytes, PyDict, PyLong},
+};
+
+pub use errors::*;
+
+/// A circuit, loaded from a circuit file, with the witness last loaded into it.
+///
+/// The circuit is flattened the first time a config is used, and reused by the later calls with
+/// the same config.
+#[pyclass(module = "expander")]
+pub struct Circuit {
+    circuit: Vec<u8>,
+    field_type: FieldType,
+    circuit_hash: [u8; 32],
+    layer_stats: Vec<LayerStats>,
+    expected_num_output_zeros: usize,
+    witness: Option<Vec<u8>>,
+    configs: ConfigCache,
+}
+
+#[pymethods]
+impl Circuit {
+    /// Load a circuit file, e.g. `circuit.txt` written by the compiler. The field of the circuit
+    /// is read from its header.
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Same as `load`, from the bytes of a circuit file.
+    #[staticmethod]
+    fn from_bytes(circuit: &[u8]) -> PyResult<Self> {
+        let field_type = detect_field_type(circuit).ok_or_else(|| {
+            ExpanderPyError::MalformedCircuit("truncated header or unknown field".to_string())
+        })?;
+        let configs = ConfigCache::default();
+        let (layer_stats, expected_num_output_zeros) =
+            configs::inspect(&configs, circuit, &field_type)?;
+
+        let mut circuit_hash = [0u8; 32];
+        SHA256hasher::new().hash(&mut circuit_hash, circuit);
+
+        Ok(Self {
+            circuit: circuit.to_vec(),
+            field_type,
+            circuit_hash,
+            layer_stats,
+            expected_num_output_zeros,
+            witness: None,
+            configs,
+        })
+    }
+
+    /// Field of the circuit, e.g. "M31x16".
+    #[getter]
+    fn field(&self) -> String {
+        format!("{:?}", self.field_type)
+    }
+
+    /// SHA256 hash of the circuit file, as recorded in the header of its proofs.
+    #[getter]
+    fn circuit_hash<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.circuit_hash)
+    }
+
+    /// Number of outputs, counted from the first one, that are zero on a satisfying witness.
+    #[getter]
+    fn expected_num_output_zeros(&self) -> usize {
+        self.expected_num_output_zeros
+    }
+
+    /// Load a witness, in the format of `witness.txt`, replacing the previous one. Raises
+    /// `ValueError` if the witness does not fit the circuit.
+    fn load_witness(&mut self, py: Python<'_>, witness: &[u8]) -> PyResult<()> {
+        py.allow_threads(|| {
+            configs::check_witness(&self.configs, &self.circuit, &self.field_type, witness)
+        })?;
+        self.witness = Some(witness.to_vec());
+        Ok(())
+    }
+
+    /// Statistics of each layer, from the input layer to the output layer, as printed by
+    /// `expander-exec inspect`. `num_uni` maps each uni gate type to its number of gates.
+    fn layer_stats<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.layer_stats
+            .iter()
+            .map(|stats| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("input_var_num", stats.input_var_num)?;
+                dict.set_item("output_var_num", stats.output_var_num)?;
+                dict.set_item("num_mul", stats.num_mul)?;
+                dict.set_item("num_add", stats.num_add)?;
+                dict.set_item("num_const", stats.num_const)?;
+                dict.set_item("num_uni", stats.num_uni.clone())?;
+                dict.set_item("num_random_coefs", stats.num_random_coefs)?;
+                dict.set_item("num_public_input_gates", stats.num_public_input_gates)?;
+                dict.set_item("skip_sumcheck_phase_two", stats.skip_sumcheck_phase_two)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Evaluate the circuit on the loaded witness. `outputs[i][j]` is output `j` on piece `i` of
+    /// the witness, as an integer. Gates with a random coefficient use coefficients sampled
+    /// afresh, as when checking a witness, so outputs combined with them are only meaningful
+    /// when zero.
+    fn evaluate<'py>(&self, py: Python<'py>) -> PyResult<Vec<Vec<Bound<'py, PyAny>>>> {
+        let witness = self.witness.as_ref().ok_or(ExpanderPyError::NoWitness)?;
+        let outputs = py.allow_threads(|| {
+            configs::evaluate(&self.configs, &self.circuit, &self.field_type, witness)
+        })?;
+
+        let int = py.get_type_bound::<PyLong>();
+        let to_int =
+            |v: &[u8; 32]| int.call_method1("from_bytes", (PyBytes::new_bound(py, v), "little"));
+        outputs
+            .iter()
+            .map(|piece| piece.iter().map(to_int).collect())
+            .collect()
+    }
+
+    /// The insecure testing PCS setup of the config named `config_name`, as the bytes of the
+    /// `pcs.pk` and `pcs.vk` files `expander-exec setup` writes for a single process. The setup
+    /// is derived from a fixed seed, so proofs can be forged against it unless the PCS is `Raw`,
+    /// which has no setup: it is only meant for testing.
+    fn insecure_testing_only_setup<'py>(
+        &self,
+        py: Python<'py>,
+        config_name: &str,
+    ) -> PyResult<(Bound<'py, PyBytes>, Bound<'py, PyBytes>)> {
+        let (pcs_proving_key, pcs_verifying_key) = py.allow_threads(|| {
+            configs::insecure_testing_only_setup(
+                &self.configs,
+                config_name,
+                &self.circuit,
+                &self.field_type,
+            )
+        })?;
+        Ok((
+            PyBytes::new_bound(py, &pcs_proving_key),
+            PyBytes::new_bound(py, &pcs_verifying_key),
+        ))
+    }
+
+    /// Prove the loaded witness with the config named `config_name`, one of
+    /// `expander.CONFIG_NAMES`, with `pcs_proving_key`, the `pcs.pk` file written by
+    /// `expander-exec setup` for a single process. Returns the proof, in the format of
+    /// `expander-exec prove`, and the serialized claimed value of the output layer.
+    fn prove<'py>(
+        &self,
+        py: Python<'py>,
+        config_name: &str,
+        pcs_proving_key: &[u8],
+    ) -> PyResult<(Bound<'py, PyBytes>, Bound<'py, PyBytes>)> {
+        let witness = self.witness.as_ref().ok_or(ExpanderPyError::NoWitness)?;
+        let (proof, claimed_v) = py.allow_threads(|| {
+            configs::prove(
+                &self.configs,
+                config_name,
+                &self.circuit,
+                &self.field_type,
+                self.circuit_hash,
+                witness,
+                pcs_proving_key,
+            )
+        })?;
+        Ok((
+            PyBytes::new_bound(py, &proof),
+            PyBytes::new_bound(py, &claimed_v),
+        ))
+    }
+
+    /// Verify a proof of the circuit, with `pcs_verifying_key`, the `pcs.vk` file written by
+    /// `expander-exec setup`, against the public inputs of `public_input`, a witness in the format
+    /// of `witness.txt`, by default the loaded witness. The config is read from the proof header.
+    ///
+    /// Raises `ValueError` if the proof does not deserialize, including a GKR proof that is
+    /// truncated or has trailing bytes, or is for another config or circuit, or if the PCS key is
+    /// not the one of the config and circuit, and returns whether it passes the checks of the
+    /// verifier otherwise.
+    #[pyo3(signature = (proof, pcs_verifying_key, public_input = None))]
+    fn verify(
+        &self,
+        py: Python<'_>,
+        proof: &[u8],
+        pcs_verifying_key: &[u8],
+        public_input: Option<&[u8]>,
+    ) -> PyResult<bool> {
+        let public_input = match public_input {
+            Some(public_input) => public_input,
+            None => self.witness.as_deref().ok_or(ExpanderPyError::NoWitness)?,
+        };
+        Ok(py.allow_threads(|| {
+            configs::verify(
+                &self.configs,
+                &self.circuit,
+                self.circuit_hash,
+                public_input,
+                proof,
+                pcs_verifying_key,
+            )
+        })?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Circuit(field={:?}, layers={}, witness={})",
+            self.field_type,
+            self.layer_stats.len(),
+            if self.witness.is_some() {
+                "loaded"
+            } else {
+                "none"
+            },
+        )
+    }
+}
+
+#[pymodule]
+fn expander(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Circuit>()?;
+    m.add("CONFIG_NAMES", GKR_CONFIG_NAMES.to_vec())?;
+    Ok(())
+}
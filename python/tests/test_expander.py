@@ -0,0 +1,122 @@
+import os
+
+import pytest
+
+import expander
+
+SCRIPTS_DIR = os.path.join(os.path.dirname(__file__), "..", "..", "scripts")
+CIRCUIT = os.path.join(SCRIPTS_DIR, "small_circuit_m31.circ")
+WITNESS = os.path.join(SCRIPTS_DIR, "small_witness_m31.circ")
+
+M31_MODULUS = 2**31 - 1
+
+
+def read_witness():
+    with open(WITNESS, "rb") as f:
+        return f.read()
+
+
+@pytest.fixture
+def circuit():
+    circuit = expander.Circuit.load(CIRCUIT)
+    circuit.load_witness(read_witness())
+    return circuit
+
+
+def test_load_and_inspect():
+    circuit = expander.Circuit.load(CIRCUIT)
+    assert circuit.field == "M31x16"
+    assert len(circuit.circuit_hash) == 32
+
+    stats = circuit.layer_stats()
+    assert len(stats) > 0
+    for layer, next_layer in zip(stats, stats[1:]):
+        assert layer["output_var_num"] == next_layer["input_var_num"]
+    assert sum(layer["num_mul"] + layer["num_add"] for layer in stats) > 0
+
+
+def test_evaluate(circuit):
+    outputs = circuit.evaluate()
+    # one piece of witness per SIMD lane
+    assert len(outputs) == 16
+    for piece in outputs:
+        assert all(0 <= v < M31_MODULUS for v in piece)
+        assert all(v == 0 for v in piece[: circuit.expected_num_output_zeros])
+
+
+def test_prove_and_verify(circuit):
+    assert "M31x16ConfigKeccakRawVanilla" in expander.CONFIG_NAMES
+    pk, vk = circuit.insecure_testing_only_setup("M31x16ConfigKeccakRawVanilla")
+    proof, claimed_v = circuit.prove("M31x16ConfigKeccakRawVanilla", pk)
+    assert len(claimed_v) > 0
+
+    assert circuit.verify(proof, vk)
+    assert circuit.verify(proof, vk, public_input=read_witness())
+
+    tampered = bytearray(proof)
+    tampered[len(proof) // 2] ^= 1
+    assert not circuit.verify(bytes(tampered), vk)
+
+    with pytest.raises(ValueError, match="proof mismatch"):
+        circuit.verify(proof[: len(proof) // 2], vk)
+    with pytest.raises(ValueError, match="malformed proof"):
+        circuit.verify(with_trailing_byte(proof, claimed_v), vk)
+
+    # the circuit flattened for the config is reused by later calls
+    proof, _ = circuit.prove("M31x16ConfigKeccakRawVanilla", pk)
+    assert circuit.verify(proof, vk)
+
+
+def test_pcs_keys_of_another_config(circuit):
+    pk, vk = circuit.insecure_testing_only_setup("M31x16ConfigKeccakRawVanilla")
+    _, orion_vk = circuit.insecure_testing_only_setup("M31x16ConfigSha2OrionVanilla")
+
+    with pytest.raises(ValueError, match="PCS setup mismatch"):
+        circuit.prove("M31x16ConfigSha2OrionVanilla", pk)
+    with pytest.raises(ValueError, match="PCS setup mismatch"):
+        circuit.prove("M31x16ConfigKeccakRawVanilla", vk)
+
+    proof, _ = circuit.prove("M31x16ConfigKeccakRawVanilla", pk)
+    with pytest.raises(ValueError, match="PCS setup mismatch"):
+        circuit.verify(proof, orion_vk)
+    with pytest.raises(ValueError, match="PCS setup mismatch"):
+        circuit.verify(proof, b"")
+
+
+def with_trailing_byte(proof, claimed_v):
+    """The proof with a byte appended to its GKR proof, which the verifier rejects."""
+    # the envelope ends with the GKR proof, prefixed by its length as a u64, and the claimed value
+    end = len(proof) - len(claimed_v)
+    start = next(
+        i
+        for i in range(end - 8)
+        if int.from_bytes(proof[i : i + 8], "little") == end - i - 8
+    )
+    length = (end - start - 8 + 1).to_bytes(8, "little")
+    return proof[:start] + length + proof[start + 8 : end] + b"\0" + proof[end:]
+
+
+def test_errors():
+    circuit = expander.Circuit.load(CIRCUIT)
+    pk, _ = circuit.insecure_testing_only_setup("M31x16ConfigKeccakRawVanilla")
+    with pytest.raises(RuntimeError, match="no witness loaded"):
+        circuit.prove("M31x16ConfigKeccakRawVanilla", pk)
+    with pytest.raises(RuntimeError, match="no witness loaded"):
+        circuit.evaluate()
+
+    witness = read_witness()
+    with pytest.raises(ValueError, match="malformed witness"):
+        circuit.load_witness(witness[: len(witness) // 2])
+
+    circuit.load_witness(witness)
+    with pytest.raises(ValueError, match="unknown config"):
+        circuit.prove("M31x16ConfigSha3RawVanilla", pk)
+    with pytest.raises(ValueError, match="the circuit is over M31x16"):
+        circuit.prove("BN254ConfigSha2Raw", pk)
+    with pytest.raises(ValueError, match="the circuit is over M31x16"):
+        circuit.insecure_testing_only_setup("BN254ConfigSha2Raw")
+
+    with pytest.raises(ValueError, match="malformed circuit"):
+        expander.Circuit.from_bytes(b"\0" * 8)
+    with pytest.raises(OSError):
+        expander.Circuit.load(os.path.join(SCRIPTS_DIR, "missing.circ"))
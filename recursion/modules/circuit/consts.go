@@ -12,3 +12,20 @@ const LEADING_FIELD_BYTES uint = 32
 // BN254 and M31 modulus reading the proof bytes in their own units of
 // field bytes.
 const RAW_COMMITMENT_LENGTH_BYTES uint = 32
+
+// PROOF_HEADER_BYTES is the length of the header in front of a proof
+// written by expander-exec: magic (8), version (4), field, hash, PCS and
+// GKR scheme enums (4 each), proving time MPI size (8) and circuit hash (32)
+const PROOF_HEADER_BYTES uint = 68
+
+// PROOF_ENVELOPE_MAGIC is b"EXPPROOF" in little endian, the first field of
+// the proof header
+const PROOF_ENVELOPE_MAGIC uint64 = 0x464f4f5250505845
+
+// PROOF_ENVELOPE_VERSION is the proof header version whose transcript absorbs
+// the public input and the claimed output of the circuit
+const PROOF_ENVELOPE_VERSION uint32 = 2
+
+// PROOF_ENVELOPE_LEGACY_VERSION is the last proof header version whose
+// transcript is bound to the PCS commitment only
+const PROOF_ENVELOPE_LEGACY_VERSION uint32 = 1
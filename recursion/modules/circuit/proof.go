@@ -9,6 +9,11 @@ import (
 type Proof struct {
 	Idx   uint
 	Elems []frontend.Variable
+
+	// BindsStatement tells if the transcript absorbs the public input and
+	// the claimed output of the circuit, i.e., for proof header versions
+	// after PROOF_ENVELOPE_LEGACY_VERSION
+	BindsStatement bool
 }
 
 func (p *Proof) Next() frontend.Variable {
@@ -34,8 +39,9 @@ func (p *Proof) Reset() {
 
 func (p *Proof) PlaceHolder() *Proof {
 	return &Proof{
-		Idx:   0,
-		Elems: make([]frontend.Variable, len(p.Elems)),
+		Idx:            0,
+		Elems:          make([]frontend.Variable, len(p.Elems)),
+		BindsStatement: p.BindsStatement,
 	}
 }
 
@@ -43,6 +49,7 @@ func NewRandomProof(n_elems uint) *Proof {
 	var proof = Proof{}
 
 	proof.Idx = 0
+	proof.BindsStatement = true
 	for i := uint(0); i < n_elems; i++ {
 		proof.Elems = append(proof.Elems, uint(123456789))
 	}
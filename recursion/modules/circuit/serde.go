@@ -39,6 +39,12 @@ func (buf *InputBuf) ReadUint64() uint64 {
 	return x
 }
 
+func (buf *InputBuf) ReadUint32() uint32 {
+	x := binary.LittleEndian.Uint32(buf.data[:4])
+	buf.Step(4)
+	return x
+}
+
 func (buf *InputBuf) ReadUint() uint {
 	return uint(buf.ReadUint64())
 }
@@ -282,6 +288,21 @@ func (buf *InputBuf) ReadWitness() (witness *Witness, err error) {
 func (buf *InputBuf) ReadProof() (proof *Proof, err error) {
 	var elem frontend.Variable
 	elems := make([]frontend.Variable, 0)
+	if buf.Len() < PROOF_HEADER_BYTES {
+		err = fmt.Errorf("Truncated proof header")
+		return
+	}
+	headerBuf := InputBuf{data: buf.data[:PROOF_HEADER_BYTES]}
+	buf.Step(PROOF_HEADER_BYTES)
+	if headerBuf.ReadUint64() != PROOF_ENVELOPE_MAGIC {
+		err = fmt.Errorf("Not an expander proof")
+		return
+	}
+	version := headerBuf.ReadUint32()
+	if version != PROOF_ENVELOPE_VERSION && version != PROOF_ENVELOPE_LEGACY_VERSION {
+		err = fmt.Errorf("Unsupported proof format version %d", version)
+		return
+	}
 	_ = buf.ReadUint64()
 	for buf.Len() > 0 {
 		if elem, err = buf.ReadField(); err != nil {
@@ -290,8 +311,9 @@ func (buf *InputBuf) ReadProof() (proof *Proof, err error) {
 		elems = append(elems, elem)
 	}
 	proof = &Proof{
-		Idx:   0,
-		Elems: elems,
+		Idx:            0,
+		Elems:          elems,
+		BindsStatement: version != PROOF_ENVELOPE_LEGACY_VERSION,
 	}
 	return
 }
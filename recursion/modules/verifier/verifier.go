@@ -205,6 +205,11 @@ func GKRVerify(
 		rMPI = append(rMPI, fsTranscript.ChallengeF())
 	}
 
+	// NOTE: the claimed output is absorbed right after the output challenge
+	if proof.BindsStatement {
+		fsTranscript.AppendFs(claimed_v...)
+	}
+
 	var alpha []frontend.Variable = nil
 	claimedV0 = claimed_v
 	claimedV1 = nil
@@ -241,6 +246,29 @@ func GKRVerify(
 	return
 }
 
+// AbsorbPublicInput appends the public inputs of all the MPI processes, in
+// rank order, to the transcript, with the SIMD lanes of each public input
+// next to each other as in its serialization
+func AbsorbPublicInput(
+	api fields.ArithmeticEngine,
+	public_input [][]frontend.Variable,
+	mpiSize uint,
+	fsTranscript *transcript.FieldHasherTranscript,
+) {
+	simdSize := int(api.SIMDPackSize())
+	if len(public_input) != int(mpiSize)*simdSize {
+		panic("Incompatible n_witnesses with mpi and simd size")
+	}
+
+	for i := 0; i < int(mpiSize); i++ {
+		for j := range public_input[i*simdSize] {
+			for k := 0; k < simdSize; k++ {
+				fsTranscript.AppendF(public_input[i*simdSize+k][j])
+			}
+		}
+	}
+}
+
 func Verify(
 	api fields.ArithmeticEngine,
 	fieldEnum fields.ECCFieldEnum,
@@ -269,6 +297,12 @@ func Verify(
 	}
 	fsTranscript.ResetCount()
 
+	// NOTE: the public inputs are absorbed right after the commitment, in the
+	// order of the MPI processes, each public input being a SIMD element
+	if proof.BindsStatement {
+		AbsorbPublicInput(api, public_input, mpiSize, fsTranscript)
+	}
+
 	originalCircuit.FillRndCoef(fsTranscript)
 
 	// NOTE: MPI Fiat-Shamir sync randomness
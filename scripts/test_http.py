@@ -54,3 +54,23 @@ if __name__ == '__main__':
     # check 400
     assert response.status_code == 400, f"Failed to detect invalid witness length: {response.text}"
     print("Invalid witness length detected successfully")
+
+    # prove asynchronously
+    response = requests.post(url+"/circuits/default/jobs", data=witness)
+    assert response.status_code == 202, f"Failed to submit job: {response.text}"
+    job_id = response.text
+    import time
+    while True:
+        status = requests.get(url+"/jobs/"+job_id).json()
+        if status["status"] in ("done", "failed"):
+            break
+        time.sleep(0.1)
+    assert status["status"] == "done", f"Job failed: {status}"
+    response = requests.get(url+"/jobs/"+job_id+"/proof")
+    assert response.status_code == 200
+    print("Job proof fetched successfully, length:", len(response.content))
+
+    # unknown circuits and jobs
+    assert requests.post(url+"/circuits/unknown/jobs", data=witness).status_code == 404
+    assert requests.get(url+"/jobs/123456789").status_code == 404
+    print(requests.get(url+"/metrics").text)
@@ -116,7 +116,7 @@ def gkr_prove(proof_config: ProofConfig, mpi_config: MPIConfig) -> str:
     prove_command_suffix: str = \
         f"./target/release/expander-exec \
         -f {proof_config.fs_hash_scheme} -p {proof_config.pcs_scheme} \
-        prove -c {proof_config.circuit} -w {proof_config.witness} -o {proof_file}"
+        prove -c {proof_config.circuit} -w {proof_config.witness} -o {proof_file} --insecure-testing-setup"
 
     prove_command: str = ' '.join(f"{mpi_config.mpi_prefix()} {prove_command_suffix}".split())
     print(prove_command)
@@ -135,7 +135,7 @@ def vanilla_gkr_verify_check(
     vanilla_verify_comand: str = \
         f"./target/release/expander-exec \
         -f {proof_config.fs_hash_scheme} -p {proof_config.pcs_scheme} \
-        verify -c {proof_config.circuit} -w {proof_config.witness} -i {proof_path} -m {mpi_config.cpus()}"
+        verify -c {proof_config.circuit} -w {proof_config.witness} -i {proof_path} -m {mpi_config.cpus()} --insecure-testing-setup"
     vanilla_verify_comand = ' '.join(vanilla_verify_comand.split())
     print(vanilla_verify_comand)
 
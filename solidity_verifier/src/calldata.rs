@@ -0,0 +1,48 @@
+use gkr_engine::Proof;
+use halo2curves::{bn256::Fr, ff::PrimeField};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Signature of the entry point of the generated contract.
+pub const VERIFY_SIGNATURE: &str = "verify(bytes,uint256[],uint256)";
+
+/// A 32-byte big endian ABI word.
+fn word(x: usize) -> [u8; 32] {
+    let mut res = [0u8; 32];
+    res[24..].copy_from_slice(&(x as u64).to_be_bytes());
+    res
+}
+
+fn field_word(x: &Fr) -> [u8; 32] {
+    let mut res = [0u8; 32];
+    res.copy_from_slice(x.to_repr().as_ref());
+    res.reverse();
+    res
+}
+
+/// ABI-encode a call to `verify(proof, public_input, claimed_v)` of the contract generated by
+/// `generate_solidity_verifier`. The proof bytes are passed as they are.
+pub fn encode_verify_calldata(proof: &Proof, public_input: &[Fr], claimed_v: &Fr) -> Vec<u8> {
+    let mut selector = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(VERIFY_SIGNATURE.as_bytes());
+    keccak.finalize(&mut selector);
+
+    let proof_len_padded = proof.bytes.len().div_ceil(32) * 32;
+    let proof_offset = 3 * 32;
+    let public_input_offset = proof_offset + 32 + proof_len_padded;
+
+    let mut res = selector[..4].to_vec();
+    res.extend_from_slice(&word(proof_offset));
+    res.extend_from_slice(&word(public_input_offset));
+    res.extend_from_slice(&field_word(claimed_v));
+
+    res.extend_from_slice(&word(proof.bytes.len()));
+    res.extend_from_slice(&proof.bytes);
+    res.resize(4 + public_input_offset, 0);
+
+    res.extend_from_slice(&word(public_input.len()));
+    public_input
+        .iter()
+        .for_each(|x| res.extend_from_slice(&field_word(x)));
+    res
+}
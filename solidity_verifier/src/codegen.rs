@@ -0,0 +1,444 @@
+//! Generation of a verifier contract specialized to a circuit.
+//!
+//! The circuit independent parts of the verifier live in `src/solidity`, and are pasted into the
+//! contract as they are. This module emits the parts that depend on the circuit, the verifying key
+//! and the Fiat-Shamir hash: the constants, the hash function of the transcript, the gates of each
+//! layer and the sequence of layers in `verify`.
+
+use std::fmt::Write;
+
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use gkr_engine::{BN254Config, FiatShamirHashType, GKREngine, GKRScheme};
+use gkr_hashers::generate_mimc_constants;
+use halo2curves::{
+    bn256::{Bn256, Fr, G2Affine},
+    ff::{Field, PrimeField},
+    group::prime::PrimeCurveAffine,
+};
+use poly_commit::{BiKZGVerifierParam, HyperKZGPCS};
+
+use crate::SolidityVerifierError;
+
+const COMMON: &str = include_str!("solidity/common.sol");
+const HYPER_KZG: &str = include_str!("solidity/hyper_kzg.sol");
+
+/// Generate the source of a contract `ExpanderVerifier` that verifies proofs of `circuit`,
+/// produced with the config `Cfg` and a single process.
+///
+/// The circuit is expected to be pre-processed for GKR, i.e., as returned by
+/// `Circuit::verifier_load_circuit`. The contract exposes
+///
+/// ```solidity
+/// function verify(bytes calldata proof, uint256[] calldata publicInput, uint256 claimedV)
+///     external view returns (bool);
+/// ```
+///
+/// which returns true iff the proof verifies, and reverts on proofs that fail to parse.
+pub fn generate_solidity_verifier<Cfg>(
+    circuit: &Circuit<BN254Config>,
+    vk: &BiKZGVerifierParam<Bn256>,
+) -> Result<String, SolidityVerifierError>
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, PCSConfig = HyperKZGPCS<Bn256>>,
+{
+    if Cfg::SCHEME != GKRScheme::Vanilla {
+        return Err(SolidityVerifierError::UnsupportedConfig(format!(
+            "GKR scheme {:?}",
+            Cfg::SCHEME
+        )));
+    }
+    let hasher = match Cfg::FIAT_SHAMIR_HASH {
+        FiatShamirHashType::Keccak256 => keccak_hasher(),
+        FiatShamirHashType::MIMC5 => mimc5_hasher(),
+        hash => {
+            return Err(SolidityVerifierError::UnsupportedConfig(format!(
+                "Fiat-Shamir hash {hash:?}"
+            )))
+        }
+    };
+    check_circuit(circuit)?;
+
+    let mut out = String::new();
+    out.push_str("// SPDX-License-Identifier: AGPL-3.0\n");
+    out.push_str("// This file is generated by the `solidity_verifier` crate of Expander.\n");
+    out.push_str("pragma solidity ^0.8.20;\n\n");
+    out.push_str("contract ExpanderVerifier {\n");
+    out.push_str(&constants(circuit, vk));
+    out.push('\n');
+    out.push_str(COMMON);
+    out.push('\n');
+    out.push_str(&hasher);
+    out.push('\n');
+    out.push_str(HYPER_KZG);
+    out.push('\n');
+    out.push_str(&layers(circuit));
+    out.push('\n');
+    out.push_str(&verify_fn(circuit));
+    out.push_str("}\n");
+    Ok(out)
+}
+
+fn check_circuit(circuit: &Circuit<BN254Config>) -> Result<(), SolidityVerifierError> {
+    if circuit.layers.is_empty() {
+        return Err(SolidityVerifierError::UnsupportedCircuit(
+            "circuit has no layers".to_string(),
+        ));
+    }
+    if let Some(i) = circuit.layers.iter().position(|l| !l.uni.is_empty()) {
+        return Err(SolidityVerifierError::UnsupportedCircuit(format!(
+            "layer {i} has uni gates"
+        )));
+    }
+    // with KZG, `pre_process_gkr` relays the input layer so that it is opened at a single point
+    if !circuit.layers[0].structure_info.skip_sumcheck_phase_two {
+        return Err(SolidityVerifierError::UnsupportedCircuit(
+            "the input layer has two claims, is the circuit pre-processed?".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A field element as a solidity literal.
+fn uint256<F: PrimeField>(x: &F) -> String {
+    let mut be = x.to_repr().as_ref().to_vec();
+    be.reverse();
+    let hex = be.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    match hex.trim_start_matches('0') {
+        "" => "0".to_string(),
+        hex => format!("0x{hex}"),
+    }
+}
+
+fn constants(circuit: &Circuit<BN254Config>, vk: &BiKZGVerifierParam<Bn256>) -> String {
+    let num_rnd_coefs = circuit
+        .layers
+        .iter()
+        .map(|l| {
+            let mul = l.mul.iter().filter(|g| g.coef_type == CoefType::Random);
+            let add = l.add.iter().filter(|g| g.coef_type == CoefType::Random);
+            let cst = l.const_.iter().filter(|g| g.coef_type == CoefType::Random);
+            mul.count() + add.count() + cst.count()
+        })
+        .sum::<usize>();
+    let num_public_inputs = circuit
+        .layers
+        .iter()
+        .flat_map(|l| &l.const_)
+        .filter_map(|g| match g.coef_type {
+            CoefType::PublicInput(idx) => Some(idx + 1),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    let output_var_num = circuit.layers.last().unwrap().output_var_num;
+    let pcs_var_num = circuit.layers[0]
+        .input_var_num
+        .max(HyperKZGPCS::<Bn256>::MINIMUM_SUPPORTED_NUM_VARS);
+
+    let mut out = format!(
+        r#"    // ============== circuit ==============
+    uint256 internal constant NUM_RND_COEFS = {num_rnd_coefs};
+    uint256 internal constant NUM_PUBLIC_INPUTS = {num_public_inputs};
+    uint256 internal constant OUTPUT_VAR_NUM = {output_var_num};
+    // Number of variables of the committed input, lifted to the minimum of KZG
+    uint256 internal constant PCS_VAR_NUM = {pcs_var_num};
+
+    // ============== verifying key ==============
+"#
+    );
+
+    // the pairing precompile expects the coordinates of G2 points as c1 * u + c0, c1 first
+    let g2 = [("TAU_G2", vk.tau_x_g2), ("G2", G2Affine::generator())];
+    for (name, p) in g2 {
+        for (coord, v) in [
+            ("X1", p.x.c1),
+            ("X0", p.x.c0),
+            ("Y1", p.y.c1),
+            ("Y0", p.y.c0),
+        ] {
+            let v = uint256(&v);
+            writeln!(out, "    uint256 internal constant {name}_{coord} = {v};").unwrap();
+        }
+    }
+    out
+}
+
+fn keccak_hasher() -> String {
+    r#"    // ============== Keccak256 ==============
+    /// Replaces the 32 bytes at `ptr` with the hash of the `len` bytes at `ptr`.
+    function hashInPlace(uint256 ptr, uint256 len) internal pure {
+        assembly ("memory-safe") {
+            mstore(ptr, keccak256(ptr, len))
+        }
+    }
+"#
+    .to_string()
+}
+
+/// Mirrors `MiMC5FiatShamirHasher<Fr>`, with the round constants unrolled.
+fn mimc5_hasher() -> String {
+    let mut out = r#"    // ============== MiMC5 ==============
+    function pow5(uint256 x) internal pure returns (uint256) {
+        uint256 x2 = mulmod(x, x, R);
+        return mulmod(mulmod(x2, x2, R), x, R);
+    }
+
+    function mimc5(uint256 h, uint256 x) internal pure returns (uint256) {
+"#
+    .to_string();
+    for c in generate_mimc_constants::<Fr>() {
+        let c = uint256(&c);
+        writeln!(out, "        x = pow5(addmod(x, addmod(h, {c}, R), R));").unwrap();
+    }
+    out.push_str(
+        r#"        return addmod(x, h, R);
+    }
+
+    /// Replaces the 32 bytes at `ptr` with the hash of the `len` bytes at `ptr`.
+    /// The input is absorbed in 32-byte little endian chunks, the last one zero padded.
+    function hashInPlace(uint256 ptr, uint256 len) internal pure {
+        uint256 end = ptr + len;
+        assembly ("memory-safe") {
+            mstore(end, 0)
+        }
+        uint256 h = 0;
+        for (uint256 p = ptr; p < end; p += 32) {
+            uint256 x;
+            assembly ("memory-safe") {
+                x := mload(p)
+            }
+            x = reverseBytes(x) % R;
+            h = addmod(h, addmod(mimc5(h, x), x, R), R);
+        }
+        uint256 digest = reverseBytes(h);
+        assembly ("memory-safe") {
+            mstore(ptr, digest)
+        }
+    }
+"#,
+    );
+    out
+}
+
+/// The coefficient of a gate as a solidity expression, `None` for a constant one.
+///
+/// Random coefficients are indexed by `rnd_idx`, in the order of `Circuit::identify_rnd_coefs`.
+fn coef<const INPUT_NUM: usize>(
+    gate: &Gate<BN254Config, INPUT_NUM>,
+    rnd_idx: &mut usize,
+) -> Option<String> {
+    match gate.coef_type {
+        CoefType::Random => {
+            *rnd_idx += 1;
+            Some(format!("rnd[{}]", *rnd_idx - 1))
+        }
+        CoefType::PublicInput(idx) if INPUT_NUM == 0 => Some(format!("publicInput[{idx}]")),
+        _ if gate.coef == Fr::ONE => None,
+        _ => Some(uint256(&gate.coef)),
+    }
+}
+
+fn with_coef(term: String, coef: Option<String>) -> String {
+    match coef {
+        Some(c) => format!("mulmod({term}, {c}, R)"),
+        None => term,
+    }
+}
+
+/// Emits `function name(params) internal pure returns (uint256 v)` summing up `terms`.
+/// Parameters are given as (type, name), and left unnamed when the terms do not use them.
+fn gates_fn(out: &mut String, name: &str, params: &[(&str, &str)], terms: &[String]) {
+    let params = params
+        .iter()
+        .map(|(ty, param)| {
+            let used = terms.iter().any(|t| t.contains(&format!("{param}[")));
+            if used {
+                format!("{ty} {param}")
+            } else {
+                ty.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        out,
+        "    function {name}({params}) internal pure returns (uint256 v) {{"
+    )
+    .unwrap();
+    for term in terms {
+        writeln!(out, "        v = addmod(v, {term}, R);").unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+    writeln!(out).unwrap();
+}
+
+/// The gates of each layer, and the sumcheck of each layer mirroring
+/// `sumcheck_verify_gkr_layer`.
+fn layers(circuit: &Circuit<BN254Config>) -> String {
+    let mut out = String::new();
+    let mut rnd_idx = 0;
+    for (i, layer) in circuit.layers.iter().enumerate() {
+        writeln!(out, "    // ============== layer {i} ==============").unwrap();
+        let mul = layer
+            .mul
+            .iter()
+            .map(|g| {
+                let term = format!(
+                    "mulmod(ez[{}], mulmod(ex[{}], ey[{}], R), R)",
+                    g.o_id, g.i_ids[0], g.i_ids[1]
+                );
+                with_coef(term, coef(g, &mut rnd_idx))
+            })
+            .collect::<Vec<_>>();
+        let add = layer
+            .add
+            .iter()
+            .map(|g| {
+                let term = format!("mulmod(ez[{}], ex[{}], R)", g.o_id, g.i_ids[0]);
+                with_coef(term, coef(g, &mut rnd_idx))
+            })
+            .collect::<Vec<_>>();
+        let cst = layer
+            .const_
+            .iter()
+            .filter(|g| g.coef_type != CoefType::Constant || g.coef != Fr::ZERO)
+            .map(|g| with_coef(format!("ez[{}]", g.o_id), coef(g, &mut rnd_idx)))
+            .collect::<Vec<_>>();
+
+        let arr = "uint256[] memory";
+        if !mul.is_empty() {
+            let params = [(arr, "ez"), (arr, "ex"), (arr, "ey"), (arr, "rnd")];
+            gates_fn(&mut out, &format!("mulGates{i}"), &params, &mul);
+        }
+        if !add.is_empty() {
+            let params = [(arr, "ez"), (arr, "ex"), (arr, "rnd")];
+            gates_fn(&mut out, &format!("addGates{i}"), &params, &add);
+        }
+        if !cst.is_empty() {
+            let params = [
+                (arr, "ez"),
+                (arr, "rnd"),
+                ("uint256[] calldata", "publicInput"),
+            ];
+            gates_fn(&mut out, &format!("cstGates{i}"), &params, &cst);
+        }
+
+        layer_fn(
+            &mut out,
+            i,
+            layer,
+            !mul.is_empty(),
+            !add.is_empty(),
+            !cst.is_empty(),
+        );
+    }
+    out
+}
+
+fn layer_fn(
+    out: &mut String,
+    i: usize,
+    layer: &CircuitLayer<BN254Config>,
+    has_mul: bool,
+    has_add: bool,
+    has_cst: bool,
+) {
+    let n = layer.input_var_num;
+    let public_input = if has_cst { " publicInput" } else { "" };
+    writeln!(
+        out,
+        "    function layer{i}(GkrState memory s, Transcript memory t, bytes calldata proof, \
+         uint256[] calldata{public_input}) internal pure {{"
+    )
+    .unwrap();
+    if has_cst {
+        writeln!(
+            out,
+            "        s.sum = addmod(s.sum, R - cstGates{i}(s.eqZ, s.rnd, publicInput), R);"
+        )
+        .unwrap();
+    }
+    writeln!(out, "        s.rx = sumcheckRounds(s, t, proof, {n});").unwrap();
+    writeln!(out, "        s.eqX = eqTable(s.rx);").unwrap();
+    writeln!(out, "        s.vx = readClaim(s, t, proof);").unwrap();
+    if has_add {
+        writeln!(
+            out,
+            "        s.sum = addmod(s.sum, R - mulmod(s.vx, addGates{i}(s.eqZ, s.eqX, s.rnd), R), R);"
+        )
+        .unwrap();
+    }
+    if layer.structure_info.skip_sumcheck_phase_two {
+        writeln!(out, "        if (s.sum != 0) s.ok = false;").unwrap();
+    } else {
+        writeln!(out, "        s.ry = sumcheckRounds(s, t, proof, {n});").unwrap();
+        writeln!(out, "        s.eqY = eqTable(s.ry);").unwrap();
+        writeln!(out, "        s.vy = readClaim(s, t, proof);").unwrap();
+        let eval_mul = if has_mul {
+            format!("mulGates{i}(s.eqZ, s.eqX, s.eqY, s.rnd)")
+        } else {
+            "0".to_string()
+        };
+        writeln!(
+            out,
+            "        if (s.sum != mulmod(mulmod(s.vx, s.vy, R), {eval_mul}, R)) s.ok = false;"
+        )
+        .unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+    writeln!(out).unwrap();
+}
+
+fn verify_fn(circuit: &Circuit<BN254Config>) -> String {
+    let mut out = String::new();
+    writeln!(out, "    // ============== verifier ==============").unwrap();
+    out.push_str(
+        r#"    function verify(bytes calldata proof, uint256[] calldata publicInput, uint256 claimedV)
+        external
+        view
+        returns (bool)
+    {
+        if (publicInput.length < NUM_PUBLIC_INPUTS) revert MissingPublicInput();
+        for (uint256 i = 0; i < publicInput.length; i++) {
+            if (publicInput[i] >= R) revert NonCanonicalInput();
+        }
+        if (claimedV >= R) revert NonCanonicalInput();
+
+        (uint256 cx, uint256 cy) = readG1(proof, 0);
+        // at most 2 * PCS_VAR_NUM + 2 field elements are appended between two challenges,
+        // besides the public inputs
+        uint256 capacity = 32 * (2 * PCS_VAR_NUM + 2);
+        if (32 * publicInput.length > capacity) capacity = 32 * publicInput.length;
+        Transcript memory t = newTranscript(capacity);
+        initTranscript(t, proof);
+        absorbPublicInput(t, publicInput);
+
+        GkrState memory s;
+        s.off = 32;
+        s.ok = true;
+        s.rnd = challenges(t, NUM_RND_COEFS);
+        s.eqZ = eqTable(challenges(t, OUTPUT_VAR_NUM));
+        absorbClaimedV(t, claimedV);
+        s.sum = claimedV;
+
+"#,
+    );
+    for i in (0..circuit.layers.len()).rev() {
+        writeln!(out, "        layer{i}(s, t, proof, publicInput);").unwrap();
+        if i > 0 {
+            if circuit.layers[i].structure_info.skip_sumcheck_phase_two {
+                writeln!(out, "        nextLayerSingleClaim(s);").unwrap();
+            } else {
+                writeln!(out, "        nextLayerTwoClaims(s, t);").unwrap();
+            }
+        }
+    }
+    out.push_str(
+        r#"        if (!s.ok) return false;
+
+        return verifyOpening(s, t, proof, cx, cy);
+    }
+"#,
+    );
+    out
+}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SolidityVerifierError {
+    #[error("unsupported config: {0}")]
+    UnsupportedConfig(String),
+
+    #[error("unsupported circuit: {0}")]
+    UnsupportedCircuit(String),
+}
@@ -0,0 +1,17 @@
+//! Solidity verifier for Expander proofs over BN254 with the KZG PCS.
+//!
+//! - [`generate_solidity_verifier`] specializes a verifier contract to a circuit and a KZG
+//!   verifying key: the gates of each layer are unrolled into the contract, so that the contract
+//!   only needs the proof, the public input and the claimed output value.
+//! - [`encode_verify_calldata`] ABI-encodes a call to the `verify` function of that contract.
+//!
+//! Only the single process, vanilla GKR scheme with MiMC5 or Keccak256 Fiat-Shamir is supported,
+//! and the contract assumes that the prover did not grind.
+
+mod calldata;
+mod codegen;
+mod errors;
+
+pub use calldata::*;
+pub use codegen::*;
+pub use errors::*;
@@ -0,0 +1,281 @@
+    // ============== errors ==============
+    error TruncatedProof();
+    error TrailingBytes();
+    error MalformedFieldElement(uint256 offset);
+    error MalformedG1Point(uint256 offset);
+    error MalformedOpening();
+    error NonCanonicalInput();
+    error MissingPublicInput();
+    error NotInvertible();
+    error PrecompileFailure();
+
+    // ============== fields ==============
+    // BN254 scalar field modulus
+    uint256 internal constant R =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617;
+    // BN254 base field modulus
+    uint256 internal constant Q =
+        21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 internal constant INV_2 =
+        10944121435919637611123202872628637544274182200208017171849102093287904247809;
+
+    // Number of extra hashes of the PCS commitment when initializing the transcript,
+    // see `BytesHashTranscript::append_commitment`
+    uint256 internal constant PCS_DIGEST_LOOP = 1000;
+
+    // Fiat-Shamir transcript mirroring `BytesHashTranscript`: the 32-byte digest is kept at
+    // `ptr`, followed by the `len` bytes appended since the digest was last refreshed.
+    struct Transcript {
+        uint256 ptr;
+        uint256 len;
+    }
+
+    // State carried from one GKR layer to the next.
+    struct GkrState {
+        // read position in the proof
+        uint256 off;
+        // the current sumcheck claim
+        uint256 sum;
+        uint256 vx;
+        uint256 vy;
+        bool ok;
+        uint256[] rx;
+        uint256[] ry;
+        uint256[] eqZ;
+        uint256[] eqX;
+        uint256[] eqY;
+        uint256[] rnd;
+    }
+
+    /// Field elements and group elements are serialized in little endian.
+    function reverseBytes(uint256 v) internal pure returns (uint256) {
+        v = ((v & 0xff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00) >> 8)
+            | ((v & 0x00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff) << 8);
+        v = ((v & 0xffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000) >> 16)
+            | ((v & 0x0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff) << 16);
+        v = ((v & 0xffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000) >> 32)
+            | ((v & 0x00000000ffffffff00000000ffffffff00000000ffffffff00000000ffffffff) << 32);
+        v = ((v & 0xffffffffffffffff0000000000000000ffffffffffffffff0000000000000000) >> 64)
+            | ((v & 0x0000000000000000ffffffffffffffff0000000000000000ffffffffffffffff) << 64);
+        return (v >> 128) | (v << 128);
+    }
+
+    function modExp(uint256 b, uint256 e, uint256 m) internal view returns (uint256 r) {
+        bool success;
+        assembly ("memory-safe") {
+            let p := mload(0x40)
+            mstore(p, 0x20)
+            mstore(add(p, 0x20), 0x20)
+            mstore(add(p, 0x40), 0x20)
+            mstore(add(p, 0x60), b)
+            mstore(add(p, 0x80), e)
+            mstore(add(p, 0xa0), m)
+            success := staticcall(gas(), 0x05, p, 0xc0, p, 0x20)
+            r := mload(p)
+        }
+        if (!success) revert PrecompileFailure();
+    }
+
+    function inverse(uint256 a) internal view returns (uint256) {
+        if (a == 0) revert NotInvertible();
+        return modExp(a, R - 2, R);
+    }
+
+    // ============== proof parsing ==============
+    function readField(bytes calldata proof, uint256 off) internal pure returns (uint256 x) {
+        if (off + 32 > proof.length) revert TruncatedProof();
+        x = reverseBytes(uint256(bytes32(proof[off:off + 32])));
+        if (x >= R) revert MalformedFieldElement(off);
+    }
+
+    /// Reads the u64 length prefix of a serialized vector.
+    function readLength(bytes calldata proof, uint256 off) internal pure returns (uint256) {
+        if (off + 8 > proof.length) revert TruncatedProof();
+        return reverseBytes(uint256(bytes32(proof[off:off + 8])));
+    }
+
+    /// Reads a compressed G1 point, encoded as x in little endian with the parity of y in the
+    /// top bit, and all zeros for the identity.
+    function readG1(bytes calldata proof, uint256 off) internal view returns (uint256 x, uint256 y) {
+        if (off + 32 > proof.length) revert TruncatedProof();
+        uint256 raw = reverseBytes(uint256(bytes32(proof[off:off + 32])));
+        uint256 ySign = raw >> 255;
+        x = raw & ((1 << 255) - 1);
+        if (x >= Q) revert MalformedG1Point(off);
+        if (x == 0 && ySign == 0) return (0, 0);
+
+        uint256 y2 = addmod(mulmod(mulmod(x, x, Q), x, Q), 3, Q);
+        y = modExp(y2, (Q + 1) / 4, Q);
+        if (mulmod(y, y, Q) != y2) revert MalformedG1Point(off);
+        if ((y & 1) != ySign) y = Q - y;
+    }
+
+    function skipFields(bytes calldata proof, uint256 off, uint256 n) internal pure returns (uint256) {
+        for (uint256 i = 0; i < n; i++) {
+            readField(proof, off);
+            off += 32;
+        }
+        return off;
+    }
+
+    // ============== transcript ==============
+    function newTranscript(uint256 capacity) internal pure returns (Transcript memory t) {
+        // 32 bytes for the digest, and 32 bytes of slack to zero pad the last hashed chunk
+        bytes memory buf = new bytes(capacity + 64);
+        uint256 ptr;
+        assembly ("memory-safe") {
+            ptr := add(buf, 0x20)
+        }
+        t.ptr = ptr;
+    }
+
+    /// Mirrors `append_commitment`: the digest is the commitment hashed `1 + PCS_DIGEST_LOOP`
+    /// times, and the commitment itself is never hashed along with the digest.
+    function initTranscript(Transcript memory t, bytes calldata proof) internal pure {
+        uint256 ptr = t.ptr;
+        assembly ("memory-safe") {
+            calldatacopy(ptr, proof.offset, 0x20)
+        }
+        for (uint256 i = 0; i <= PCS_DIGEST_LOOP; i++) {
+            hashInPlace(ptr, 32);
+        }
+        t.len = 0;
+    }
+
+    function appendCalldata(Transcript memory t, bytes calldata proof, uint256 off, uint256 n)
+        internal
+        pure
+    {
+        uint256 dst = t.ptr + 32 + t.len;
+        assembly ("memory-safe") {
+            calldatacopy(dst, add(proof.offset, off), n)
+        }
+        t.len += n;
+    }
+
+    function appendField(Transcript memory t, uint256 x) internal pure {
+        uint256 dst = t.ptr + 32 + t.len;
+        uint256 le = reverseBytes(x);
+        assembly ("memory-safe") {
+            mstore(dst, le)
+        }
+        t.len += 32;
+    }
+
+    /// Hash the appended bytes into the digest, as `BytesHashTranscript::unlock_proof` does for
+    /// the bytes appended while the proof is locked.
+    function flush(Transcript memory t) internal pure {
+        if (t.len > 0) {
+            hashInPlace(t.ptr, 32 + t.len);
+            t.len = 0;
+        }
+    }
+
+    /// See `absorb_public_input`.
+    function absorbPublicInput(Transcript memory t, uint256[] calldata publicInput) internal pure {
+        for (uint256 i = 0; i < publicInput.length; i++) {
+            appendField(t, publicInput[i]);
+        }
+        flush(t);
+    }
+
+    /// See `absorb_claimed_v`.
+    function absorbClaimedV(Transcript memory t, uint256 claimedV) internal pure {
+        appendField(t, claimedV);
+        flush(t);
+    }
+
+    function challenge(Transcript memory t) internal pure returns (uint256) {
+        uint256 ptr = t.ptr;
+        hashInPlace(ptr, 32 + t.len);
+        t.len = 0;
+        uint256 digest;
+        assembly ("memory-safe") {
+            digest := mload(ptr)
+        }
+        return reverseBytes(digest) % R;
+    }
+
+    function challenges(Transcript memory t, uint256 n) internal pure returns (uint256[] memory r) {
+        r = new uint256[](n);
+        for (uint256 i = 0; i < n; i++) {
+            r[i] = challenge(t);
+        }
+    }
+
+    // ============== GKR ==============
+    /// eq(x, r) for all x in the hypercube, with the first variable as the least significant bit
+    function eqTable(uint256[] memory r) internal pure returns (uint256[] memory e) {
+        e = new uint256[](1 << r.length);
+        e[0] = 1;
+        uint256 cur = 1;
+        for (uint256 i = 0; i < r.length; i++) {
+            for (uint256 j = 0; j < cur; j++) {
+                uint256 v = mulmod(e[j], r[i], R);
+                e[j + cur] = v;
+                e[j] = addmod(e[j], R - v, R);
+            }
+            cur <<= 1;
+        }
+    }
+
+    /// eq(x, rx) + alpha * eq(x, ry), the eq table for the two claims on the next layer
+    function combineEq(uint256[] memory ex, uint256[] memory ey, uint256 alpha)
+        internal
+        pure
+        returns (uint256[] memory e)
+    {
+        e = new uint256[](ex.length);
+        for (uint256 i = 0; i < ex.length; i++) {
+            e[i] = addmod(ex[i], mulmod(alpha, ey[i], R), R);
+        }
+    }
+
+    function degree2Eval(uint256 p0, uint256 p1, uint256 p2, uint256 x) internal pure returns (uint256) {
+        uint256 c2 = mulmod(INV_2, addmod(addmod(p2, p0, R), R - addmod(p1, p1, R), R), R);
+        uint256 c1 = addmod(p1, R - addmod(p0, c2, R), R);
+        return addmod(p0, mulmod(addmod(mulmod(c2, x, R), c1, R), x, R), R);
+    }
+
+    function sumcheckRounds(GkrState memory s, Transcript memory t, bytes calldata proof, uint256 n)
+        internal
+        pure
+        returns (uint256[] memory r)
+    {
+        r = new uint256[](n);
+        for (uint256 i = 0; i < n; i++) {
+            uint256 p0 = readField(proof, s.off);
+            uint256 p1 = readField(proof, s.off + 32);
+            uint256 p2 = readField(proof, s.off + 64);
+            appendCalldata(t, proof, s.off, 96);
+            s.off += 96;
+
+            uint256 x = challenge(t);
+            if (addmod(p0, p1, R) != s.sum) s.ok = false;
+            s.sum = degree2Eval(p0, p1, p2, x);
+            r[i] = x;
+        }
+    }
+
+    function readClaim(GkrState memory s, Transcript memory t, bytes calldata proof)
+        internal
+        pure
+        returns (uint256 v)
+    {
+        v = readField(proof, s.off);
+        appendCalldata(t, proof, s.off, 32);
+        s.off += 32;
+    }
+
+    /// Moves on to the next layer with the single claim vx on rx.
+    function nextLayerSingleClaim(GkrState memory s) internal pure {
+        s.eqZ = s.eqX;
+        s.sum = s.vx;
+    }
+
+    /// Moves on to the next layer with the claims vx on rx and vy on ry, combined by a random alpha.
+    function nextLayerTwoClaims(GkrState memory s, Transcript memory t) internal pure {
+        uint256 alpha = challenge(t);
+        s.eqZ = combineEq(s.eqX, s.eqY, alpha);
+        s.sum = addmod(s.vx, mulmod(alpha, s.vy, R), R);
+    }
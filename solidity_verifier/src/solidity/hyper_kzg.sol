@@ -0,0 +1,266 @@
+    // ============== HyperKZG ==============
+    // Offsets into the proof and challenges of a HyperKZG opening.
+    struct KzgState {
+        uint256 foldedOff;
+        uint256 betaX2Off;
+        uint256 posOff;
+        uint256 negOff;
+        uint256 betaXOff;
+        uint256 quotientOff;
+        uint256 beta;
+        uint256 gamma;
+        uint256 tau;
+        uint256 vBeta;
+        uint256 vNegBeta;
+        uint256 vBeta2;
+    }
+
+    function ecAdd(uint256 x1, uint256 y1, uint256 x2, uint256 y2)
+        internal
+        view
+        returns (uint256 x, uint256 y)
+    {
+        bool success;
+        assembly ("memory-safe") {
+            let p := mload(0x40)
+            mstore(p, x1)
+            mstore(add(p, 0x20), y1)
+            mstore(add(p, 0x40), x2)
+            mstore(add(p, 0x60), y2)
+            success := staticcall(gas(), 0x06, p, 0x80, p, 0x40)
+            x := mload(p)
+            y := mload(add(p, 0x20))
+        }
+        if (!success) revert PrecompileFailure();
+    }
+
+    function ecMul(uint256 x1, uint256 y1, uint256 s) internal view returns (uint256 x, uint256 y) {
+        bool success;
+        assembly ("memory-safe") {
+            let p := mload(0x40)
+            mstore(p, x1)
+            mstore(add(p, 0x20), y1)
+            mstore(add(p, 0x40), s)
+            success := staticcall(gas(), 0x07, p, 0x60, p, 0x40)
+            x := mload(p)
+            y := mload(add(p, 0x20))
+        }
+        if (!success) revert PrecompileFailure();
+    }
+
+    /// e(a, tau G2) * e(b, G2) == 1
+    function pairingCheck(uint256 ax, uint256 ay, uint256 bx, uint256 by) internal view returns (bool) {
+        uint256[12] memory input = [
+            ax,
+            ay,
+            TAU_G2_X1,
+            TAU_G2_X0,
+            TAU_G2_Y1,
+            TAU_G2_Y0,
+            bx,
+            by,
+            G2_X1,
+            G2_X0,
+            G2_Y1,
+            G2_Y0
+        ];
+        uint256[1] memory out;
+        bool success;
+        assembly ("memory-safe") {
+            success := staticcall(gas(), 0x08, input, 0x180, out, 0x20)
+        }
+        if (!success) revert PrecompileFailure();
+        return out[0] == 1;
+    }
+
+    /// Skips a `HyperKZGExportedLocalEvals`.
+    function skipLocalEvals(bytes calldata proof, uint256 off) internal pure returns (uint256) {
+        off = skipFields(proof, off, 1);
+        off = skipFields(proof, off + 8, readLength(proof, off));
+        return skipFields(proof, off + 8, readLength(proof, off));
+    }
+
+    /// Validates a `HyperBiKZGOpening` of a single process and records the offsets of its parts.
+    /// Returns the offset right after the opening.
+    function parseOpening(KzgState memory k, bytes calldata proof, uint256 off)
+        internal
+        view
+        returns (uint256)
+    {
+        if (readLength(proof, off) != PCS_VAR_NUM - 1) revert MalformedOpening();
+        off += 8;
+        k.foldedOff = off;
+        for (uint256 i = 0; i < PCS_VAR_NUM - 1; i++) {
+            readG1(proof, off);
+            off += 32;
+        }
+
+        // aggregated evals, only used by multi-process openings
+        for (uint256 i = 0; i < 3; i++) {
+            off = skipLocalEvals(proof, off);
+        }
+
+        k.betaX2Off = off;
+        off = skipFields(proof, off, 1);
+        if (readLength(proof, off) != PCS_VAR_NUM) revert MalformedOpening();
+        k.posOff = off + 8;
+        off = skipFields(proof, off + 8, PCS_VAR_NUM);
+        if (readLength(proof, off) != PCS_VAR_NUM) revert MalformedOpening();
+        k.negOff = off + 8;
+        off = skipFields(proof, off + 8, PCS_VAR_NUM);
+
+        // beta_x_commitment, beta_y_commitment, quotient_delta_x_commitment and
+        // quotient_delta_y_commitment, the y parts are only used by multi-process openings
+        k.betaXOff = off;
+        k.quotientOff = off + 64;
+        for (uint256 i = 0; i < 4; i++) {
+            readG1(proof, off);
+            off += 32;
+        }
+        return off;
+    }
+
+    /// The evaluations of the folded polynomials at beta^2, recovered from those at beta and -beta.
+    function beta2Evals(KzgState memory k, bytes calldata proof, uint256[] memory alphas)
+        internal
+        view
+        returns (uint256[] memory b2)
+    {
+        uint256 betaInv = inverse(k.beta);
+        b2 = new uint256[](PCS_VAR_NUM + 1);
+        b2[0] = readField(proof, k.betaX2Off);
+        for (uint256 i = 0; i < PCS_VAR_NUM; i++) {
+            uint256 pos = readField(proof, k.posOff + 32 * i);
+            uint256 neg = readField(proof, k.negOff + 32 * i);
+            uint256 even = mulmod(addmod(pos, neg, R), addmod(1, R - alphas[i], R), R);
+            uint256 odd = mulmod(mulmod(addmod(pos, R - neg, R), betaInv, R), alphas[i], R);
+            b2[i + 1] = mulmod(INV_2, addmod(even, odd, R), R);
+        }
+    }
+
+    function aggregateEvals(KzgState memory k, bytes calldata proof, uint256[] memory b2) internal pure {
+        uint256 g = 1;
+        for (uint256 i = 0; i < PCS_VAR_NUM; i++) {
+            k.vBeta = addmod(k.vBeta, mulmod(readField(proof, k.posOff + 32 * i), g, R), R);
+            k.vNegBeta = addmod(k.vNegBeta, mulmod(readField(proof, k.negOff + 32 * i), g, R), R);
+            k.vBeta2 = addmod(k.vBeta2, mulmod(b2[i], g, R), R);
+            g = mulmod(g, k.gamma, R);
+        }
+    }
+
+    /// Evaluation at tau of the degree 2 polynomial through (beta, vBeta), (-beta, vNegBeta) and
+    /// (beta^2, vBeta2).
+    function lagrangeEval(KzgState memory k) internal view returns (uint256) {
+        uint256 b = k.beta;
+        uint256 b2 = mulmod(b, b, R);
+        uint256 t0 = addmod(k.tau, R - b, R);
+        uint256 t1 = addmod(k.tau, b, R);
+        uint256 t2 = addmod(k.tau, R - b2, R);
+
+        uint256 w0 = mulmod(k.vBeta, inverse(mulmod(addmod(b, b, R), addmod(b, R - b2, R), R)), R);
+        uint256 w1 = mulmod(
+            k.vNegBeta, inverse(mulmod(R - addmod(b, b, R), R - addmod(b, b2, R), R)), R
+        );
+        uint256 w2 = mulmod(k.vBeta2, inverse(mulmod(addmod(b2, R - b, R), addmod(b2, b, R), R)), R);
+
+        return addmod(
+            addmod(mulmod(w0, mulmod(t1, t2, R), R), mulmod(w1, mulmod(t0, t2, R), R), R),
+            mulmod(w2, mulmod(t0, t1, R), R),
+            R
+        );
+    }
+
+    function quotientWeight(KzgState memory k) internal pure returns (uint256) {
+        uint256 b2 = mulmod(k.beta, k.beta, R);
+        return mulmod(
+            mulmod(addmod(k.tau, R - k.beta, R), addmod(k.tau, R - b2, R), R),
+            addmod(k.tau, k.beta, R),
+            R
+        );
+    }
+
+    /// p + s * (the G1 point at `off` in the proof)
+    function mulAddG1(uint256 px, uint256 py, bytes calldata proof, uint256 off, uint256 s)
+        internal
+        view
+        returns (uint256, uint256)
+    {
+        (uint256 x, uint256 y) = readG1(proof, off);
+        (x, y) = ecMul(x, y, s);
+        return ecAdd(px, py, x, y);
+    }
+
+    /// The G1 side of the final pairing check:
+    /// eval G1 - C - sum_i gamma^(i + 1) F_i + (tau - beta)(tau - beta^2)(tau + beta) B - tau W
+    function pairingLhs(KzgState memory k, bytes calldata proof, uint256 cx, uint256 cy, uint256 eval)
+        internal
+        view
+        returns (uint256 px, uint256 py)
+    {
+        (px, py) = ecMul(1, 2, eval);
+        (px, py) = ecAdd(px, py, cx, cy == 0 ? 0 : Q - cy);
+
+        uint256 g = k.gamma;
+        for (uint256 i = 0; i < PCS_VAR_NUM - 1; i++) {
+            (px, py) = mulAddG1(px, py, proof, k.foldedOff + 32 * i, R - g);
+            g = mulmod(g, k.gamma, R);
+        }
+        (px, py) = mulAddG1(px, py, proof, k.betaXOff, quotientWeight(k));
+        (px, py) = mulAddG1(px, py, proof, k.quotientOff, R - k.tau);
+    }
+
+    /// Checks the evaluations in the opening against the claim vx of the input layer, and
+    /// samples beta and gamma along the way.
+    function checkEvals(KzgState memory k, GkrState memory s, Transcript memory t, bytes calldata proof)
+        internal
+        view
+        returns (bool)
+    {
+        // the opening point is lifted to PCS_VAR_NUM variables with zeros
+        uint256[] memory alphas = new uint256[](PCS_VAR_NUM);
+        for (uint256 i = 0; i < s.rx.length; i++) {
+            alphas[i] = s.rx[i];
+        }
+
+        appendCalldata(t, proof, k.foldedOff, 32 * (PCS_VAR_NUM - 1));
+        k.beta = challenge(t);
+
+        uint256[] memory b2 = beta2Evals(k, proof, alphas);
+        appendCalldata(t, proof, k.betaX2Off, 32);
+        for (uint256 i = 0; i < PCS_VAR_NUM; i++) {
+            appendCalldata(t, proof, k.posOff + 32 * i, 32);
+            appendCalldata(t, proof, k.negOff + 32 * i, 32);
+        }
+        if (b2[PCS_VAR_NUM] != s.vx) return false;
+
+        k.gamma = challenge(t);
+        aggregateEvals(k, proof, b2);
+        return true;
+    }
+
+    function finalPairing(KzgState memory k, bytes calldata proof, uint256 cx, uint256 cy)
+        internal
+        view
+        returns (bool)
+    {
+        (uint256 px, uint256 py) = pairingLhs(k, proof, cx, cy, lagrangeEval(k));
+        (uint256 wx, uint256 wy) = readG1(proof, k.quotientOff);
+        return pairingCheck(wx, wy, px, py);
+    }
+
+    /// Mirrors `coeff_form_uni_hyperkzg_verify`, for the opening of the input layer at rx.
+    function verifyOpening(
+        GkrState memory s,
+        Transcript memory t,
+        bytes calldata proof,
+        uint256 cx,
+        uint256 cy
+    ) internal view returns (bool) {
+        KzgState memory k;
+        if (parseOpening(k, proof, s.off) != proof.length) revert TrailingBytes();
+        if (!checkEvals(k, s, t, proof)) return false;
+
+        appendCalldata(t, proof, k.betaXOff, 32);
+        k.tau = challenge(t);
+        return finalPairing(k, proof, cx, cy);
+    }
@@ -0,0 +1,323 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use circuit::{Circuit, CircuitLayer, CoefType, Gate, GateUni};
+use gkr::{BN254ConfigKeccakKZG, BN254ConfigMIMC5KZG, Prover, Verifier};
+use gkr_engine::{BN254Config, GKREngine, MPIConfig, Proof};
+use halo2curves::bn256::{Bn256, Fr};
+use poly_commit::{expander_pcs_init_testing_only, BiKZGVerifierParam, HyperKZGPCS};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{Address, Bytes, ExecutionResult, Output, TxKind},
+    DatabaseCommit, Evm,
+};
+use solidity_verifier::{
+    encode_verify_calldata, generate_solidity_verifier, SolidityVerifierError,
+};
+
+fn gate<const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef_type: CoefType,
+    coef: u64,
+) -> Gate<BN254Config, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef: Fr::from(coef),
+        gate_type: 0,
+    }
+}
+
+/// A two layer circuit with all kinds of coefficients, before pre-processing.
+fn test_circuit() -> Circuit<BN254Config> {
+    let layer_0 = CircuitLayer::<BN254Config> {
+        input_var_num: 2,
+        output_var_num: 2,
+        mul: vec![
+            gate([0, 1], 0, CoefType::Constant, 1),
+            gate([2, 3], 1, CoefType::Constant, 3),
+        ],
+        add: vec![
+            gate([0], 2, CoefType::Random, 0),
+            gate([1], 1, CoefType::Constant, 1),
+        ],
+        const_: vec![
+            gate([], 3, CoefType::PublicInput(0), 0),
+            gate([], 2, CoefType::Constant, 7),
+            gate([], 0, CoefType::Random, 0),
+        ],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<BN254Config> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![gate([0, 1], 0, CoefType::Constant, 1)],
+        add: vec![
+            gate([2], 1, CoefType::Constant, 2),
+            gate([3], 1, CoefType::Constant, 1),
+        ],
+        const_: vec![gate([], 1, CoefType::PublicInput(1), 0)],
+        ..Default::default()
+    };
+
+    Circuit {
+        layers: vec![layer_0, layer_1],
+        public_input: vec![Fr::from(5u64), Fr::from(11u64)],
+        ..Default::default()
+    }
+}
+
+/// Proves the test circuit, and checks the proof with the rust verifier.
+fn prove<Cfg>(circuit: &mut Circuit<BN254Config>) -> (Fr, Proof, BiKZGVerifierParam<Bn256>)
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, PCSField = Fr, PCSConfig = HyperKZGPCS<Bn256>>,
+{
+    circuit.pre_process_gkr::<Cfg>();
+    circuit.layers[0].input_vals = (1..=4u64).map(Fr::from).collect();
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(circuit);
+
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<BN254Config, Fr, HyperKZGPCS<Bn256>>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    let (claimed_v, proof) = prover.prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch);
+
+    let verifier = Verifier::<Cfg>::new(MPIConfig::verifier_new(1));
+    let public_input = circuit.public_input.clone();
+    assert!(verifier.verify(
+        circuit,
+        &public_input,
+        &claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        &proof,
+    ));
+
+    (claimed_v, proof, pcs_verification_key)
+}
+
+/// Compiles the contract with `solc`, which must be installed.
+fn compile(source: &str) -> Vec<u8> {
+    let mut child = Command::new("solc")
+        .args(["--bin", "--optimize", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("solc (>= 0.8.20) is required by the EVM tests");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "solc failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines().skip_while(|l| !l.starts_with("Binary:"));
+    let hex = lines.nth(1).unwrap().trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn transact(db: &mut CacheDB<EmptyDB>, transact_to: TxKind, data: Vec<u8>) -> ExecutionResult {
+    let mut evm = Evm::builder()
+        .with_db(&mut *db)
+        .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(usize::MAX))
+        .modify_tx_env(|tx| {
+            tx.caller = Address::with_last_byte(1);
+            tx.transact_to = transact_to;
+            tx.data = Bytes::from(data);
+            tx.gas_limit = 1_000_000_000;
+        })
+        .build();
+    let result_and_state = evm.transact().unwrap();
+    drop(evm);
+    db.commit(result_and_state.state);
+    result_and_state.result
+}
+
+#[derive(Debug, PartialEq)]
+enum CallResult {
+    Returned(bool),
+    Reverted,
+}
+
+struct DeployedVerifier {
+    db: CacheDB<EmptyDB>,
+    address: Address,
+}
+
+impl DeployedVerifier {
+    fn deploy(code: Vec<u8>) -> Self {
+        let mut db = CacheDB::new(EmptyDB::default());
+        let address = match transact(&mut db, TxKind::Create, code) {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => address,
+            res => panic!("deployment failed: {res:?}"),
+        };
+        Self { db, address }
+    }
+
+    fn verify(&mut self, proof: &Proof, public_input: &[Fr], claimed_v: &Fr) -> CallResult {
+        let calldata = encode_verify_calldata(proof, public_input, claimed_v);
+        match transact(&mut self.db, TxKind::Call(self.address), calldata) {
+            ExecutionResult::Success {
+                output: Output::Call(out),
+                gas_used,
+                ..
+            } => {
+                println!("gas used: {gas_used}");
+                assert_eq!(out.len(), 32);
+                CallResult::Returned(out[31] == 1)
+            }
+            ExecutionResult::Revert { .. } => CallResult::Reverted,
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+}
+
+fn test_solidity_verifier_helper<Cfg>()
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, PCSField = Fr, PCSConfig = HyperKZGPCS<Bn256>>,
+{
+    let mut circuit = test_circuit();
+    let (claimed_v, proof, vk) = prove::<Cfg>(&mut circuit);
+    let public_input = circuit.public_input.clone();
+
+    let source = generate_solidity_verifier::<Cfg>(&circuit, &vk).unwrap();
+    let mut contract = DeployedVerifier::deploy(compile(&source));
+
+    assert_eq!(
+        contract.verify(&proof, &public_input, &claimed_v),
+        CallResult::Returned(true)
+    );
+
+    // wrong claim
+    let wrong_v = claimed_v + Fr::from(1u64);
+    assert_eq!(
+        contract.verify(&proof, &public_input, &wrong_v),
+        CallResult::Returned(false)
+    );
+
+    // wrong public input
+    let wrong_public_input = vec![public_input[0], public_input[1] + Fr::from(1u64)];
+    assert_eq!(
+        contract.verify(&proof, &wrong_public_input, &claimed_v),
+        CallResult::Returned(false)
+    );
+
+    // tampered sumcheck message, right after the commitment
+    let mut tampered = proof.clone();
+    tampered.bytes[32] ^= 1;
+    assert_eq!(
+        contract.verify(&tampered, &public_input, &claimed_v),
+        CallResult::Returned(false)
+    );
+
+    // malformed proofs revert
+    let truncated = Proof {
+        bytes: proof.bytes[..proof.bytes.len() - 1].to_vec(),
+    };
+    assert_eq!(
+        contract.verify(&truncated, &public_input, &claimed_v),
+        CallResult::Reverted
+    );
+    let mut trailing = proof.clone();
+    trailing.bytes.push(0);
+    assert_eq!(
+        contract.verify(&trailing, &public_input, &claimed_v),
+        CallResult::Reverted
+    );
+    assert_eq!(
+        contract.verify(&proof, &public_input[..1], &claimed_v),
+        CallResult::Reverted
+    );
+}
+
+#[test]
+#[ignore = "requires solc"]
+fn test_solidity_verifier_keccak() {
+    test_solidity_verifier_helper::<BN254ConfigKeccakKZG>();
+}
+
+#[test]
+#[ignore = "requires solc"]
+fn test_solidity_verifier_mimc5() {
+    test_solidity_verifier_helper::<BN254ConfigMIMC5KZG>();
+}
+
+#[test]
+fn test_solidity_verifier_rejects_unsupported_circuits() {
+    let mut circuit = test_circuit();
+    circuit.identify_structure_info();
+    let (_, _, vk, _) = expander_pcs_init_testing_only::<BN254Config, Fr, HyperKZGPCS<Bn256>>(
+        2,
+        &MPIConfig::prover_new(None, None),
+    );
+
+    // two claims on the input layer, i.e., not pre-processed
+    assert!(matches!(
+        generate_solidity_verifier::<BN254ConfigKeccakKZG>(&circuit, &vk),
+        Err(SolidityVerifierError::UnsupportedCircuit(_))
+    ));
+
+    circuit.pre_process_gkr::<BN254ConfigKeccakKZG>();
+    circuit.layers[1].uni.push(GateUni {
+        i_ids: [0],
+        o_id: 0,
+        coef_type: CoefType::Constant,
+        coef: Fr::from(1u64),
+        gate_type: 12345,
+    });
+    assert!(matches!(
+        generate_solidity_verifier::<BN254ConfigKeccakKZG>(&circuit, &vk),
+        Err(SolidityVerifierError::UnsupportedCircuit(_))
+    ));
+}
+
+#[test]
+fn test_verify_calldata_layout() {
+    let proof = Proof {
+        bytes: (0..40u8).collect(),
+    };
+    let public_input = [Fr::from(3u64), Fr::from(4u64)];
+    let calldata = encode_verify_calldata(&proof, &public_input, &Fr::from(5u64));
+
+    let words = calldata[4..].chunks(32).collect::<Vec<_>>();
+    assert_eq!(calldata.len(), 4 + 32 * 9);
+
+    let last_byte = |w: &[u8]| {
+        assert!(w[..31].iter().all(|b| *b == 0));
+        w[31]
+    };
+    // head: offset of proof, offset of public input, claimed value
+    assert_eq!(last_byte(words[0]), 0x60);
+    assert_eq!(last_byte(words[1]), 0xc0);
+    assert_eq!(last_byte(words[2]), 5);
+    // proof, zero padded to 64 bytes
+    assert_eq!(last_byte(words[3]), 40);
+    assert_eq!(&calldata[4 + 32 * 4..4 + 32 * 4 + 40], &proof.bytes[..]);
+    assert!(words[5][8..].iter().all(|b| *b == 0));
+    // public input
+    assert_eq!(last_byte(words[6]), 2);
+    assert_eq!(last_byte(words[7]), 3);
+    assert_eq!(last_byte(words[8]), 4);
+}
@@ -1,166 +1,149 @@
 //! This module implements helper functions for the prover side of the sumcheck protocol
 //! to evaluate power gates
 
-use std::ops::Mul;
+use std::ops::{Add, Mul};
 
-use arith::{Field, SimdField};
-use gkr_engine::FieldEngine;
+use arith::Field;
+use gkr_engine::{
+    run_simd_pair_kernel, twin_slice, twin_slice_mut, twin_value, FieldEngine, FieldType,
+    SimdPairKernel,
+};
 
-pub(crate) struct SumcheckPowerGateHelper<const D: usize> {
+use crate::utils::{extend_evals, gf2_evals_from_coefs};
+
+pub(crate) struct SumcheckPowerGateHelper {
     var_num: usize,
+    /// The distinct degrees of the power gates, each with its own bookkeeping tables
+    uni_degrees: Vec<usize>,
+    /// The degree of the sumcheck polynomial
+    degree: usize,
     sumcheck_var_idx: usize,
     cur_eval_size: usize,
 }
 
-impl<const D: usize> SumcheckPowerGateHelper<D> {
-    pub(crate) fn new(var_num: usize) -> Self {
+impl SumcheckPowerGateHelper {
+    pub(crate) fn new(var_num: usize, uni_degrees: Vec<usize>, degree: usize) -> Self {
         SumcheckPowerGateHelper {
             var_num,
+            uni_degrees,
+            degree,
             sumcheck_var_idx: 0,
             cur_eval_size: 1 << var_num,
         }
     }
 
-    // Function to interpolate a quadratic polynomial and update an array of points
-    fn interpolate_3<F: FieldEngine>(p_add: &[F::Field; 3], p: &mut [F::Field; D]) {
-        // Calculate coefficients for the interpolating polynomial
-        let p_add_coef_0 = p_add[0];
-        let p_add_coef_2 = (p_add[2] - p_add[1] - p_add[1] + p_add[0]) * F::CircuitField::INV_2;
-
-        let p_add_coef_1 = p_add[1] - p_add_coef_0 - p_add_coef_2;
-
-        // Update the p array by evaluating the interpolated polynomial at different points
-        // and adding the results to the existing values
-        p[0] += p_add_coef_0;
-        p[1] += p_add_coef_0 + p_add_coef_1 + p_add_coef_2;
-        p[2] += p_add_coef_0 + p_add_coef_1.double() + p_add_coef_2.double().double();
-        p[3] += p_add_coef_0 + p_add_coef_1.mul_by_3() + p_add_coef_2.mul_by_3().mul_by_3();
-        p[4] += p_add_coef_0
-            + p_add_coef_1.double().double()
-            + p_add_coef_2 * F::CircuitField::from(16);
-        p[5] += p_add_coef_0 + p_add_coef_1.mul_by_5() + p_add_coef_2 * F::CircuitField::from(25);
-        p[6] += p_add_coef_0
-            + p_add_coef_1.mul_by_3().double()
-            + p_add_coef_2 * F::CircuitField::from(36);
-    }
-
-    #[inline]
+    /// Add to `p` the evaluations at 0, 1, ..., degree of `sum_x hg_d(x) * f(x)^d` along the
+    /// current variable, for each degree d of power gates.
+    #[inline(always)]
     fn evaluate<VF: Field, ChallengeF: Field, EvalF>(
+        uni_degrees: &[usize],
         eval_size: usize,
         src_v: &[VF],
-        bk_hg_5: &[ChallengeF],
-        bk_hg_1: &[ChallengeF],
-        gate_exists_5: &[bool],
-        gate_exists_1: &[bool],
+        bk_hg: &[Vec<ChallengeF>],
+        gate_exists: &[Vec<bool>],
         p: &mut [EvalF],
-    ) -> [EvalF; 3]
-    where
-        EvalF: Field + From<ChallengeF> + Mul<ChallengeF, Output = EvalF> + Mul<VF, Output = EvalF>,
+        to_eval: impl Fn(ChallengeF) -> EvalF,
+    ) where
+        EvalF: Field + Mul<VF, Output = EvalF>,
     {
         log::trace!("Eval size: {eval_size}");
-        for i in 0..eval_size {
-            if !gate_exists_5[i * 2] && !gate_exists_5[i * 2 + 1] {
-                continue;
-            }
-            let mut f_v = [VF::ZERO; D];
-            let mut hg_v = [ChallengeF::ZERO; D];
-            f_v[0] = src_v[i * 2];
-            f_v[1] = src_v[i * 2 + 1];
-            hg_v[0] = bk_hg_5[i * 2];
-            hg_v[1] = bk_hg_5[i * 2 + 1];
-            let delta_f = f_v[1] - f_v[0];
-            let delta_hg = hg_v[1] - hg_v[0];
-
-            for i in 2..D {
-                f_v[i] = f_v[i - 1] + delta_f;
-                hg_v[i] = hg_v[i - 1] + delta_hg;
+        for ((&d, bk_hg), gate_exists) in uni_degrees.iter().zip(bk_hg).zip(gate_exists) {
+            // hg(x) * f(x)^d is of degree d + 1, given by its evaluations at 0, 1, ..., d + 1
+            let mut p_d = vec![EvalF::ZERO; d + 2];
+            for i in 0..eval_size {
+                if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
+                    continue;
+                }
+                let mut f_v = src_v[i * 2];
+                let mut hg_v = bk_hg[i * 2];
+                let delta_f = src_v[i * 2 + 1] - f_v;
+                let delta_hg = bk_hg[i * 2 + 1] - hg_v;
+
+                for p_d_j in p_d.iter_mut() {
+                    *p_d_j += to_eval(hg_v) * f_v.exp(d as u128);
+                    f_v += delta_f;
+                    hg_v += delta_hg;
+                }
             }
-            for i in 0..D {
-                let pow5 = f_v[i].square().square() * f_v[i];
-                p[i] += EvalF::from(hg_v[i]) * pow5;
+
+            for (p_j, p_d_j) in p.iter_mut().zip(extend_evals(&p_d, p.len())) {
+                *p_j += p_d_j;
             }
         }
+    }
+
+    /// Add to `coefs` the coefficients of `sum_x hg_d(x) * f(x)^d` along the current variable,
+    /// for each degree d of power gates. The degrees are powers of two, the field being of
+    /// characteristic 2, where `f(x)^d = f(0)^d + x^d * (f(1) - f(0))^d`.
+    #[inline(always)]
+    fn evaluate_coefs_char_2<VF: Field, ChallengeF: Field, EvalF>(
+        uni_degrees: &[usize],
+        eval_size: usize,
+        src_v: &[VF],
+        bk_hg: &[Vec<ChallengeF>],
+        gate_exists: &[Vec<bool>],
+        coefs: &mut [EvalF],
+        to_eval: impl Fn(ChallengeF) -> EvalF,
+    ) where
+        EvalF: Field + Mul<VF, Output = EvalF>,
+    {
+        for ((&d, bk_hg), gate_exists) in uni_degrees.iter().zip(bk_hg).zip(gate_exists) {
+            assert!(d.is_power_of_two());
+            for i in 0..eval_size {
+                if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
+                    continue;
+                }
+                let f_0_d = src_v[i * 2].exp(d as u128);
+                let delta_f_d = (src_v[i * 2 + 1] - src_v[i * 2]).exp(d as u128);
+                let hg_0 = to_eval(bk_hg[i * 2]);
+                let delta_hg = to_eval(bk_hg[i * 2 + 1] - bk_hg[i * 2]);
 
-        let mut p_add = [EvalF::ZERO; 3];
-        for i in 0..eval_size {
-            if !gate_exists_1[i * 2] && !gate_exists_1[i * 2 + 1] {
-                continue;
+                coefs[0] += hg_0 * f_0_d;
+                coefs[1] += delta_hg * f_0_d;
+                coefs[d] += hg_0 * delta_f_d;
+                coefs[d + 1] += delta_hg * delta_f_d;
             }
-            let mut f_v = [VF::ZERO; 3];
-            let mut hg_v = [ChallengeF::ZERO; 3];
-            f_v[0] = src_v[i * 2];
-            f_v[1] = src_v[i * 2 + 1];
-            hg_v[0] = bk_hg_1[i * 2];
-            hg_v[1] = bk_hg_1[i * 2 + 1];
-            p_add[0] += EvalF::from(hg_v[0]) * f_v[0];
-            p_add[1] += EvalF::from(hg_v[1]) * f_v[1];
-            p_add[2] += EvalF::from(hg_v[0] + hg_v[1]) * (f_v[0] + f_v[1]);
         }
-        p_add[2] = p_add[1].mul_by_6() + p_add[0].mul_by_3() - p_add[2].double();
-
-        // interpolate p_add into 7 points
-        p_add
-        // [p_add, p]
-        // Self::interpolate_3(&p_add, &mut p);
-        // p
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub(crate) fn poly_eval_at<F: FieldEngine>(
         &self,
         var_idx: usize,
         bk_f: &[F::Field],
-        bk_hg_5: &[F::ChallengeField],
-        bk_hg_1: &[F::ChallengeField],
+        bk_hg: &[Vec<F::ChallengeField>],
         init_v: &[F::SimdCircuitField],
-        gate_exists_5: &[bool],
-        gate_exists_1: &[bool],
-    ) -> [F::Field; D] {
-        let mut p = [F::Field::zero(); D];
+        gate_exists: &[Vec<bool>],
+    ) -> Vec<F::Field> {
         log::trace!("bk_f: {:?}", &bk_f[..4]);
-        log::trace!("bk_hg: {:?}", &bk_hg_5[..4]);
         log::trace!("init_v: {:?}", &init_v[..4]);
 
-        let eval_size = 1 << (self.var_num - var_idx - 1);
-        let p_add = {
-            if var_idx == 0 {
-                Self::evaluate(
-                    eval_size,
-                    init_v,
-                    bk_hg_5,
-                    bk_hg_1,
-                    gate_exists_5,
-                    gate_exists_1,
-                    &mut p,
-                )
-            } else {
-                Self::evaluate(
-                    eval_size,
-                    bk_f,
-                    bk_hg_5,
-                    bk_hg_1,
-                    gate_exists_5,
-                    gate_exists_1,
-                    &mut p,
-                )
-            }
-        };
+        let char_2 = F::FIELD_TYPE == FieldType::GF2Ext128;
+        let p = run_simd_pair_kernel::<F::SimdCircuitField, F::Field, _>(PowerGateEvaluation {
+            uni_degrees: &self.uni_degrees,
+            eval_size: 1 << (self.var_num - var_idx - 1),
+            len: self.degree + 1,
+            char_2,
+            init_v: (var_idx == 0).then_some(init_v),
+            bk_f,
+            bk_hg,
+            gate_exists,
+        });
 
-        Self::interpolate_3::<F>(&p_add, &mut p);
+        if char_2 {
+            // over GF2_128, the points are 0, 1, X, X^2, reached from the coefficients
+            return gf2_evals_from_coefs(&p);
+        }
         p
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub(crate) fn receive_challenge<F: FieldEngine>(
         &mut self,
         var_idx: usize,
         r: F::ChallengeField,
         bk_f: &mut [F::Field],
-        bk_hg_5: &mut [F::ChallengeField],
-        bk_hg_1: &mut [F::ChallengeField],
+        bk_hg: &mut [Vec<F::ChallengeField>],
         init_v: &[F::SimdCircuitField],
-        gate_exists_5: &mut [bool],
-        gate_exists_1: &mut [bool],
+        gate_exists: &mut [Vec<bool>],
     ) {
         assert_eq!(var_idx, self.sumcheck_var_idx);
         assert!(var_idx < self.var_num);
@@ -168,35 +151,138 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
 
         self.cur_eval_size >>= 1;
 
-        if var_idx == 0 {
+        run_simd_pair_kernel::<F::SimdCircuitField, F::Field, _>(PowerGateChallenge {
+            eval_size: self.cur_eval_size,
+            r: F::Field::from(r),
+            init_v: (var_idx == 0).then_some(init_v),
+            bk_f,
+        });
+
+        for (bk_hg, gate_exists) in bk_hg.iter_mut().zip(gate_exists.iter_mut()) {
             for i in 0..self.cur_eval_size {
-                bk_f[i] = r * (init_v[2 * i + 1] - init_v[2 * i]) + init_v[2 * i];
+                if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
+                    gate_exists[i] = false;
+                    bk_hg[i] = F::ChallengeField::zero();
+                } else {
+                    gate_exists[i] = true;
+                    bk_hg[i] = bk_hg[2 * i] + (bk_hg[2 * i + 1] - bk_hg[2 * i]) * r;
+                }
             }
         }
-        {
-            for i in 0..self.cur_eval_size {
-                bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]).scale(&r);
-            }
+
+        self.sumcheck_var_idx += 1;
+    }
+}
+
+/// The evaluations, or the coefficients over GF2_128, of the power gates along the current
+/// variable, run on the fields of the engine or their AVX-512 twins
+struct PowerGateEvaluation<'a, VF, ChallengeF, EvalF> {
+    uni_degrees: &'a [usize],
+    eval_size: usize,
+    // the number of evaluations, or coefficients
+    len: usize,
+    char_2: bool,
+    // the input values in place of bk_f at the first variable
+    init_v: Option<&'a [VF]>,
+    bk_f: &'a [EvalF],
+    bk_hg: &'a [Vec<ChallengeF>],
+    gate_exists: &'a [Vec<bool>],
+}
+
+impl<VF, ChallengeF, EvalF> SimdPairKernel for PowerGateEvaluation<'_, VF, ChallengeF, EvalF>
+where
+    VF: Field,
+    ChallengeF: Field,
+    EvalF: Field + From<ChallengeF>,
+{
+    type Output = Vec<EvalF>;
+
+    #[inline(always)]
+    fn run<V, E>(self) -> Vec<EvalF>
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    {
+        // the values of hg are packed into the field of the engine, then into its twin
+        let to_eval = |hg: ChallengeF| twin_value::<EvalF, E>(EvalF::from(hg));
+        let mut p = vec![E::ZERO; self.len];
+        match (self.char_2, self.init_v) {
+            (true, Some(init_v)) => SumcheckPowerGateHelper::evaluate_coefs_char_2(
+                self.uni_degrees,
+                self.eval_size,
+                twin_slice::<_, V>(init_v),
+                self.bk_hg,
+                self.gate_exists,
+                &mut p,
+                to_eval,
+            ),
+            (true, None) => SumcheckPowerGateHelper::evaluate_coefs_char_2(
+                self.uni_degrees,
+                self.eval_size,
+                twin_slice::<_, E>(self.bk_f),
+                self.bk_hg,
+                self.gate_exists,
+                &mut p,
+                to_eval,
+            ),
+            (false, Some(init_v)) => SumcheckPowerGateHelper::evaluate(
+                self.uni_degrees,
+                self.eval_size,
+                twin_slice::<_, V>(init_v),
+                self.bk_hg,
+                self.gate_exists,
+                &mut p,
+                to_eval,
+            ),
+            (false, None) => SumcheckPowerGateHelper::evaluate(
+                self.uni_degrees,
+                self.eval_size,
+                twin_slice::<_, E>(self.bk_f),
+                self.bk_hg,
+                self.gate_exists,
+                &mut p,
+                to_eval,
+            ),
         }
+        p.into_iter().map(twin_value::<E, EvalF>).collect()
+    }
+}
 
-        for i in 0..self.cur_eval_size {
-            if !gate_exists_5[i * 2] && !gate_exists_5[i * 2 + 1] {
-                gate_exists_5[i] = false;
-                bk_hg_5[i] = F::ChallengeField::zero();
-            } else {
-                gate_exists_5[i] = true;
-                bk_hg_5[i] = bk_hg_5[2 * i] + (bk_hg_5[2 * i + 1] - bk_hg_5[2 * i]) * r;
-            }
+/// The folding of the bookkeeping table of f at a challenge, run on the fields of the engine or
+/// their AVX-512 twins
+struct PowerGateChallenge<'a, VF, EvalF> {
+    eval_size: usize,
+    // the challenge, packed
+    r: EvalF,
+    // the input values folded into bk_f at the first variable
+    init_v: Option<&'a [VF]>,
+    bk_f: &'a mut [EvalF],
+}
+
+impl<VF: Field, EvalF: Field> SimdPairKernel for PowerGateChallenge<'_, VF, EvalF> {
+    type Output = ();
 
-            if !gate_exists_1[i * 2] && !gate_exists_1[i * 2 + 1] {
-                gate_exists_1[i] = false;
-                bk_hg_1[i] = F::ChallengeField::zero();
-            } else {
-                gate_exists_1[i] = true;
-                bk_hg_1[i] = bk_hg_1[2 * i] + (bk_hg_1[2 * i + 1] - bk_hg_1[2 * i]) * r;
+    #[inline(always)]
+    fn run<V, E>(self)
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    {
+        let r = twin_value::<_, E>(self.r);
+        let bk_f = twin_slice_mut::<_, E>(self.bk_f);
+
+        match self.init_v {
+            Some(init_v) => {
+                let init_v = twin_slice::<_, V>(init_v);
+                for i in 0..self.eval_size {
+                    bk_f[i] = r * (init_v[2 * i + 1] - init_v[2 * i]) + init_v[2 * i];
+                }
+            }
+            None => {
+                for i in 0..self.eval_size {
+                    bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]) * r;
+                }
             }
         }
-
-        self.sumcheck_var_idx += 1;
     }
 }
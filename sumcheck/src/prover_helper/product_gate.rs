@@ -1,10 +1,13 @@
 //! This module implements helper functions for the prover side of the sumcheck protocol
 //! to evaluate Mul gates
 
-use std::ops::Mul;
+use std::ops::{Add, Mul};
 
-use arith::{ExtensionField, Field, SimdField};
-use gkr_engine::{FieldEngine, FieldType};
+use arith::{ExtensionField, Field};
+use gkr_engine::{
+    run_simd_pair_kernel, twin_slice, twin_slice_mut, twin_value, FieldEngine, FieldType,
+    SimdPairKernel,
+};
 
 pub(crate) struct SumcheckProductGateHelper {
     var_num: usize,
@@ -15,7 +18,7 @@ impl SumcheckProductGateHelper {
         SumcheckProductGateHelper { var_num }
     }
 
-    #[inline]
+    #[inline(always)]
     fn evaluate<VF: Field, EvalF>(
         eval_size: usize,
         bk_f: &[VF],
@@ -79,13 +82,14 @@ impl SumcheckProductGateHelper {
         let eval_size = 1 << (self.var_num - var_idx - 1);
         log::trace!("Eval size: {eval_size}");
 
-        let [p0, p1, mut p2] = {
-            if var_idx == 0 {
-                Self::evaluate(eval_size, init_v, bk_hg, gate_exists)
-            } else {
-                Self::evaluate(eval_size, bk_f, bk_hg, gate_exists)
-            }
-        };
+        let [p0, p1, mut p2] =
+            run_simd_pair_kernel::<F::SimdCircuitField, F::Field, _>(ProductGateEvaluation {
+                eval_size,
+                init_v: (var_idx == 0).then_some(init_v),
+                bk_f,
+                bk_hg,
+                gate_exists,
+            });
 
         if F::FIELD_TYPE == FieldType::GF2Ext128 {
             // over GF2_128, the three points are at 0, 1 and X
@@ -115,23 +119,104 @@ impl SumcheckProductGateHelper {
 
         let eval_size = 1 << (self.var_num - var_idx - 1);
 
-        if var_idx == 0 {
-            for i in 0..eval_size {
-                bk_f[i] = r * (init_v[2 * i + 1] - init_v[2 * i]) + init_v[2 * i];
+        run_simd_pair_kernel::<F::SimdCircuitField, F::Field, _>(ProductGateChallenge {
+            eval_size,
+            r: F::Field::from(r),
+            init_v: (var_idx == 0).then_some(init_v),
+            bk_f,
+            bk_hg,
+            gate_exists,
+        });
+    }
+}
+
+/// The evaluations of the product at 0, 1 and 2, run on the fields of the engine or their AVX-512
+/// twins
+struct ProductGateEvaluation<'a, VF, EvalF> {
+    eval_size: usize,
+    // the input values in place of bk_f at the first variable
+    init_v: Option<&'a [VF]>,
+    bk_f: &'a [EvalF],
+    bk_hg: &'a [EvalF],
+    gate_exists: &'a [bool],
+}
+
+impl<VF: Field, EvalF: Field> SimdPairKernel for ProductGateEvaluation<'_, VF, EvalF> {
+    type Output = [EvalF; 3];
+
+    #[inline(always)]
+    fn run<V, E>(self) -> [EvalF; 3]
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    {
+        let bk_hg = twin_slice::<_, E>(self.bk_hg);
+        let evals = match self.init_v {
+            Some(init_v) => SumcheckProductGateHelper::evaluate(
+                self.eval_size,
+                twin_slice::<_, V>(init_v),
+                bk_hg,
+                self.gate_exists,
+            ),
+            None => SumcheckProductGateHelper::evaluate(
+                self.eval_size,
+                twin_slice::<_, E>(self.bk_f),
+                bk_hg,
+                self.gate_exists,
+            ),
+        };
+        evals.map(twin_value::<E, EvalF>)
+    }
+}
+
+/// The folding of the bookkeeping tables at a challenge, run on the fields of the engine or their
+/// AVX-512 twins
+struct ProductGateChallenge<'a, VF, EvalF> {
+    eval_size: usize,
+    // the challenge, packed
+    r: EvalF,
+    // the input values folded into bk_f at the first variable
+    init_v: Option<&'a [VF]>,
+    bk_f: &'a mut [EvalF],
+    bk_hg: &'a mut [EvalF],
+    gate_exists: &'a mut [bool],
+}
+
+impl<VF: Field, EvalF: Field> SimdPairKernel for ProductGateChallenge<'_, VF, EvalF> {
+    type Output = ();
+
+    #[inline(always)]
+    fn run<V, E>(self)
+    where
+        V: Field,
+        E: Field + Mul<V, Output = E> + Add<V, Output = E>,
+    {
+        let r = twin_value::<_, E>(self.r);
+        let bk_f = twin_slice_mut::<_, E>(self.bk_f);
+        let bk_hg = twin_slice_mut::<_, E>(self.bk_hg);
+        let gate_exists = self.gate_exists;
+
+        match self.init_v {
+            Some(init_v) => {
+                let init_v = twin_slice::<_, V>(init_v);
+                for i in 0..self.eval_size {
+                    bk_f[i] = r * (init_v[2 * i + 1] - init_v[2 * i]) + init_v[2 * i];
+                }
             }
-        } else {
-            for i in 0..eval_size {
-                bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]).scale(&r);
+            None => {
+                for i in 0..self.eval_size {
+                    bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]) * r;
+                }
             }
         }
 
-        for i in 0..eval_size {
+        for i in 0..self.eval_size {
             if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                 gate_exists[i] = false;
-                bk_hg[i] = F::Field::zero();
+                bk_hg[i] = E::zero();
             } else {
                 gate_exists[i] = true;
-                bk_hg[i] = bk_hg[2 * i] + (bk_hg[2 * i + 1] - bk_hg[2 * i]).scale(&r);
+                bk_hg[i] = bk_hg[2 * i] + (bk_hg[2 * i + 1] - bk_hg[2 * i]) * r;
             }
         }
     }
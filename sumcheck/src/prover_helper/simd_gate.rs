@@ -5,7 +5,9 @@
 use std::marker::PhantomData;
 
 use arith::{ExtensionField, Field};
-use gkr_engine::{FieldEngine, FieldType};
+use gkr_engine::{run_simd_kernel, FieldEngine, FieldType, SimdKernel};
+
+use crate::utils::{extend_evals, gf2_evals_from_coefs};
 
 pub(crate) struct SumcheckSimdProdGateHelper<F: FieldEngine> {
     pub(crate) var_num: usize,
@@ -31,12 +33,86 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
         bk_hg: &mut [F::ChallengeField],
     ) -> [F::ChallengeField; 4] {
         assert_eq!(degree, 3);
+        run_simd_kernel::<F::ChallengeField, _>(SimdGateEvaluation::<F> {
+            eval_size: 1 << (self.var_num - var_idx - 1),
+            bk_eq,
+            bk_f,
+            bk_hg,
+        })
+    }
+
+    /// Evaluate the GKR2 sumcheck polynomial at a SIMD variable,
+    /// after x-sumcheck rounds have fixed the x variables. The
+    /// polynomial is of degree `degree` in the SIMD variables.
+    ///
+    /// `uni_evals` are the evaluations of the power gates of each degree of `uni_degrees`.
+    pub(crate) fn gkr2_poly_eval_at(
+        &self,
+        var_idx: usize,
+        degree: usize,
+        bk_eq: &[F::ChallengeField],
+        bk_v_simd: &[F::ChallengeField],
+        uni_degrees: &[usize],
+        uni_evals: &[F::ChallengeField],
+    ) -> Vec<F::ChallengeField> {
+        run_simd_kernel::<F::ChallengeField, _>(SimdGateGkr2Evaluation::<F> {
+            eval_size: 1 << (self.var_num - var_idx - 1),
+            degree,
+            bk_eq,
+            bk_v_simd,
+            uni_degrees,
+            uni_evals,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn receive_challenge(
+        &mut self,
+        var_idx: usize,
+        r: F::ChallengeField,
+        bk_eq: &mut [F::ChallengeField],
+        bk_f: &mut [F::ChallengeField],
+        bk_hg: &mut [F::ChallengeField],
+    ) {
+        assert!(var_idx < self.var_num);
+
+        run_simd_kernel::<F::ChallengeField, _>(SimdGateChallenge::<F> {
+            eval_size: 1 << (self.var_num - var_idx - 1),
+            r,
+            bk_eq,
+            bk_f,
+            bk_hg,
+        });
+    }
+}
+
+// The challenge fields have no AVX-512 twin: the kernels below run on them, entered through an
+// AVX-512 function when the CPU supports it, see `gkr_engine::run_simd_kernel`.
+
+/// The evaluations of the SIMD gate product along the current variable
+struct SimdGateEvaluation<'a, F: FieldEngine> {
+    eval_size: usize,
+    bk_eq: &'a [F::ChallengeField],
+    bk_f: &'a [F::ChallengeField],
+    bk_hg: &'a [F::ChallengeField],
+}
+
+impl<F: FieldEngine> SimdKernel for SimdGateEvaluation<'_, F> {
+    type Output = [F::ChallengeField; 4];
+
+    #[inline(always)]
+    fn run<G: Field>(self) -> Self::Output {
         let mut p0 = F::ChallengeField::zero();
         let mut p1 = F::ChallengeField::zero();
         let mut p2 = F::ChallengeField::zero();
         let mut p3 = F::ChallengeField::zero();
 
-        let eval_size = 1 << (self.var_num - var_idx - 1);
+        let Self {
+            eval_size,
+            bk_eq,
+            bk_f,
+            bk_hg,
+        } = self;
 
         if F::FIELD_TYPE == FieldType::GF2Ext128 {
             for i in 0..eval_size {
@@ -87,95 +163,101 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
 
         [p0, p1, p2, p3]
     }
+}
 
-    /// Evaluate the GKR2 sumcheck polynomial at a SIMD variable,
-    /// after x-sumcheck rounds have fixed the x variables. The
-    /// polynomial is degree (D-1) in the SIMD variables.
-    pub(crate) fn gkr2_poly_eval_at<const D: usize>(
-        &self,
-        var_idx: usize,
-        bk_eq: &[F::ChallengeField],
-        bk_v_simd: &[F::ChallengeField],
-        add_eval: F::ChallengeField,
-        pow_5_eval: F::ChallengeField,
-    ) -> [F::ChallengeField; D] {
-        let mut p = [F::ChallengeField::zero(); D];
-        let mut p_add = [F::ChallengeField::zero(); 3];
-        let eval_size = 1 << (self.var_num - var_idx - 1);
+/// The evaluations of the GKR2 sumcheck polynomial along the current SIMD or MPI variable
+struct SimdGateGkr2Evaluation<'a, F: FieldEngine> {
+    eval_size: usize,
+    degree: usize,
+    bk_eq: &'a [F::ChallengeField],
+    bk_v_simd: &'a [F::ChallengeField],
+    uni_degrees: &'a [usize],
+    uni_evals: &'a [F::ChallengeField],
+}
 
-        for i in 0..eval_size {
-            // witness polynomial along current variable
-            let mut f_v = [F::ChallengeField::zero(); D];
-            // eq polynomial along current variable
-            let mut eq_v = [F::ChallengeField::zero(); D];
-            f_v[0] = bk_v_simd[i * 2];
-            f_v[1] = bk_v_simd[i * 2 + 1];
-            eq_v[0] = bk_eq[i * 2];
-            eq_v[1] = bk_eq[i * 2 + 1];
-
-            // Evaluate term eq(A, r_z) * Pow5(r_z, r_x) * V(A, r_x)^5
-            let delta_f = f_v[1] - f_v[0];
-            let delta_eq = eq_v[1] - eq_v[0];
-            for i in 2..D {
-                f_v[i] = f_v[i - 1] + delta_f;
-                eq_v[i] = eq_v[i - 1] + delta_eq;
+impl<F: FieldEngine> SimdKernel for SimdGateGkr2Evaluation<'_, F> {
+    type Output = Vec<F::ChallengeField>;
+
+    #[inline(always)]
+    fn run<G: Field>(self) -> Self::Output {
+        let Self {
+            eval_size,
+            degree,
+            bk_eq,
+            bk_v_simd,
+            uni_degrees,
+            uni_evals,
+        } = self;
+        let mut p = vec![F::ChallengeField::zero(); degree + 1];
+
+        if F::FIELD_TYPE == FieldType::GF2Ext128 {
+            // over GF2_128, the points are 0, 1, X, X^2, reached from the coefficients of
+            // eq(A, r_z) * V(A, r_x)^d, the degrees being powers of two
+            for (&d, &uni_eval) in uni_degrees.iter().zip(uni_evals) {
+                assert!(d.is_power_of_two());
+                for i in 0..eval_size {
+                    let f_0_d = bk_v_simd[i * 2].exp(d as u128) * uni_eval;
+                    let delta_f_d =
+                        (bk_v_simd[i * 2 + 1] - bk_v_simd[i * 2]).exp(d as u128) * uni_eval;
+                    let eq_0 = bk_eq[i * 2];
+                    let delta_eq = bk_eq[i * 2 + 1] - bk_eq[i * 2];
+
+                    p[0] += eq_0 * f_0_d;
+                    p[1] += delta_eq * f_0_d;
+                    p[d] += eq_0 * delta_f_d;
+                    p[d + 1] += delta_eq * delta_f_d;
+                }
             }
-            for i in 0..D {
-                let pow5 = f_v[i].square().square() * f_v[i];
-                p[i] += pow_5_eval * pow5 * eq_v[i];
+            return gf2_evals_from_coefs(&p);
+        }
+
+        for (&d, &uni_eval) in uni_degrees.iter().zip(uni_evals) {
+            // Evaluate term eq(A, r_z) * Pow_d(r_z, r_x) * V(A, r_x)^d, of degree d + 1
+            let mut p_d = vec![F::ChallengeField::zero(); d + 2];
+            for i in 0..eval_size {
+                // witness and eq polynomials along current variable
+                let mut f_v = bk_v_simd[i * 2];
+                let mut eq_v = bk_eq[i * 2];
+                let delta_f = bk_v_simd[i * 2 + 1] - f_v;
+                let delta_eq = bk_eq[i * 2 + 1] - eq_v;
+
+                for p_d_j in p_d.iter_mut() {
+                    *p_d_j += f_v.exp(d as u128) * eq_v;
+                    f_v += delta_f;
+                    eq_v += delta_eq;
+                }
             }
 
-            // Evaluate term eq(A, r_z) * Add(r_z, r_x) * V(A, r_x)
-            p_add[0] += add_eval * f_v[0] * eq_v[0];
-            p_add[1] += add_eval * f_v[1] * eq_v[1];
-            // Intermediate term for p_add[2]
-            p_add[2] += add_eval * (f_v[0] + f_v[1]) * (eq_v[0] + eq_v[1]);
+            // Extend p_d to the degree + 1 points of p
+            for (p_j, p_d_j) in p.iter_mut().zip(extend_evals(&p_d, degree + 1)) {
+                *p_j += uni_eval * p_d_j;
+            }
         }
-        p_add[2] = p_add[1].mul_by_6() + p_add[0].mul_by_3() - p_add[2].double();
-
-        // Interpolate p_add into 7 points, add to p
-        Self::interpolate_3::<D>(&p_add, &mut p);
         p
     }
+}
 
-    // Function to interpolate a quadratic polynomial and update an array of points
-    fn interpolate_3<const D: usize>(
-        p_add: &[F::ChallengeField; 3],
-        p: &mut [F::ChallengeField; D],
-    ) {
-        // Calculate coefficients for the interpolating polynomial
-        let p_add_coef_0 = p_add[0];
-        let p_add_coef_2 = (p_add[2] - p_add[1] - p_add[1] + p_add[0]) * F::CircuitField::INV_2;
-
-        let p_add_coef_1 = p_add[1] - p_add_coef_0 - p_add_coef_2;
-
-        // Update the p array by evaluating the interpolated polynomial at different points
-        // and adding the results to the existing values
-        p[0] += p_add_coef_0;
-        p[1] += p_add_coef_0 + p_add_coef_1 + p_add_coef_2;
-        p[2] += p_add_coef_0 + p_add_coef_1.double() + p_add_coef_2.double().double();
-        p[3] += p_add_coef_0 + p_add_coef_1.mul_by_3() + p_add_coef_2.mul_by_3().mul_by_3();
-        p[4] += p_add_coef_0
-            + p_add_coef_1.double().double()
-            + p_add_coef_2 * F::CircuitField::from(16);
-        p[5] += p_add_coef_0 + p_add_coef_1.mul_by_5() + p_add_coef_2 * F::CircuitField::from(25);
-        p[6] += p_add_coef_0
-            + p_add_coef_1.mul_by_3().double()
-            + p_add_coef_2 * F::CircuitField::from(36);
-    }
+/// The folding of the bookkeeping tables at a challenge
+struct SimdGateChallenge<'a, F: FieldEngine> {
+    eval_size: usize,
+    r: F::ChallengeField,
+    bk_eq: &'a mut [F::ChallengeField],
+    bk_f: &'a mut [F::ChallengeField],
+    bk_hg: &'a mut [F::ChallengeField],
+}
 
-    #[inline]
-    pub(crate) fn receive_challenge(
-        &mut self,
-        var_idx: usize,
-        r: F::ChallengeField,
-        bk_eq: &mut [F::ChallengeField],
-        bk_f: &mut [F::ChallengeField],
-        bk_hg: &mut [F::ChallengeField],
-    ) {
-        assert!(var_idx < self.var_num);
+impl<F: FieldEngine> SimdKernel for SimdGateChallenge<'_, F> {
+    type Output = ();
 
-        let eval_size = 1 << (self.var_num - var_idx - 1);
+    #[inline(always)]
+    fn run<G: Field>(self) {
+        let Self {
+            eval_size,
+            r,
+            bk_eq,
+            bk_f,
+            bk_hg,
+        } = self;
         for i in 0..eval_size {
             bk_eq[i] = bk_eq[2 * i] + (bk_eq[2 * i + 1] - bk_eq[2 * i]) * r;
             bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]) * r;
@@ -1,4 +1,4 @@
-use crate::{unpack_and_combine, ProverScratchPad};
+use crate::{sumcheck_gkr_square_degree, unpack_and_combine, ProverScratchPad};
 use arith::{Field, SimdField};
 use circuit::CircuitLayer;
 use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine};
@@ -6,8 +6,7 @@ use polynomials::EqPolynomial;
 
 use super::{power_gate::SumcheckPowerGateHelper, simd_gate::SumcheckSimdProdGateHelper};
 
-// todo: Move D to GKRFieldConfig
-pub(crate) struct SumcheckGkrSquareHelper<'a, F: FieldEngine, const D: usize> {
+pub(crate) struct SumcheckGkrSquareHelper<'a, F: FieldEngine> {
     pub(crate) rx: Vec<F::ChallengeField>,
     pub(crate) r_simd_var: Vec<F::ChallengeField>,
     pub(crate) r_mpi_var: Vec<F::ChallengeField>,
@@ -19,7 +18,12 @@ pub(crate) struct SumcheckGkrSquareHelper<'a, F: FieldEngine, const D: usize> {
 
     pub(crate) simd_var_num: usize,
 
-    x_helper: SumcheckPowerGateHelper<D>,
+    /// The distinct degrees of the uni gates of the layer
+    uni_degrees: Vec<usize>,
+    /// The degree of the sumcheck polynomial of the layer
+    degree: usize,
+
+    x_helper: SumcheckPowerGateHelper,
     simd_helper: SumcheckSimdProdGateHelper<F>,
     mpi_helper: SumcheckSimdProdGateHelper<F>,
 
@@ -27,7 +31,7 @@ pub(crate) struct SumcheckGkrSquareHelper<'a, F: FieldEngine, const D: usize> {
     mpi_config: &'a MPIConfig<'a>,
 }
 
-impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
+impl<'a, F: FieldEngine> SumcheckGkrSquareHelper<'a, F> {
     #[inline]
     pub(crate) fn new(
         layer: &'a CircuitLayer<F>,
@@ -36,6 +40,8 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
         mpi_config: &'a MPIConfig,
     ) -> Self {
         let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+        let uni_degrees = layer.uni_degrees();
+        let degree = sumcheck_gkr_square_degree(layer);
 
         SumcheckGkrSquareHelper {
             rx: vec![],
@@ -47,25 +53,35 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             challenge,
             simd_var_num,
 
-            x_helper: SumcheckPowerGateHelper::new(layer.input_var_num),
+            x_helper: SumcheckPowerGateHelper::new(
+                layer.input_var_num,
+                uni_degrees.clone(),
+                degree,
+            ),
             simd_helper: SumcheckSimdProdGateHelper::new(simd_var_num),
             mpi_helper: SumcheckSimdProdGateHelper::new(
                 mpi_config.world_size().trailing_zeros() as usize
             ),
             mpi_config,
+            uni_degrees,
+            degree,
         }
     }
 
+    /// The evaluations of the uni gates of each degree, once all x variables are bound
+    #[inline]
+    fn uni_evals(&self) -> Vec<F::ChallengeField> {
+        self.sp.hg_evals_uni.iter().map(|hg| hg[0]).collect()
+    }
+
     #[inline]
-    pub(crate) fn poly_evals_at_x(&self, var_idx: usize) -> [F::ChallengeField; D] {
+    pub(crate) fn poly_evals_at_x(&self, var_idx: usize) -> Vec<F::ChallengeField> {
         let local_vals_simd = self.x_helper.poly_eval_at::<F>(
             var_idx,
             &self.sp.v_evals,
-            &self.sp.hg_evals_5,
-            &self.sp.hg_evals_1,
+            &self.sp.hg_evals_uni,
             &self.layer.input_vals,
-            &self.sp.gate_exists_5,
-            &self.sp.gate_exists_1,
+            &self.sp.gate_exists_uni,
         );
 
         // SIMD
@@ -76,37 +92,32 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
 
         self.mpi_config
             .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
-            .try_into()
-            .unwrap()
     }
 
     #[inline]
-    pub(crate) fn poly_evals_at_simd(&self, var_idx: usize) -> [F::ChallengeField; D] {
-        let local_vals = self
-            .simd_helper
-            .gkr2_poly_eval_at::<D>(
-                var_idx,
-                &self.sp.eq_evals_at_r_simd0,
-                &self.sp.simd_var_v_evals,
-                self.sp.hg_evals_1[0],
-                self.sp.hg_evals_5[0],
-            )
-            .to_vec();
+    pub(crate) fn poly_evals_at_simd(&self, var_idx: usize) -> Vec<F::ChallengeField> {
+        let local_vals = self.simd_helper.gkr2_poly_eval_at(
+            var_idx,
+            self.degree,
+            &self.sp.eq_evals_at_r_simd0,
+            &self.sp.simd_var_v_evals,
+            &self.uni_degrees,
+            &self.uni_evals(),
+        );
 
         self.mpi_config
             .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
-            .try_into()
-            .unwrap()
     }
 
-    pub(crate) fn poly_evals_at_mpi(&mut self, var_idx: usize) -> [F::ChallengeField; D] {
+    pub(crate) fn poly_evals_at_mpi(&mut self, var_idx: usize) -> Vec<F::ChallengeField> {
         assert!(var_idx < self.mpi_config.world_size().trailing_zeros() as usize);
-        let mut evals = self.mpi_helper.gkr2_poly_eval_at::<D>(
+        let mut evals = self.mpi_helper.gkr2_poly_eval_at(
             var_idx,
+            self.degree,
             &self.sp.eq_evals_at_r_mpi0,
             &self.sp.mpi_var_v_evals,
-            self.sp.hg_evals_1[0],
-            self.sp.hg_evals_5[0],
+            &self.uni_degrees,
+            &self.uni_evals(),
         );
         for eval in evals.iter_mut() {
             *eval *= self.sp.eq_evals_at_r_simd0[0];
@@ -120,11 +131,9 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             var_idx,
             r,
             &mut self.sp.v_evals,
-            &mut self.sp.hg_evals_5,
-            &mut self.sp.hg_evals_1,
+            &mut self.sp.hg_evals_uni,
             &self.layer.input_vals,
-            &mut self.sp.gate_exists_5,
-            &mut self.sp.gate_exists_1,
+            &mut self.sp.gate_exists_uni,
         );
         log::trace!("v_eval[0]:= {:?}", self.sp.v_evals[0]);
         self.rx.push(r);
@@ -198,20 +207,23 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
         let uni = &self.layer.uni; // univariate things like square, pow5, etc.
         let vals = &self.layer.input_vals;
         let eq_evals_at_rz0 = &mut self.sp.eq_evals_at_rz0;
-        let gate_exists_5 = &mut self.sp.gate_exists_5;
-        let gate_exists_1 = &mut self.sp.gate_exists_1;
-        let hg_evals_5 = &mut self.sp.hg_evals_5;
-        let hg_evals_1 = &mut self.sp.hg_evals_1;
-        // hg_vals[0..vals.len()].fill(F::zero()); // FIXED: consider memset unsafe?
-        unsafe {
-            std::ptr::write_bytes(hg_evals_5.as_mut_ptr(), 0, vals.len());
-            std::ptr::write_bytes(hg_evals_1.as_mut_ptr(), 0, vals.len());
-        }
-        // gate_exists[0..vals.len()].fill(false); // FIXED: consider memset unsafe?
-        unsafe {
-            std::ptr::write_bytes(gate_exists_5.as_mut_ptr(), 0, vals.len());
-            std::ptr::write_bytes(gate_exists_1.as_mut_ptr(), 0, vals.len());
+
+        // one table per degree of uni gates, kept across layers
+        let n_degrees = self.uni_degrees.len();
+        self.sp.hg_evals_uni.resize_with(n_degrees, Vec::new);
+        self.sp.gate_exists_uni.resize_with(n_degrees, Vec::new);
+        for (hg_evals, gate_exists) in self
+            .sp
+            .hg_evals_uni
+            .iter_mut()
+            .zip(self.sp.gate_exists_uni.iter_mut())
+        {
+            hg_evals.clear();
+            hg_evals.resize(vals.len(), F::ChallengeField::zero());
+            gate_exists.clear();
+            gate_exists.resize(vals.len(), false);
         }
+
         EqPolynomial::<F::ChallengeField>::eq_eval_at(
             &self.challenge.rz,
             &F::ChallengeField::one(),
@@ -221,17 +233,10 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
         );
 
         for g in uni.iter() {
-            match g.gate_type {
-                12345 => {
-                    hg_evals_5[g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
-                    gate_exists_5[g.i_ids[0]] = true;
-                }
-                12346 => {
-                    hg_evals_1[g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
-                    gate_exists_1[g.i_ids[0]] = true;
-                }
-                _ => panic!("Unsupported gate type"),
-            }
+            let degree = g.uni_type().degree();
+            let k = self.uni_degrees.binary_search(&degree).unwrap();
+            self.sp.hg_evals_uni[k][g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
+            self.sp.gate_exists_uni[k][g.i_ids[0]] = true;
         }
     }
 }
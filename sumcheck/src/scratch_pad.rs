@@ -6,11 +6,13 @@ use arith::{ExtensionField, Field};
 use circuit::Circuit;
 use gkr_engine::{FieldEngine, FieldType};
 
+use crate::SUMCHECK_GKR_SQUARE_MAX_DEGREE;
+
 #[derive(Clone, Debug, Default)]
 pub struct ProverScratchPad<F: FieldEngine> {
     pub v_evals: Vec<F::Field>,
-    pub hg_evals_5: Vec<F::ChallengeField>,
-    pub hg_evals_1: Vec<F::ChallengeField>,
+    /// GKR square bookkeeping tables, one per degree of uni gates of the layer
+    pub hg_evals_uni: Vec<Vec<F::ChallengeField>>,
     pub hg_evals: Vec<F::Field>,
     pub simd_var_v_evals: Vec<F::ChallengeField>,
     pub simd_var_hg_evals: Vec<F::ChallengeField>,
@@ -24,8 +26,8 @@ pub struct ProverScratchPad<F: FieldEngine> {
     pub eq_evals_first_half: Vec<F::ChallengeField>,
     pub eq_evals_second_half: Vec<F::ChallengeField>,
 
-    pub gate_exists_5: Vec<bool>,
-    pub gate_exists_1: Vec<bool>,
+    pub gate_exists: Vec<bool>,
+    pub gate_exists_uni: Vec<Vec<bool>>,
 
     pub phase2_coef: F::ChallengeField,
 }
@@ -37,8 +39,7 @@ impl<F: FieldEngine> ProverScratchPad<F> {
         let max_io_size = max(max_input_size, max_output_size);
         ProverScratchPad {
             v_evals: vec![F::Field::default(); max_input_size],
-            hg_evals_5: vec![F::ChallengeField::default(); max_input_size],
-            hg_evals_1: vec![F::ChallengeField::default(); max_input_size],
+            hg_evals_uni: vec![],
             hg_evals: vec![F::Field::default(); max_input_size],
             simd_var_v_evals: vec![F::ChallengeField::default(); F::get_field_pack_size()],
             simd_var_hg_evals: vec![F::ChallengeField::default(); F::get_field_pack_size()],
@@ -64,8 +65,8 @@ impl<F: FieldEngine> ProverScratchPad<F> {
                 )
             ],
 
-            gate_exists_5: vec![false; max_input_size],
-            gate_exists_1: vec![false; max_input_size],
+            gate_exists: vec![false; max_input_size],
+            gate_exists_uni: vec![],
             phase2_coef: F::ChallengeField::ZERO,
         }
     }
@@ -93,9 +94,11 @@ pub struct VerifierScratchPad<F: FieldEngine> {
     pub gf2_deg2_eval_coef: F::ChallengeField, // 1 / x(x - 1)
     pub deg3_eval_at: [F::ChallengeField; 4],
     pub deg3_lag_denoms_inv: [F::ChallengeField; 4],
-    // ====== for deg6 eval ======
-    pub deg6_eval_at: [F::ChallengeField; 7],
-    pub deg6_lag_denoms_inv: [F::ChallengeField; 7],
+    // ====== for the eval of GKR square degrees ======
+    /// 0, 1, ..., SUMCHECK_GKR_SQUARE_MAX_DEGREE
+    pub deg_n_eval_at: Vec<F::ChallengeField>,
+    /// The Lagrange denominators of the first n + 1 points of `deg_n_eval_at`, indexed by n
+    pub deg_n_lag_denoms_inv: Vec<Vec<F::ChallengeField>>,
 }
 
 impl<F: FieldEngine> VerifierScratchPad<F> {
@@ -108,6 +111,12 @@ impl<F: FieldEngine> VerifierScratchPad<F> {
             .unwrap();
         max_num_var = max(max_num_var, circuit.log_input_size());
 
+        Self::with_max_num_var(max_num_var, mpi_world_size)
+    }
+
+    /// A scratch pad for layers of at most `2^max_num_var` gates, for circuits that are not
+    /// given as a layered `Circuit`.
+    pub fn with_max_num_var(max_num_var: usize, mpi_world_size: usize) -> Self {
         let max_io_size = 1usize << max_num_var;
         let simd_size = F::get_field_pack_size();
 
@@ -148,27 +157,26 @@ impl<F: FieldEngine> VerifierScratchPad<F> {
             deg3_lag_denoms_inv[i] = denominator.inv().unwrap();
         }
 
-        let deg6_eval_at = [
-            F::ChallengeField::ZERO,
-            F::ChallengeField::ONE,
-            F::ChallengeField::from(2),
-            F::ChallengeField::from(3),
-            F::ChallengeField::from(4),
-            F::ChallengeField::from(5),
-            F::ChallengeField::from(6),
-        ];
-
-        let mut deg6_lag_denoms_inv = [F::ChallengeField::ZERO; 7];
-        for i in 0..7 {
-            let mut denominator = F::ChallengeField::ONE;
-            for j in 0..7 {
-                if j == i {
-                    continue;
-                }
-                denominator *= deg6_eval_at[i] - deg6_eval_at[j];
-            }
-            deg6_lag_denoms_inv[i] = denominator.inv().unwrap();
-        }
+        let deg_n_eval_at = (0..=SUMCHECK_GKR_SQUARE_MAX_DEGREE)
+            .map(|i| F::ChallengeField::from(i as u32))
+            .collect::<Vec<_>>();
+
+        let deg_n_lag_denoms_inv = (0..=SUMCHECK_GKR_SQUARE_MAX_DEGREE)
+            .map(|n| {
+                (0..=n)
+                    .map(|i| {
+                        let mut denominator = F::ChallengeField::ONE;
+                        for j in 0..=n {
+                            if j == i {
+                                continue;
+                            }
+                            denominator *= deg_n_eval_at[i] - deg_n_eval_at[j];
+                        }
+                        denominator.inv().unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
 
         Self {
             eq_evals_at_rz0: vec![F::ChallengeField::zero(); max_io_size],
@@ -195,8 +203,8 @@ impl<F: FieldEngine> VerifierScratchPad<F> {
             gf2_deg2_eval_coef,
             deg3_eval_at,
             deg3_lag_denoms_inv,
-            deg6_eval_at,
-            deg6_lag_denoms_inv,
+            deg_n_eval_at,
+            deg_n_lag_denoms_inv,
         }
     }
 }
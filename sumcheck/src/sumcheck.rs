@@ -1,4 +1,4 @@
-use circuit::CircuitLayer;
+use circuit::{CircuitLayer, MAX_UNI_GATE_DEGREE};
 use gkr_engine::{
     ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
     Transcript,
@@ -6,7 +6,7 @@ use gkr_engine::{
 
 use crate::{
     prover_helper::{SumcheckGkrSquareHelper, SumcheckGkrVanillaHelper},
-    utils::transcript_io,
+    utils::{transcript_io, SumcheckProverIO, TranscriptProverIO},
     ProverScratchPad,
 };
 
@@ -15,9 +15,26 @@ use crate::{
 pub const SUMCHECK_GKR_DEGREE: usize = 2;
 pub const SUMCHECK_GKR_SIMD_MPI_DEGREE: usize = 3;
 
-/// The degree of the polynomial for sumcheck in the GKR square case.
-/// It is 6 for both SIMD/MPI and non-SIMD/MPI variables.
-pub const SUMCHECK_GKR_SQUARE_DEGREE: usize = 6;
+/// The degree of the polynomial for sumcheck of a layer in the GKR square case, for both SIMD/MPI
+/// and non-SIMD/MPI variables. It is one more than the highest degree of the uni gates of the
+/// layer, e.g. 6 for a layer of pow5 gates.
+#[inline]
+pub fn sumcheck_gkr_square_degree<F: FieldEngine>(layer: &CircuitLayer<F>) -> usize {
+    layer.uni_degree().max(1) + 1
+}
+
+/// Whether GKR square supports the uni gates of the layer over the field of `F`, see
+/// `UniGateType::is_supported_over`.
+#[inline]
+pub fn gkr_square_supports_layer<F: FieldEngine>(layer: &CircuitLayer<F>) -> bool {
+    layer
+        .uni
+        .iter()
+        .all(|gate| gate.uni_type().is_supported_over(F::FIELD_TYPE))
+}
+
+/// The highest degree of the polynomial for sumcheck in the GKR square case.
+pub const SUMCHECK_GKR_SQUARE_MAX_DEGREE: usize = MAX_UNI_GATE_DEGREE + 1;
 
 // FIXME
 #[allow(clippy::too_many_arguments)]
@@ -32,6 +49,33 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     sp: &mut ProverScratchPad<F>,
     mpi_config: &impl MPIEngine,
     is_output_layer: bool,
+) -> (F::ChallengeField, Option<F::ChallengeField>) {
+    let mut io = TranscriptProverIO {
+        transcript,
+        mpi_config,
+    };
+    sumcheck_prove_gkr_layer_with_io(
+        layer,
+        challenge,
+        alpha,
+        &mut io,
+        sp,
+        mpi_config,
+        is_output_layer,
+    )
+}
+
+/// Same as `sumcheck_prove_gkr_layer`, except that the messages to the verifier go through `io`.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn sumcheck_prove_gkr_layer_with_io<F: FieldEngine>(
+    layer: &CircuitLayer<F>,
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    alpha: Option<F::ChallengeField>,
+    io: &mut impl SumcheckProverIO<F::ChallengeField>,
+    sp: &mut ProverScratchPad<F>,
+    mpi_config: &impl MPIEngine,
+    is_output_layer: bool,
 ) -> (F::ChallengeField, Option<F::ChallengeField>) {
     let mut helper =
         SumcheckGkrVanillaHelper::new(layer, challenge, alpha, sp, mpi_config, is_output_layer);
@@ -43,7 +87,7 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     helper.prepare_x_vals();
     for i_var in 0..helper.input_var_num {
         let evals = helper.poly_evals_at_rx(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = io.send_round_poly(&evals);
         helper.receive_rx(i_var, r);
         log::trace!("x i_var={i_var} evals: {evals:?} r: {r:?}");
     }
@@ -52,7 +96,7 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     for i_var in 0..helper.simd_var_num {
         let evals =
             helper.poly_evals_at_r_simd_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = io.send_round_poly(&evals);
         helper.receive_r_simd_var(i_var, r);
         log::trace!("SIMD i_var={i_var} evals: {evals:?} r: {r:?}");
     }
@@ -60,12 +104,12 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     helper.prepare_mpi_var_vals(mpi_config);
     for i_var in 0..mpi_config.world_size().trailing_zeros() as usize {
         let evals = helper.poly_evals_at_r_mpi_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = io.send_round_poly(&evals);
         helper.receive_r_mpi_var(i_var, r);
     }
 
     let vx_claim = helper.vx_claim();
-    transcript.append_field_element(&vx_claim);
+    io.send_claim(&vx_claim);
 
     // gkr phase 2 over variable y
     let mut vy_claim = None;
@@ -73,11 +117,11 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
         helper.prepare_y_vals(mpi_config);
         for i_var in 0..helper.input_var_num {
             let evals = helper.poly_evals_at_ry(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-            let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+            let r = io.send_round_poly(&evals);
             helper.receive_ry(i_var, r);
         }
         vy_claim = Some(helper.vy_claim(mpi_config));
-        transcript.append_field_element(&vy_claim.unwrap());
+        io.send_claim(&vy_claim.unwrap());
     }
 
     let rx = helper.rx;
@@ -103,8 +147,7 @@ pub fn sumcheck_prove_gkr_square_layer<F: FieldEngine, T: Transcript>(
     sp: &mut ProverScratchPad<F>,
     mpi_config: &MPIConfig,
 ) {
-    const D: usize = SUMCHECK_GKR_SQUARE_DEGREE + 1;
-    let mut helper = SumcheckGkrSquareHelper::<F, D>::new(layer, challenge, sp, mpi_config);
+    let mut helper = SumcheckGkrSquareHelper::new(layer, challenge, sp, mpi_config);
 
     helper.prepare_simd();
     helper.prepare_mpi();
@@ -1,6 +1,8 @@
-use arith::{ExtensionField, SimdField};
+use arith::{ExtensionField, Field, SimdField};
 use gkr_engine::{MPIEngine, Transcript};
 
+use crate::SUMCHECK_GKR_SQUARE_MAX_DEGREE;
+
 #[inline(always)]
 /// Input
 /// - a SIMD field, denoted by p := [p0, ... pn]
@@ -29,9 +31,9 @@ where
     F: ExtensionField,
     T: Transcript,
 {
-    // 3 for x, y; 4 for simd var; 7 for pow5, 9 for pow7
+    // 3 for x, y; 4 for simd var; up to 9 for the pow7 gates of GKR square
     assert!(
-        ps.len() == 3 || ps.len() == 4 || ps.len() == 7 || ps.len() == 9,
+        (3..=SUMCHECK_GKR_SQUARE_MAX_DEGREE + 1).contains(&ps.len()),
         "Unexpected polynomial size"
     );
     for p in ps {
@@ -41,3 +43,73 @@ where
     mpi_config.root_broadcast_f(&mut r);
     r
 }
+
+/// The channel the prover of a GKR layer sumcheck sends its messages to the verifier through.
+pub trait SumcheckProverIO<F: ExtensionField> {
+    /// Send a round polynomial, given by its evaluations at 0, 1, ..., degree, and return the
+    /// challenge of the round.
+    fn send_round_poly(&mut self, evals: &[F]) -> F;
+
+    /// Send the claim on the input layer at the end of a sumcheck phase.
+    fn send_claim(&mut self, claim: &F);
+}
+
+/// The messages are sent in the clear, by appending them to the transcript.
+pub struct TranscriptProverIO<'a, T: Transcript, M: MPIEngine> {
+    pub transcript: &'a mut T,
+    pub mpi_config: &'a M,
+}
+
+impl<F: ExtensionField, T: Transcript, M: MPIEngine> SumcheckProverIO<F>
+    for TranscriptProverIO<'_, T, M>
+{
+    #[inline]
+    fn send_round_poly(&mut self, evals: &[F]) -> F {
+        transcript_io(self.mpi_config, evals, self.transcript)
+    }
+
+    #[inline]
+    fn send_claim(&mut self, claim: &F) {
+        self.transcript.append_field_element(claim);
+    }
+}
+
+/// Extend the evaluations at 0, 1, ..., k of a polynomial of degree at most k to its
+/// evaluations at 0, 1, ..., n - 1, by walking its table of forward differences.
+#[inline]
+pub(crate) fn extend_evals<F: Field>(evals: &[F], n: usize) -> Vec<F> {
+    let k = evals.len() - 1;
+
+    // the j-th forward differences at the current point, starting at 0
+    let mut diffs = evals.to_vec();
+    for level in 1..=k {
+        for j in (level..=k).rev() {
+            diffs[j] = diffs[j] - diffs[j - 1];
+        }
+    }
+
+    (0..n)
+        .map(|_| {
+            let eval = diffs[0];
+            for j in 0..k {
+                diffs[j] = diffs[j] + diffs[j + 1];
+            }
+            eval
+        })
+        .collect()
+}
+
+/// Evaluate the polynomial with coefficients `coefs` at 0, 1, X, X^2, ..., the points of the
+/// round polynomials over GF2_128, as many points as coefficients.
+#[inline]
+pub(crate) fn gf2_evals_from_coefs<F: ExtensionField>(coefs: &[F]) -> Vec<F> {
+    (0..coefs.len())
+        .map(|i| match i {
+            0 => coefs[0],
+            // Horner's rule at X^(i - 1)
+            _ => coefs.iter().rev().fold(F::ZERO, |acc, coef| {
+                (1..i).fold(acc, |acc, _| acc.mul_by_x()) + *coef
+            }),
+        })
+        .collect()
+}
@@ -180,25 +180,16 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
         v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
     }
 
-    /// GKR2 equivalent of `eval_add`. (Note that GKR2 uses pow1 gates instead of add gates)
+    /// GKR2 equivalent of `eval_add` and `eval_mul`, for the uni gates of degree `degree`.
     #[inline(always)]
-    pub fn eval_pow_1(gates: &[GateUni<F>], sp: &VerifierScratchPad<F>) -> F::ChallengeField {
-        let mut v = F::ChallengeField::zero();
-        for gate in gates {
-            // Gates of type 12346 represent an add gate
-            if gate.gate_type == 12346 {
-                v += sp.eq_evals_at_rz0[gate.o_id] * sp.eq_evals_at_rx[gate.i_ids[0]] * gate.coef;
-            }
-        }
-        v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
-    }
-
-    #[inline(always)]
-    pub fn eval_pow_5(gates: &[GateUni<F>], sp: &VerifierScratchPad<F>) -> F::ChallengeField {
+    pub fn eval_uni(
+        gates: &[GateUni<F>],
+        degree: usize,
+        sp: &VerifierScratchPad<F>,
+    ) -> F::ChallengeField {
         let mut v = F::ChallengeField::zero();
         for gate in gates {
-            // Gates of type 12345 represent a pow5 gate
-            if gate.gate_type == 12345 {
+            if gate.uni_type().degree() == degree {
                 v += sp.eq_evals_at_rz0[gate.o_id] * sp.eq_evals_at_rx[gate.i_ids[0]] * gate.coef;
             }
         }
@@ -272,8 +263,10 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
         Self::lag_eval(vals, x, sp)
     }
 
+    /// Evaluate at `x` the polynomial of degree `vals.len() - 1` with the evaluations `vals` at
+    /// 0, 1, ..., up to `SUMCHECK_GKR_SQUARE_MAX_DEGREE`.
     #[inline(always)]
-    pub fn degree_6_eval(
+    pub fn degree_n_eval(
         vals: &[F::ChallengeField],
         x: F::ChallengeField,
         sp: &VerifierScratchPad<F>,
@@ -289,8 +282,10 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
         sp: &VerifierScratchPad<F>,
     ) -> F::ChallengeField {
         let (evals, lag_denoms_inv) = match vals.len() {
-            4 => (sp.deg3_eval_at.to_vec(), sp.deg3_lag_denoms_inv.to_vec()),
-            7 => (sp.deg6_eval_at.to_vec(), sp.deg6_lag_denoms_inv.to_vec()),
+            4 => (&sp.deg3_eval_at[..], &sp.deg3_lag_denoms_inv[..]),
+            n if n <= sp.deg_n_eval_at.len() => {
+                (&sp.deg_n_eval_at[..n], &sp.deg_n_lag_denoms_inv[n - 1][..])
+            }
             _ => panic!("unsupported degree"),
         };
 
@@ -1,6 +1,11 @@
 use arith::{ExtensionField, Fr};
+use babybear::{BabyBearExt3, Poseidon2BabyBear16};
 use gkr_engine::Transcript;
-use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_hashers::{
+    Keccak256hasher, MiMC5FiatShamirHasher, Poseidon2FiatShamirHasher, PoseidonFiatShamirHasher,
+    SHA256hasher,
+};
+use goldilocks::{GoldilocksExt2, Poseidon2Goldilocks12};
 use mersenne31::{M31Ext3, M31x16};
 use sha2::{Digest, Sha256};
 
@@ -77,6 +82,14 @@ fn test_transcript_expected_behavior() {
     >();
     test_transcript_expected_behavior_helper::<Fr, BytesHashTranscript<MiMC5FiatShamirHasher<Fr>>>(
     );
+    test_transcript_expected_behavior_helper::<
+        BabyBearExt3,
+        BytesHashTranscript<Poseidon2FiatShamirHasher<Poseidon2BabyBear16>>,
+    >();
+    test_transcript_expected_behavior_helper::<
+        GoldilocksExt2,
+        BytesHashTranscript<Poseidon2FiatShamirHasher<Poseidon2Goldilocks12>>,
+    >();
 }
 
 fn get_transcript_output_helper<F, T>(input: &[u32]) -> F
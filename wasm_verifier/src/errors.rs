@@ -0,0 +1,42 @@
+use gkr::ProofInputError;
+use gkr_engine::{ExpErrors, VerifyError};
+use serdes::SerdeError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WasmVerifierError {
+    #[error("unsupported config: {0}")]
+    UnsupportedConfig(String),
+
+    #[error("malformed circuit: {0}")]
+    MalformedCircuit(String),
+
+    #[error("invalid public input: {0}")]
+    InvalidPublicInput(String),
+
+    /// No `pcs.vk` file was given to [`crate::verify`]
+    #[error("missing PCS verifying key")]
+    MissingPcsVerifyingKey,
+
+    #[error("serde error: {0}")]
+    SerdeError(#[from] SerdeError),
+
+    #[error(transparent)]
+    ExpErrors(#[from] ExpErrors),
+
+    #[error("proof rejected: {0}")]
+    VerifyError(#[from] VerifyError),
+}
+
+impl From<ProofInputError> for WasmVerifierError {
+    fn from(e: ProofInputError) -> Self {
+        match e {
+            ProofInputError::UnsupportedConfig(msg) => Self::UnsupportedConfig(msg),
+            ProofInputError::MalformedCircuit(msg) => Self::MalformedCircuit(msg),
+            ProofInputError::InvalidWitness(e) => Self::InvalidPublicInput(e.to_string()),
+            ProofInputError::PcsSetupMismatch(msg) => ExpErrors::PCSSetupError(msg).into(),
+            ProofInputError::ProofMismatch(msg) => ExpErrors::ProofMismatchError(msg).into(),
+            ProofInputError::MalformedProof(e) => Self::VerifyError(e),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! GKR verifier for wasm32, e.g., to check Expander proofs in a browser.
+//!
+//! The verifier is built from `gkr`, `gkr_engine` and `poly_commit` without their default
+//! features, i.e., without MPI and without the rayon thread pool, and the SIMD fields fall back to
+//! their portable implementation on wasm32. [`verify`] is the `wasm-bindgen` entry point, and
+//! [`verify_proof`] the same function for Rust callers.
+//!
+//! The config is picked from the proof header among the configs served by `expander-exec`. The
+//! crate still depends on the standard library, it is not `no_std`.
+
+mod errors;
+mod verify;
+
+pub use errors::*;
+pub use verify::*;
+use wasm_bindgen::prelude::*;
+
+/// Verify an Expander proof, see [`verify_proof`] for the format of the arguments.
+///
+/// `pcs_verifying_key` is required: a call without it, e.g., with `undefined`, throws rather than
+/// falling back to a testing setup whose trapdoor is public.
+#[wasm_bindgen]
+pub fn verify(
+    circuit: &[u8],
+    public_input: &[u8],
+    proof: &[u8],
+    pcs_verifying_key: Option<Vec<u8>>,
+) -> Result<bool, JsError> {
+    let pcs_verifying_key = pcs_verifying_key.ok_or(WasmVerifierError::MissingPcsVerifyingKey)?;
+    Ok(verify_proof(
+        circuit,
+        public_input,
+        proof,
+        &pcs_verifying_key,
+    )?)
+}
@@ -0,0 +1,37 @@
+use gkr::verify_serialized_proof;
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
+
+use crate::WasmVerifierError;
+
+/// Hash of the circuit bytes, as recorded in the proof header by `expander-exec prove`.
+pub fn circuit_hash(circuit: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    SHA256hasher::new().hash(&mut hash, circuit);
+    hash
+}
+
+/// Verify a proof, picking the config from the proof header.
+///
+/// - `circuit` is a serialized `RecursiveCircuit`, i.e., a `circuit.txt` file,
+/// - `public_input` is a serialized `Witness`, i.e., a `witness.txt` file, of which only the public
+///   inputs are read, as for `expander-exec verify`,
+/// - `proof` is a serialized `ProofEnvelope`, as written by `expander-exec prove`,
+/// - `pcs_verifying_key` is the `pcs.vk` file written by `expander-exec setup` for the circuit and
+///   the MPI size of the prover.
+///
+/// Returns an error if the inputs are malformed or do not match each other, and whether the proof
+/// verifies otherwise.
+pub fn verify_proof(
+    circuit: &[u8],
+    public_input: &[u8],
+    proof: &[u8],
+    pcs_verifying_key: &[u8],
+) -> Result<bool, WasmVerifierError> {
+    Ok(verify_serialized_proof(
+        circuit,
+        &circuit_hash(circuit),
+        public_input,
+        proof,
+        pcs_verifying_key,
+    )?)
+}
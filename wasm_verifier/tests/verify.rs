@@ -0,0 +1,140 @@
+use arith::Field;
+use circuit::RecursiveCircuit;
+use gkr::{M31x16ConfigKeccakRawVanilla, Prover};
+use gkr_engine::{
+    ExpErrors, FieldEngine, GKREngine, M31x16Config, MPIConfig, PCSSetupFileKind, PCSSetupHeader,
+    ProofEnvelope, ProofHeader,
+};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+use wasm_verifier::{circuit_hash, verify_proof, WasmVerifierError};
+
+type Cfg = M31x16ConfigKeccakRawVanilla<'static>;
+type F = <M31x16Config as FieldEngine>::CircuitField;
+
+/// Checks x0 * x1 + p0 == x2, with x3 + r * x2 as an unchecked output.
+const CIRCUIT_TEXT: &str = "\
+    recursive_circuit
+    modulus 0x7fffffff
+    num_public_inputs 1
+    num_outputs 2
+    expected_num_output_zeros 1
+    layers 0
+    segment 0
+    i_var_num 2
+    o_var_num 1
+    mul 0 1 -> 0 const 01000000
+    add 2 -> 0 const feffff7f
+    add 3 -> 1 const 01000000
+    add 2 -> 1 random
+    const -> 0 public 0
+    end
+";
+
+/// Serialized circuit, witness, proof envelope and PCS verifying key of the test circuit, as
+/// `expander-exec` writes them.
+fn prove() -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
+    let rc = RecursiveCircuit::<M31x16Config>::from_text(CIRCUIT_TEXT).unwrap();
+    let mut circuit_bytes = vec![];
+    rc.serialize_into(&mut circuit_bytes).unwrap();
+
+    let num_witnesses = M31x16Config::get_field_pack_size();
+    let private_inputs = (0..num_witnesses as u32)
+        .map(|i| vec![F::from(i), F::from(i + 1), F::from(i * (i + 1) + 1)])
+        .collect::<Vec<_>>();
+    let public_inputs = vec![vec![F::ONE]; num_witnesses];
+    let witness = rc
+        .solve_witness::<Cfg>(&private_inputs, &public_inputs)
+        .unwrap();
+    let mut witness_bytes = vec![];
+    witness.serialize_into(&mut witness_bytes).unwrap();
+
+    // a single process config, the prover needs no MPI either
+    let mpi_config = MPIConfig::verifier_new(1);
+    let mut circuit = rc.flatten::<Cfg>();
+    circuit.pre_process_gkr::<Cfg>();
+    circuit.load_witness_bytes(&witness_bytes, &mpi_config, true, false);
+
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<
+            <Cfg as GKREngine>::FieldConfig,
+            <Cfg as GKREngine>::PCSField,
+            <Cfg as GKREngine>::PCSConfig,
+        >(circuit.log_input_size(), &mpi_config);
+    let (claimed_v, proof) = prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let envelope = ProofEnvelope {
+        header: ProofHeader::new::<Cfg>(1, circuit_hash(&circuit_bytes)),
+        proof,
+        claimed_v,
+    };
+    let mut proof_bytes = vec![];
+    envelope.serialize_into(&mut proof_bytes).unwrap();
+
+    let mut vk_bytes = vec![];
+    PCSSetupHeader::new::<Cfg>(
+        PCSSetupFileKind::VerifyingKey,
+        circuit.log_input_size(),
+        1,
+        0,
+    )
+    .serialize_into(&mut vk_bytes)
+    .unwrap();
+    pcs_verification_key.serialize_into(&mut vk_bytes).unwrap();
+
+    (circuit_bytes, witness_bytes, proof_bytes, vk_bytes)
+}
+
+#[test]
+fn test_verify_proof() {
+    let (circuit, witness, proof, vk) = prove();
+    assert!(verify_proof(&circuit, &witness, &proof, &vk).unwrap());
+
+    // flip a bit in the last byte of the proof, i.e., in the claimed value
+    let mut tampered = proof.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(!matches!(
+        verify_proof(&circuit, &witness, &tampered, &vk),
+        Ok(true)
+    ));
+
+    let mut other_circuit = circuit.clone();
+    *other_circuit.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        verify_proof(&other_circuit, &witness, &proof, &vk),
+        Err(WasmVerifierError::ExpErrors(ExpErrors::ProofMismatchError(
+            _
+        )))
+    ));
+
+    assert!(matches!(
+        verify_proof(&circuit, &witness[..witness.len() / 2], &proof, &vk),
+        Err(WasmVerifierError::InvalidPublicInput(_) | WasmVerifierError::SerdeError(_))
+    ));
+
+    assert!(matches!(
+        verify_proof(&circuit, &witness, &proof, &[]),
+        Err(WasmVerifierError::ExpErrors(ExpErrors::PCSSetupError(_)))
+    ));
+}
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn test_verify_in_wasm() {
+        let (circuit, witness, proof) = super::prove();
+        assert!(matches!(
+            wasm_verifier::verify(&circuit, &witness, &proof, &vk),
+            Ok(true)
+        ));
+    }
+}